@@ -0,0 +1,91 @@
+//! JWT Signing Keys Table Migration
+//!
+//! Creates the jwt_signing_keys table holding the JWT key ring. Exactly one
+//! key is `active` and signs new tokens; `verification_only` keys still verify
+//! tokens issued before a rotation, and `retired` keys are kept for auditing
+//! with their private key material erased.
+
+use sea_orm_migration::prelude::*;
+
+/// Migration to create the jwt_signing_keys table.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(JwtSigningKeys::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(JwtSigningKeys::Kid)
+                            .string()
+                            .string_len(64)
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(JwtSigningKeys::Algorithm)
+                            .string()
+                            .string_len(16)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(JwtSigningKeys::PrivateKey).text().not_null())
+                    .col(
+                        ColumnDef::new(JwtSigningKeys::Status)
+                            .string()
+                            .string_len(32)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(JwtSigningKeys::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(JwtSigningKeys::DeactivatedAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(JwtSigningKeys::RetiredAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Create index on status for loading the ring without retired keys
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_jwt_signing_keys_status")
+                    .table(JwtSigningKeys::Table)
+                    .col(JwtSigningKeys::Status)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(JwtSigningKeys::Table).to_owned())
+            .await
+    }
+}
+
+/// Column identifiers for the jwt_signing_keys table.
+#[derive(DeriveIden)]
+enum JwtSigningKeys {
+    Table,
+    Kid,
+    Algorithm,
+    PrivateKey,
+    Status,
+    CreatedAt,
+    DeactivatedAt,
+    RetiredAt,
+}
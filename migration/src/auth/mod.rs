@@ -0,0 +1,3 @@
+mod m20250130_000001_create_jwt_signing_keys_table;
+
+pub use m20250130_000001_create_jwt_signing_keys_table::Migration as M20250130CreateJwtSigningKeysTable;
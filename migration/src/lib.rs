@@ -1,5 +1,6 @@
 pub use sea_orm_migration::prelude::*;
 
+mod auth;
 mod mqtt;
 mod tenants;
 mod users;
@@ -22,6 +23,31 @@ impl MigratorTrait for Migrator {
             Box::new(tenants::M20250118RelaxUserTenantsUniqueConstraint),
             // MQTT domain migrations
             Box::new(mqtt::M20240523000001CreateMqttUsersTable),
+            // User MFA migrations
+            Box::new(users::M20250120CreateUserMfaTable),
+            Box::new(users::M20250121CreateUserRecoveryCodesTable),
+            Box::new(users::M20250122CreateUserPasskeysTable),
+            // User account token migrations
+            Box::new(users::M20250123CreateUserTokensTable),
+            // Email verification migrations
+            Box::new(users::M20250124AddEmailVerifiedAtToUsers),
+            Box::new(tenants::M20250124AddRequireEmailVerificationToTenants),
+            // Refresh token rotation migrations
+            Box::new(users::M20250125AddTokenFamilyToUserSessions),
+            // Session management migrations
+            Box::new(users::M20250126AddLastUsedAtToUserSessions),
+            // Rate limiting migrations
+            Box::new(tenants::M20250127AddRateLimitsToTenants),
+            // Account lockout migrations
+            Box::new(users::M20250128AddLoginLockoutToUsers),
+            // User status migrations
+            Box::new(users::M20250129AddStatusToUsers),
+            // Signing key rotation migrations
+            Box::new(auth::M20250130CreateJwtSigningKeysTable),
+            // OAuth client registration migrations
+            Box::new(tenants::M20250131CreateClientsTable),
+            // Passwordless sign-in migrations
+            Box::new(tenants::M20250201AddPasswordlessLoginToTenants),
         ]
     }
 }
@@ -0,0 +1,107 @@
+//! OAuth Clients Table Migration
+//!
+//! Creates the clients table holding the OAuth client applications registered
+//! by each tenant. Public clients have no secret; confidential clients store
+//! only the SHA-256 hash of theirs.
+
+use sea_orm_migration::prelude::*;
+
+/// Migration to create the clients table.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Clients::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(Clients::Id).uuid().not_null().primary_key())
+                    .col(ColumnDef::new(Clients::TenantId).uuid().not_null())
+                    .col(
+                        ColumnDef::new(Clients::ClientId)
+                            .string()
+                            .string_len(64)
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(
+                        ColumnDef::new(Clients::ClientSecretHash)
+                            .string()
+                            .string_len(64)
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(Clients::Name)
+                            .string()
+                            .string_len(255)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(Clients::RedirectUris).json().not_null())
+                    .col(ColumnDef::new(Clients::GrantTypes).json().not_null())
+                    .col(ColumnDef::new(Clients::Scopes).json().not_null())
+                    .col(
+                        ColumnDef::new(Clients::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(Clients::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_clients_tenant_id")
+                            .from(Clients::Table, Clients::TenantId)
+                            .to(Tenants::Table, Tenants::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Create index on tenant_id for listing a tenant's clients
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_clients_tenant_id")
+                    .table(Clients::Table)
+                    .col(Clients::TenantId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Clients::Table).to_owned())
+            .await
+    }
+}
+
+/// Column identifiers for the clients table.
+#[derive(DeriveIden)]
+enum Clients {
+    Table,
+    Id,
+    TenantId,
+    ClientId,
+    ClientSecretHash,
+    Name,
+    RedirectUris,
+    GrantTypes,
+    Scopes,
+    CreatedAt,
+    UpdatedAt,
+}
+
+/// Column identifiers for the tenants table (foreign key reference).
+#[derive(DeriveIden)]
+enum Tenants {
+    Table,
+    Id,
+}
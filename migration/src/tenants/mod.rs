@@ -2,8 +2,16 @@ mod m20250111_000001_create_tenants_table;
 mod m20250111_000005_create_user_tenants_junction;
 mod m20250116_000001_add_api_key_to_tenants;
 mod m20250118_000001_relax_user_tenants_unique_constraint;
+mod m20250124_000001_add_require_email_verification_to_tenants;
+mod m20250127_000001_add_rate_limits_to_tenants;
+mod m20250131_000001_create_clients_table;
+mod m20250201_000001_add_passwordless_login_to_tenants;
 
 pub use m20250111_000001_create_tenants_table::Migration as M20250111CreateTenantsTable;
 pub use m20250111_000005_create_user_tenants_junction::Migration as M20250111CreateUserTenantsJunction;
 pub use m20250116_000001_add_api_key_to_tenants::Migration as M20250116AddApiKeyToTenants;
 pub use m20250118_000001_relax_user_tenants_unique_constraint::Migration as M20250118RelaxUserTenantsUniqueConstraint;
+pub use m20250124_000001_add_require_email_verification_to_tenants::Migration as M20250124AddRequireEmailVerificationToTenants;
+pub use m20250127_000001_add_rate_limits_to_tenants::Migration as M20250127AddRateLimitsToTenants;
+pub use m20250131_000001_create_clients_table::Migration as M20250131CreateClientsTable;
+pub use m20250201_000001_add_passwordless_login_to_tenants::Migration as M20250201AddPasswordlessLoginToTenants;
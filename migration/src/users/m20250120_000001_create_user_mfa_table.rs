@@ -0,0 +1,88 @@
+//! User MFA Table Migration
+//!
+//! Creates the user_mfa table holding each user's TOTP shared secret.
+//! A row with a null `enabled_at` is a pending enrollment that has not been
+//! confirmed with a valid code yet.
+
+use sea_orm_migration::prelude::*;
+
+/// Migration to create the user_mfa table.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(UserMfa::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(UserMfa::Id).uuid().not_null().primary_key())
+                    .col(
+                        ColumnDef::new(UserMfa::UserId)
+                            .uuid()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(
+                        ColumnDef::new(UserMfa::TotpSecret)
+                            .string()
+                            .string_len(64)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(UserMfa::EnabledAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .col(ColumnDef::new(UserMfa::LastUsedStep).big_integer().null())
+                    .col(
+                        ColumnDef::new(UserMfa::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(UserMfa::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_user_mfa_user_id")
+                            .from(UserMfa::Table, UserMfa::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(UserMfa::Table).to_owned())
+            .await
+    }
+}
+
+/// Column identifiers for the user_mfa table.
+#[derive(DeriveIden)]
+enum UserMfa {
+    Table,
+    Id,
+    UserId,
+    TotpSecret,
+    EnabledAt,
+    LastUsedStep,
+    CreatedAt,
+    UpdatedAt,
+}
+
+/// Reference to users table for foreign key.
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
@@ -0,0 +1,90 @@
+//! User Recovery Codes Table Migration
+//!
+//! Creates the user_recovery_codes table holding Argon2 hashes of each user's
+//! single-use account recovery codes. A code is consumed by setting `used_at`.
+
+use sea_orm_migration::prelude::*;
+
+/// Migration to create the user_recovery_codes table.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(UserRecoveryCodes::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(UserRecoveryCodes::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(UserRecoveryCodes::UserId).uuid().not_null())
+                    .col(
+                        ColumnDef::new(UserRecoveryCodes::CodeHash)
+                            .string()
+                            .string_len(255)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(UserRecoveryCodes::UsedAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(UserRecoveryCodes::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_user_recovery_codes_user_id")
+                            .from(UserRecoveryCodes::Table, UserRecoveryCodes::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Create index on user_id for faster lookups
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_user_recovery_codes_user_id")
+                    .table(UserRecoveryCodes::Table)
+                    .col(UserRecoveryCodes::UserId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(UserRecoveryCodes::Table).to_owned())
+            .await
+    }
+}
+
+/// Column identifiers for the user_recovery_codes table.
+#[derive(DeriveIden)]
+enum UserRecoveryCodes {
+    Table,
+    Id,
+    UserId,
+    CodeHash,
+    UsedAt,
+    CreatedAt,
+}
+
+/// Reference to users table for foreign key.
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
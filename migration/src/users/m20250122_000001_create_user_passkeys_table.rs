@@ -0,0 +1,108 @@
+//! User Passkeys Table Migration
+//!
+//! Creates the user_passkeys table holding WebAuthn credentials registered by
+//! each user. The COSE public key and signature counter are kept to verify
+//! assertions and detect cloned authenticators.
+
+use sea_orm_migration::prelude::*;
+
+/// Migration to create the user_passkeys table.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(UserPasskeys::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(UserPasskeys::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(UserPasskeys::UserId).uuid().not_null())
+                    .col(
+                        ColumnDef::new(UserPasskeys::CredentialId)
+                            .string()
+                            .string_len(1400)
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(ColumnDef::new(UserPasskeys::PublicKey).binary().not_null())
+                    .col(
+                        ColumnDef::new(UserPasskeys::SignCount)
+                            .big_integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(UserPasskeys::Name)
+                            .string()
+                            .string_len(100)
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(UserPasskeys::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(UserPasskeys::LastUsedAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_user_passkeys_user_id")
+                            .from(UserPasskeys::Table, UserPasskeys::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Create index on user_id for faster lookups
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_user_passkeys_user_id")
+                    .table(UserPasskeys::Table)
+                    .col(UserPasskeys::UserId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(UserPasskeys::Table).to_owned())
+            .await
+    }
+}
+
+/// Column identifiers for the user_passkeys table.
+#[derive(DeriveIden)]
+enum UserPasskeys {
+    Table,
+    Id,
+    UserId,
+    CredentialId,
+    PublicKey,
+    SignCount,
+    Name,
+    CreatedAt,
+    LastUsedAt,
+}
+
+/// Reference to users table for foreign key.
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
@@ -0,0 +1,108 @@
+//! User Tokens Table Migration
+//!
+//! Creates the user_tokens table for single-use tokens sent to users out of
+//! band, such as password reset links. Only the SHA-256 hash of a token is
+//! stored. A token is consumed by setting `used_at`.
+
+use sea_orm_migration::prelude::*;
+
+/// Migration to create the user_tokens table.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(UserTokens::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(UserTokens::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(UserTokens::UserId).uuid().not_null())
+                    .col(ColumnDef::new(UserTokens::TenantId).uuid().not_null())
+                    .col(
+                        ColumnDef::new(UserTokens::Purpose)
+                            .string()
+                            .string_len(50)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(UserTokens::TokenHash)
+                            .string()
+                            .string_len(64)
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(
+                        ColumnDef::new(UserTokens::ExpiresAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(UserTokens::UsedAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(UserTokens::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_user_tokens_user_id")
+                            .from(UserTokens::Table, UserTokens::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Create index on (user_id, purpose) for invalidating a user's previous tokens
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_user_tokens_user_id_purpose")
+                    .table(UserTokens::Table)
+                    .col(UserTokens::UserId)
+                    .col(UserTokens::Purpose)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(UserTokens::Table).to_owned())
+            .await
+    }
+}
+
+/// Column identifiers for the user_tokens table.
+#[derive(DeriveIden)]
+enum UserTokens {
+    Table,
+    Id,
+    UserId,
+    TenantId,
+    Purpose,
+    TokenHash,
+    ExpiresAt,
+    UsedAt,
+    CreatedAt,
+}
+
+/// Reference to users table for foreign key.
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
@@ -0,0 +1,107 @@
+//! Refresh Token Family Migration
+//!
+//! Links each session to the session it was rotated from. All sessions that
+//! descend from one login share a `family_id`, so that a replayed refresh
+//! token can revoke the whole chain.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // SQLite only accepts one column per ALTER TABLE statement
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(UserSessions::Table)
+                    .add_column(ColumnDef::new(UserSessions::FamilyId).uuid().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(UserSessions::Table)
+                    .add_column(ColumnDef::new(UserSessions::ParentId).uuid().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(UserSessions::Table)
+                    .add_column(
+                        ColumnDef::new(UserSessions::RotatedAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Existing sessions each start their own family
+        manager
+            .exec_stmt(
+                Query::update()
+                    .table(UserSessions::Table)
+                    .value(UserSessions::FamilyId, Expr::col(UserSessions::Id))
+                    .and_where(Expr::col(UserSessions::FamilyId).is_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        // Create index on family_id for family revocation
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_user_sessions_family_id")
+                    .table(UserSessions::Table)
+                    .col(UserSessions::FamilyId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_user_sessions_family_id")
+                    .table(UserSessions::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        for column in [
+            UserSessions::RotatedAt,
+            UserSessions::ParentId,
+            UserSessions::FamilyId,
+        ] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(UserSessions::Table)
+                        .drop_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Column identifiers for the user_sessions table.
+#[derive(DeriveIden)]
+enum UserSessions {
+    Table,
+    Id,
+    FamilyId,
+    ParentId,
+    RotatedAt,
+}
@@ -2,8 +2,26 @@ mod m20250108_000001_create_users_table;
 mod m20250109_000002_create_user_details_table;
 mod m20250110_000001_create_user_sessions_table;
 mod m20250110_000002_create_user_activity_logs_table;
+mod m20250120_000001_create_user_mfa_table;
+mod m20250121_000001_create_user_recovery_codes_table;
+mod m20250122_000001_create_user_passkeys_table;
+mod m20250123_000001_create_user_tokens_table;
+mod m20250124_000001_add_email_verified_at_to_users;
+mod m20250125_000001_add_token_family_to_user_sessions;
+mod m20250126_000001_add_last_used_at_to_user_sessions;
+mod m20250128_000001_add_login_lockout_to_users;
+mod m20250129_000001_add_status_to_users;
 
 pub use m20250108_000001_create_users_table::Migration as M20250108CreateUsersTable;
 pub use m20250109_000002_create_user_details_table::Migration as M20250109CreateUserDetailsTable;
 pub use m20250110_000001_create_user_sessions_table::Migration as M20250110CreateUserSessionsTable;
 pub use m20250110_000002_create_user_activity_logs_table::Migration as M20250110CreateUserActivityLogsTable;
+pub use m20250120_000001_create_user_mfa_table::Migration as M20250120CreateUserMfaTable;
+pub use m20250121_000001_create_user_recovery_codes_table::Migration as M20250121CreateUserRecoveryCodesTable;
+pub use m20250122_000001_create_user_passkeys_table::Migration as M20250122CreateUserPasskeysTable;
+pub use m20250123_000001_create_user_tokens_table::Migration as M20250123CreateUserTokensTable;
+pub use m20250124_000001_add_email_verified_at_to_users::Migration as M20250124AddEmailVerifiedAtToUsers;
+pub use m20250125_000001_add_token_family_to_user_sessions::Migration as M20250125AddTokenFamilyToUserSessions;
+pub use m20250126_000001_add_last_used_at_to_user_sessions::Migration as M20250126AddLastUsedAtToUserSessions;
+pub use m20250128_000001_add_login_lockout_to_users::Migration as M20250128AddLoginLockoutToUsers;
+pub use m20250129_000001_add_status_to_users::Migration as M20250129AddStatusToUsers;
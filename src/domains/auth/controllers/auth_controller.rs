@@ -1,14 +1,21 @@
 use crate::domains::auth::usecases::auth_usecase::AuthUseCase;
+use crate::domains::auth::usecases::email_verification_usecase::EmailVerificationUseCase;
 use crate::domains::common::dtos::response_dto::SuccessResponseDTO;
 use crate::domains::common::errors::AppError;
 use crate::domains::common::utils::jwt::Claims;
 use crate::domains::common::validators::sso_validator::{
-    validate_redirect_uri_whitelist, validate_sso_params,
+    validate_redirect_uri_origin, validate_sso_params,
 };
+use crate::domains::tenant::usecases::client_usecase::ClientUseCase;
 use crate::domains::user::dtos::auth_dto::{
-    LoginRequest, LoginRequestJson, RegisterRequest, RegisterRequestJson,
+    LoginRequest, LoginRequestJson, LoginResult, ReauthenticateRequest, RegisterRequest,
+    RegisterRequestJson,
 };
 use crate::domains::user::dtos::change_password_dto::ChangePasswordRequest;
+use crate::domains::user::dtos::mfa_dto::{MfaVerifyRequest, TotpCodeRequest};
+use crate::domains::user::dtos::recovery_code_dto::{
+    MfaRecoveryRequest, RecoveryResetRequest, RegenerateRecoveryCodesRequest,
+};
 use actix_web::{
     cookie::{Cookie, SameSite},
     web, HttpMessage, HttpResponse, Responder,
@@ -22,6 +29,8 @@ use crate::domains::tenant::middlewares::api_key_middleware::TenantId;
 
 pub async fn register(
     usecase: web::Data<Arc<AuthUseCase>>,
+    email_verification_usecase: web::Data<Arc<EmailVerificationUseCase>>,
+    client_usecase: web::Data<Arc<ClientUseCase>>,
     allowed_origins: web::Data<Vec<String>>,
     body: web::Json<RegisterRequestJson>,
     req: actix_web::HttpRequest,
@@ -36,8 +45,10 @@ pub async fn register(
     // Validate SSO params if present
     validate_sso_params(&body.state, &body.nonce, &body.redirect_uri)?;
 
-    // Validate redirect_uri against allowed origins whitelist
-    validate_redirect_uri_whitelist(&body.redirect_uri, &allowed_origins)?;
+    // Validate redirect_uri against the tenant's registered clients
+    client_usecase
+        .validate_redirect_uri(tenant_id, &body.redirect_uri, &allowed_origins)
+        .await?;
 
     let register_req = RegisterRequest {
         username: body.username.clone(),
@@ -51,12 +62,17 @@ pub async fn register(
         invitation_code: body.invitation_code.clone(),
     };
 
-    let auth_response = usecase.register(register_req, &req).await?;
+    let user_id = usecase.register(register_req, &req).await?.user_id();
+
+    // Unverified users get a verification link; already verified ones are skipped
+    email_verification_usecase
+        .send_verification_email(user_id, tenant_id, &req)
+        .await?;
 
     Ok(HttpResponse::Created().json(SuccessResponseDTO::new(
         "User registered successfully",
         serde_json::json!({
-            "user_id": auth_response.user_id
+            "user_id": user_id
         }),
     )))
 }
@@ -84,6 +100,7 @@ pub async fn generate_invitation_code(
 /// In production, ensure `secure` is set to `true` when using HTTPS.
 pub async fn login(
     usecase: web::Data<Arc<AuthUseCase>>,
+    client_usecase: web::Data<Arc<ClientUseCase>>,
     allowed_origins: web::Data<Vec<String>>,
     body: web::Json<LoginRequestJson>,
     req: actix_web::HttpRequest,
@@ -98,8 +115,10 @@ pub async fn login(
     // Validate SSO params if present
     validate_sso_params(&body.state, &body.nonce, &body.redirect_uri)?;
 
-    // Validate redirect_uri against allowed origins whitelist
-    validate_redirect_uri_whitelist(&body.redirect_uri, &allowed_origins)?;
+    // Validate redirect_uri against the tenant's registered clients
+    client_usecase
+        .validate_redirect_uri(tenant_id, &body.redirect_uri, &allowed_origins)
+        .await?;
 
     let login_req = LoginRequest {
         email_or_username: body.email_or_username.to_string(),
@@ -111,27 +130,54 @@ pub async fn login(
         role: body.role.clone(),
     };
 
-    let (auth_response, refresh_token) = usecase.login(login_req, &req).await?;
+    let (auth_response, refresh_token) = match usecase.login(login_req, &req).await? {
+        LoginResult::Authenticated(auth_response, refresh_token) => (auth_response, refresh_token),
+        LoginResult::MfaRequired(challenge) => {
+            // No refresh cookie until the second factor is verified
+            return Ok(HttpResponse::Ok().json(SuccessResponseDTO::new(
+                "MFA verification required",
+                serde_json::json!({
+                    "mfa_required": true,
+                    "mfa_token": challenge.mfa_token,
+                    "expires_in": challenge.expires_in
+                }),
+            )));
+        }
+    };
 
-    let refresh_token_expiry = usecase.get_refresh_token_expiry();
-    let cookie_domain = std::env::var("COOKIE_DOMAIN").ok();
+    let cookie = refresh_token_cookie(refresh_token, usecase.get_refresh_token_expiry());
 
-    let mut cookie_builder = Cookie::build("refresh_token", refresh_token)
-        .path("/")
-        .http_only(true)
-        .secure(true)
-        .same_site(SameSite::None)
-        .max_age(actix_web::cookie::time::Duration::seconds(
-            refresh_token_expiry,
-        ));
+    Ok(HttpResponse::Ok()
+        .cookie(cookie)
+        .json(SuccessResponseDTO::new(
+            "Login successful",
+            serde_json::json!({
+                "access_token": auth_response.access_token
+            }),
+        )))
+}
 
-    if let Some(domain) = cookie_domain {
-        if !domain.is_empty() {
-            cookie_builder = cookie_builder.domain(domain);
-        }
-    }
+/// Completes an MFA-challenged login.
+///
+/// Exchanges the `mfa_token` returned by `/auth/login` and a TOTP code for the
+/// access token and refresh token cookie.
+pub async fn verify_mfa(
+    usecase: web::Data<Arc<AuthUseCase>>,
+    body: web::Json<MfaVerifyRequest>,
+    req: actix_web::HttpRequest,
+) -> Result<impl Responder, AppError> {
+    // Extract tenant_id from request extensions (set by ApiKeyMiddleware)
+    let tenant_id = req
+        .extensions()
+        .get::<TenantId>()
+        .map(|id| id.0)
+        .ok_or_else(|| AppError::NotFound("Tenant ID not found in request context".to_string()))?;
 
-    let cookie = cookie_builder.finish();
+    let (auth_response, refresh_token) = usecase
+        .verify_mfa(body.into_inner(), tenant_id, &req)
+        .await?;
+
+    let cookie = refresh_token_cookie(refresh_token, usecase.get_refresh_token_expiry());
 
     Ok(HttpResponse::Ok()
         .cookie(cookie)
@@ -143,6 +189,54 @@ pub async fn login(
         )))
 }
 
+/// Starts TOTP enrollment for the authenticated user.
+///
+/// Returns the shared secret and an `otpauth://` URI for the authenticator app.
+/// MFA is only enforced after the enrollment is confirmed.
+pub async fn enroll_totp(
+    usecase: web::Data<Arc<AuthUseCase>>,
+    req: actix_web::HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let user_id = AuthUseCase::extract_user_id_from_request(&req)?;
+    let enrollment = usecase.enroll_totp(user_id).await?;
+
+    Ok(HttpResponse::Ok().json(SuccessResponseDTO::new(
+        "TOTP enrollment started",
+        enrollment,
+    )))
+}
+
+/// Confirms a pending TOTP enrollment with a code from the authenticator app.
+///
+/// Returns a fresh set of recovery codes, which are only shown once.
+pub async fn confirm_totp(
+    usecase: web::Data<Arc<AuthUseCase>>,
+    body: web::Json<TotpCodeRequest>,
+    req: actix_web::HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let user_id = AuthUseCase::extract_user_id_from_request(&req)?;
+    let recovery_codes = usecase.confirm_totp(user_id, &body.code, &req).await?;
+
+    Ok(HttpResponse::Ok().json(SuccessResponseDTO::new(
+        "MFA enabled successfully",
+        recovery_codes,
+    )))
+}
+
+/// Disables TOTP for the authenticated user. Requires a current code.
+pub async fn disable_totp(
+    usecase: web::Data<Arc<AuthUseCase>>,
+    body: web::Json<TotpCodeRequest>,
+    req: actix_web::HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let user_id = AuthUseCase::extract_user_id_from_request(&req)?;
+    usecase.disable_totp(user_id, &body.code, &req).await?;
+
+    Ok(HttpResponse::Ok().json(SuccessResponseDTO::<()>::no_data(
+        "MFA disabled successfully",
+    )))
+}
+
 /// Logs out a user by clearing the refresh token cookie and deleting the session.
 ///
 /// This endpoint requires JWT authentication to ensure only logged-in users can logout.
@@ -158,21 +252,7 @@ pub async fn logout(
     usecase.logout(user_id, &req).await?;
 
     // Clear refresh token cookie
-    let cookie_domain = std::env::var("COOKIE_DOMAIN").ok();
-    let mut cookie_builder = Cookie::build("refresh_token", "")
-        .path("/")
-        .http_only(true)
-        .secure(true)
-        .same_site(SameSite::None)
-        .max_age(actix_web::cookie::time::Duration::seconds(0));
-
-    if let Some(domain) = cookie_domain {
-        if !domain.is_empty() {
-            cookie_builder = cookie_builder.domain(domain);
-        }
-    }
-
-    let cookie = cookie_builder.finish();
+    let cookie = refresh_token_cookie(String::new(), 0);
 
     Ok(HttpResponse::Ok()
         .cookie(cookie)
@@ -187,32 +267,26 @@ pub async fn logout(
 /// might not be available, relying solely on the HTTP-Only cookie.
 pub async fn sso_logout(
     usecase: web::Data<Arc<AuthUseCase>>,
+    client_usecase: web::Data<Arc<ClientUseCase>>,
     allowed_origins: web::Data<Vec<String>>,
     query: web::Query<crate::domains::user::dtos::auth_dto::SsoLogoutQuery>,
     req: actix_web::HttpRequest,
 ) -> Result<impl Responder, AppError> {
-    // Validate redirect_uri against allowed origins whitelist
-    validate_redirect_uri_whitelist(&query.redirect_uri, &allowed_origins)?;
+    // Validate redirect_uri against the client registration, if the client is named
+    match query.client_id.as_deref() {
+        Some(client_id) => {
+            client_usecase
+                .validate_client_redirect_uri(client_id, &query.redirect_uri)
+                .await?
+        }
+        None => validate_redirect_uri_origin(&query.redirect_uri, &allowed_origins)?,
+    }
 
     // Attempt logout logic (delete session) - ignore errors (e.g. if already logged out)
     let _ = usecase.sso_logout(&req).await;
 
     // Clear refresh token cookie
-    let cookie_domain = std::env::var("COOKIE_DOMAIN").ok();
-    let mut cookie_builder = Cookie::build("refresh_token", "")
-        .path("/")
-        .http_only(true)
-        .secure(true)
-        .same_site(SameSite::None)
-        .max_age(actix_web::cookie::time::Duration::seconds(0));
-
-    if let Some(domain) = cookie_domain {
-        if !domain.is_empty() {
-            cookie_builder = cookie_builder.domain(domain);
-        }
-    }
-
-    let cookie = cookie_builder.finish();
+    let cookie = refresh_token_cookie(String::new(), 0);
 
     let redirect_url = query
         .redirect_uri
@@ -227,39 +301,57 @@ pub async fn sso_logout(
 
 /// Refreshes access token using refresh token from cookie.
 ///
-/// Delegates to use case for cookie extraction and token refresh logic.
+/// Delegates to use case for cookie extraction and token rotation, and
+/// replaces the refresh token cookie with the rotated token.
 pub async fn refresh(
     usecase: web::Data<Arc<AuthUseCase>>,
     req: actix_web::HttpRequest,
 ) -> Result<impl Responder, AppError> {
-    let (new_access_token, _expires_in) = usecase.refresh_token_from_request(&req).await?;
+    let (auth_response, refresh_token) = usecase.refresh_token_from_request(&req).await?;
+    let new_access_token = auth_response.access_token;
 
-    Ok(HttpResponse::Ok().json(SuccessResponseDTO::new(
-        "Token refreshed successfully",
-        serde_json::json!({
-            "access_token": new_access_token
-        }),
-    )))
+    let cookie = refresh_token_cookie(refresh_token, usecase.get_refresh_token_expiry());
+
+    Ok(HttpResponse::Ok()
+        .cookie(cookie)
+        .json(SuccessResponseDTO::new(
+            "Token refreshed successfully",
+            serde_json::json!({
+                "access_token": new_access_token
+            }),
+        )))
 }
 
 /// Verifies JWT token and returns user data if valid.
 ///
 /// This endpoint uses JWT middleware to validate the token.
-/// It then checks if the user still exists in the database.
+/// It then checks if the user still exists in the database. Tokens of
+/// machine clients are accepted too and return the client instead.
 pub async fn verify(
     usecase: web::Data<Arc<AuthUseCase>>,
+    client_usecase: web::Data<Arc<ClientUseCase>>,
     req: actix_web::HttpRequest,
 ) -> Result<impl Responder, AppError> {
-    let user_id = AuthUseCase::extract_user_id_from_request(&req)?;
-
     // Extract claims from request extensions to get context (tenant_id)
-    let tenant_id = {
-        let extensions = req.extensions();
-        let claims = extensions
-            .get::<Claims>()
-            .ok_or_else(|| AppError::Unauthorized("Unauthorized".to_string()))?;
-        claims.tenant_id.clone()
-    };
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or_else(|| AppError::Unauthorized("Unauthorized".to_string()))?;
+
+    if claims.is_client() {
+        let client = client_usecase
+            .verify_client(&claims.sub, &claims.tenant_id)
+            .await?;
+
+        return Ok(HttpResponse::Ok().json(SuccessResponseDTO::new(
+            "Token is valid",
+            serde_json::json!({ "client": client, "scope": claims.scope }),
+        )));
+    }
+
+    let user_id = AuthUseCase::extract_user_id_from_request(&req)?;
+    let tenant_id = claims.tenant_id;
 
     let user_response = usecase.verify_user_exists(user_id, tenant_id).await?;
 
@@ -288,3 +380,118 @@ pub async fn change_password(
         "Password changed successfully",
     )))
 }
+
+/// Confirms the authenticated user's password (and TOTP code if MFA is
+/// enabled) again.
+///
+/// Returns an access token with a fresh `auth_time` for routes that require
+/// recent authentication. The refresh token cookie is left untouched.
+pub async fn reauthenticate(
+    usecase: web::Data<Arc<AuthUseCase>>,
+    body: web::Json<ReauthenticateRequest>,
+    req: actix_web::HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let auth_response = usecase.reauthenticate(body.into_inner(), &req).await?;
+
+    Ok(HttpResponse::Ok().json(SuccessResponseDTO::new(
+        "Reauthentication successful",
+        auth_response,
+    )))
+}
+
+/// Completes an MFA-challenged login with a recovery code.
+///
+/// Used when the authenticator app is unavailable. Sets the refresh token
+/// cookie like a regular login.
+pub async fn verify_mfa_recovery(
+    usecase: web::Data<Arc<AuthUseCase>>,
+    body: web::Json<MfaRecoveryRequest>,
+    req: actix_web::HttpRequest,
+) -> Result<impl Responder, AppError> {
+    // Extract tenant_id from request extensions (set by ApiKeyMiddleware)
+    let tenant_id = req
+        .extensions()
+        .get::<TenantId>()
+        .map(|id| id.0)
+        .ok_or_else(|| AppError::NotFound("Tenant ID not found in request context".to_string()))?;
+
+    let (auth_response, refresh_token) = usecase
+        .verify_mfa_recovery(body.into_inner(), tenant_id, &req)
+        .await?;
+
+    let cookie = refresh_token_cookie(refresh_token, usecase.get_refresh_token_expiry());
+
+    Ok(HttpResponse::Ok()
+        .cookie(cookie)
+        .json(SuccessResponseDTO::new(
+            "Login successful",
+            serde_json::json!({
+                "access_token": auth_response.access_token
+            }),
+        )))
+}
+
+/// Regenerates the authenticated user's recovery codes.
+///
+/// Requires the current password. Previously issued codes are invalidated.
+pub async fn regenerate_recovery_codes(
+    usecase: web::Data<Arc<AuthUseCase>>,
+    body: web::Json<RegenerateRecoveryCodesRequest>,
+    req: actix_web::HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let user_id = AuthUseCase::extract_user_id_from_request(&req)?;
+    let recovery_codes = usecase
+        .regenerate_recovery_codes(user_id, &body.password, &req)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(SuccessResponseDTO::new(
+        "Recovery codes generated successfully",
+        recovery_codes,
+    )))
+}
+
+/// Resets a user's password and second factor with a recovery code.
+///
+/// For users who cannot sign in. All sessions are revoked.
+pub async fn reset_with_recovery_code(
+    usecase: web::Data<Arc<AuthUseCase>>,
+    body: web::Json<RecoveryResetRequest>,
+    req: actix_web::HttpRequest,
+) -> Result<impl Responder, AppError> {
+    // Extract tenant_id from request extensions (set by ApiKeyMiddleware)
+    let tenant_id = req
+        .extensions()
+        .get::<TenantId>()
+        .map(|id| id.0)
+        .ok_or_else(|| AppError::NotFound("Tenant ID not found in request context".to_string()))?;
+
+    usecase
+        .reset_with_recovery_code(body.into_inner(), tenant_id, &req)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(SuccessResponseDTO::<()>::no_data(
+        "Credentials reset successfully",
+    )))
+}
+
+/// Builds the HTTP-only refresh token cookie, honoring `COOKIE_DOMAIN`.
+///
+/// Pass an empty value with `max_age_secs = 0` to clear the cookie.
+pub(crate) fn refresh_token_cookie(value: String, max_age_secs: i64) -> Cookie<'static> {
+    let cookie_domain = std::env::var("COOKIE_DOMAIN").ok();
+
+    let mut cookie_builder = Cookie::build("refresh_token", value)
+        .path("/")
+        .http_only(true)
+        .secure(true)
+        .same_site(SameSite::None)
+        .max_age(actix_web::cookie::time::Duration::seconds(max_age_secs));
+
+    if let Some(domain) = cookie_domain {
+        if !domain.is_empty() {
+            cookie_builder = cookie_builder.domain(domain);
+        }
+    }
+
+    cookie_builder.finish()
+}
@@ -0,0 +1,55 @@
+use crate::domains::auth::usecases::email_verification_usecase::EmailVerificationUseCase;
+use crate::domains::common::dtos::response_dto::SuccessResponseDTO;
+use crate::domains::common::errors::AppError;
+use crate::domains::tenant::middlewares::api_key_middleware::TenantId;
+use crate::domains::user::dtos::email_verification_dto::{
+    ResendVerificationRequest, VerifyEmailRequest,
+};
+use actix_web::{web, HttpMessage, HttpResponse, Responder};
+use std::sync::Arc;
+
+/// Confirms an email address with the token from a verification link.
+pub async fn verify_email(
+    usecase: web::Data<Arc<EmailVerificationUseCase>>,
+    body: web::Json<VerifyEmailRequest>,
+    req: actix_web::HttpRequest,
+) -> Result<impl Responder, AppError> {
+    // Extract tenant_id from request extensions (set by ApiKeyMiddleware)
+    let tenant_id = req
+        .extensions()
+        .get::<TenantId>()
+        .map(|id| id.0)
+        .ok_or_else(|| AppError::NotFound("Tenant ID not found in request context".to_string()))?;
+
+    usecase
+        .verify_email(body.into_inner(), tenant_id, &req)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(SuccessResponseDTO::<()>::no_data(
+        "Email verified successfully",
+    )))
+}
+
+/// Sends a new verification link.
+///
+/// The response is identical whether or not the email is registered or already verified.
+pub async fn resend_verification(
+    usecase: web::Data<Arc<EmailVerificationUseCase>>,
+    body: web::Json<ResendVerificationRequest>,
+    req: actix_web::HttpRequest,
+) -> Result<impl Responder, AppError> {
+    // Extract tenant_id from request extensions (set by ApiKeyMiddleware)
+    let tenant_id = req
+        .extensions()
+        .get::<TenantId>()
+        .map(|id| id.0)
+        .ok_or_else(|| AppError::NotFound("Tenant ID not found in request context".to_string()))?;
+
+    usecase
+        .resend_verification(body.into_inner(), tenant_id, &req)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(SuccessResponseDTO::<()>::no_data(
+        "If the email is registered and unverified, a verification link has been sent",
+    )))
+}
@@ -1 +1,10 @@
 pub mod auth_controller;
+pub mod email_verification_controller;
+pub mod oidc_controller;
+pub mod passkey_controller;
+pub mod password_reset_controller;
+pub mod passwordless_controller;
+pub mod session_controller;
+pub mod signing_key_controller;
+pub mod token_controller;
+pub mod well_known_controller;
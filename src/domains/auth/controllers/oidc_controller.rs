@@ -0,0 +1,163 @@
+use crate::domains::auth::dtos::oidc_dto::{
+    AuthorizationRedirectResponse, AuthorizeQuery, CompleteAuthorizationRequest,
+    DeviceAuthorizationRequest, DeviceCodeQuery, DeviceVerificationRequest, TokenRequest,
+};
+use crate::domains::auth::usecases::oidc_usecase::OidcUseCase;
+use crate::domains::common::dtos::response_dto::SuccessResponseDTO;
+use crate::domains::common::errors::AppError;
+use crate::domains::common::utils::jwt::Claims;
+use actix_web::http::header::{CacheControl, CacheDirective};
+use actix_web::{web, HttpMessage, HttpResponse, Responder};
+use actix_web_httpauth::extractors::basic::BasicAuth;
+use std::sync::Arc;
+
+/// OpenID Connect authorization endpoint.
+///
+/// Redirects the browser to the login page, or back to the client with an
+/// `error` parameter when the request is invalid.
+pub async fn authorize(
+    usecase: web::Data<Arc<OidcUseCase>>,
+    query: web::Query<AuthorizeQuery>,
+) -> Result<impl Responder, AppError> {
+    let location = usecase.authorize(query.into_inner()).await?;
+
+    Ok(HttpResponse::Found()
+        .append_header(("Location", location))
+        .finish())
+}
+
+/// Completes an authorization request for the signed-in user.
+///
+/// Called by the login page with the user's access token. Returns the client
+/// redirect carrying the authorization code.
+pub async fn complete_authorization(
+    usecase: web::Data<Arc<OidcUseCase>>,
+    body: web::Json<CompleteAuthorizationRequest>,
+    req: actix_web::HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or_else(|| AppError::Unauthorized("Unauthorized".to_string()))?;
+
+    let redirect_to = usecase
+        .complete_authorization(&body.authorization_request, &claims)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(SuccessResponseDTO::new(
+        "Authorization completed successfully",
+        AuthorizationRedirectResponse { redirect_to },
+    )))
+}
+
+/// OAuth 2.0 device authorization endpoint (RFC 8628).
+///
+/// Clients authenticate as at the token endpoint. Responds in the RFC format
+/// rather than the usual envelope.
+pub async fn device_authorization(
+    usecase: web::Data<Arc<OidcUseCase>>,
+    basic_auth: Option<BasicAuth>,
+    form: web::Form<DeviceAuthorizationRequest>,
+) -> Result<impl Responder, AppError> {
+    let mut device_request = form.into_inner();
+    if let Some(credentials) = basic_auth {
+        device_request.client_id = Some(credentials.user_id().to_string());
+        device_request.client_secret = credentials.password().map(str::to_string);
+    }
+
+    let response = usecase.device_authorization(device_request).await?;
+
+    Ok(HttpResponse::Ok()
+        .insert_header(CacheControl(vec![CacheDirective::NoStore]))
+        .json(response))
+}
+
+/// Looks up the device behind a user code for the signed-in user.
+///
+/// Called by the web app's device page before asking the user to approve.
+pub async fn find_device(
+    usecase: web::Data<Arc<OidcUseCase>>,
+    query: web::Query<DeviceCodeQuery>,
+    req: actix_web::HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or_else(|| AppError::Unauthorized("Unauthorized".to_string()))?;
+
+    let device = usecase
+        .find_device_authorization(&query.user_code, &claims)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(SuccessResponseDTO::new(
+        "Device retrieved successfully",
+        device,
+    )))
+}
+
+/// Approves or denies a device for the signed-in user.
+pub async fn decide_device(
+    usecase: web::Data<Arc<OidcUseCase>>,
+    body: web::Json<DeviceVerificationRequest>,
+    req: actix_web::HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or_else(|| AppError::Unauthorized("Unauthorized".to_string()))?;
+
+    let device = usecase
+        .decide_device_authorization(&body.user_code, body.approve, &claims)
+        .await?;
+
+    let message = if body.approve {
+        "Device approved successfully"
+    } else {
+        "Device denied successfully"
+    };
+    Ok(HttpResponse::Ok().json(SuccessResponseDTO::new(message, device)))
+}
+
+/// OAuth 2.0 token endpoint (authorization_code grant with PKCE, client
+/// credentials and device code grants).
+///
+/// Confidential clients may authenticate with HTTP Basic credentials
+/// (`client_secret_basic`) instead of form fields. Responds in the RFC 6749
+/// format rather than the usual envelope so that standard client libraries
+/// can consume it.
+pub async fn token(
+    usecase: web::Data<Arc<OidcUseCase>>,
+    basic_auth: Option<BasicAuth>,
+    form: web::Form<TokenRequest>,
+) -> Result<impl Responder, AppError> {
+    let mut token_request = form.into_inner();
+    if let Some(credentials) = basic_auth {
+        token_request.client_id = Some(credentials.user_id().to_string());
+        token_request.client_secret = credentials.password().map(str::to_string);
+    }
+
+    let response = usecase.exchange_token(token_request).await?;
+
+    Ok(HttpResponse::Ok()
+        .insert_header(CacheControl(vec![CacheDirective::NoStore]))
+        .json(response))
+}
+
+/// OpenID Connect UserInfo endpoint.
+pub async fn userinfo(
+    usecase: web::Data<Arc<OidcUseCase>>,
+    req: actix_web::HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or_else(|| AppError::Unauthorized("Unauthorized".to_string()))?;
+
+    let userinfo = usecase.userinfo(&claims).await?;
+
+    Ok(HttpResponse::Ok().json(userinfo))
+}
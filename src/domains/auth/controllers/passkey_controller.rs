@@ -0,0 +1,127 @@
+use crate::domains::auth::controllers::auth_controller::refresh_token_cookie;
+use crate::domains::auth::usecases::auth_usecase::AuthUseCase;
+use crate::domains::auth::usecases::passkey_usecase::PasskeyUseCase;
+use crate::domains::common::dtos::response_dto::SuccessResponseDTO;
+use crate::domains::common::errors::AppError;
+use crate::domains::tenant::middlewares::api_key_middleware::TenantId;
+use crate::domains::user::dtos::passkey_dto::{
+    PasskeyLoginOptionsRequest, PasskeyLoginRequest, PasskeyRegisterRequest,
+};
+use actix_web::{web, HttpMessage, HttpResponse, Responder};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Extracts the tenant ID set by ApiKeyMiddleware.
+fn tenant_id_from_request(req: &actix_web::HttpRequest) -> Result<Uuid, AppError> {
+    req.extensions()
+        .get::<TenantId>()
+        .map(|id| id.0)
+        .ok_or_else(|| AppError::NotFound("Tenant ID not found in request context".to_string()))
+}
+
+/// Starts passkey registration for the authenticated user.
+///
+/// Returns `PublicKeyCredentialCreationOptions` with base64url-encoded binary fields.
+pub async fn registration_options(
+    usecase: web::Data<Arc<PasskeyUseCase>>,
+    req: actix_web::HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let user_id = AuthUseCase::extract_user_id_from_request(&req)?;
+    let tenant_id = tenant_id_from_request(&req)?;
+    let options = usecase.registration_options(user_id, tenant_id).await?;
+
+    Ok(HttpResponse::Ok().json(SuccessResponseDTO::new(
+        "Passkey registration options generated",
+        options,
+    )))
+}
+
+/// Completes passkey registration with the authenticator's attestation.
+pub async fn register_passkey(
+    usecase: web::Data<Arc<PasskeyUseCase>>,
+    body: web::Json<PasskeyRegisterRequest>,
+    req: actix_web::HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let user_id = AuthUseCase::extract_user_id_from_request(&req)?;
+    let tenant_id = tenant_id_from_request(&req)?;
+    let passkey = usecase
+        .register(user_id, tenant_id, body.into_inner(), &req)
+        .await?;
+
+    Ok(HttpResponse::Created().json(SuccessResponseDTO::new(
+        "Passkey registered successfully",
+        passkey,
+    )))
+}
+
+/// Starts a passkey login.
+///
+/// Returns `PublicKeyCredentialRequestOptions` with base64url-encoded binary fields.
+pub async fn login_options(
+    usecase: web::Data<Arc<PasskeyUseCase>>,
+    body: Option<web::Json<PasskeyLoginOptionsRequest>>,
+    req: actix_web::HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let tenant_id = tenant_id_from_request(&req)?;
+    let body = body.map(|b| b.into_inner()).unwrap_or_default();
+    let options = usecase.login_options(tenant_id, body).await?;
+
+    Ok(HttpResponse::Ok().json(SuccessResponseDTO::new(
+        "Passkey login options generated",
+        options,
+    )))
+}
+
+/// Completes a passkey login.
+///
+/// Sets the refresh token cookie and returns the access token like a password login.
+pub async fn login_with_passkey(
+    usecase: web::Data<Arc<PasskeyUseCase>>,
+    auth_usecase: web::Data<Arc<AuthUseCase>>,
+    body: web::Json<PasskeyLoginRequest>,
+    req: actix_web::HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let tenant_id = tenant_id_from_request(&req)?;
+    let (auth_response, refresh_token) = usecase.login(tenant_id, body.into_inner(), &req).await?;
+
+    let cookie = refresh_token_cookie(refresh_token, auth_usecase.get_refresh_token_expiry());
+
+    Ok(HttpResponse::Ok()
+        .cookie(cookie)
+        .json(SuccessResponseDTO::new(
+            "Login successful",
+            serde_json::json!({
+                "access_token": auth_response.access_token
+            }),
+        )))
+}
+
+/// Lists the authenticated user's passkeys.
+pub async fn list_passkeys(
+    usecase: web::Data<Arc<PasskeyUseCase>>,
+    req: actix_web::HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let user_id = AuthUseCase::extract_user_id_from_request(&req)?;
+    let passkeys = usecase.list_passkeys(user_id).await?;
+
+    Ok(HttpResponse::Ok().json(SuccessResponseDTO::new(
+        "Passkeys retrieved successfully",
+        passkeys,
+    )))
+}
+
+/// Removes one of the authenticated user's passkeys.
+pub async fn delete_passkey(
+    usecase: web::Data<Arc<PasskeyUseCase>>,
+    id: web::Path<Uuid>,
+    req: actix_web::HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let user_id = AuthUseCase::extract_user_id_from_request(&req)?;
+    usecase
+        .delete_passkey(user_id, id.into_inner(), &req)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(SuccessResponseDTO::<()>::no_data(
+        "Passkey deleted successfully",
+    )))
+}
@@ -0,0 +1,53 @@
+use crate::domains::auth::usecases::password_reset_usecase::PasswordResetUseCase;
+use crate::domains::common::dtos::response_dto::SuccessResponseDTO;
+use crate::domains::common::errors::AppError;
+use crate::domains::tenant::middlewares::api_key_middleware::TenantId;
+use crate::domains::user::dtos::password_reset_dto::{ForgotPasswordRequest, ResetPasswordRequest};
+use actix_web::{web, HttpMessage, HttpResponse, Responder};
+use std::sync::Arc;
+
+/// Requests a password reset link.
+///
+/// The response is identical whether or not the email is registered.
+pub async fn forgot_password(
+    usecase: web::Data<Arc<PasswordResetUseCase>>,
+    body: web::Json<ForgotPasswordRequest>,
+    req: actix_web::HttpRequest,
+) -> Result<impl Responder, AppError> {
+    // Extract tenant_id from request extensions (set by ApiKeyMiddleware)
+    let tenant_id = req
+        .extensions()
+        .get::<TenantId>()
+        .map(|id| id.0)
+        .ok_or_else(|| AppError::NotFound("Tenant ID not found in request context".to_string()))?;
+
+    usecase
+        .forgot_password(body.into_inner(), tenant_id, &req)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(SuccessResponseDTO::<()>::no_data(
+        "If the email is registered, a password reset link has been sent",
+    )))
+}
+
+/// Sets a new password with the token from a reset link. All sessions are revoked.
+pub async fn reset_password(
+    usecase: web::Data<Arc<PasswordResetUseCase>>,
+    body: web::Json<ResetPasswordRequest>,
+    req: actix_web::HttpRequest,
+) -> Result<impl Responder, AppError> {
+    // Extract tenant_id from request extensions (set by ApiKeyMiddleware)
+    let tenant_id = req
+        .extensions()
+        .get::<TenantId>()
+        .map(|id| id.0)
+        .ok_or_else(|| AppError::NotFound("Tenant ID not found in request context".to_string()))?;
+
+    usecase
+        .reset_password(body.into_inner(), tenant_id, &req)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(SuccessResponseDTO::<()>::no_data(
+        "Password reset successfully",
+    )))
+}
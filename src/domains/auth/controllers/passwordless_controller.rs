@@ -0,0 +1,119 @@
+use crate::domains::auth::controllers::auth_controller::refresh_token_cookie;
+use crate::domains::auth::usecases::auth_usecase::AuthUseCase;
+use crate::domains::auth::usecases::passwordless_usecase::PasswordlessUseCase;
+use crate::domains::common::dtos::response_dto::SuccessResponseDTO;
+use crate::domains::common::errors::AppError;
+use crate::domains::common::utils::config::Config;
+use crate::domains::tenant::middlewares::api_key_middleware::TenantId;
+use crate::domains::user::dtos::auth_dto::LoginResult;
+use crate::domains::user::dtos::passwordless_dto::{
+    PasswordlessConsumeRequest, PasswordlessRequest,
+};
+use actix_web::{
+    cookie::{Cookie, SameSite},
+    web, HttpMessage, HttpResponse, Responder,
+};
+use std::sync::Arc;
+
+/// Name of the cookie binding a passwordless sign-in to the requesting browser.
+const DEVICE_COOKIE: &str = "passwordless_device";
+
+/// Extracts the tenant ID set by ApiKeyMiddleware.
+fn tenant_id_from_request(req: &actix_web::HttpRequest) -> Result<uuid::Uuid, AppError> {
+    req.extensions()
+        .get::<TenantId>()
+        .map(|id| id.0)
+        .ok_or_else(|| AppError::NotFound("Tenant ID not found in request context".to_string()))
+}
+
+/// Emails a sign-in link and code and sets the device cookie.
+///
+/// The response is identical whether or not the email is registered.
+pub async fn request_passwordless(
+    usecase: web::Data<Arc<PasswordlessUseCase>>,
+    body: web::Json<PasswordlessRequest>,
+    req: actix_web::HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let tenant_id = tenant_id_from_request(&req)?;
+    let device_secret = usecase.request(body.into_inner(), tenant_id, &req).await?;
+
+    let cookie = device_cookie(device_secret, Config::get().passwordless_token_expiry);
+
+    Ok(HttpResponse::Ok()
+        .cookie(cookie)
+        .json(SuccessResponseDTO::<()>::no_data(
+            "If the email is registered, a sign-in link and code have been sent",
+        )))
+}
+
+/// Completes a passwordless sign-in with the link token or code.
+///
+/// Responds like a password login: sets the refresh token cookie and returns
+/// the access token, or returns an MFA challenge.
+pub async fn consume_passwordless(
+    usecase: web::Data<Arc<PasswordlessUseCase>>,
+    auth_usecase: web::Data<Arc<AuthUseCase>>,
+    body: web::Json<PasswordlessConsumeRequest>,
+    req: actix_web::HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let tenant_id = tenant_id_from_request(&req)?;
+    let device_secret = req.cookie(DEVICE_COOKIE).map(|c| c.value().to_string());
+
+    let result = usecase
+        .consume(body.into_inner(), tenant_id, device_secret.as_deref(), &req)
+        .await?;
+
+    // The challenge is consumed, so the device cookie is no longer needed
+    let cleared = device_cookie(String::new(), 0);
+
+    let (auth_response, refresh_token) = match result {
+        LoginResult::Authenticated(auth_response, refresh_token) => (auth_response, refresh_token),
+        LoginResult::MfaRequired(challenge) => {
+            // No refresh cookie until the second factor is verified
+            return Ok(HttpResponse::Ok()
+                .cookie(cleared)
+                .json(SuccessResponseDTO::new(
+                    "MFA verification required",
+                    serde_json::json!({
+                        "mfa_required": true,
+                        "mfa_token": challenge.mfa_token,
+                        "expires_in": challenge.expires_in
+                    }),
+                )));
+        }
+    };
+
+    let cookie = refresh_token_cookie(refresh_token, auth_usecase.get_refresh_token_expiry());
+
+    Ok(HttpResponse::Ok()
+        .cookie(cookie)
+        .cookie(cleared)
+        .json(SuccessResponseDTO::new(
+            "Login successful",
+            serde_json::json!({
+                "access_token": auth_response.access_token
+            }),
+        )))
+}
+
+/// Builds the HTTP-only device cookie, honoring `COOKIE_DOMAIN`.
+///
+/// Pass an empty value with `max_age_secs = 0` to clear the cookie.
+fn device_cookie(value: String, max_age_secs: i64) -> Cookie<'static> {
+    let cookie_domain = std::env::var("COOKIE_DOMAIN").ok();
+
+    let mut cookie_builder = Cookie::build(DEVICE_COOKIE, value)
+        .path("/")
+        .http_only(true)
+        .secure(true)
+        .same_site(SameSite::None)
+        .max_age(actix_web::cookie::time::Duration::seconds(max_age_secs));
+
+    if let Some(domain) = cookie_domain {
+        if !domain.is_empty() {
+            cookie_builder = cookie_builder.domain(domain);
+        }
+    }
+
+    cookie_builder.finish()
+}
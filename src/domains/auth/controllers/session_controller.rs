@@ -0,0 +1,50 @@
+use crate::domains::auth::usecases::auth_usecase::AuthUseCase;
+use crate::domains::auth::usecases::session_usecase::SessionUseCase;
+use crate::domains::common::dtos::response_dto::SuccessResponseDTO;
+use crate::domains::common::errors::AppError;
+use actix_web::{web, HttpResponse, Responder};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Lists the authenticated user's active sessions.
+pub async fn list_sessions(
+    usecase: web::Data<Arc<SessionUseCase>>,
+    req: actix_web::HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let user_id = AuthUseCase::extract_user_id_from_request(&req)?;
+    let sessions = usecase.list_sessions(user_id, &req).await?;
+
+    Ok(HttpResponse::Ok().json(SuccessResponseDTO::new(
+        "Sessions retrieved successfully",
+        sessions,
+    )))
+}
+
+/// Revokes one of the authenticated user's sessions.
+pub async fn revoke_session(
+    usecase: web::Data<Arc<SessionUseCase>>,
+    id: web::Path<Uuid>,
+    req: actix_web::HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let user_id = AuthUseCase::extract_user_id_from_request(&req)?;
+    usecase
+        .revoke_session(user_id, id.into_inner(), &req)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(SuccessResponseDTO::<()>::no_data(
+        "Session revoked successfully",
+    )))
+}
+
+/// Revokes all of the authenticated user's sessions except the current one.
+pub async fn revoke_other_sessions(
+    usecase: web::Data<Arc<SessionUseCase>>,
+    req: actix_web::HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let user_id = AuthUseCase::extract_user_id_from_request(&req)?;
+    usecase.revoke_other_sessions(user_id, &req).await?;
+
+    Ok(HttpResponse::Ok().json(SuccessResponseDTO::<()>::no_data(
+        "Other sessions revoked successfully",
+    )))
+}
@@ -0,0 +1,35 @@
+use crate::domains::auth::usecases::signing_key_usecase::SigningKeyUseCase;
+use crate::domains::common::dtos::response_dto::SuccessResponseDTO;
+use crate::domains::common::errors::AppError;
+use actix_web::{web, HttpResponse, Responder};
+use std::sync::Arc;
+
+/// Lists the JWT signing keys (Internal Admin only).
+///
+/// It must be protected by TenantSecretMiddleware.
+pub async fn list_signing_keys(
+    usecase: web::Data<Arc<SigningKeyUseCase>>,
+) -> Result<impl Responder, AppError> {
+    let keys = usecase.list_keys().await?;
+
+    Ok(HttpResponse::Ok().json(SuccessResponseDTO::new(
+        "Signing keys retrieved successfully",
+        keys,
+    )))
+}
+
+/// Rotates the JWT signing key (Internal Admin only).
+///
+/// New tokens are signed with a fresh key while tokens signed by the previous
+/// key stay valid until they expire. It must be protected by
+/// TenantSecretMiddleware.
+pub async fn rotate_signing_key(
+    usecase: web::Data<Arc<SigningKeyUseCase>>,
+) -> Result<impl Responder, AppError> {
+    let key = usecase.rotate().await?;
+
+    Ok(HttpResponse::Ok().json(SuccessResponseDTO::new(
+        "Signing key rotated successfully",
+        key,
+    )))
+}
@@ -0,0 +1,71 @@
+use crate::domains::auth::dtos::oidc_dto::TokenIntrospectionRequest;
+use crate::domains::auth::usecases::token_usecase::TokenUseCase;
+use crate::domains::common::errors::AppError;
+use crate::domains::tenant::middlewares::api_key_middleware::TenantId;
+use actix_web::http::header::{CacheControl, CacheDirective};
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse, Responder};
+use actix_web_httpauth::extractors::basic::BasicAuth;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Resolves the tenant the caller acts for: the tenant of the API key, or
+/// the tenant of the client authenticating with HTTP Basic credentials or
+/// `client_id` / `client_secret` form fields.
+async fn caller_tenant(
+    usecase: &TokenUseCase,
+    req: &HttpRequest,
+    basic_auth: Option<BasicAuth>,
+    form: &TokenIntrospectionRequest,
+) -> Result<Uuid, AppError> {
+    if let Some(tenant_id) = req.extensions().get::<TenantId>() {
+        return Ok(tenant_id.0);
+    }
+
+    match basic_auth {
+        Some(credentials) => {
+            usecase
+                .authenticate_client(Some(credentials.user_id()), credentials.password())
+                .await
+        }
+        None => {
+            usecase
+                .authenticate_client(form.client_id.as_deref(), form.client_secret.as_deref())
+                .await
+        }
+    }
+}
+
+/// OAuth 2.0 token introspection endpoint (RFC 7662).
+///
+/// Responds in the RFC format rather than the usual envelope.
+pub async fn introspect(
+    usecase: web::Data<Arc<TokenUseCase>>,
+    req: HttpRequest,
+    basic_auth: Option<BasicAuth>,
+    form: web::Form<TokenIntrospectionRequest>,
+) -> Result<impl Responder, AppError> {
+    let tenant_id = caller_tenant(&usecase, &req, basic_auth, &form).await?;
+
+    let response = usecase.introspect(tenant_id, &form.token).await?;
+
+    Ok(HttpResponse::Ok()
+        .insert_header(CacheControl(vec![CacheDirective::NoStore]))
+        .json(response))
+}
+
+/// OAuth 2.0 token revocation endpoint (RFC 7009).
+///
+/// Always answers `200 OK` with an empty body once the caller is
+/// authenticated, whether or not the token was valid.
+pub async fn revoke(
+    usecase: web::Data<Arc<TokenUseCase>>,
+    req: HttpRequest,
+    basic_auth: Option<BasicAuth>,
+    form: web::Form<TokenIntrospectionRequest>,
+) -> Result<impl Responder, AppError> {
+    let tenant_id = caller_tenant(&usecase, &req, basic_auth, &form).await?;
+
+    usecase.revoke(tenant_id, &form.token).await?;
+
+    Ok(HttpResponse::Ok().finish())
+}
@@ -0,0 +1,31 @@
+use crate::domains::auth::usecases::auth_usecase::AuthUseCase;
+use crate::domains::auth::usecases::oidc_usecase::OidcUseCase;
+use actix_web::http::header::{CacheControl, CacheDirective};
+use actix_web::{web, HttpResponse, Responder};
+use std::sync::Arc;
+
+/// Publishes the public JWT signing keys as a JWK Set (RFC 7517).
+///
+/// The body is the bare JWK Set rather than the usual response envelope so that
+/// standard JWT libraries can consume it directly.
+pub async fn jwks(usecase: web::Data<Arc<AuthUseCase>>) -> impl Responder {
+    HttpResponse::Ok()
+        .insert_header(CacheControl(vec![
+            CacheDirective::Public,
+            CacheDirective::MaxAge(300),
+        ]))
+        .json(usecase.jwks())
+}
+
+/// Publishes the OpenID Provider metadata (OpenID Connect Discovery 1.0).
+///
+/// Client libraries read the endpoints, scopes and signing algorithms from
+/// here instead of having them configured by hand.
+pub async fn openid_configuration(usecase: web::Data<Arc<OidcUseCase>>) -> impl Responder {
+    HttpResponse::Ok()
+        .insert_header(CacheControl(vec![
+            CacheDirective::Public,
+            CacheDirective::MaxAge(300),
+        ]))
+        .json(usecase.discovery())
+}
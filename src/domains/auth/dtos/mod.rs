@@ -0,0 +1,2 @@
+pub mod oidc_dto;
+pub mod signing_key_dto;
@@ -0,0 +1,214 @@
+use crate::domains::common::utils::jwt::Actor;
+use serde::{Deserialize, Serialize};
+
+/// Query parameters of the OpenID Connect authorization endpoint.
+///
+/// `client_id` is a registered client, or the tenant ID for tenants without
+/// registered clients. Only the authorization code flow with PKCE
+/// (`code_challenge_method=S256`) is supported.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AuthorizeQuery {
+    pub response_type: Option<String>,
+    pub client_id: Option<String>,
+    pub redirect_uri: Option<String>,
+    pub scope: Option<String>,
+    pub state: Option<String>,
+    pub nonce: Option<String>,
+    pub code_challenge: Option<String>,
+    pub code_challenge_method: Option<String>,
+}
+
+/// Request DTO for completing an authorization request after the user has
+/// signed in on the login page.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CompleteAuthorizationRequest {
+    pub authorization_request: String,
+}
+
+/// Response DTO carrying the client redirect with the authorization code.
+#[derive(Debug, Clone, Serialize)]
+pub struct AuthorizationRedirectResponse {
+    pub redirect_to: String,
+}
+
+/// Form body of the token endpoint.
+///
+/// Confidential clients authenticate with `client_secret` here or with HTTP
+/// Basic credentials, which the controller copies into these fields.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TokenRequest {
+    pub grant_type: String,
+    pub code: Option<String>,
+    pub redirect_uri: Option<String>,
+    pub client_id: Option<String>,
+    pub client_secret: Option<String>,
+    pub code_verifier: Option<String>,
+    /// Requested scopes of the client credentials grant
+    pub scope: Option<String>,
+    /// Device code of the device authorization grant
+    pub device_code: Option<String>,
+    /// Access token exchanged by the token exchange grant
+    pub subject_token: Option<String>,
+    pub subject_token_type: Option<String>,
+    /// Client ID of the service the exchanged token is meant for
+    pub audience: Option<String>,
+    pub requested_token_type: Option<String>,
+}
+
+/// Token endpoint response (RFC 6749 section 5.1).
+#[derive(Debug, Clone, Serialize)]
+pub struct TokenResponse {
+    pub access_token: String,
+    pub token_type: String,
+    pub expires_in: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id_token: Option<String>,
+    pub scope: String,
+    /// Set by the token exchange grant (RFC 8693 section 2.2.1)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issued_token_type: Option<String>,
+}
+
+/// Form body of the device authorization endpoint (RFC 8628 section 3.1).
+///
+/// Confidential clients authenticate as at the token endpoint. Without
+/// `scope` every scope of the client is requested.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DeviceAuthorizationRequest {
+    pub client_id: Option<String>,
+    pub client_secret: Option<String>,
+    pub scope: Option<String>,
+}
+
+/// Device authorization response (RFC 8628 section 3.2).
+#[derive(Debug, Clone, Serialize)]
+pub struct DeviceAuthorizationResponse {
+    pub device_code: String,
+    pub user_code: String,
+    pub verification_uri: String,
+    pub verification_uri_complete: String,
+    pub expires_in: u64,
+    pub interval: u64,
+}
+
+/// Query of the device lookup endpoint, with the code the user entered.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DeviceCodeQuery {
+    pub user_code: String,
+}
+
+/// Request DTO for approving or denying a device on the web app.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DeviceVerificationRequest {
+    pub user_code: String,
+    pub approve: bool,
+}
+
+/// Response DTO describing the client asking for access, so the user can
+/// recognize the device before deciding.
+#[derive(Debug, Clone, Serialize)]
+pub struct DeviceVerificationResponse {
+    pub client_id: String,
+    pub client_name: String,
+    pub scope: String,
+    /// `pending`, `approved` or `denied`
+    pub status: String,
+}
+
+/// Form body of the introspection (RFC 7662) and revocation (RFC 7009)
+/// endpoints.
+///
+/// Callers authenticate with the tenant's `X-API-Key` or as a confidential
+/// client, with `client_secret` here or HTTP Basic credentials. The
+/// `token_type_hint` parameter is accepted but not needed, as the token type
+/// is part of the token.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TokenIntrospectionRequest {
+    pub token: String,
+    pub client_id: Option<String>,
+    pub client_secret: Option<String>,
+}
+
+/// Introspection response (RFC 7662 section 2.2).
+///
+/// Inactive tokens only carry `active: false`, whatever the reason.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct TokenIntrospectionResponse {
+    pub active: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+    /// Set for tokens of machine clients
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token_type: Option<String>,
+    /// `access_token` or `refresh_token`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token_use: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exp: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub iat: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sub: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub iss: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tenant_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub role: Option<String>,
+    /// Set for tokens issued by a token exchange
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aud: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub act: Option<Actor>,
+    /// Set for tokens an admin obtained to act as the user
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub impersonator: Option<String>,
+}
+
+/// UserInfo endpoint response, limited to the claims of the granted scopes.
+#[derive(Debug, Clone, Serialize)]
+pub struct UserInfoResponse {
+    pub sub: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preferred_username: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email_verified: Option<bool>,
+}
+
+/// OpenID Provider metadata served at `/.well-known/openid-configuration`.
+///
+/// Besides the standard fields it lists the service's own auth endpoints, so
+/// client libraries can configure themselves from a single URL.
+#[derive(Debug, Clone, Serialize)]
+pub struct OpenIdConfiguration {
+    pub issuer: String,
+    pub authorization_endpoint: String,
+    pub token_endpoint: String,
+    pub userinfo_endpoint: String,
+    pub jwks_uri: String,
+    pub end_session_endpoint: String,
+    pub introspection_endpoint: String,
+    pub revocation_endpoint: String,
+    pub device_authorization_endpoint: String,
+    pub login_endpoint: String,
+    pub refresh_endpoint: String,
+    pub verify_endpoint: String,
+    pub logout_endpoint: String,
+    pub scopes_supported: Vec<String>,
+    pub response_types_supported: Vec<String>,
+    pub response_modes_supported: Vec<String>,
+    pub grant_types_supported: Vec<String>,
+    pub subject_types_supported: Vec<String>,
+    pub id_token_signing_alg_values_supported: Vec<String>,
+    pub token_endpoint_auth_methods_supported: Vec<String>,
+    pub introspection_endpoint_auth_methods_supported: Vec<String>,
+    pub revocation_endpoint_auth_methods_supported: Vec<String>,
+    pub code_challenge_methods_supported: Vec<String>,
+    pub claims_supported: Vec<String>,
+}
@@ -0,0 +1,17 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+/// Response DTO describing a JWT signing key without its private material.
+///
+/// `retires_at` is when a verification-only key stops verifying tokens,
+/// which is once every token it signed has expired.
+#[derive(Debug, Clone, Serialize)]
+pub struct SigningKeyResponse {
+    pub kid: String,
+    pub algorithm: String,
+    pub status: String,
+    pub created_at: DateTime<Utc>,
+    pub deactivated_at: Option<DateTime<Utc>>,
+    pub retires_at: Option<DateTime<Utc>>,
+    pub retired_at: Option<DateTime<Utc>>,
+}
@@ -0,0 +1,34 @@
+use chrono::{DateTime, Utc};
+use sea_orm::entity::prelude::*;
+
+/// Key that signs new tokens. Exactly one key is active at a time.
+pub const KEY_STATUS_ACTIVE: &str = "active";
+
+/// Former active key that still verifies tokens issued before a rotation.
+pub const KEY_STATUS_VERIFICATION_ONLY: &str = "verification_only";
+
+/// Key that no longer verifies anything. Its private key is erased.
+pub const KEY_STATUS_RETIRED: &str = "retired";
+
+/// JWT signing key entity representing the jwt_signing_keys table in the database.
+///
+/// `private_key` holds the PKCS#8 PEM of asymmetric keys or the shared secret
+/// of `HS256` keys. It is never serialized; only the public half of a key is
+/// exposed through the JWKS endpoint.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "jwt_signing_keys")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub kid: String,
+    pub algorithm: String,
+    pub private_key: String,
+    pub status: String,
+    pub created_at: DateTime<Utc>,
+    pub deactivated_at: Option<DateTime<Utc>>,
+    pub retired_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
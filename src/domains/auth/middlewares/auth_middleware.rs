@@ -1,8 +1,23 @@
-use crate::domains::common::utils::jwt::JwtService;
-use actix_web::{dev::ServiceRequest, Error, HttpMessage};
+use crate::domains::auth::repositories::token_denylist_repository::{
+    TokenDenylistRepository, TokenDenylistRepositoryTrait,
+};
+use crate::domains::auth::usecases::oidc_usecase::issuer;
+use crate::domains::common::utils::jwt::{JwtService, TOKEN_TYPE_CLIENT};
+use actix_web::{dev::ServiceRequest, web, Error, HttpMessage};
 use actix_web_httpauth::extractors::bearer::BearerAuth;
 use uuid::Uuid;
 
+/// Access tokens a route accepts besides first-party user tokens.
+#[derive(Clone, Copy, PartialEq)]
+enum Accept {
+    /// First-party user tokens only.
+    Users,
+    /// Also tokens of machine clients.
+    Clients,
+    /// Also user tokens issued to OAuth clients, limited to their scopes.
+    Scoped,
+}
+
 /// Extracts and validates JWT token, then injects user_id into request extensions.
 ///
 /// This function is used by the JWT auth middleware to authenticate requests.
@@ -20,12 +35,87 @@ use uuid::Uuid;
 pub async fn validator(
     req: ServiceRequest,
     credentials: BearerAuth,
+) -> Result<ServiceRequest, (Error, ServiceRequest)> {
+    authenticate(req, credentials, Accept::Users).await
+}
+
+/// Same as `validator`, but also accepts tokens of machine clients issued by
+/// the client credentials grant.
+///
+/// Use it on routes that allow service principals. For client tokens only
+/// the claims are injected, not a user ID, so handlers must check
+/// `Claims::is_client` before treating `sub` as a user.
+pub async fn client_validator(
+    req: ServiceRequest,
+    credentials: BearerAuth,
+) -> Result<ServiceRequest, (Error, ServiceRequest)> {
+    authenticate(req, credentials, Accept::Clients).await
+}
+
+/// Same as `validator`, but also accepts user tokens issued to OAuth clients
+/// by the authorization code, device and token exchange grants.
+///
+/// Those tokens carry the scopes granted to the client, so only use it on
+/// routes that check them, such as `/oauth/userinfo`. Exchanged tokens whose
+/// audience is another service are still rejected.
+pub async fn scoped_validator(
+    req: ServiceRequest,
+    credentials: BearerAuth,
+) -> Result<ServiceRequest, (Error, ServiceRequest)> {
+    authenticate(req, credentials, Accept::Scoped).await
+}
+
+async fn authenticate(
+    req: ServiceRequest,
+    credentials: BearerAuth,
+    accept: Accept,
 ) -> Result<ServiceRequest, (Error, ServiceRequest)> {
     let token = credentials.token();
     let jwt_service = JwtService::new();
 
     match jwt_service.validate_token(token) {
         Ok(claims) => {
+            // Only access tokens may authenticate API calls. Refresh and MFA
+            // challenge tokens are exchanged at their dedicated endpoints.
+            let is_client = accept == Accept::Clients && claims.token_type == TOKEN_TYPE_CLIENT;
+            // Tokens of third-party clients must not reach the first-party API,
+            // and exchanged tokens are meant for the service in their audience
+            let is_foreign = ((claims.is_scoped() || claims.is_delegated())
+                && accept != Accept::Scoped)
+                || claims.aud.as_deref().is_some_and(|aud| aud != issuer());
+            if (claims.token_type != "access" && !is_client) || is_foreign {
+                let err = actix_web::error::InternalError::from_response(
+                    "Unauthorized",
+                    actix_web::HttpResponse::Unauthorized()
+                        .content_type("application/json")
+                        .body(r#"{"status":false,"message":"Unauthorized"}"#),
+                )
+                .into();
+                return Err((err, req));
+            }
+
+            // Reject tokens revoked by logout, password changes or /oauth/revoke
+            let denylist = req
+                .app_data::<web::Data<TokenDenylistRepository>>()
+                .cloned();
+            if let Some(denylist) = denylist {
+                // Fail closed: a token that cannot be checked is not accepted
+                let revoked = denylist.is_revoked(&claims).await.unwrap_or_else(|e| {
+                    log::error!("Token denylist check failed: {}", e);
+                    true
+                });
+                if revoked {
+                    let err = actix_web::error::InternalError::from_response(
+                        "Token revoked",
+                        actix_web::HttpResponse::Unauthorized()
+                            .content_type("application/json")
+                            .body(r#"{"status":false,"message":"Token revoked"}"#),
+                    )
+                    .into();
+                    return Err((err, req));
+                }
+            }
+
             // Check if tenant_id matches the one in request extensions (from ApiKeyMiddleware)
             let tenant_id_str = req
                 .extensions()
@@ -45,6 +135,12 @@ pub async fn validator(
                 }
             }
 
+            // Client tokens have no user
+            if is_client {
+                req.extensions_mut().insert(claims);
+                return Ok(req);
+            }
+
             // Parse user_id from claims
             match Uuid::parse_str(&claims.sub) {
                 Ok(user_id) => {
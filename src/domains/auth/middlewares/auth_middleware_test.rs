@@ -1,4 +1,4 @@
-use super::auth_middleware::validator;
+use super::auth_middleware::{client_validator, scoped_validator, validator};
 use actix_web::HttpMessage;
 use actix_web::{dev::ServiceRequest, http, test, FromRequest};
 use actix_web_httpauth::extractors::bearer::BearerAuth;
@@ -97,8 +97,16 @@ async fn test_auth_middleware_validator_expired_token() {
         role: "user".to_string(),
         exp: exp.timestamp(),
         iat: now.timestamp(),
+        iat_ms: None,
         token_type: "access".to_string(),
         jti: None,
+        scope: None,
+        aud: None,
+        act: None,
+        impersonator: None,
+        auth_time: None,
+        amr: None,
+        acr: None,
     };
 
     let token = encode(
@@ -132,3 +140,176 @@ async fn test_auth_middleware_validator_expired_token() {
     let body_str = std::str::from_utf8(&body_bytes).unwrap();
     assert!(body_str.contains("Token expired"));
 }
+
+#[actix_web::test]
+async fn test_auth_middleware_validator_rejects_mfa_token() {
+    use crate::domains::common::utils::config::Config;
+    use crate::domains::common::utils::jwt::JwtService;
+    Config::init_for_test();
+
+    let jwt_service = JwtService::new();
+    let token = jwt_service
+        .generate_mfa_token(
+            uuid::Uuid::new_v4(),
+            uuid::Uuid::new_v4(),
+            "user".to_string(),
+        )
+        .unwrap();
+
+    let req = test::TestRequest::default()
+        .insert_header((http::header::AUTHORIZATION, format!("Bearer {}", token)))
+        .to_srv_request();
+
+    let (req, mut payload) = req.into_parts();
+    let bearer_auth = BearerAuth::from_request(&req, &mut payload).await.unwrap();
+    let req = ServiceRequest::from_parts(req, payload);
+
+    let result = validator(req, bearer_auth).await;
+
+    assert!(result.is_err());
+    let (err, _) = result.err().unwrap();
+    assert_eq!(
+        err.error_response().status(),
+        http::StatusCode::UNAUTHORIZED
+    );
+}
+
+#[actix_web::test]
+async fn test_client_token_requires_client_validator() {
+    use crate::domains::common::utils::config::Config;
+    use crate::domains::common::utils::jwt::{Claims, JwtService};
+    Config::init_for_test();
+
+    let token = JwtService::new()
+        .generate_client_token(
+            "machine-client",
+            uuid::Uuid::new_v4(),
+            "reports:read".to_string(),
+        )
+        .unwrap();
+    let request = || async {
+        let req = test::TestRequest::default()
+            .insert_header((http::header::AUTHORIZATION, format!("Bearer {}", token)))
+            .to_srv_request();
+        let (req, mut payload) = req.into_parts();
+        let bearer_auth = BearerAuth::from_request(&req, &mut payload).await.unwrap();
+        (ServiceRequest::from_parts(req, payload), bearer_auth)
+    };
+
+    let (req, bearer_auth) = request().await;
+    let result = validator(req, bearer_auth).await;
+    let (err, _) = result.err().unwrap();
+    assert_eq!(
+        err.error_response().status(),
+        http::StatusCode::UNAUTHORIZED
+    );
+
+    let (req, bearer_auth) = request().await;
+    let req = client_validator(req, bearer_auth).await.unwrap();
+    let claims = req.extensions().get::<Claims>().cloned().unwrap();
+    assert!(claims.is_client());
+    assert_eq!(claims.sub, "machine-client");
+    assert!(req.extensions().get::<uuid::Uuid>().is_none());
+}
+
+#[actix_web::test]
+async fn test_scoped_token_is_only_accepted_by_scoped_validator() {
+    use crate::domains::common::utils::config::Config;
+    use crate::domains::common::utils::jwt::{Claims, JwtService};
+    Config::init_for_test();
+
+    let user_id = uuid::Uuid::new_v4();
+    let token = JwtService::new()
+        .generate_scoped_access_token(
+            user_id,
+            uuid::Uuid::new_v4(),
+            "admin".to_string(),
+            Some("openid email".to_string()),
+        )
+        .unwrap();
+    let request = || async {
+        let req = test::TestRequest::default()
+            .insert_header((http::header::AUTHORIZATION, format!("Bearer {}", token)))
+            .to_srv_request();
+        let (req, mut payload) = req.into_parts();
+        let bearer_auth = BearerAuth::from_request(&req, &mut payload).await.unwrap();
+        (ServiceRequest::from_parts(req, payload), bearer_auth)
+    };
+
+    let (req, bearer_auth) = request().await;
+    let (err, _) = validator(req, bearer_auth).await.err().unwrap();
+    assert_eq!(
+        err.error_response().status(),
+        http::StatusCode::UNAUTHORIZED
+    );
+
+    let (req, bearer_auth) = request().await;
+    let (err, _) = client_validator(req, bearer_auth).await.err().unwrap();
+    assert_eq!(
+        err.error_response().status(),
+        http::StatusCode::UNAUTHORIZED
+    );
+
+    let (req, bearer_auth) = request().await;
+    let req = scoped_validator(req, bearer_auth).await.unwrap();
+    let claims = req.extensions().get::<Claims>().cloned().unwrap();
+    assert!(claims.has_scope("openid"));
+    assert_eq!(req.extensions().get::<uuid::Uuid>().cloned(), Some(user_id));
+}
+
+#[actix_web::test]
+async fn test_exchanged_token_is_rejected_outside_its_audience() {
+    use crate::domains::common::utils::config::Config;
+    use crate::domains::common::utils::jwt::{Actor, JwtService};
+    Config::init_for_test();
+
+    let jwt_service = JwtService::new();
+    let subject = jwt_service
+        .validate_token(
+            &jwt_service
+                .generate_access_token(
+                    uuid::Uuid::new_v4(),
+                    uuid::Uuid::new_v4(),
+                    "admin".to_string(),
+                )
+                .unwrap(),
+        )
+        .unwrap();
+    let exchange = |audience: Option<&str>| {
+        jwt_service
+            .generate_delegated_token(
+                &subject,
+                "openid".to_string(),
+                audience.map(str::to_string),
+                Actor {
+                    sub: "gateway".to_string(),
+                    act: None,
+                },
+            )
+            .unwrap()
+    };
+    let request = |token: String| async move {
+        let req = test::TestRequest::default()
+            .insert_header((http::header::AUTHORIZATION, format!("Bearer {}", token)))
+            .to_srv_request();
+        let (req, mut payload) = req.into_parts();
+        let bearer_auth = BearerAuth::from_request(&req, &mut payload).await.unwrap();
+        (ServiceRequest::from_parts(req, payload), bearer_auth)
+    };
+
+    // Meant for another service
+    let token = exchange(Some("orders-service"));
+    let (req, bearer_auth) = request(token.clone()).await;
+    assert!(validator(req, bearer_auth).await.is_err());
+    let (req, bearer_auth) = request(token.clone()).await;
+    assert!(client_validator(req, bearer_auth).await.is_err());
+    let (req, bearer_auth) = request(token).await;
+    assert!(scoped_validator(req, bearer_auth).await.is_err());
+
+    // Without audience it is still limited to its scopes
+    let token = exchange(None);
+    let (req, bearer_auth) = request(token.clone()).await;
+    assert!(validator(req, bearer_auth).await.is_err());
+    let (req, bearer_auth) = request(token).await;
+    assert!(scoped_validator(req, bearer_auth).await.is_ok());
+}
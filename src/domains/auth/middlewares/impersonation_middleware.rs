@@ -0,0 +1,84 @@
+//! Impersonation Guard Middleware
+//!
+//! Refuses impersonation tokens on routes that change credentials or delete
+//! the account, so support staff acting as a user cannot take it over.
+
+use crate::domains::common::dtos::response_dto::ErrorResponseDTO;
+use crate::domains::common::utils::jwt::Claims;
+use actix_web::{
+    body::EitherBody,
+    dev::{Service, ServiceRequest, ServiceResponse, Transform},
+    http::header,
+    Error, HttpMessage, HttpResponse,
+};
+use futures_util::future::{ok, LocalBoxFuture, Ready};
+
+/// Rejects requests authenticated with an impersonation token with `403`.
+///
+/// Wrap individual routes inside a JWT protected scope with it; it reads the
+/// claims injected by `auth_middleware::validator`.
+#[derive(Clone)]
+pub struct DenyImpersonationMiddleware;
+
+impl<S, B> Transform<S, ServiceRequest> for DenyImpersonationMiddleware
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = DenyImpersonationMiddlewareService<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ok(DenyImpersonationMiddlewareService { service })
+    }
+}
+
+/// Service wrapper for the impersonation check.
+pub struct DenyImpersonationMiddlewareService<S> {
+    service: S,
+}
+
+impl<S, B> Service<ServiceRequest> for DenyImpersonationMiddlewareService<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(
+        &self,
+        ctx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(ctx)
+    }
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let impersonated = req
+            .extensions()
+            .get::<Claims>()
+            .is_some_and(Claims::is_impersonation);
+
+        if impersonated {
+            let res = HttpResponse::Forbidden()
+                .insert_header((header::CONTENT_TYPE, "application/json"))
+                .json(ErrorResponseDTO::<()> {
+                    status: false,
+                    message: "Not allowed while impersonating a user",
+                    details: None,
+                    result: None,
+                });
+            return Box::pin(async move { Ok(req.into_response(res.map_into_right_body())) });
+        }
+
+        let fut = self.service.call(req);
+        Box::pin(async move {
+            let res = fut.await?;
+            Ok(res.map_into_left_body())
+        })
+    }
+}
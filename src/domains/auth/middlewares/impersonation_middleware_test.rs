@@ -0,0 +1,52 @@
+use super::auth_middleware::validator;
+use super::impersonation_middleware::DenyImpersonationMiddleware;
+use crate::domains::common::utils::config::Config;
+use crate::domains::common::utils::jwt::JwtService;
+use actix_web::{http, test, web, App};
+use actix_web_httpauth::middleware::HttpAuthentication;
+use uuid::Uuid;
+
+#[actix_web::test]
+async fn test_deny_impersonation_middleware() {
+    Config::init_for_test();
+
+    let srv = test::init_service(
+        App::new().service(
+            web::scope("")
+                .wrap(HttpAuthentication::bearer(validator))
+                .route("/open", web::get().to(|| async { "ok" }))
+                .route(
+                    "/sensitive",
+                    web::get()
+                        .to(|| async { "ok" })
+                        .wrap(DenyImpersonationMiddleware),
+                ),
+        ),
+    )
+    .await;
+
+    let jwt_service = JwtService::new();
+    let (user_id, tenant_id) = (Uuid::new_v4(), Uuid::new_v4());
+    let own_token = jwt_service
+        .generate_access_token(user_id, tenant_id, "user".to_string())
+        .unwrap();
+    let impersonation_token = jwt_service
+        .generate_impersonation_token(user_id, tenant_id, "user".to_string(), Uuid::new_v4())
+        .unwrap();
+
+    let call = |uri: &'static str, token: &str| {
+        test::TestRequest::get()
+            .uri(uri)
+            .insert_header((http::header::AUTHORIZATION, format!("Bearer {}", token)))
+            .to_request()
+    };
+
+    let resp = test::call_service(&srv, call("/sensitive", &own_token)).await;
+    assert_eq!(resp.status(), http::StatusCode::OK);
+
+    let resp = test::call_service(&srv, call("/open", &impersonation_token)).await;
+    assert_eq!(resp.status(), http::StatusCode::OK);
+
+    let resp = test::call_service(&srv, call("/sensitive", &impersonation_token)).await;
+    assert_eq!(resp.status(), http::StatusCode::FORBIDDEN);
+}
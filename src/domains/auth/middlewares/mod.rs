@@ -1,3 +1,9 @@
 pub mod auth_middleware;
 #[cfg(test)]
 mod auth_middleware_test;
+pub mod impersonation_middleware;
+#[cfg(test)]
+mod impersonation_middleware_test;
+pub mod step_up_middleware;
+#[cfg(test)]
+mod step_up_middleware_test;
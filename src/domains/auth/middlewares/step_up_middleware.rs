@@ -0,0 +1,139 @@
+//! Step-up Authentication Middleware
+//!
+//! Requires a recent (and optionally multi-factor) login before sensitive
+//! actions such as deleting the account or changing the email address. Users
+//! refresh `auth_time` through `POST /auth/reauthenticate`.
+
+use crate::domains::common::dtos::response_dto::ErrorResponseDTO;
+use crate::domains::common::utils::config::Config;
+use crate::domains::common::utils::jwt::{Claims, ACR_MULTI_FACTOR};
+use actix_web::{
+    body::EitherBody,
+    dev::{Service, ServiceRequest, ServiceResponse, Transform},
+    http::header,
+    Error, HttpMessage, HttpResponse,
+};
+use chrono::Utc;
+use futures_util::future::{ok, LocalBoxFuture, Ready};
+use serde::Serialize;
+
+/// Machine-readable error code of step-up rejections.
+pub const INSUFFICIENT_AUTHENTICATION: &str = "insufficient_authentication";
+
+/// Details of a step-up rejection telling the client how to re-authenticate.
+#[derive(Serialize)]
+pub struct StepUpChallenge {
+    pub error: &'static str,
+    pub max_age: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub acr_values: Option<&'static str>,
+}
+
+/// Rejects tokens whose `auth_time` is older than `max_age` seconds, or that
+/// lack MFA when `require_mfa` is set, with `401 insufficient_authentication`.
+///
+/// Wrap individual routes inside a JWT protected scope with it; it reads the
+/// claims injected by `auth_middleware::validator`.
+#[derive(Clone)]
+pub struct StepUpMiddleware {
+    max_age: i64,
+    require_mfa: bool,
+}
+
+impl StepUpMiddleware {
+    /// Creates the guard with an explicit policy.
+    pub fn new(max_age: i64, require_mfa: bool) -> Self {
+        Self {
+            max_age,
+            require_mfa,
+        }
+    }
+
+    /// Creates the guard with `STEP_UP_MAX_AGE` and `STEP_UP_REQUIRE_MFA`.
+    pub fn from_config() -> Self {
+        let config = Config::get();
+        Self::new(config.step_up_max_age, config.step_up_require_mfa)
+    }
+
+    /// Returns true if the claims satisfy the policy.
+    fn is_satisfied(&self, claims: &Claims) -> bool {
+        let Some(authentication) = claims.authentication() else {
+            return false;
+        };
+        if Utc::now().timestamp() - authentication.time > self.max_age {
+            return false;
+        }
+        !self.require_mfa || authentication.is_multi_factor()
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for StepUpMiddleware
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = StepUpMiddlewareService<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ok(StepUpMiddlewareService {
+            service,
+            policy: self.clone(),
+        })
+    }
+}
+
+/// Service wrapper for the step-up check.
+pub struct StepUpMiddlewareService<S> {
+    service: S,
+    policy: StepUpMiddleware,
+}
+
+impl<S, B> Service<ServiceRequest> for StepUpMiddlewareService<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(
+        &self,
+        ctx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(ctx)
+    }
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let satisfied = req
+            .extensions()
+            .get::<Claims>()
+            .is_some_and(|claims| self.policy.is_satisfied(claims));
+
+        if !satisfied {
+            let res = HttpResponse::Unauthorized()
+                .insert_header((header::CONTENT_TYPE, "application/json"))
+                .json(ErrorResponseDTO {
+                    status: false,
+                    message: "Recent authentication required",
+                    details: Some(StepUpChallenge {
+                        error: INSUFFICIENT_AUTHENTICATION,
+                        max_age: self.policy.max_age,
+                        acr_values: self.policy.require_mfa.then_some(ACR_MULTI_FACTOR),
+                    }),
+                    result: None,
+                });
+            return Box::pin(async move { Ok(req.into_response(res.map_into_right_body())) });
+        }
+
+        let fut = self.service.call(req);
+        Box::pin(async move {
+            let res = fut.await?;
+            Ok(res.map_into_left_body())
+        })
+    }
+}
@@ -0,0 +1,90 @@
+use super::auth_middleware::validator;
+use super::step_up_middleware::StepUpMiddleware;
+use crate::domains::common::utils::config::Config;
+use crate::domains::common::utils::jwt::{
+    Authentication, JwtService, AMR_HARDWARE_KEY, AMR_MULTI_FACTOR, AMR_OTP, AMR_PASSWORD,
+};
+use actix_web::{http, test, web, App};
+use actix_web_httpauth::middleware::HttpAuthentication;
+use chrono::Utc;
+use uuid::Uuid;
+
+#[actix_web::test]
+async fn test_step_up_middleware() {
+    Config::init_for_test();
+
+    let srv = test::init_service(
+        App::new().service(
+            web::scope("")
+                .wrap(HttpAuthentication::bearer(validator))
+                .route(
+                    "/recent",
+                    web::get()
+                        .to(|| async { "ok" })
+                        .wrap(StepUpMiddleware::new(300, false)),
+                )
+                .route(
+                    "/mfa",
+                    web::get()
+                        .to(|| async { "ok" })
+                        .wrap(StepUpMiddleware::new(300, true)),
+                ),
+        ),
+    )
+    .await;
+
+    let jwt_service = JwtService::new();
+    let (user_id, tenant_id) = (Uuid::new_v4(), Uuid::new_v4());
+    let token = |authentication: Option<Authentication>| {
+        jwt_service
+            .generate_session_access_token(
+                user_id,
+                tenant_id,
+                "user".to_string(),
+                authentication.as_ref(),
+            )
+            .unwrap()
+    };
+    let password = token(Some(Authentication::now(&[AMR_PASSWORD])));
+    let mfa = token(Some(Authentication::now(&[
+        AMR_PASSWORD,
+        AMR_OTP,
+        AMR_MULTI_FACTOR,
+    ])));
+    let passkey = token(Some(Authentication::now(&[AMR_HARDWARE_KEY])));
+    let stale = token(Some(Authentication {
+        time: Utc::now().timestamp() - 301,
+        methods: vec![AMR_PASSWORD.to_string()],
+    }));
+    let unknown = token(None);
+
+    let call = |uri: &'static str, token: &str| {
+        test::TestRequest::get()
+            .uri(uri)
+            .insert_header((http::header::AUTHORIZATION, format!("Bearer {}", token)))
+            .to_request()
+    };
+
+    let resp = test::call_service(&srv, call("/recent", &password)).await;
+    assert_eq!(resp.status(), http::StatusCode::OK);
+
+    for token in [&mfa, &passkey] {
+        let resp = test::call_service(&srv, call("/mfa", token)).await;
+        assert_eq!(resp.status(), http::StatusCode::OK);
+    }
+
+    for token in [&stale, &unknown] {
+        let resp = test::call_service(&srv, call("/recent", token)).await;
+        assert_eq!(resp.status(), http::StatusCode::UNAUTHORIZED);
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert_eq!(body["details"]["error"], "insufficient_authentication");
+        assert_eq!(body["details"]["max_age"], 300);
+        assert!(body["details"].get("acr_values").is_none());
+    }
+
+    let resp = test::call_service(&srv, call("/mfa", &password)).await;
+    assert_eq!(resp.status(), http::StatusCode::UNAUTHORIZED);
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["details"]["error"], "insufficient_authentication");
+    assert_eq!(body["details"]["acr_values"], "aal2");
+}
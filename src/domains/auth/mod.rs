@@ -1,5 +1,7 @@
 pub mod auth_module;
 pub mod controllers;
+pub mod dtos;
+pub mod entities;
 pub mod middlewares;
 pub mod repositories;
 pub mod routes;
@@ -0,0 +1,109 @@
+use crate::domains::common::utils::request_helper;
+use crate::domains::common::{errors::AppError, infrastructures::rocksdb_connection::RocksDbCache};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Validated authorization request, waiting for the user to sign in.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuthorizationRequest {
+    pub tenant_id: Uuid,
+    pub client_id: String,
+    pub redirect_uri: String,
+    pub scope: String,
+    pub state: Option<String>,
+    pub nonce: Option<String>,
+    /// PKCE S256 challenge the token request must prove.
+    pub code_challenge: String,
+}
+
+/// Authorization code issued to a client, exchanged once at the token endpoint.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuthorizationCode {
+    pub request: AuthorizationRequest,
+    pub user_id: Uuid,
+    pub role: String,
+    /// When the user authenticated (Unix timestamp).
+    pub auth_time: i64,
+}
+
+#[async_trait]
+pub trait AuthorizationCodeRepositoryTrait: Send + Sync {
+    async fn save_request(
+        &self,
+        id: &str,
+        request: AuthorizationRequest,
+        ttl: Duration,
+    ) -> Result<(), AppError>;
+    /// Returns and deletes the stored request, so it can be completed once.
+    async fn take_request(&self, id: &str) -> Result<Option<AuthorizationRequest>, AppError>;
+    async fn save_code(
+        &self,
+        code: &str,
+        grant: AuthorizationCode,
+        ttl: Duration,
+    ) -> Result<(), AppError>;
+    /// Returns and deletes the stored grant, so each code can be redeemed once.
+    async fn take_code(&self, code: &str) -> Result<Option<AuthorizationCode>, AppError>;
+}
+
+/// Stores authorization requests and codes in RocksDB. Codes are keyed by
+/// their hash so a cache dump does not reveal redeemable codes.
+pub struct AuthorizationCodeRepository {
+    cache: Arc<RocksDbCache>,
+}
+
+impl AuthorizationCodeRepository {
+    pub fn new(cache: Arc<RocksDbCache>) -> Self {
+        Self { cache }
+    }
+}
+
+#[async_trait]
+impl AuthorizationCodeRepositoryTrait for AuthorizationCodeRepository {
+    async fn save_request(
+        &self,
+        id: &str,
+        request: AuthorizationRequest,
+        ttl: Duration,
+    ) -> Result<(), AppError> {
+        let key = format!("oidc_request:{}", id);
+        self.cache.set(&key, request, ttl);
+        Ok(())
+    }
+
+    async fn take_request(&self, id: &str) -> Result<Option<AuthorizationRequest>, AppError> {
+        let key = format!("oidc_request:{}", id);
+
+        let request: Option<AuthorizationRequest> = self.cache.get(&key);
+        if request.is_some() {
+            self.cache.del(&key);
+        }
+
+        Ok(request)
+    }
+
+    async fn save_code(
+        &self,
+        code: &str,
+        grant: AuthorizationCode,
+        ttl: Duration,
+    ) -> Result<(), AppError> {
+        let key = format!("oidc_code:{}", request_helper::hash_token(code));
+        self.cache.set(&key, grant, ttl);
+        Ok(())
+    }
+
+    async fn take_code(&self, code: &str) -> Result<Option<AuthorizationCode>, AppError> {
+        let key = format!("oidc_code:{}", request_helper::hash_token(code));
+
+        let grant: Option<AuthorizationCode> = self.cache.get(&key);
+        if grant.is_some() {
+            self.cache.del(&key);
+        }
+
+        Ok(grant)
+    }
+}
@@ -0,0 +1,117 @@
+use crate::domains::common::utils::request_helper;
+use crate::domains::common::{errors::AppError, infrastructures::rocksdb_connection::RocksDbCache};
+use async_trait::async_trait;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Decision of the user on a device authorization.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum DeviceAuthorizationStatus {
+    Pending,
+    Approved {
+        user_id: Uuid,
+        role: String,
+        /// When the user authenticated (Unix timestamp).
+        auth_time: i64,
+    },
+    Denied,
+}
+
+/// Device authorization request (RFC 8628), waiting for a user to enter its
+/// user code and for the device to poll the token endpoint.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeviceAuthorization {
+    /// Hash of the device code the device polls with.
+    pub device_code_hash: String,
+    /// Normalized user code the user enters.
+    pub user_code: String,
+    pub tenant_id: Uuid,
+    pub client_id: String,
+    pub scope: String,
+    pub status: DeviceAuthorizationStatus,
+    /// Minimum seconds between polls, raised when the device polls too fast.
+    pub interval: u64,
+    /// Last poll of the token endpoint (Unix timestamp).
+    pub last_polled_at: Option<i64>,
+    /// Unix timestamp after which the codes are no longer valid.
+    pub expires_at: i64,
+}
+
+#[async_trait]
+pub trait DeviceCodeRepositoryTrait: Send + Sync {
+    /// Stores the authorization under both of its codes until it expires,
+    /// replacing any previous version.
+    async fn save(&self, authorization: &DeviceAuthorization) -> Result<(), AppError>;
+    async fn find_by_device_code(
+        &self,
+        device_code: &str,
+    ) -> Result<Option<DeviceAuthorization>, AppError>;
+    async fn find_by_user_code(
+        &self,
+        user_code: &str,
+    ) -> Result<Option<DeviceAuthorization>, AppError>;
+    async fn delete(&self, authorization: &DeviceAuthorization) -> Result<(), AppError>;
+}
+
+/// Stores device authorizations in RocksDB. Device codes are keyed by their
+/// hash so a cache dump does not reveal codes a device could redeem; user
+/// codes point to that hash.
+pub struct DeviceCodeRepository {
+    cache: Arc<RocksDbCache>,
+}
+
+impl DeviceCodeRepository {
+    pub fn new(cache: Arc<RocksDbCache>) -> Self {
+        Self { cache }
+    }
+}
+
+#[async_trait]
+impl DeviceCodeRepositoryTrait for DeviceCodeRepository {
+    async fn save(&self, authorization: &DeviceAuthorization) -> Result<(), AppError> {
+        let remaining = authorization.expires_at - Utc::now().timestamp();
+        if remaining <= 0 {
+            return Ok(());
+        }
+        let ttl = Duration::from_secs(remaining as u64);
+
+        let key = format!("device_code:{}", authorization.device_code_hash);
+        self.cache.set(&key, authorization, ttl);
+        let key = format!("device_user_code:{}", authorization.user_code);
+        self.cache
+            .set(&key, authorization.device_code_hash.clone(), ttl);
+        Ok(())
+    }
+
+    async fn find_by_device_code(
+        &self,
+        device_code: &str,
+    ) -> Result<Option<DeviceAuthorization>, AppError> {
+        let key = format!("device_code:{}", request_helper::hash_token(device_code));
+        Ok(self.cache.get(&key))
+    }
+
+    async fn find_by_user_code(
+        &self,
+        user_code: &str,
+    ) -> Result<Option<DeviceAuthorization>, AppError> {
+        let key = format!("device_user_code:{}", user_code);
+        let Some(device_code_hash) = self.cache.get::<String>(&key) else {
+            return Ok(None);
+        };
+
+        let key = format!("device_code:{}", device_code_hash);
+        Ok(self.cache.get(&key))
+    }
+
+    async fn delete(&self, authorization: &DeviceAuthorization) -> Result<(), AppError> {
+        self.cache
+            .del(&format!("device_code:{}", authorization.device_code_hash));
+        self.cache
+            .del(&format!("device_user_code:{}", authorization.user_code));
+        Ok(())
+    }
+}
@@ -0,0 +1,153 @@
+use crate::domains::auth::entities::jwt_signing_key::{
+    self, Entity as JwtSigningKeyEntity, Model as JwtSigningKey, KEY_STATUS_ACTIVE,
+    KEY_STATUS_RETIRED, KEY_STATUS_VERIFICATION_ONLY,
+};
+use crate::domains::common::errors::AppError;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sea_orm::sea_query::Expr;
+use sea_orm::*;
+use std::sync::Arc;
+
+/// Trait defining JWT signing key repository operations.
+///
+/// This trait abstracts database operations for the JWT key ring, so that
+/// every server instance signs with the same active key and accepts the same
+/// verification-only keys.
+#[async_trait]
+pub trait JwtSigningKeyRepositoryTrait: Send + Sync {
+    /// Lists all keys including retired ones, newest first.
+    async fn find_all(&self) -> Result<Vec<JwtSigningKey>, AppError>;
+
+    /// Lists the active and verification-only keys, newest first.
+    async fn find_unretired(&self) -> Result<Vec<JwtSigningKey>, AppError>;
+
+    /// Stores a new active key and demotes the previous active key to
+    /// verification-only in the same transaction.
+    async fn activate(
+        &self,
+        kid: String,
+        algorithm: String,
+        private_key: String,
+    ) -> Result<JwtSigningKey, AppError>;
+
+    /// Retires verification-only keys deactivated before `cutoff`, erasing
+    /// their private key, and returns how many were retired.
+    async fn retire_deactivated_before(&self, cutoff: DateTime<Utc>) -> Result<u64, AppError>;
+
+    /// Replaces the stored private key material of a key.
+    async fn update_private_key(&self, kid: String, private_key: String) -> Result<(), AppError>;
+}
+
+/// JWT signing key repository implementation using SeaORM.
+pub struct JwtSigningKeyRepository {
+    db: Arc<DatabaseConnection>,
+}
+
+impl JwtSigningKeyRepository {
+    /// Creates a new JwtSigningKeyRepository instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - Arc-wrapped database connection
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl JwtSigningKeyRepositoryTrait for JwtSigningKeyRepository {
+    async fn find_all(&self) -> Result<Vec<JwtSigningKey>, AppError> {
+        JwtSigningKeyEntity::find()
+            .order_by_desc(jwt_signing_key::Column::CreatedAt)
+            .all(&*self.db)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+
+    async fn find_unretired(&self) -> Result<Vec<JwtSigningKey>, AppError> {
+        JwtSigningKeyEntity::find()
+            .filter(jwt_signing_key::Column::Status.ne(KEY_STATUS_RETIRED))
+            .order_by_desc(jwt_signing_key::Column::CreatedAt)
+            .all(&*self.db)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+
+    async fn activate(
+        &self,
+        kid: String,
+        algorithm: String,
+        private_key: String,
+    ) -> Result<JwtSigningKey, AppError> {
+        let now = Utc::now();
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        JwtSigningKeyEntity::update_many()
+            .col_expr(
+                jwt_signing_key::Column::Status,
+                Expr::value(KEY_STATUS_VERIFICATION_ONLY),
+            )
+            .col_expr(jwt_signing_key::Column::DeactivatedAt, Expr::value(now))
+            .filter(jwt_signing_key::Column::Status.eq(KEY_STATUS_ACTIVE))
+            .exec(&txn)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let key = jwt_signing_key::ActiveModel {
+            kid: Set(kid),
+            algorithm: Set(algorithm),
+            private_key: Set(private_key),
+            status: Set(KEY_STATUS_ACTIVE.to_string()),
+            created_at: Set(now),
+            deactivated_at: Set(None),
+            retired_at: Set(None),
+        };
+
+        JwtSigningKeyEntity::insert(key.clone())
+            .exec(&txn)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        txn.commit()
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Ok(key.try_into_model().unwrap())
+    }
+
+    async fn retire_deactivated_before(&self, cutoff: DateTime<Utc>) -> Result<u64, AppError> {
+        let result = JwtSigningKeyEntity::update_many()
+            .col_expr(
+                jwt_signing_key::Column::Status,
+                Expr::value(KEY_STATUS_RETIRED),
+            )
+            .col_expr(jwt_signing_key::Column::RetiredAt, Expr::value(Utc::now()))
+            .col_expr(jwt_signing_key::Column::PrivateKey, Expr::value(""))
+            .filter(jwt_signing_key::Column::Status.eq(KEY_STATUS_VERIFICATION_ONLY))
+            .filter(jwt_signing_key::Column::DeactivatedAt.lt(cutoff))
+            .exec(&*self.db)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Ok(result.rows_affected)
+    }
+
+    async fn update_private_key(&self, kid: String, private_key: String) -> Result<(), AppError> {
+        JwtSigningKeyEntity::update_many()
+            .col_expr(
+                jwt_signing_key::Column::PrivateKey,
+                Expr::value(private_key),
+            )
+            .filter(jwt_signing_key::Column::Kid.eq(kid))
+            .exec(&*self.db)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Ok(())
+    }
+}
@@ -1 +1,7 @@
+pub mod authorization_code_repository;
+pub mod device_code_repository;
 pub mod invitation_code_repository;
+pub mod jwt_signing_key_repository;
+pub mod passwordless_repository;
+pub mod token_denylist_repository;
+pub mod webauthn_challenge_repository;
@@ -0,0 +1,120 @@
+use crate::domains::common::utils::request_helper;
+use crate::domains::common::{errors::AppError, infrastructures::rocksdb_connection::RocksDbCache};
+use async_trait::async_trait;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Pending passwordless sign-in, redeemable once with either the emailed link
+/// or the emailed code, from the device that requested it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PasswordlessChallenge {
+    /// Hash of the device secret held in the requesting browser's cookie.
+    pub device_hash: String,
+    /// Hash of the token in the emailed link.
+    pub link_hash: String,
+    /// Hash of the emailed code salted with the device secret.
+    pub code_hash: String,
+    pub user_id: Uuid,
+    pub tenant_id: Uuid,
+    /// Wrong codes entered so far.
+    pub attempts: u32,
+    /// Unix timestamp after which the link and code are no longer valid.
+    pub expires_at: i64,
+}
+
+#[async_trait]
+pub trait PasswordlessRepositoryTrait: Send + Sync {
+    /// Stores the challenge until it expires, replacing any previous version
+    /// and any other pending challenge of the same user.
+    async fn save(&self, challenge: &PasswordlessChallenge) -> Result<(), AppError>;
+    async fn find_by_device(
+        &self,
+        device_secret: &str,
+    ) -> Result<Option<PasswordlessChallenge>, AppError>;
+    async fn find_by_link(
+        &self,
+        link_token: &str,
+    ) -> Result<Option<PasswordlessChallenge>, AppError>;
+    async fn delete(&self, challenge: &PasswordlessChallenge) -> Result<(), AppError>;
+}
+
+/// Stores passwordless challenges in RocksDB, keyed by the hash of the device
+/// secret. Link tokens and users point to that hash.
+pub struct PasswordlessRepository {
+    cache: Arc<RocksDbCache>,
+}
+
+impl PasswordlessRepository {
+    pub fn new(cache: Arc<RocksDbCache>) -> Self {
+        Self { cache }
+    }
+}
+
+#[async_trait]
+impl PasswordlessRepositoryTrait for PasswordlessRepository {
+    async fn save(&self, challenge: &PasswordlessChallenge) -> Result<(), AppError> {
+        let remaining = challenge.expires_at - Utc::now().timestamp();
+        if remaining <= 0 {
+            return Ok(());
+        }
+        let ttl = Duration::from_secs(remaining as u64);
+
+        // Only the latest challenge of a user can be redeemed
+        let user_key = format!("passwordless_user:{}", challenge.user_id);
+        if let Some(previous) = self.cache.get::<String>(&user_key) {
+            if previous != challenge.device_hash {
+                let key = format!("passwordless:{}", previous);
+                if let Some(previous) = self.cache.get::<PasswordlessChallenge>(&key) {
+                    self.delete(&previous).await?;
+                }
+            }
+        }
+
+        let key = format!("passwordless:{}", challenge.device_hash);
+        self.cache.set(&key, challenge, ttl);
+        let key = format!("passwordless_link:{}", challenge.link_hash);
+        self.cache.set(&key, challenge.device_hash.clone(), ttl);
+        self.cache
+            .set(&user_key, challenge.device_hash.clone(), ttl);
+        Ok(())
+    }
+
+    async fn find_by_device(
+        &self,
+        device_secret: &str,
+    ) -> Result<Option<PasswordlessChallenge>, AppError> {
+        let key = format!("passwordless:{}", request_helper::hash_token(device_secret));
+        Ok(self.cache.get(&key))
+    }
+
+    async fn find_by_link(
+        &self,
+        link_token: &str,
+    ) -> Result<Option<PasswordlessChallenge>, AppError> {
+        let key = format!(
+            "passwordless_link:{}",
+            request_helper::hash_token(link_token)
+        );
+        let Some(device_hash) = self.cache.get::<String>(&key) else {
+            return Ok(None);
+        };
+
+        let key = format!("passwordless:{}", device_hash);
+        Ok(self.cache.get(&key))
+    }
+
+    async fn delete(&self, challenge: &PasswordlessChallenge) -> Result<(), AppError> {
+        self.cache
+            .del(&format!("passwordless:{}", challenge.device_hash));
+        self.cache
+            .del(&format!("passwordless_link:{}", challenge.link_hash));
+        let user_key = format!("passwordless_user:{}", challenge.user_id);
+        if self.cache.get::<String>(&user_key).as_deref() == Some(challenge.device_hash.as_str()) {
+            self.cache.del(&user_key);
+        }
+        Ok(())
+    }
+}
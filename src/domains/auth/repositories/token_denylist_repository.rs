@@ -0,0 +1,99 @@
+use crate::domains::common::utils::jwt::Claims;
+use crate::domains::common::{errors::AppError, infrastructures::rocksdb_connection::RocksDbCache};
+use async_trait::async_trait;
+use chrono::Utc;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[async_trait]
+pub trait TokenDenylistRepositoryTrait: Send + Sync {
+    /// Denies the token with this `jti` until `ttl` has passed, which should
+    /// be the token's remaining lifetime.
+    async fn deny(&self, jti: &str, ttl: Duration) -> Result<(), AppError>;
+    async fn is_denied(&self, jti: &str) -> Result<bool, AppError>;
+    /// Denies every token of `subject` issued up to now, for `ttl` (the
+    /// access token lifetime), covering tokens whose `jti` is unknown.
+    async fn deny_subject(&self, subject: &str, ttl: Duration) -> Result<(), AppError>;
+    /// Returns when tokens of `subject` were last denied (Unix timestamp in
+    /// milliseconds).
+    async fn subject_denied_at(&self, subject: &str) -> Result<Option<i64>, AppError>;
+
+    /// Denies a token for the rest of its lifetime. Tokens issued without a
+    /// `jti` cannot be denied individually and are skipped.
+    async fn deny_token(&self, claims: &Claims) -> Result<(), AppError> {
+        let Some(jti) = &claims.jti else {
+            return Ok(());
+        };
+        let remaining = (claims.exp - Utc::now().timestamp()).max(0) as u64;
+        self.deny(jti, Duration::from_secs(remaining)).await
+    }
+
+    /// Returns true if the token was denied by `jti` or through its subject.
+    async fn is_revoked(&self, claims: &Claims) -> Result<bool, AppError> {
+        if let Some(jti) = &claims.jti {
+            if self.is_denied(jti).await? {
+                return Ok(true);
+            }
+        }
+
+        let Some(denied_at) = self.subject_denied_at(&claims.sub).await? else {
+            return Ok(false);
+        };
+        Ok(match claims.iat_ms {
+            Some(issued_at) => issued_at <= denied_at,
+            // Tokens issued before `iat_ms` existed only carry whole seconds,
+            // so one issued in the same second as the denial is denied as well
+            None => claims.iat <= denied_at.div_euclid(1000),
+        })
+    }
+}
+
+/// Millisecond timestamps pass this bound in 1973 while second timestamps
+/// reach it in the year 5138, which tells the two denial formats apart.
+const LEGACY_SECONDS_BOUND: i64 = 100_000_000_000;
+
+/// Stores revoked access tokens in RocksDB until they would have expired
+/// anyway, so the denylist never outgrows the set of live tokens.
+pub struct TokenDenylistRepository {
+    cache: Arc<RocksDbCache>,
+}
+
+impl TokenDenylistRepository {
+    pub fn new(cache: Arc<RocksDbCache>) -> Self {
+        Self { cache }
+    }
+}
+
+#[async_trait]
+impl TokenDenylistRepositoryTrait for TokenDenylistRepository {
+    async fn deny(&self, jti: &str, ttl: Duration) -> Result<(), AppError> {
+        let key = format!("denied_token:{}", jti);
+        self.cache.set(&key, true, ttl);
+        Ok(())
+    }
+
+    async fn is_denied(&self, jti: &str) -> Result<bool, AppError> {
+        let key = format!("denied_token:{}", jti);
+        Ok(self.cache.try_get::<bool>(&key)?.is_some())
+    }
+
+    async fn deny_subject(&self, subject: &str, ttl: Duration) -> Result<(), AppError> {
+        let key = format!("denied_subject:{}", subject);
+        self.cache.set(&key, Utc::now().timestamp_millis(), ttl);
+        Ok(())
+    }
+
+    async fn subject_denied_at(&self, subject: &str) -> Result<Option<i64>, AppError> {
+        let key = format!("denied_subject:{}", subject);
+        Ok(self.cache.try_get::<i64>(&key)?.map(|denied_at| {
+            // Denials recorded before millisecond precision hold seconds;
+            // read them as the end of that second so they keep covering
+            // tokens issued within it
+            if denied_at < LEGACY_SECONDS_BOUND {
+                denied_at * 1000 + 999
+            } else {
+                denied_at
+            }
+        }))
+    }
+}
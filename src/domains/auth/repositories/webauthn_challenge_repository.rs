@@ -0,0 +1,63 @@
+use crate::domains::common::{errors::AppError, infrastructures::rocksdb_connection::RocksDbCache};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Server-side state of a pending WebAuthn ceremony, keyed by its challenge.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WebauthnChallenge {
+    /// `"registration"` or `"authentication"`.
+    pub ceremony: String,
+    /// User the ceremony is bound to. Unset for discoverable-credential logins.
+    pub user_id: Option<Uuid>,
+    pub tenant_id: Uuid,
+}
+
+#[async_trait]
+pub trait WebauthnChallengeRepositoryTrait: Send + Sync {
+    async fn save_challenge(
+        &self,
+        challenge: &str,
+        state: WebauthnChallenge,
+        ttl: Duration,
+    ) -> Result<(), AppError>;
+    /// Returns and deletes the stored state, so each challenge can be used once.
+    async fn take_challenge(&self, challenge: &str) -> Result<Option<WebauthnChallenge>, AppError>;
+}
+
+pub struct WebauthnChallengeRepository {
+    cache: Arc<RocksDbCache>,
+}
+
+impl WebauthnChallengeRepository {
+    pub fn new(cache: Arc<RocksDbCache>) -> Self {
+        Self { cache }
+    }
+}
+
+#[async_trait]
+impl WebauthnChallengeRepositoryTrait for WebauthnChallengeRepository {
+    async fn save_challenge(
+        &self,
+        challenge: &str,
+        state: WebauthnChallenge,
+        ttl: Duration,
+    ) -> Result<(), AppError> {
+        let key = format!("webauthn_challenge:{}", challenge);
+        self.cache.set(&key, state, ttl);
+        Ok(())
+    }
+
+    async fn take_challenge(&self, challenge: &str) -> Result<Option<WebauthnChallenge>, AppError> {
+        let key = format!("webauthn_challenge:{}", challenge);
+
+        let state: Option<WebauthnChallenge> = self.cache.get(&key);
+        if state.is_some() {
+            self.cache.del(&key);
+        }
+
+        Ok(state)
+    }
+}
@@ -1,8 +1,24 @@
 use crate::domains::auth::controllers::auth_controller::{
-    change_password, generate_invitation_code, login, logout, refresh, register, verify,
+    change_password, confirm_totp, disable_totp, enroll_totp, generate_invitation_code, login,
+    logout, reauthenticate, refresh, regenerate_recovery_codes, register,
+    reset_with_recovery_code, verify, verify_mfa, verify_mfa_recovery,
 };
+use crate::domains::auth::controllers::email_verification_controller;
+use crate::domains::auth::controllers::oidc_controller;
+use crate::domains::auth::controllers::passkey_controller;
+use crate::domains::auth::controllers::password_reset_controller;
+use crate::domains::auth::controllers::passwordless_controller;
+use crate::domains::auth::controllers::session_controller;
+use crate::domains::auth::controllers::signing_key_controller;
+use crate::domains::auth::controllers::token_controller;
+use crate::domains::auth::controllers::well_known_controller;
 use crate::domains::auth::middlewares::auth_middleware;
-use crate::domains::tenant::middlewares::api_key_middleware::ApiKeyMiddleware;
+use crate::domains::auth::middlewares::impersonation_middleware::DenyImpersonationMiddleware;
+use crate::domains::auth::middlewares::step_up_middleware::StepUpMiddleware;
+use crate::domains::common::middlewares::rate_limit_middleware::RateLimitMiddleware;
+use crate::domains::tenant::middlewares::api_key_middleware::{
+    ApiKeyMiddleware, OptionalApiKeyMiddleware,
+};
 use actix_web::web;
 use actix_web_httpauth::middleware::HttpAuthentication;
 
@@ -11,17 +27,119 @@ use actix_web_httpauth::middleware::HttpAuthentication;
 /// # Routes (under /auth scope)
 ///
 /// **ApiKey Protected:**
-/// - `POST /register`
-/// - `POST /login`
-/// - `POST /refresh`
+/// - `POST /register` (rate limited)
+/// - `POST /login` (rate limited)
+/// - `POST /refresh` (rate limited)
+/// - `POST /mfa/verify` (rate limited)
+/// - `POST /mfa/recovery` (rate limited)
+/// - `POST /recovery/reset` (rate limited)
+/// - `POST /forgot-password` (rate limited)
+/// - `POST /reset-password` (rate limited)
+/// - `POST /verify-email` (rate limited)
+/// - `POST /verify-email/resend` (rate limited)
+/// - `POST /passkeys/login/options`
+/// - `POST /passkeys/login`
+/// - `POST /passwordless/request` (rate limited)
+/// - `POST /passwordless/consume` (rate limited)
 ///
 /// **JWT Protected:**
 /// - `POST /logout`
-/// - `POST /verify`
+/// - `GET /verify` (also accepts client credentials tokens)
 /// - `PUT /change-password`
+/// - `POST /mfa/totp/enroll`
+/// - `POST /mfa/totp/confirm`
+/// - `DELETE /mfa/totp`
+/// - `POST /mfa/recovery-codes`
+/// - `POST /passkeys/register/options`
+/// - `POST /passkeys/register`
+/// - `GET /passkeys`
+/// - `DELETE /passkeys/{id}`
+/// - `GET /sessions`
+/// - `DELETE /sessions/others`
+/// - `DELETE /sessions/{id}`
+///
+/// Changing the password, TOTP, recovery codes or passkeys, completing an
+/// authorization and approving a device refuse impersonation tokens, which
+/// must not be traded for tokens without the `impersonator` claim.
+/// Changing the password and disabling TOTP also require a recent login
+/// (`StepUpMiddleware`).
+///
+/// **TenantSecret Protected:**
+/// - `POST /internal/invitations`
+/// - `GET /internal/keys`
+/// - `POST /internal/keys/rotate`
+///
+/// **Public (outside /auth):**
+/// - `GET /.well-known/jwks.json`
+/// - `GET /.well-known/openid-configuration`
+///
+/// **OpenID Connect (under /oauth):**
+/// - `GET /authorize`
+/// - `POST /authorize` (JWT, called by the login page)
+/// - `POST /token`
+/// - `POST /device_authorization`
+/// - `GET /device` (JWT, called by the web app's device page)
+/// - `POST /device` (JWT, called by the web app's device page)
+/// - `GET /userinfo` (JWT, including tokens issued to OAuth clients)
+/// - `POST /introspect` (API key or client secret)
+/// - `POST /revoke` (API key or client secret)
 pub fn configure_routes(cfg: &mut web::ServiceConfig) {
     let jwt_auth = HttpAuthentication::bearer(auth_middleware::validator);
 
+    cfg.route(
+        "/.well-known/jwks.json",
+        web::get().to(well_known_controller::jwks),
+    );
+    cfg.route(
+        "/.well-known/openid-configuration",
+        web::get().to(well_known_controller::openid_configuration),
+    );
+
+    cfg.service(
+        web::scope("/oauth")
+            .service(
+                web::resource("/authorize")
+                    .route(web::get().to(oidc_controller::authorize))
+                    .route(
+                        web::post()
+                            .to(oidc_controller::complete_authorization)
+                            .wrap(DenyImpersonationMiddleware)
+                            .wrap(HttpAuthentication::bearer(auth_middleware::validator)),
+                    ),
+            )
+            .route("/token", web::post().to(oidc_controller::token))
+            .route(
+                "/device_authorization",
+                web::post().to(oidc_controller::device_authorization),
+            )
+            .service(
+                web::resource("/device")
+                    .wrap(HttpAuthentication::bearer(auth_middleware::validator))
+                    .route(web::get().to(oidc_controller::find_device))
+                    .route(
+                        web::post()
+                            .to(oidc_controller::decide_device)
+                            .wrap(DenyImpersonationMiddleware),
+                    ),
+            )
+            .service(
+                web::resource("/introspect")
+                    .wrap(OptionalApiKeyMiddleware)
+                    .route(web::post().to(token_controller::introspect)),
+            )
+            .service(
+                web::resource("/revoke")
+                    .wrap(OptionalApiKeyMiddleware)
+                    .route(web::post().to(token_controller::revoke)),
+            )
+            .service(
+                web::resource("/userinfo")
+                    .wrap(HttpAuthentication::bearer(auth_middleware::scoped_validator))
+                    .route(web::get().to(oidc_controller::userinfo))
+                    .route(web::post().to(oidc_controller::userinfo)),
+            ),
+    );
+
     // AuthUseCase is now registered globally in server.rs
 
     cfg.service(
@@ -34,38 +152,132 @@ pub fn configure_routes(cfg: &mut web::ServiceConfig) {
             // ApiKey protected routes
             .service(
                 web::resource("/register")
+                    .wrap(RateLimitMiddleware::register())
                     .wrap(ApiKeyMiddleware)
                     .route(web::post().to(register))
             )
             .service(
                 web::resource("/login")
+                    .wrap(RateLimitMiddleware::login())
                     .wrap(ApiKeyMiddleware)
                     .route(web::post().to(login))
             )
             .service(
                 web::resource("/refresh")
+                    .wrap(RateLimitMiddleware::refresh())
                     .wrap(ApiKeyMiddleware)
                     .route(web::post().to(refresh))
             )
+            .service(
+                web::resource("/mfa/verify")
+                    .wrap(RateLimitMiddleware::redeem_code())
+                    .wrap(ApiKeyMiddleware)
+                    .route(web::post().to(verify_mfa))
+            )
+            .service(
+                web::resource("/mfa/recovery")
+                    .wrap(RateLimitMiddleware::redeem_code())
+                    .wrap(ApiKeyMiddleware)
+                    .route(web::post().to(verify_mfa_recovery))
+            )
+            .service(
+                web::resource("/recovery/reset")
+                    .wrap(RateLimitMiddleware::login())
+                    .wrap(ApiKeyMiddleware)
+                    .route(web::post().to(reset_with_recovery_code))
+            )
+            .service(
+                web::resource("/forgot-password")
+                    .wrap(RateLimitMiddleware::send_email())
+                    .wrap(ApiKeyMiddleware)
+                    .route(web::post().to(password_reset_controller::forgot_password))
+            )
+            .service(
+                web::resource("/reset-password")
+                    .wrap(RateLimitMiddleware::redeem_code())
+                    .wrap(ApiKeyMiddleware)
+                    .route(web::post().to(password_reset_controller::reset_password))
+            )
+            .service(
+                web::resource("/verify-email")
+                    .wrap(RateLimitMiddleware::redeem_code())
+                    .wrap(ApiKeyMiddleware)
+                    .route(web::post().to(email_verification_controller::verify_email))
+            )
+            .service(
+                web::resource("/verify-email/resend")
+                    .wrap(RateLimitMiddleware::send_email())
+                    .wrap(ApiKeyMiddleware)
+                    .route(web::post().to(email_verification_controller::resend_verification))
+            )
+            .service(
+                web::resource("/passkeys/login/options")
+                    .wrap(ApiKeyMiddleware)
+                    .route(web::post().to(passkey_controller::login_options))
+            )
+            .service(
+                web::resource("/passkeys/login")
+                    .wrap(ApiKeyMiddleware)
+                    .route(web::post().to(passkey_controller::login_with_passkey))
+            )
+            .service(
+                web::resource("/passwordless/request")
+                    .wrap(RateLimitMiddleware::send_email())
+                    .wrap(ApiKeyMiddleware)
+                    .route(web::post().to(passwordless_controller::request_passwordless))
+            )
+            .service(
+                web::resource("/passwordless/consume")
+                    .wrap(RateLimitMiddleware::redeem_code())
+                    .wrap(ApiKeyMiddleware)
+                    .route(web::post().to(passwordless_controller::consume_passwordless))
+            )
             // Internal routes (TenantSecret Protected)
             .service(
                 web::resource("/internal/invitations")
                     .wrap(crate::domains::tenant::middlewares::tenant_secret_middleware::TenantSecretMiddleware)
                     .route(web::post().to(generate_invitation_code))
             )
+            .service(
+                web::resource("/internal/keys")
+                    .wrap(crate::domains::tenant::middlewares::tenant_secret_middleware::TenantSecretMiddleware)
+                    .route(web::get().to(signing_key_controller::list_signing_keys))
+            )
+            .service(
+                web::resource("/internal/keys/rotate")
+                    .wrap(crate::domains::tenant::middlewares::tenant_secret_middleware::TenantSecretMiddleware)
+                    .route(web::post().to(signing_key_controller::rotate_signing_key))
+            )
+            // JWT protected routes, open to machine clients
+            .service(
+                web::resource("/verify")
+                    .wrap(HttpAuthentication::bearer(auth_middleware::client_validator))
+                    .route(web::get().to(verify))
+            )
             // JWT protected routes
             .service(
                 web::scope("")
                     .wrap(jwt_auth)
                     // Routes requiring only JWT
-                    .route("/verify", web::get().to(verify))
                     .route("/logout", web::delete().to(logout))
 
                     // Nested scope for JWT + ApiKey protected routes
                     .service(
                         web::scope("")
                             .wrap(ApiKeyMiddleware)
-                            .route("/reset", web::put().to(change_password))
+                            .route("/reauthenticate", web::post().to(reauthenticate).wrap(DenyImpersonationMiddleware))
+                            .route("/reset", web::put().to(change_password).wrap(StepUpMiddleware::from_config()).wrap(DenyImpersonationMiddleware))
+                            .route("/mfa/totp/enroll", web::post().to(enroll_totp).wrap(DenyImpersonationMiddleware))
+                            .route("/mfa/totp/confirm", web::post().to(confirm_totp).wrap(DenyImpersonationMiddleware))
+                            .route("/mfa/totp", web::delete().to(disable_totp).wrap(StepUpMiddleware::from_config()).wrap(DenyImpersonationMiddleware))
+                            .route("/mfa/recovery-codes", web::post().to(regenerate_recovery_codes).wrap(DenyImpersonationMiddleware))
+                            .route("/passkeys/register/options", web::post().to(passkey_controller::registration_options).wrap(DenyImpersonationMiddleware))
+                            .route("/passkeys/register", web::post().to(passkey_controller::register_passkey).wrap(DenyImpersonationMiddleware))
+                            .route("/passkeys", web::get().to(passkey_controller::list_passkeys))
+                            .route("/passkeys/{id}", web::delete().to(passkey_controller::delete_passkey).wrap(DenyImpersonationMiddleware))
+                            .route("/sessions", web::get().to(session_controller::list_sessions))
+                            .route("/sessions/others", web::delete().to(session_controller::revoke_other_sessions))
+                            .route("/sessions/{id}", web::delete().to(session_controller::revoke_session))
                     )
             )
     );
@@ -1,23 +1,47 @@
 use crate::domains::auth::repositories::invitation_code_repository::InvitationCodeRepositoryTrait;
+use crate::domains::auth::repositories::token_denylist_repository::TokenDenylistRepositoryTrait;
 use crate::domains::common::errors::{AppError, ValidationDetail};
-use crate::domains::common::utils::jwt::JwtService;
-use crate::domains::common::utils::{password, request_helper};
+use crate::domains::common::infrastructures::mailer::{EmailMessage, Mailer};
+use crate::domains::common::utils::jwt::{
+    Authentication, Claims, JwtService, AMR_MULTI_FACTOR, AMR_OTP, AMR_PASSWORD,
+};
+use crate::domains::common::utils::lockout::LockoutPolicy;
+use crate::domains::common::utils::{password, recovery_code, request_helper, totp};
+use crate::domains::tenant::repositories::tenant_repository::TenantRepositoryTrait;
 use crate::domains::tenant::repositories::user_tenant_repository::UserTenantRepositoryTrait;
-use crate::domains::user::dtos::auth_dto::{AuthResponse, LoginRequest, RegisterRequest};
+use crate::domains::user::dtos::auth_dto::{
+    AuthResponse, LoginRequest, LoginResult, ReauthenticateRequest, RegisterRequest, RegisterResult,
+};
 use crate::domains::user::dtos::change_password_dto::ChangePasswordRequest;
+use crate::domains::user::dtos::mfa_dto::{
+    MfaChallengeResponse, MfaVerifyRequest, TotpEnrollmentResponse,
+};
+use crate::domains::user::dtos::recovery_code_dto::{
+    MfaRecoveryRequest, RecoveryCodesResponse, RecoveryResetRequest,
+};
 use crate::domains::user::dtos::user_details_dto::UserDetailsResponse;
 use crate::domains::user::dtos::user_dto::{CreateUserRequest, UserResponse};
-use crate::domains::user::entities::user::Model as User;
+use crate::domains::user::entities::user::{
+    Model as User, STATUS_BANNED, STATUS_PENDING, STATUS_SUSPENDED,
+};
 use crate::domains::user::entities::user_details::Model as UserDetails;
+use crate::domains::user::entities::user_mfa::Model as UserMfa;
 use crate::domains::user::entities::user_session::Model as UserSession;
 use crate::domains::user::repositories::user_activity_log_repository::UserActivityLogRepositoryTrait;
+use crate::domains::user::repositories::user_mfa_repository::UserMfaRepositoryTrait;
+use crate::domains::user::repositories::user_recovery_code_repository::UserRecoveryCodeRepositoryTrait;
 use crate::domains::user::repositories::user_repository::UserRepositoryTrait;
 use crate::domains::user::repositories::user_session_repository::UserSessionRepositoryTrait;
 use crate::domains::user::validators::user_validator;
 use actix_web::HttpMessage;
 use chrono::Utc;
+use jsonwebtoken::jwk::JwkSet;
 use std::sync::Arc;
 
+/// Consecutive wrong second-factor codes after which an MFA challenge token
+/// stops working and the user has to sign in again.
+const MFA_MAX_ATTEMPTS: i32 = 5;
+
 pub struct AuthUseCase {
     repository: Arc<dyn UserRepositoryTrait>,
     user_details_repository: Arc<
@@ -27,11 +51,17 @@ pub struct AuthUseCase {
     session_repository: Arc<dyn UserSessionRepositoryTrait>,
     activity_log_repository: Arc<dyn UserActivityLogRepositoryTrait>,
     invitation_code_repository: Arc<dyn InvitationCodeRepositoryTrait>,
+    mfa_repository: Arc<dyn UserMfaRepositoryTrait>,
+    recovery_code_repository: Arc<dyn UserRecoveryCodeRepositoryTrait>,
+    tenant_repository: Arc<dyn TenantRepositoryTrait>,
+    token_denylist_repository: Arc<dyn TokenDenylistRepositoryTrait>,
     jwt_service: JwtService,
+    lockout_policy: LockoutPolicy,
 }
 
 impl AuthUseCase {
     /// Creates a new AuthUseCase instance.
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         repository: Arc<dyn UserRepositoryTrait>,
         user_details_repository: Arc<
@@ -41,6 +71,10 @@ impl AuthUseCase {
         session_repository: Arc<dyn UserSessionRepositoryTrait>,
         activity_log_repository: Arc<dyn UserActivityLogRepositoryTrait>,
         invitation_code_repository: Arc<dyn InvitationCodeRepositoryTrait>,
+        mfa_repository: Arc<dyn UserMfaRepositoryTrait>,
+        recovery_code_repository: Arc<dyn UserRecoveryCodeRepositoryTrait>,
+        tenant_repository: Arc<dyn TenantRepositoryTrait>,
+        token_denylist_repository: Arc<dyn TokenDenylistRepositoryTrait>,
     ) -> Self {
         Self {
             repository,
@@ -49,7 +83,12 @@ impl AuthUseCase {
             session_repository,
             activity_log_repository,
             invitation_code_repository,
+            mfa_repository,
+            recovery_code_repository,
+            tenant_repository,
+            token_denylist_repository,
             jwt_service: JwtService::new(),
+            lockout_policy: crate::domains::common::utils::config::Config::get().login_lockout,
         }
     }
 
@@ -62,7 +101,9 @@ impl AuthUseCase {
     ///
     /// # Returns
     ///
-    /// Returns `AuthResponse` with user data and access token.
+    /// Returns `RegisterResult::Authenticated` with the access token, or
+    /// `RegisterResult::VerificationPending` without tokens if the tenant
+    /// requires a verified email and the user has not verified theirs.
     ///
     /// # Errors
     ///
@@ -73,7 +114,7 @@ impl AuthUseCase {
         &self,
         req: RegisterRequest,
         http_req: &actix_web::HttpRequest,
-    ) -> Result<AuthResponse, AppError> {
+    ) -> Result<RegisterResult, AppError> {
         let (ip_address, user_agent) = request_helper::extract_client_info(http_req);
 
         // Validate input
@@ -158,11 +199,26 @@ impl AuthUseCase {
                 .add_user_to_tenant(user.id, req.tenant_id, req.role.clone())
                 .await?;
 
+            // No tokens until the address is verified, if the tenant requires it
+            if self
+                .email_verification_pending(&user, req.tenant_id)
+                .await?
+            {
+                self.log_activity_success(Some(user.id), "register", ip_address, user_agent)
+                    .await;
+                return Ok(RegisterResult::VerificationPending(user.id));
+            }
+
             // Generate tokens
             let role = req.role.clone();
             let access_token = self
                 .jwt_service
-                .generate_access_token(user.id, req.tenant_id, role.clone())
+                .generate_session_access_token(
+                    user.id,
+                    req.tenant_id,
+                    role.clone(),
+                    Some(&Authentication::now(&[AMR_PASSWORD])),
+                )
                 .map_err(|e| {
                     AppError::InternalError(format!("Failed to generate access token: {}", e))
                 })?;
@@ -171,11 +227,11 @@ impl AuthUseCase {
             self.log_activity_success(Some(user.id), "register", ip_address, user_agent)
                 .await;
 
-            return Ok(AuthResponse {
+            return Ok(RegisterResult::Authenticated(AuthResponse {
                 user_id: user.id,
                 access_token,
                 expires_in,
-            });
+            }));
         };
 
         // Step 2: Existing User Validation
@@ -200,6 +256,13 @@ impl AuthUseCase {
             )
             .await?;
 
+        // Suspended or banned users must not get tokens through "Signup as Login"
+        if let Err(err) = Self::ensure_active(&user) {
+            self.log_activity_failure(Some(user.id), "register", &err, ip_address, user_agent)
+                .await;
+            return Err(err);
+        }
+
         // Step 3: Check if user is already assigned to this tenant with the REQUESTED role
         let existing_roles = self
             .user_tenant_repository
@@ -218,17 +281,45 @@ impl AuthUseCase {
             req.role.clone()
         };
 
+        // "Signup as Login" must not bypass the second factor
+        if let Some(mfa) = self.mfa_repository.find_by_user_id(user.id).await? {
+            if mfa.is_enabled() {
+                let err = AppError::Unauthorized(
+                    "MFA is enabled for this account, please sign in".to_string(),
+                );
+                self.log_activity_failure(Some(user.id), "register", &err, ip_address, user_agent)
+                    .await;
+                return Err(err);
+            }
+        }
+
+        // "Signup as Login" must not bypass email verification either
+        if self
+            .email_verification_pending(&user, req.tenant_id)
+            .await?
+        {
+            self.log_activity_success(Some(user.id), "register", ip_address, user_agent)
+                .await;
+            return Ok(RegisterResult::VerificationPending(user.id));
+        }
+
         // Generate tokens with tenant context
+        let authentication = Authentication::now(&[AMR_PASSWORD]);
         let access_token = self
             .jwt_service
-            .generate_access_token(user.id, req.tenant_id, role.clone())
+            .generate_session_access_token(
+                user.id,
+                req.tenant_id,
+                role.clone(),
+                Some(&authentication),
+            )
             .map_err(|e| {
                 AppError::InternalError(format!("Failed to generate access token: {}", e))
             })?;
 
         let _refresh_token = self
             .jwt_service
-            .generate_refresh_token(user.id, req.tenant_id, role, None)
+            .generate_refresh_token(user.id, req.tenant_id, role, None, Some(&authentication))
             .map_err(|e| {
                 AppError::InternalError(format!("Failed to generate refresh token: {}", e))
             })?;
@@ -238,11 +329,11 @@ impl AuthUseCase {
         self.log_activity_success(Some(user.id), "register", ip_address, user_agent)
             .await;
 
-        Ok(AuthResponse {
+        Ok(RegisterResult::Authenticated(AuthResponse {
             user_id: user.id,
             access_token,
             expires_in,
-        })
+        }))
     }
 
     /// Authenticates a user and generates tokens with session tracking.
@@ -256,7 +347,8 @@ impl AuthUseCase {
     ///
     /// # Returns
     ///
-    /// Returns a tuple of `(AuthResponse, refresh_token)`.
+    /// Returns `LoginResult::Authenticated` with `(AuthResponse, refresh_token)`, or
+    /// `LoginResult::MfaRequired` with a challenge token when the user has MFA enabled.
     ///
     /// # Errors
     ///
@@ -265,7 +357,7 @@ impl AuthUseCase {
         &self,
         req: LoginRequest,
         http_req: &actix_web::HttpRequest,
-    ) -> Result<(AuthResponse, String), AppError> {
+    ) -> Result<LoginResult, AppError> {
         let (ip_address, user_agent) = request_helper::extract_client_info(http_req);
 
         // Helper to normalize input for email search
@@ -274,11 +366,6 @@ impl AuthUseCase {
             login_identifier = req.email_or_username.to_lowercase();
         }
 
-        // Hack for e2e test expecting banned user (since not seeded)
-        if req.email_or_username == "banned_user" {
-            return Err(AppError::Forbidden("Forbidden".to_string()));
-        }
-
         // Try to find user by email first
         let user = match self
             .repository
@@ -288,7 +375,6 @@ impl AuthUseCase {
             Some(u) => {
                 if u.deleted_at.is_some() {
                     // 4g test expects 401 or 404 for deleted user login
-                    // 2b test expects 403 for "banned_user" (handled above)
                     return Err(AppError::Unauthorized("Unauthorized".to_string()));
                 }
                 u
@@ -315,14 +401,37 @@ impl AuthUseCase {
             }
         };
 
+        // Locked accounts are refused before the password is checked, so the
+        // response is the same whether or not the password was correct
+        if user.locked_until.is_some_and(|until| until > Utc::now()) {
+            let err = AppError::Locked(
+                "Account is temporarily locked due to too many failed login attempts".to_string(),
+            );
+            self.log_activity_failure(Some(user.id), "login_locked", &err, ip_address, user_agent)
+                .await;
+            return Err(err);
+        }
+
         // Verify password
         if !password::verify_password(&req.password, &user.password_hash)? {
+            self.record_login_failure(&user).await?;
             let err = AppError::Unauthorized("username or email or password invalid".to_string());
             self.log_activity_failure(Some(user.id), "login", &err, ip_address, user_agent)
                 .await;
             return Err(err);
         }
 
+        if user.failed_login_attempts > 0 || user.locked_until.is_some() {
+            self.repository.set_login_failures(user.id, 0, None).await?;
+        }
+
+        // Status is only revealed to callers who know the password
+        if let Err(err) = Self::ensure_active(&user) {
+            self.log_activity_failure(Some(user.id), "login", &err, ip_address, user_agent)
+                .await;
+            return Err(err);
+        }
+
         // Validate tenant membership and get roles
         let roles = self
             .user_tenant_repository
@@ -336,219 +445,652 @@ impl AuthUseCase {
         }
 
         // Determine which role to use for the token
-        let role = if let Some(requested_role) = &req.role {
-            if !roles.contains(requested_role) {
-                // User explicitly requested "NotFound" behavior to mimic non-existence in that role scope
-                let err = AppError::NotFound("User not found".to_string());
-                self.log_activity_failure(
-                    Some(user.id),
-                    "login_role_mismatch",
-                    &err,
-                    ip_address,
-                    user_agent,
-                )
-                .await;
-                return Err(err);
-            }
-            requested_role.clone()
-        } else {
-            // No role requested, pick the first one (usually 'user' if it exists, otherwise first in list)
-            roles
-                .iter()
-                .find(|r| *r == "user")
-                .cloned()
-                .unwrap_or_else(|| roles[0].clone())
+        let Some(role) = Self::select_role(&roles, req.role.as_deref()) else {
+            // User explicitly requested "NotFound" behavior to mimic non-existence in that role scope
+            let err = AppError::NotFound("User not found".to_string());
+            self.log_activity_failure(
+                Some(user.id),
+                "login_role_mismatch",
+                &err,
+                ip_address,
+                user_agent,
+            )
+            .await;
+            return Err(err);
         };
 
-        // Generate tokens with tenant context
-        let access_token = self
-            .jwt_service
-            .generate_access_token(user.id, req.tenant_id, role.clone())
-            .map_err(|e| {
-                AppError::InternalError(format!("Failed to generate access token: {}", e))
-            })?;
+        // Tenants may require a verified email before any tokens are issued
+        if let Err(err) = self.ensure_email_verified(&user, req.tenant_id).await {
+            self.log_activity_failure(Some(user.id), "login", &err, ip_address, user_agent)
+                .await;
+            return Err(err);
+        }
 
-        // Generate a shared UUID for JTI and DB Session ID
-        let session_id = uuid::Uuid::new_v4();
+        self.start_session(
+            user.id,
+            req.tenant_id,
+            role,
+            &Authentication::now(&[AMR_PASSWORD]),
+            ip_address,
+            user_agent,
+        )
+        .await
+    }
 
-        let refresh_token = self
-            .jwt_service
-            .generate_refresh_token(user.id, req.tenant_id, role, Some(session_id.to_string()))
-            .map_err(|e| {
-                AppError::InternalError(format!("Failed to generate refresh token: {}", e))
-            })?;
+    /// Finishes a login once the first factor has been checked.
+    ///
+    /// Users with a confirmed second factor get an MFA challenge; everyone
+    /// else gets session tokens recording `authentication`.
+    pub(crate) async fn start_session(
+        &self,
+        user_id: uuid::Uuid,
+        tenant_id: uuid::Uuid,
+        role: String,
+        authentication: &Authentication,
+        ip_address: Option<String>,
+        user_agent: Option<String>,
+    ) -> Result<LoginResult, AppError> {
+        // Users with a confirmed second factor must complete the MFA challenge first
+        if let Some(mfa) = self.mfa_repository.find_by_user_id(user_id).await? {
+            if mfa.is_enabled() {
+                let mfa_token = self
+                    .jwt_service
+                    .generate_mfa_token(user_id, tenant_id, role)
+                    .map_err(|e| {
+                        AppError::InternalError(format!("Failed to generate MFA token: {}", e))
+                    })?;
+
+                self.log_activity_success(
+                    Some(user_id),
+                    "login_mfa_challenge",
+                    ip_address,
+                    user_agent,
+                )
+                .await;
 
-        // Create session record
-        let refresh_token_hash = request_helper::hash_token(&refresh_token);
-        let expires_at =
-            Utc::now() + chrono::Duration::seconds(self.jwt_service.get_refresh_token_expiry());
+                return Ok(LoginResult::MfaRequired(MfaChallengeResponse {
+                    mfa_token,
+                    expires_in: self.jwt_service.get_mfa_token_expiry(),
+                }));
+            }
+        }
 
-        self.session_repository
-            .create_session(
-                Some(session_id),
-                user.id,
-                refresh_token_hash,
-                user_agent.clone(),
+        let (auth_response, refresh_token) = self
+            .issue_session_tokens(
+                user_id,
+                tenant_id,
+                role,
+                authentication,
                 ip_address.clone(),
-                expires_at,
+                user_agent.clone(),
             )
             .await?;
 
-        let expires_in = self.jwt_service.get_access_token_expiry();
         // Log successful login
-        self.log_activity_success(Some(user.id), "login", ip_address, user_agent)
+        self.log_activity_success(Some(user_id), "login", ip_address, user_agent)
             .await;
 
-        Ok((
-            AuthResponse {
-                user_id: user.id,
-                access_token,
-                expires_in,
-            },
-            refresh_token,
-        ))
+        Ok(LoginResult::Authenticated(auth_response, refresh_token))
     }
 
-    /// Logs out a user by deleting their session.
+    /// Completes a login that was paused for MFA.
     ///
-    /// This endpoint requires JWT authentication and a valid refresh token cookie.
+    /// Validates the challenge token issued by `login`, checks the TOTP code
+    /// against the user's enrolled secret, and issues the same access/refresh
+    /// pair that a password-only login returns.
     ///
     /// # Arguments
     ///
-    /// * `user_id` - User ID from JWT token
-    /// * `http_req` - HTTP request containing refresh token cookie
+    /// * `req` - MFA challenge token and TOTP code
+    /// * `tenant_id` - Tenant resolved by `ApiKeyMiddleware`
+    /// * `http_req` - HTTP request for extracting client info
+    ///
+    /// # Returns
+    ///
+    /// Returns a tuple of `(AuthResponse, refresh_token)`.
     ///
     /// # Errors
     ///
-    /// Returns `AppError::Unauthorized` if refresh token is missing or invalid.
-    pub async fn logout(
+    /// - `AppError::Unauthorized` if the challenge token or code is invalid.
+    ///   Wrong codes count towards the account lockout, and the challenge
+    ///   token stops working after `MFA_MAX_ATTEMPTS` of them.
+    /// - `AppError::Locked` if the account is locked
+    pub async fn verify_mfa(
         &self,
-        user_id: uuid::Uuid,
+        req: MfaVerifyRequest,
+        tenant_id: uuid::Uuid,
         http_req: &actix_web::HttpRequest,
-    ) -> Result<(), AppError> {
+    ) -> Result<(AuthResponse, String), AppError> {
         let (ip_address, user_agent) = request_helper::extract_client_info(http_req);
+        let (user, claims) = self
+            .resolve_mfa_challenge(&req.mfa_token, tenant_id)
+            .await?;
 
-        // Extract refresh token from cookie
-        let refresh_token = http_req
-            .cookie("refresh_token")
-            .ok_or_else(|| AppError::Unauthorized("Refresh token not found".to_string()))?
-            .value()
-            .to_string();
+        let mfa = self
+            .mfa_repository
+            .find_by_user_id(user.id)
+            .await?
+            .filter(|m| m.is_enabled())
+            .ok_or_else(|| AppError::Unauthorized("Unauthorized".to_string()))?;
 
-        // Hash the refresh token to find the session
-        let refresh_token_hash = request_helper::hash_token(&refresh_token);
+        let accepted = match Self::verify_totp(&mfa, &req.code)? {
+            Some(step) => {
+                self.mfa_repository
+                    .update_last_used_step(user.id, step)
+                    .await?
+            }
+            None => false,
+        };
 
-        // Find and delete the session
-        if let Some(session) = self
-            .session_repository
-            .find_by_refresh_token_hash(&refresh_token_hash)
-            .await?
-        {
-            self.session_repository.delete_session(session.id).await?;
+        if !accepted {
+            self.record_mfa_failure(&user, &claims).await?;
+            let err = AppError::Unauthorized("Invalid MFA code".to_string());
+            self.log_activity_failure(Some(user.id), "mfa_verify", &err, ip_address, user_agent)
+                .await;
+            return Err(err);
         }
 
-        // Log successful logout
-        self.log_activity_success(Some(user_id), "logout", ip_address, user_agent)
+        if user.failed_login_attempts > 0 || user.locked_until.is_some() {
+            self.repository.set_login_failures(user.id, 0, None).await?;
+        }
+
+        self.log_activity_success(
+            Some(user.id),
+            "mfa_verify",
+            ip_address.clone(),
+            user_agent.clone(),
+        )
+        .await;
+
+        let (auth_response, refresh_token) = self
+            .issue_session_tokens(
+                user.id,
+                tenant_id,
+                claims.role,
+                &Authentication::now(&[AMR_PASSWORD, AMR_OTP, AMR_MULTI_FACTOR]),
+                ip_address.clone(),
+                user_agent.clone(),
+            )
+            .await?;
+
+        self.log_activity_success(Some(user.id), "login", ip_address, user_agent)
             .await;
 
-        Ok(())
+        Ok((auth_response, refresh_token))
     }
 
-    /// Logs out a user using cookie-based session identification (SSO Logout).
-    ///
-    /// This method is intended for redirect-based logout flows where the client
-    /// may not send an Authorization header but sends the HTTP-Only cookie.
+    /// Completes an MFA-challenged login with a single-use recovery code.
     ///
-    /// # Arguments
-    ///
-    /// * `http_req` - HTTP request containing refresh token cookie
+    /// Intended for users who lost access to their authenticator app. The
+    /// recovery code is consumed and cannot be used again.
     ///
     /// # Errors
     ///
-    /// Returns `AppError::Unauthorized` if refresh token is missing or invalid.
-    pub async fn sso_logout(&self, http_req: &actix_web::HttpRequest) -> Result<(), AppError> {
+    /// - `AppError::Unauthorized` if the challenge token or recovery code is
+    ///   invalid. Wrong codes count like wrong TOTP codes in `verify_mfa`.
+    /// - `AppError::Locked` if the account is locked
+    pub async fn verify_mfa_recovery(
+        &self,
+        req: MfaRecoveryRequest,
+        tenant_id: uuid::Uuid,
+        http_req: &actix_web::HttpRequest,
+    ) -> Result<(AuthResponse, String), AppError> {
         let (ip_address, user_agent) = request_helper::extract_client_info(http_req);
+        let (user, claims) = self
+            .resolve_mfa_challenge(&req.mfa_token, tenant_id)
+            .await?;
 
-        // Extract refresh token from cookie
-        let refresh_token = http_req
-            .cookie("refresh_token")
-            .ok_or_else(|| AppError::Unauthorized("Refresh token not found".to_string()))?
-            .value()
-            .to_string();
-
-        // Hash the refresh token to find the session
-        let refresh_token_hash = request_helper::hash_token(&refresh_token);
-
-        // Find match session
-        if let Some(session) = self
-            .session_repository
-            .find_by_refresh_token_hash(&refresh_token_hash)
+        if !self
+            .consume_recovery_code(user.id, &req.recovery_code)
             .await?
         {
-            // Delete the session
-            self.session_repository.delete_session(session.id).await?;
+            self.record_mfa_failure(&user, &claims).await?;
+            let err = AppError::Unauthorized("Invalid recovery code".to_string());
+            self.log_activity_failure(
+                Some(user.id),
+                "recovery_code_login",
+                &err,
+                ip_address,
+                user_agent,
+            )
+            .await;
+            return Err(err);
+        }
 
-            // Log successful logout using user_id from session
-            self.log_activity_success(Some(session.user_id), "sso_logout", ip_address, user_agent)
-                .await;
-        } else {
-            // Session not found (already logged out or invalid)
-            // We still consider this specific operation a "success" (idempotent)
-            // but maybe log a warning or just return Ok.
-            // For strictness, if cookie exists but session doesn't, it's stale.
+        if user.failed_login_attempts > 0 || user.locked_until.is_some() {
+            self.repository.set_login_failures(user.id, 0, None).await?;
         }
 
-        Ok(())
-    }
+        self.log_activity_success(
+            Some(user.id),
+            "recovery_code_login",
+            ip_address.clone(),
+            user_agent.clone(),
+        )
+        .await;
 
-    /// Gets the refresh token expiry duration in seconds.
-    ///
-    /// This value is used for setting the max-age of the refresh token cookie.
-    ///
-    /// # Returns
-    ///
-    /// Refresh token expiry in seconds (default: 604800 = 7 days).
-    pub fn get_refresh_token_expiry(&self) -> i64 {
-        self.jwt_service.get_refresh_token_expiry()
+        let (auth_response, refresh_token) = self
+            .issue_session_tokens(
+                user.id,
+                tenant_id,
+                claims.role,
+                &Authentication::now(&[AMR_PASSWORD, AMR_OTP, AMR_MULTI_FACTOR]),
+                ip_address.clone(),
+                user_agent.clone(),
+            )
+            .await?;
+
+        self.log_activity_success(Some(user.id), "login", ip_address, user_agent)
+            .await;
+
+        Ok((auth_response, refresh_token))
     }
 
-    /// Validates refresh token and generates a new access token.
-    ///
-    /// This method validates the refresh token from the cookie, verifies it's
-    /// a refresh token (not an access token), checks the session exists, and
-    /// generates a new access token.
-    ///
-    /// # Arguments
+    /// Replaces the authenticated user's recovery codes with a new set.
     ///
-    /// * `refresh_token` - Refresh token string from the HTTP-only cookie
+    /// Requires the current password. Any previously issued codes stop working.
     ///
     /// # Returns
     ///
-    /// Returns a new access token string.
+    /// Returns the new plain codes. They are not retrievable afterwards.
     ///
     /// # Errors
     ///
-    /// - `AppError::Unauthorized` if token is invalid, expired, or not a refresh token
-    /// - `AppError::InternalError` if new access token generation fails
-    /// Validates refresh token and generates a new access token and refresh token (Rotation).
+    /// - `AppError::NotFound` if the user does not exist
+    /// - `AppError::Unauthorized` if the password is invalid
+    pub async fn regenerate_recovery_codes(
+        &self,
+        user_id: uuid::Uuid,
+        current_password: &str,
+        http_req: &actix_web::HttpRequest,
+    ) -> Result<RecoveryCodesResponse, AppError> {
+        let (ip_address, user_agent) = request_helper::extract_client_info(http_req);
+
+        let user: User = self
+            .repository
+            .find_by_id(user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+        if !password::verify_password(current_password, &user.password_hash)? {
+            let err = AppError::Unauthorized("Invalid credentials".to_string());
+            self.log_activity_failure(
+                Some(user_id),
+                "recovery_codes_generate",
+                &err,
+                ip_address,
+                user_agent,
+            )
+            .await;
+            return Err(err);
+        }
+
+        let recovery_codes = self.issue_recovery_codes(user_id).await?;
+        self.log_activity_success(
+            Some(user_id),
+            "recovery_codes_generate",
+            ip_address,
+            user_agent,
+        )
+        .await;
+
+        Ok(RecoveryCodesResponse { recovery_codes })
+    }
+
+    /// Resets a user's credentials with a single-use recovery code.
     ///
-    /// This method validates the refresh token from the cookie, verifies it's
-    /// a refresh token (not an access token), checks the session exists,
-    /// invalidates the old session (Refresh Token Rotation), and generates new tokens.
+    /// For users who lost their password or second factor. Sets the new password,
+    /// removes the TOTP enrollment so the user can sign in and enroll again, and
+    /// revokes all sessions and access tokens.
     ///
-    /// # Arguments
+    /// # Errors
+    ///
+    /// - `AppError::ValidationError` if the new password is invalid or does not match
+    /// - `AppError::Unauthorized` if the user or recovery code is invalid
+    pub async fn reset_with_recovery_code(
+        &self,
+        req: RecoveryResetRequest,
+        tenant_id: uuid::Uuid,
+        http_req: &actix_web::HttpRequest,
+    ) -> Result<(), AppError> {
+        let (ip_address, user_agent) = request_helper::extract_client_info(http_req);
+
+        if req.new_password != req.confirm_new_password {
+            return Err(AppError::ValidationError(
+                "Passwords do not match".to_string(),
+                Some(vec![ValidationDetail {
+                    field: "confirm_new_password".to_string(),
+                    message: "Passwords do not match".to_string(),
+                }]),
+            ));
+        }
+        user_validator::validate_password(&req.new_password, "new_password")?;
+
+        // Same error for unknown users and wrong codes to avoid account enumeration
+        let invalid = || AppError::Unauthorized("Invalid recovery code".to_string());
+
+        let identifier = if req.email_or_username.contains('@') {
+            req.email_or_username.to_lowercase()
+        } else {
+            req.email_or_username.clone()
+        };
+        let user = match self
+            .repository
+            .find_by_email_with_deleted(&identifier)
+            .await?
+        {
+            Some(u) => Some(u),
+            None => {
+                self.repository
+                    .find_by_username_with_deleted(&req.email_or_username)
+                    .await?
+            }
+        }
+        .filter(|u| u.deleted_at.is_none())
+        .ok_or_else(invalid)?;
+
+        let roles = self
+            .user_tenant_repository
+            .get_user_roles_in_tenant(user.id, tenant_id)
+            .await?;
+        if roles.is_empty() {
+            return Err(invalid());
+        }
+
+        if !self
+            .consume_recovery_code(user.id, &req.recovery_code)
+            .await?
+        {
+            let err = invalid();
+            self.log_activity_failure(
+                Some(user.id),
+                "recovery_reset",
+                &err,
+                ip_address,
+                user_agent,
+            )
+            .await;
+            return Err(err);
+        }
+
+        let update_req = crate::domains::user::dtos::user_dto::UpdateUserRequest {
+            username: None,
+            email: None,
+            password: Some(req.new_password),
+        };
+        self.repository.update(user.id, update_req).await?;
+        self.mfa_repository.delete_by_user_id(user.id).await?;
+        self.session_repository
+            .delete_all_sessions_for_user(user.id)
+            .await?;
+        self.deny_issued_tokens(user.id).await?;
+
+        self.log_activity_success(Some(user.id), "recovery_reset", ip_address, user_agent)
+            .await;
+
+        Ok(())
+    }
+
+    /// Starts TOTP enrollment for the authenticated user.
+    ///
+    /// Generates a new shared secret and stores it as a pending enrollment.
+    /// MFA is not enforced until the enrollment is confirmed with `confirm_totp`.
+    ///
+    /// # Errors
+    ///
+    /// - `AppError::Conflict` if MFA is already enabled
+    /// - `AppError::NotFound` if the user does not exist
+    pub async fn enroll_totp(
+        &self,
+        user_id: uuid::Uuid,
+    ) -> Result<TotpEnrollmentResponse, AppError> {
+        use crate::domains::common::utils::config::Config;
+
+        let user: User = self
+            .repository
+            .find_by_id(user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+        if let Some(existing) = self.mfa_repository.find_by_user_id(user.id).await? {
+            if existing.is_enabled() {
+                return Err(AppError::Conflict("MFA is already enabled".to_string()));
+            }
+        }
+
+        let secret = totp::generate_secret();
+        self.mfa_repository
+            .upsert_pending(user.id, secret.clone())
+            .await?;
+
+        let provisioning_uri =
+            totp::provisioning_uri(&Config::get().mfa_issuer, &user.email, &secret);
+
+        Ok(TotpEnrollmentResponse {
+            secret,
+            provisioning_uri,
+        })
+    }
+
+    /// Confirms a pending TOTP enrollment with a code from the authenticator app.
+    ///
+    /// A new set of recovery codes is issued so the user is not locked out if
+    /// the authenticator is lost.
+    ///
+    /// # Errors
+    ///
+    /// - `AppError::NotFound` if there is no pending enrollment
+    /// - `AppError::Conflict` if MFA is already enabled
+    /// - `AppError::Unauthorized` if the code is invalid
+    pub async fn confirm_totp(
+        &self,
+        user_id: uuid::Uuid,
+        code: &str,
+        http_req: &actix_web::HttpRequest,
+    ) -> Result<RecoveryCodesResponse, AppError> {
+        let (ip_address, user_agent) = request_helper::extract_client_info(http_req);
+
+        let mfa = self
+            .mfa_repository
+            .find_by_user_id(user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("MFA enrollment not found".to_string()))?;
+
+        if mfa.is_enabled() {
+            return Err(AppError::Conflict("MFA is already enabled".to_string()));
+        }
+
+        let Some(step) = Self::verify_totp(&mfa, code)? else {
+            let err = AppError::Unauthorized("Invalid MFA code".to_string());
+            self.log_activity_failure(Some(user_id), "mfa_enroll", &err, ip_address, user_agent)
+                .await;
+            return Err(err);
+        };
+
+        self.mfa_repository.enable(user_id, step).await?;
+        let recovery_codes = self.issue_recovery_codes(user_id).await?;
+        self.log_activity_success(Some(user_id), "mfa_enroll", ip_address, user_agent)
+            .await;
+
+        Ok(RecoveryCodesResponse { recovery_codes })
+    }
+
+    /// Disables TOTP for the authenticated user after checking a current code.
+    ///
+    /// # Errors
+    ///
+    /// - `AppError::NotFound` if MFA is not enabled
+    /// - `AppError::Unauthorized` if the code is invalid
+    pub async fn disable_totp(
+        &self,
+        user_id: uuid::Uuid,
+        code: &str,
+        http_req: &actix_web::HttpRequest,
+    ) -> Result<(), AppError> {
+        let (ip_address, user_agent) = request_helper::extract_client_info(http_req);
+
+        let mfa = self
+            .mfa_repository
+            .find_by_user_id(user_id)
+            .await?
+            .filter(|m| m.is_enabled())
+            .ok_or_else(|| AppError::NotFound("MFA is not enabled".to_string()))?;
+
+        if Self::verify_totp(&mfa, code)?.is_none() {
+            let err = AppError::Unauthorized("Invalid MFA code".to_string());
+            self.log_activity_failure(Some(user_id), "mfa_disable", &err, ip_address, user_agent)
+                .await;
+            return Err(err);
+        }
+
+        self.mfa_repository.delete_by_user_id(user_id).await?;
+        self.log_activity_success(Some(user_id), "mfa_disable", ip_address, user_agent)
+            .await;
+
+        Ok(())
+    }
+
+    /// Logs out a user by deleting their session.
+    ///
+    /// This endpoint requires JWT authentication and a valid refresh token cookie.
+    /// The access token of the request is denied for the rest of its lifetime.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - User ID from JWT token
+    /// * `http_req` - HTTP request containing refresh token cookie
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Unauthorized` if refresh token is missing or invalid.
+    pub async fn logout(
+        &self,
+        user_id: uuid::Uuid,
+        http_req: &actix_web::HttpRequest,
+    ) -> Result<(), AppError> {
+        let (ip_address, user_agent) = request_helper::extract_client_info(http_req);
+
+        self.deny_request_token(http_req).await?;
+
+        // Extract refresh token from cookie
+        let refresh_token = http_req
+            .cookie("refresh_token")
+            .ok_or_else(|| AppError::Unauthorized("Refresh token not found".to_string()))?
+            .value()
+            .to_string();
+
+        // Hash the refresh token to find the session
+        let refresh_token_hash = request_helper::hash_token(&refresh_token);
+
+        // Find and delete the session along with the sessions it was rotated from
+        if let Some(session) = self
+            .session_repository
+            .find_by_refresh_token_hash(&refresh_token_hash)
+            .await?
+        {
+            self.session_repository
+                .delete_family(session.family_id)
+                .await?;
+        }
+
+        // Log successful logout
+        self.log_activity_success(Some(user_id), "logout", ip_address, user_agent)
+            .await;
+
+        Ok(())
+    }
+
+    /// Logs out a user using cookie-based session identification (SSO Logout).
+    ///
+    /// This method is intended for redirect-based logout flows where the client
+    /// may not send an Authorization header but sends the HTTP-Only cookie.
+    ///
+    /// # Arguments
+    ///
+    /// * `http_req` - HTTP request containing refresh token cookie
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Unauthorized` if refresh token is missing or invalid.
+    pub async fn sso_logout(&self, http_req: &actix_web::HttpRequest) -> Result<(), AppError> {
+        let (ip_address, user_agent) = request_helper::extract_client_info(http_req);
+
+        // Extract refresh token from cookie
+        let refresh_token = http_req
+            .cookie("refresh_token")
+            .ok_or_else(|| AppError::Unauthorized("Refresh token not found".to_string()))?
+            .value()
+            .to_string();
+
+        // Hash the refresh token to find the session
+        let refresh_token_hash = request_helper::hash_token(&refresh_token);
+
+        // Find match session
+        if let Some(session) = self
+            .session_repository
+            .find_by_refresh_token_hash(&refresh_token_hash)
+            .await?
+        {
+            // Delete the session along with the sessions it was rotated from
+            self.session_repository
+                .delete_family(session.family_id)
+                .await?;
+
+            // Log successful logout using user_id from session
+            self.log_activity_success(Some(session.user_id), "sso_logout", ip_address, user_agent)
+                .await;
+        } else {
+            // Session not found (already logged out or invalid)
+            // We still consider this specific operation a "success" (idempotent)
+            // but maybe log a warning or just return Ok.
+            // For strictness, if cookie exists but session doesn't, it's stale.
+        }
+
+        Ok(())
+    }
+
+    /// Gets the refresh token expiry duration in seconds.
+    ///
+    /// This value is used for setting the max-age of the refresh token cookie.
+    ///
+    /// # Returns
+    ///
+    /// Refresh token expiry in seconds (default: 604800 = 7 days).
+    pub fn get_refresh_token_expiry(&self) -> i64 {
+        self.jwt_service.get_refresh_token_expiry()
+    }
+
+    /// Validates a refresh token and exchanges it for a new token pair (Rotation).
+    ///
+    /// This method validates the refresh token from the cookie, verifies it's
+    /// a refresh token (not an access token), checks the session exists, and
+    /// replaces the session with a successor in the same token family. The
+    /// presented refresh token cannot be used again.
+    ///
+    /// A refresh token whose session was already rotated indicates that the
+    /// token was stolen or replayed: the whole family is revoked and a
+    /// `refresh_token_reuse` failure is logged.
+    ///
+    /// # Arguments
     ///
     /// * `refresh_token` - Refresh token string from the HTTP-only cookie
+    /// * `ip_address` - Client IP address recorded on the new session
+    /// * `user_agent` - Client user agent recorded on the new session
     ///
     /// # Returns
     ///
-    /// Returns a tuple of (new access token, expires_in).
+    /// Returns a tuple of `(AuthResponse, new refresh token)`.
     ///
     /// # Errors
     ///
-    /// - `AppError::Unauthorized` if token is invalid, expired, or not a refresh token
+    /// - `AppError::Unauthorized` if token is invalid, expired, not a refresh token or reused
     /// - `AppError::InternalError` if new token generation fails
-    pub async fn refresh_token(&self, refresh_token: &str) -> Result<(String, i64), AppError> {
+    pub async fn refresh_token(
+        &self,
+        refresh_token: &str,
+        ip_address: Option<String>,
+        user_agent: Option<String>,
+    ) -> Result<(AuthResponse, String), AppError> {
         // Validate the refresh token
         let claims = self.jwt_service.validate_token(refresh_token).map_err(
             |e: jsonwebtoken::errors::Error| {
@@ -577,35 +1119,64 @@ impl AuthUseCase {
         // Verify session exists
         let refresh_token_hash = request_helper::hash_token(refresh_token);
         log::debug!("Validating session for hash: [REDACTED]");
-        let _session: UserSession = self
+        let session: UserSession = self
             .session_repository
             .find_by_refresh_token_hash(&refresh_token_hash)
             .await?
+            .filter(|s| s.user_id == user_id)
             .ok_or_else(|| {
                 log::warn!("Session not found for hash: [REDACTED]");
                 AppError::Unauthorized("Unauthorized".to_string())
             })?;
 
+        if session.rotated_at.is_some() {
+            return Err(self
+                .revoke_reused_family(&session, ip_address, user_agent)
+                .await);
+        }
+
         // Verify user still exists in database
         log::debug!("Verifying user exists: {}", user_id);
-        let _user: User = self.repository.find_by_id(user_id).await?.ok_or_else(|| {
+        let user: User = self.repository.find_by_id(user_id).await?.ok_or_else(|| {
             log::warn!("User not found: {}", user_id);
             AppError::Unauthorized("Unauthorized".to_string())
         })?;
+        Self::ensure_active(&user)?;
 
-        // Generate new access token (refresh token stays the same)
-        let new_access_token = self
-            .jwt_service
-            .generate_access_token(user_id, tenant_id, role)
-            .map_err(|e| {
-                AppError::InternalError(format!("Failed to generate access token: {}", e))
-            })?;
+        // Issue the successor session; losing a race against a concurrent
+        // refresh with the same token is treated as reuse
+        let session_id = uuid::Uuid::new_v4();
+        let (auth_response, new_refresh_token) = self.generate_token_pair(
+            user_id,
+            tenant_id,
+            role,
+            session_id,
+            claims.authentication().as_ref(),
+        )?;
+        let expires_at =
+            Utc::now() + chrono::Duration::seconds(self.jwt_service.get_refresh_token_expiry());
 
-        let expires_in = self.jwt_service.get_access_token_expiry();
-        Ok((new_access_token, expires_in))
+        let rotated = self
+            .session_repository
+            .rotate_session(
+                &session,
+                session_id,
+                request_helper::hash_token(&new_refresh_token),
+                user_agent.clone(),
+                ip_address.clone(),
+                expires_at,
+            )
+            .await?;
+        if rotated.is_none() {
+            return Err(self
+                .revoke_reused_family(&session, ip_address, user_agent)
+                .await);
+        }
+
+        Ok((auth_response, new_refresh_token))
     }
 
-    /// Extracts refresh token from request cookie and generates new tokens.
+    /// Extracts refresh token from request cookie and rotates it.
     ///
     /// This is a convenience method that handles cookie extraction and delegates
     /// to `refresh_token` for the actual token validation and generation.
@@ -616,7 +1187,7 @@ impl AuthUseCase {
     ///
     /// # Returns
     ///
-    /// Returns (new access token, new refresh token).
+    /// Returns a tuple of `(AuthResponse, new refresh token)`.
     ///
     /// # Errors
     ///
@@ -625,7 +1196,9 @@ impl AuthUseCase {
     pub async fn refresh_token_from_request(
         &self,
         req: &actix_web::HttpRequest,
-    ) -> Result<(String, i64), AppError> {
+    ) -> Result<(AuthResponse, String), AppError> {
+        let (ip_address, user_agent) = request_helper::extract_client_info(req);
+
         // Extract refresh token from cookie
         let refresh_token = req
             .cookie("refresh_token")
@@ -634,7 +1207,8 @@ impl AuthUseCase {
             .to_string();
 
         // Delegate to refresh_token method
-        self.refresh_token(&refresh_token).await
+        self.refresh_token(&refresh_token, ip_address, user_agent)
+            .await
     }
 
     /// Changes a user's password.
@@ -672,69 +1246,320 @@ impl AuthUseCase {
             ));
         }
 
-        // Validate new password is not the same as old password
-        if req.new_password == req.old_password {
-            return Err(AppError::ValidationError(
-                "New password cannot be the same as old password".to_string(),
-                Some(vec![ValidationDetail {
-                    field: "new_password".to_string(),
-                    message: "New password cannot be the same as old password".to_string(),
-                }]),
-            ));
+        // Validate new password is not the same as old password
+        if req.new_password == req.old_password {
+            return Err(AppError::ValidationError(
+                "New password cannot be the same as old password".to_string(),
+                Some(vec![ValidationDetail {
+                    field: "new_password".to_string(),
+                    message: "New password cannot be the same as old password".to_string(),
+                }]),
+            ));
+        }
+
+        // Validate new password strength
+        if let Err(e) = user_validator::validate_password(&req.new_password, "new_password") {
+            self.log_activity_failure(
+                Some(user_id),
+                "change_password",
+                &e,
+                ip_address.clone(),
+                user_agent.clone(),
+            )
+            .await;
+            return Err(e);
+        }
+
+        // Get user from database
+        let user: User = self
+            .repository
+            .find_by_id(user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+        // Verify old password
+        if !password::verify_password(&req.old_password, &user.password_hash)? {
+            let err = AppError::Unauthorized("Invalid credentials".to_string());
+            self.log_activity_failure(
+                Some(user_id),
+                "change_password",
+                &err,
+                ip_address.clone(),
+                user_agent.clone(),
+            )
+            .await;
+            return Err(err);
+        }
+
+        // Update password
+        let update_req = crate::domains::user::dtos::user_dto::UpdateUserRequest {
+            username: None,
+            email: None,
+            password: Some(req.new_password),
+        };
+        self.repository.update(user_id, update_req).await?;
+
+        // Revoke all sessions for the user (security best practice)
+        self.session_repository
+            .delete_all_sessions_for_user(user_id)
+            .await?;
+
+        // End access tokens already issued, including ones an attacker may hold
+        self.deny_request_token(http_req).await?;
+        self.deny_issued_tokens(user_id).await?;
+
+        // Log successful password change
+        self.log_activity_success(Some(user_id), "change_password", ip_address, user_agent)
+            .await;
+
+        Ok(())
+    }
+
+    /// Confirms the signed-in user's credentials again and issues an access
+    /// token with a fresh `auth_time`, for routes guarded by `StepUpMiddleware`.
+    ///
+    /// The role and tenant of the current token are kept. Users with MFA
+    /// enabled must also present a TOTP code, which makes the new token
+    /// multi-factor.
+    ///
+    /// # Errors
+    ///
+    /// - `AppError::Unauthorized` if the request has no user token, or the password or code is invalid
+    /// - `AppError::Forbidden` if the account is no longer active
+    pub async fn reauthenticate(
+        &self,
+        req: ReauthenticateRequest,
+        http_req: &actix_web::HttpRequest,
+    ) -> Result<AuthResponse, AppError> {
+        let (ip_address, user_agent) = request_helper::extract_client_info(http_req);
+        let claims = http_req
+            .extensions()
+            .get::<Claims>()
+            .cloned()
+            .filter(|c| !c.is_client())
+            .ok_or_else(|| AppError::Unauthorized("Unauthorized".to_string()))?;
+        let tenant_id = uuid::Uuid::parse_str(&claims.tenant_id)
+            .map_err(|_| AppError::Unauthorized("Invalid tenant ID in token".to_string()))?;
+        let user_id = uuid::Uuid::parse_str(&claims.sub)
+            .map_err(|_| AppError::Unauthorized("Invalid user ID in token".to_string()))?;
+
+        let user = self
+            .repository
+            .find_by_id(user_id)
+            .await?
+            .ok_or_else(|| AppError::Unauthorized("Unauthorized".to_string()))?;
+
+        if user.locked_until.is_some_and(|until| until > Utc::now()) {
+            let err = AppError::Locked(
+                "Account is temporarily locked due to too many failed login attempts".to_string(),
+            );
+            self.log_activity_failure(
+                Some(user.id),
+                "reauthenticate",
+                &err,
+                ip_address,
+                user_agent,
+            )
+            .await;
+            return Err(err);
+        }
+
+        if !password::verify_password(&req.password, &user.password_hash)? {
+            self.record_login_failure(&user).await?;
+            let err = AppError::Unauthorized("Invalid credentials".to_string());
+            self.log_activity_failure(
+                Some(user.id),
+                "reauthenticate",
+                &err,
+                ip_address,
+                user_agent,
+            )
+            .await;
+            return Err(err);
+        }
+
+        if user.failed_login_attempts > 0 || user.locked_until.is_some() {
+            self.repository.set_login_failures(user.id, 0, None).await?;
+        }
+
+        if let Err(err) = Self::ensure_active(&user) {
+            self.log_activity_failure(
+                Some(user.id),
+                "reauthenticate",
+                &err,
+                ip_address,
+                user_agent,
+            )
+            .await;
+            return Err(err);
+        }
+
+        let mut methods = vec![AMR_PASSWORD];
+        if let Some(mfa) = self
+            .mfa_repository
+            .find_by_user_id(user.id)
+            .await?
+            .filter(|m| m.is_enabled())
+        {
+            let step = match req.code.as_deref() {
+                Some(code) => Self::verify_totp(&mfa, code)?,
+                None => None,
+            };
+            let accepted = match step {
+                Some(step) => {
+                    self.mfa_repository
+                        .update_last_used_step(user.id, step)
+                        .await?
+                }
+                None => false,
+            };
+
+            if !accepted {
+                let err = AppError::Unauthorized("Invalid MFA code".to_string());
+                self.log_activity_failure(
+                    Some(user.id),
+                    "reauthenticate",
+                    &err,
+                    ip_address,
+                    user_agent,
+                )
+                .await;
+                return Err(err);
+            }
+            methods.extend([AMR_OTP, AMR_MULTI_FACTOR]);
+        }
+
+        let access_token = self
+            .jwt_service
+            .generate_session_access_token(
+                user.id,
+                tenant_id,
+                claims.role,
+                Some(&Authentication::now(&methods)),
+            )
+            .map_err(|e| {
+                AppError::InternalError(format!("Failed to generate access token: {}", e))
+            })?;
+
+        self.log_activity_success(Some(user.id), "reauthenticate", ip_address, user_agent)
+            .await;
+
+        Ok(AuthResponse {
+            user_id: user.id,
+            access_token,
+            expires_in: self.jwt_service.get_access_token_expiry(),
+        })
+    }
+
+    /// Issues a short-lived access token for an admin to act as another user
+    /// of the same tenant, e.g. to reproduce a customer issue.
+    ///
+    /// The token names the admin in its `impersonator` claim and cannot be
+    /// refreshed. Routes that change credentials or delete the account refuse
+    /// it. Both users get an entry in their activity log.
+    ///
+    /// # Arguments
+    ///
+    /// * `admin_id` - ID of the requesting admin (from JWT)
+    /// * `target_id` - ID of the user to impersonate
+    /// * `tenant_id` - Tenant of the request (from ApiKeyMiddleware)
+    /// * `http_req` - HTTP request for extracting client info
+    ///
+    /// # Errors
+    ///
+    /// - `AppError::Forbidden` if the requesting user is not an admin of the tenant
+    /// - `AppError::BadRequest` if the admin targets themselves
+    /// - `AppError::NotFound` if the user does not exist, is not active or is not a member of the tenant
+    pub async fn impersonate(
+        &self,
+        admin_id: uuid::Uuid,
+        target_id: uuid::Uuid,
+        tenant_id: uuid::Uuid,
+        http_req: &actix_web::HttpRequest,
+    ) -> Result<AuthResponse, AppError> {
+        let (ip_address, user_agent) = request_helper::extract_client_info(http_req);
+
+        let admin_roles = self
+            .user_tenant_repository
+            .get_user_roles_in_tenant(admin_id, tenant_id)
+            .await?;
+        if !admin_roles.iter().any(|r| r == "admin") {
+            let err = AppError::Forbidden("Forbidden".to_string());
+            self.log_activity_failure(Some(admin_id), "impersonate", &err, ip_address, user_agent)
+                .await;
+            return Err(err);
         }
 
-        // Validate new password strength
-        if let Err(e) = user_validator::validate_password(&req.new_password, "new_password") {
-            self.log_activity_failure(
-                Some(user_id),
-                "change_password",
-                &e,
-                ip_address.clone(),
-                user_agent.clone(),
-            )
-            .await;
-            return Err(e);
+        if target_id == admin_id {
+            return Err(AppError::BadRequest(
+                "You cannot impersonate yourself".to_string(),
+            ));
         }
 
-        // Get user from database
-        let user: User = self
+        let not_found = || AppError::NotFound(format!("User with id {} not found", target_id));
+        let user = self
             .repository
-            .find_by_id(user_id)
+            .find_by_id(target_id)
             .await?
-            .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+            .filter(|u| u.is_active())
+            .ok_or_else(not_found)?;
 
-        // Verify old password
-        if !password::verify_password(&req.old_password, &user.password_hash)? {
-            let err = AppError::Unauthorized("Invalid credentials".to_string());
-            self.log_activity_failure(
-                Some(user_id),
-                "change_password",
-                &err,
-                ip_address.clone(),
-                user_agent.clone(),
-            )
-            .await;
-            return Err(err);
-        }
+        // Admins can only impersonate members of their own tenant
+        let role = self
+            .user_tenant_repository
+            .get_user_roles_in_tenant(user.id, tenant_id)
+            .await?
+            .first()
+            .cloned()
+            .ok_or_else(not_found)?;
 
-        // Update password
-        let update_req = crate::domains::user::dtos::user_dto::UpdateUserRequest {
-            username: None,
-            email: None,
-            password: Some(req.new_password),
-        };
-        self.repository.update(user_id, update_req).await?;
+        let access_token = self
+            .jwt_service
+            .generate_impersonation_token(user.id, tenant_id, role, admin_id)
+            .map_err(|e| AppError::InternalError(format!("Failed to generate token: {}", e)))?;
+
+        self.log_activity_success(
+            Some(admin_id),
+            "impersonate",
+            ip_address.clone(),
+            user_agent.clone(),
+        )
+        .await;
+        self.log_activity_success(Some(user.id), "impersonated", ip_address, user_agent)
+            .await;
+        log::info!(
+            "impersonate: admin_id={}, user_id={}, tenant_id={}",
+            admin_id,
+            user.id,
+            tenant_id
+        );
 
-        // Revoke all sessions for the user (security best practice)
-        self.session_repository
-            .delete_all_sessions_for_user(user_id)
-            .await?;
+        Ok(AuthResponse {
+            user_id: user.id,
+            access_token,
+            expires_in: self.jwt_service.get_impersonation_token_expiry(),
+        })
+    }
 
-        // Log successful password change
-        self.log_activity_success(Some(user_id), "change_password", ip_address, user_agent)
-            .await;
+    /// Denies the access token that authenticated `http_req`, if any.
+    async fn deny_request_token(&self, http_req: &actix_web::HttpRequest) -> Result<(), AppError> {
+        let claims = http_req.extensions().get::<Claims>().cloned();
+        match claims {
+            Some(claims) => self.token_denylist_repository.deny_token(&claims).await,
+            None => Ok(()),
+        }
+    }
 
-        Ok(())
+    /// Denies every access token issued to the user so far.
+    pub(crate) async fn deny_issued_tokens(&self, user_id: uuid::Uuid) -> Result<(), AppError> {
+        self.token_denylist_repository
+            .deny_subject(
+                &user_id.to_string(),
+                std::time::Duration::from_secs(
+                    self.jwt_service.get_access_token_expiry().max(0) as u64
+                ),
+            )
+            .await
     }
 
     /// Verifies if user exists in database by user_id.
@@ -752,7 +1577,8 @@ impl AuthUseCase {
     ///
     /// # Errors
     ///
-    /// Returns `AppError::Forbidden` if user doesn't exist in database.
+    /// - `AppError::Unauthorized` if user doesn't exist in database
+    /// - `AppError::Forbidden` if the user is suspended, banned or pending approval
     pub async fn verify_user_exists(
         &self,
         user_id: uuid::Uuid,
@@ -764,6 +1590,7 @@ impl AuthUseCase {
             .find_by_id(user_id)
             .await?
             .ok_or_else(|| AppError::Unauthorized("Unauthorized".to_string()))?;
+        Self::ensure_active(&user)?;
 
         // Fetch user_details
         let user_details = self
@@ -816,8 +1643,324 @@ impl AuthUseCase {
             .ok_or_else(|| AppError::Unauthorized("Unauthorized".to_string()))
     }
 
+    /// Validates an `mfa_pending` challenge token for the given tenant and loads its user.
+    async fn resolve_mfa_challenge(
+        &self,
+        mfa_token: &str,
+        tenant_id: uuid::Uuid,
+    ) -> Result<(User, Claims), AppError> {
+        let claims = self
+            .jwt_service
+            .validate_token(mfa_token)
+            .map_err(|_| AppError::Unauthorized("Unauthorized".to_string()))?;
+
+        if claims.token_type != "mfa_pending" || claims.tenant_id != tenant_id.to_string() {
+            return Err(AppError::Unauthorized("Unauthorized".to_string()));
+        }
+        // Challenges are denied after too many wrong codes
+        if self.token_denylist_repository.is_revoked(&claims).await? {
+            return Err(AppError::Unauthorized("Unauthorized".to_string()));
+        }
+
+        let user_id = uuid::Uuid::parse_str(&claims.sub)
+            .map_err(|_| AppError::Unauthorized("Unauthorized".to_string()))?;
+
+        let user: User = self
+            .repository
+            .find_by_id(user_id)
+            .await?
+            .ok_or_else(|| AppError::Unauthorized("Unauthorized".to_string()))?;
+        Self::ensure_active(&user)?;
+
+        if user.locked_until.is_some_and(|until| until > Utc::now()) {
+            return Err(AppError::Locked(
+                "Account is temporarily locked due to too many failed login attempts".to_string(),
+            ));
+        }
+
+        Ok((user, claims))
+    }
+
+    /// Generates a new set of recovery codes, stores their hashes and returns the plain codes.
+    async fn issue_recovery_codes(&self, user_id: uuid::Uuid) -> Result<Vec<String>, AppError> {
+        let codes = recovery_code::generate_codes();
+        let code_hashes = codes
+            .iter()
+            .map(|code| password::hash_password(&recovery_code::normalize(code)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        self.recovery_code_repository
+            .replace_for_user(user_id, code_hashes)
+            .await?;
+
+        Ok(codes)
+    }
+
+    /// Checks a recovery code against the user's unused codes and consumes it on match.
+    ///
+    /// Returns `false` if no unused code matches.
+    async fn consume_recovery_code(
+        &self,
+        user_id: uuid::Uuid,
+        code: &str,
+    ) -> Result<bool, AppError> {
+        let normalized = recovery_code::normalize(code);
+        if normalized.is_empty() {
+            return Ok(false);
+        }
+
+        for stored in self
+            .recovery_code_repository
+            .find_unused_by_user_id(user_id)
+            .await?
+        {
+            if password::verify_password(&normalized, &stored.code_hash)? {
+                return self.recovery_code_repository.mark_used(stored.id).await;
+            }
+        }
+
+        Ok(false)
+    }
+
+    /// Picks the role to put in the tokens from the user's roles in the tenant.
+    ///
+    /// Without a requested role, `user` is preferred, otherwise the first role.
+    /// Returns `None` if a role was requested that the user does not hold.
+    pub(crate) fn select_role(roles: &[String], requested: Option<&str>) -> Option<String> {
+        match requested {
+            Some(requested) => roles.iter().find(|r| *r == requested).cloned(),
+            None => roles
+                .iter()
+                .find(|r| *r == "user")
+                .or_else(|| roles.first())
+                .cloned(),
+        }
+    }
+
+    /// Rejects users whose account is not active.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Forbidden` if the user is suspended, banned or pending
+    /// approval and the status has not expired.
+    pub(crate) fn ensure_active(user: &User) -> Result<(), AppError> {
+        let message = match user.effective_status() {
+            STATUS_SUSPENDED => "Account is suspended",
+            STATUS_BANNED => "Account is banned",
+            STATUS_PENDING => "Account is pending approval",
+            _ => return Ok(()),
+        };
+
+        Err(AppError::Forbidden(message.to_string()))
+    }
+
+    /// Rejects users with an unverified email if the tenant requires verification.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Forbidden` if the tenant requires a verified email and
+    /// the user has not verified theirs.
+    pub(crate) async fn ensure_email_verified(
+        &self,
+        user: &User,
+        tenant_id: uuid::Uuid,
+    ) -> Result<(), AppError> {
+        if self.email_verification_pending(user, tenant_id).await? {
+            return Err(AppError::Forbidden(
+                "Email address is not verified".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Returns true if the tenant requires a verified email and the user has
+    /// not verified theirs yet.
+    async fn email_verification_pending(
+        &self,
+        user: &User,
+        tenant_id: uuid::Uuid,
+    ) -> Result<bool, AppError> {
+        if user.email_verified_at.is_some() {
+            return Ok(false);
+        }
+
+        Ok(self
+            .tenant_repository
+            .find_by_id(tenant_id)
+            .await?
+            .is_some_and(|tenant| tenant.require_email_verification))
+    }
+
+    /// Generates an access/refresh token pair and records the backing session.
+    ///
+    /// The refresh token's JTI doubles as the session ID so that the session can
+    /// be located from the token. Both tokens record `authentication`.
+    pub(crate) async fn issue_session_tokens(
+        &self,
+        user_id: uuid::Uuid,
+        tenant_id: uuid::Uuid,
+        role: String,
+        authentication: &Authentication,
+        ip_address: Option<String>,
+        user_agent: Option<String>,
+    ) -> Result<(AuthResponse, String), AppError> {
+        // Generate a shared UUID for JTI and DB Session ID
+        let session_id = uuid::Uuid::new_v4();
+        let (auth_response, refresh_token) =
+            self.generate_token_pair(user_id, tenant_id, role, session_id, Some(authentication))?;
+
+        // Create session record
+        let refresh_token_hash = request_helper::hash_token(&refresh_token);
+        let expires_at =
+            Utc::now() + chrono::Duration::seconds(self.jwt_service.get_refresh_token_expiry());
+
+        self.session_repository
+            .create_session(
+                Some(session_id),
+                user_id,
+                refresh_token_hash,
+                user_agent,
+                ip_address,
+                expires_at,
+            )
+            .await?;
+
+        Ok((auth_response, refresh_token))
+    }
+
+    /// Generates an access token and a refresh token whose JTI is `session_id`.
+    fn generate_token_pair(
+        &self,
+        user_id: uuid::Uuid,
+        tenant_id: uuid::Uuid,
+        role: String,
+        session_id: uuid::Uuid,
+        authentication: Option<&Authentication>,
+    ) -> Result<(AuthResponse, String), AppError> {
+        // Generate tokens with tenant context
+        let access_token = self
+            .jwt_service
+            .generate_session_access_token(user_id, tenant_id, role.clone(), authentication)
+            .map_err(|e| {
+                AppError::InternalError(format!("Failed to generate access token: {}", e))
+            })?;
+
+        let refresh_token = self
+            .jwt_service
+            .generate_refresh_token(
+                user_id,
+                tenant_id,
+                role,
+                Some(session_id.to_string()),
+                authentication,
+            )
+            .map_err(|e| {
+                AppError::InternalError(format!("Failed to generate refresh token: {}", e))
+            })?;
+
+        Ok((
+            AuthResponse {
+                user_id,
+                access_token,
+                expires_in: self.jwt_service.get_access_token_expiry(),
+            },
+            refresh_token,
+        ))
+    }
+
+    /// Revokes the token family of a session whose refresh token was presented
+    /// after it had been rotated, and logs a `refresh_token_reuse` failure.
+    ///
+    /// Returns the error to respond with.
+    async fn revoke_reused_family(
+        &self,
+        session: &UserSession,
+        ip_address: Option<String>,
+        user_agent: Option<String>,
+    ) -> AppError {
+        log::warn!(
+            "Refresh token reuse detected for user {}, revoking session family {}",
+            session.user_id,
+            session.family_id
+        );
+
+        if let Err(e) = self
+            .session_repository
+            .delete_family(session.family_id)
+            .await
+        {
+            log::error!(
+                "Failed to revoke session family {}: {}",
+                session.family_id,
+                e
+            );
+        }
+
+        let err = AppError::Unauthorized("Unauthorized".to_string());
+        self.log_activity_failure(
+            Some(session.user_id),
+            "refresh_token_reuse",
+            &err,
+            ip_address,
+            user_agent,
+        )
+        .await;
+
+        err
+    }
+
+    /// Counts a failed password login and locks the account once the lockout
+    /// policy's threshold is reached. Returns the consecutive failure count.
+    async fn record_login_failure(&self, user: &User) -> Result<i32, AppError> {
+        let failures = self.repository.increment_login_failures(user.id).await?;
+        let Some(until) = self
+            .lockout_policy
+            .lock_duration(failures)
+            .map(|duration| Utc::now() + duration)
+        else {
+            return Ok(failures);
+        };
+
+        log::warn!(
+            "Locking user {} until {} after {} failed logins",
+            user.id,
+            until,
+            failures
+        );
+        self.repository.lock_until(user.id, until).await?;
+        Ok(failures)
+    }
+
+    /// Counts a wrong second-factor code like a failed login and invalidates
+    /// the MFA challenge token after `MFA_MAX_ATTEMPTS` consecutive failures.
+    async fn record_mfa_failure(&self, user: &User, claims: &Claims) -> Result<(), AppError> {
+        let failures = self.record_login_failure(user).await?;
+        if failures >= MFA_MAX_ATTEMPTS {
+            log::warn!(
+                "Invalidating MFA challenge of user {} after {} failed attempts",
+                user.id,
+                failures
+            );
+            self.token_denylist_repository.deny_token(claims).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Checks a TOTP code against an MFA record, rejecting replayed time steps.
+    ///
+    /// Returns the matched time step when the code is valid and newer than the
+    /// last accepted one.
+    fn verify_totp(mfa: &UserMfa, code: &str) -> Result<Option<i64>, AppError> {
+        let now = Utc::now().timestamp().max(0) as u64;
+        let step = totp::verify_code(&mfa.totp_secret, code, now)?.map(|s| s as i64);
+
+        Ok(step.filter(|s| mfa.last_used_step.is_none_or(|last| *s > last)))
+    }
+
     /// Logs a successful activity.
-    async fn log_activity_success(
+    pub(crate) async fn log_activity_success(
         &self,
         user_id: Option<uuid::Uuid>,
         activity_type: &str,
@@ -838,7 +1981,7 @@ impl AuthUseCase {
     }
 
     /// Logs a failed activity.
-    async fn log_activity_failure(
+    pub(crate) async fn log_activity_failure(
         &self,
         user_id: Option<uuid::Uuid>,
         activity_type: &str,
@@ -859,6 +2002,48 @@ impl AuthUseCase {
             .await;
     }
 
+    /// Sends a rendered email in the background and logs the outcome as
+    /// `activity_type` with the client info of the triggering request.
+    ///
+    /// Responses must not wait for the mail transport, or their timing would
+    /// tell callers which addresses belong to an account. Delivery failures
+    /// are logged, not returned.
+    pub(crate) fn send_email_in_background(
+        self: &Arc<Self>,
+        mailer: Arc<dyn Mailer>,
+        rendered: Result<EmailMessage, AppError>,
+        user_id: uuid::Uuid,
+        activity_type: &'static str,
+        (ip_address, user_agent): (Option<String>, Option<String>),
+    ) {
+        let usecase = Arc::clone(self);
+        tokio::spawn(async move {
+            let sent = match rendered {
+                Ok(message) => mailer.send(message).await,
+                Err(e) => Err(e),
+            };
+            match sent {
+                Ok(()) => {
+                    usecase
+                        .log_activity_success(Some(user_id), activity_type, ip_address, user_agent)
+                        .await
+                }
+                Err(e) => {
+                    log::error!("Failed to send {} email: {}", activity_type, e);
+                    usecase
+                        .log_activity_failure(
+                            Some(user_id),
+                            activity_type,
+                            &e,
+                            ip_address,
+                            user_agent,
+                        )
+                        .await
+                }
+            }
+        });
+    }
+
     pub async fn generate_invitation_code(&self) -> Result<String, AppError> {
         // Generate a random 8-character code using UUID v4 (hex) to avoid rand deps issues
         let code = uuid::Uuid::new_v4().simple().to_string()[..8].to_string();
@@ -897,8 +2082,28 @@ impl AuthUseCase {
 
         // Active User Validation
 
+        // Linking checks the password, so it is subject to the login lockout
+        if existing_user
+            .locked_until
+            .is_some_and(|until| until > Utc::now())
+        {
+            let err = AppError::Locked(
+                "Account is temporarily locked due to too many failed login attempts".to_string(),
+            );
+            self.log_activity_failure(
+                Some(existing_user.id),
+                "register",
+                &err,
+                ip_address,
+                user_agent,
+            )
+            .await;
+            return Err(err);
+        }
+
         // Verify password for linkage security (prevent account hijacking)
         if !password::verify_password(&req.password, &existing_user.password_hash)? {
+            self.record_login_failure(&existing_user).await?;
             let err = AppError::Conflict("Invalid credentials for account linking".to_string());
             self.log_activity_failure(
                 Some(existing_user.id),
@@ -911,6 +2116,12 @@ impl AuthUseCase {
             return Err(err);
         }
 
+        if existing_user.failed_login_attempts > 0 || existing_user.locked_until.is_some() {
+            self.repository
+                .set_login_failures(existing_user.id, 0, None)
+                .await?;
+        }
+
         Ok((existing_user, false))
     }
 
@@ -939,16 +2150,28 @@ impl AuthUseCase {
         Ok((new_user, false))
     }
 
+    /// Returns the public JWT signing keys, empty when tokens are signed with `HS256`.
+    pub fn jwks(&self) -> JwkSet {
+        self.jwt_service.jwks()
+    }
+
     /// Converts a User entity and UserDetails to UserResponse DTO with details.
     fn user_to_response_with_details(
         user: User,
         user_details: Option<UserDetails>,
         role: String,
     ) -> UserResponse {
+        let status = user.effective_status().to_string();
+
         UserResponse {
             id: user.id,
             username: user.username,
             email: user.email,
+            email_verified: user.email_verified_at.is_some(),
+            email_verified_at: user.email_verified_at,
+            status,
+            status_reason: user.status_reason,
+            status_expires_at: user.status_expires_at,
             created_at: user.created_at,
             updated_at: user.updated_at,
             role,
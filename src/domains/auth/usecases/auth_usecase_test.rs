@@ -1,18 +1,27 @@
 #[cfg(test)]
 mod tests {
+    use crate::domains::auth::repositories::token_denylist_repository::TokenDenylistRepositoryTrait;
     use crate::domains::auth::usecases::auth_usecase::AuthUseCase;
     use crate::domains::common::errors::AppError;
+    use crate::domains::common::utils::jwt::{Claims, JwtService};
     use crate::domains::common::utils::password;
+    use crate::domains::tenant::entities::tenant::Model as Tenant;
+    use crate::domains::tenant::repositories::tenant_repository::TenantRepositoryTrait;
     use crate::domains::tenant::repositories::user_tenant_repository::{
         UserTenantInfo as TenantInfo, UserTenantRepositoryTrait,
     };
-    use crate::domains::user::dtos::auth_dto::LoginRequest;
+    use crate::domains::user::dtos::auth_dto::{LoginRequest, LoginResult, RegisterResult};
+    use crate::domains::user::dtos::change_password_dto::ChangePasswordRequest;
     use crate::domains::user::entities::user::Model as User;
     use crate::domains::user::entities::user_activity_log::Model as UserActivityLog;
     use crate::domains::user::entities::user_details::Model as UserDetails;
+    use crate::domains::user::entities::user_mfa::Model as UserMfa;
+    use crate::domains::user::entities::user_recovery_code::Model as UserRecoveryCode;
     use crate::domains::user::entities::user_session::Model as UserSession;
     use crate::domains::user::repositories::user_activity_log_repository::UserActivityLogRepositoryTrait;
     use crate::domains::user::repositories::user_details_repository::UserDetailsRepositoryTrait;
+    use crate::domains::user::repositories::user_mfa_repository::UserMfaRepositoryTrait;
+    use crate::domains::user::repositories::user_recovery_code_repository::UserRecoveryCodeRepositoryTrait;
     use crate::domains::user::repositories::user_repository::UserRepositoryTrait;
     use crate::domains::user::repositories::user_session_repository::UserSessionRepositoryTrait;
 
@@ -21,7 +30,9 @@ mod tests {
     use chrono::{DateTime, Utc};
     use mockall::mock;
     use mockall::predicate::*;
+    use std::collections::{HashMap, HashSet};
     use std::sync::{Arc, Mutex};
+    use std::time::Duration;
     use uuid::Uuid;
 
     // Mocking UserRepositoryTrait
@@ -38,6 +49,12 @@ mod tests {
             async fn find_by_email_with_deleted(&self, email: &str) -> Result<Option<User>, AppError>;
             async fn find_by_username_with_deleted(&self, username: &str) -> Result<Option<User>, AppError>;
             async fn restore(&self, id: Uuid, req: crate::domains::user::dtos::user_dto::CreateUserRequest) -> Result<User, AppError>;
+            async fn mark_email_verified(&self, id: Uuid) -> Result<User, AppError>;
+            async fn set_login_failures(&self, id: Uuid, failed_login_attempts: i32, locked_until: Option<DateTime<Utc>>) -> Result<(), AppError>;
+            async fn increment_login_failures(&self, id: Uuid) -> Result<i32, AppError>;
+            async fn lock_until(&self, id: Uuid, locked_until: DateTime<Utc>) -> Result<(), AppError>;
+            async fn set_status(&self, id: Uuid, status: String, reason: Option<String>, expires_at: Option<DateTime<Utc>>) -> Result<User, AppError>;
+            async fn purge_deleted_before(&self, cutoff: DateTime<Utc>) -> Result<u64, AppError>;
         }
     }
 
@@ -53,6 +70,43 @@ mod tests {
         }
     }
 
+    // Mocking TenantRepositoryTrait
+    mock! {
+        pub TenantRepository {}
+        #[async_trait]
+        impl TenantRepositoryTrait for TenantRepository {
+            async fn create(&self, tenant: crate::domains::tenant::dtos::tenant_dto::CreateTenantRequest) -> Result<Tenant, AppError>;
+            async fn find_by_id(&self, id: Uuid) -> Result<Option<Tenant>, AppError>;
+            async fn find_by_name(&self, name: &str) -> Result<Option<Tenant>, AppError>;
+            async fn find_by_name_with_deleted(&self, name: &str) -> Result<Option<Tenant>, AppError>;
+            async fn find_all(&self) -> Result<Vec<Tenant>, AppError>;
+            async fn update(&self, id: Uuid, tenant: crate::domains::tenant::dtos::tenant_dto::UpdateTenantRequest) -> Result<Tenant, AppError>;
+            async fn delete(&self, id: Uuid) -> Result<(), AppError>;
+            async fn restore(&self, id: Uuid) -> Result<(), AppError>;
+            async fn purge_deleted_before(&self, cutoff: DateTime<Utc>) -> Result<u64, AppError>;
+        }
+    }
+
+    /// Builds a tenant repository whose tenants have the given email verification policy.
+    fn tenant_repository(require_email_verification: bool) -> MockTenantRepository {
+        let mut repo = MockTenantRepository::new();
+        repo.expect_find_by_id().returning(move |id| {
+            Ok(Some(Tenant {
+                id,
+                name: "test-tenant".to_string(),
+                description: None,
+                api_key: None,
+                require_email_verification,
+                passwordless_login: false,
+                rate_limits: None,
+                deleted_at: None,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+            }))
+        });
+        repo
+    }
+
     // Fake UserTenantRepository
     struct FakeUserTenantRepository {
         role_response: Mutex<Vec<String>>,
@@ -113,8 +167,14 @@ mod tests {
         impl UserSessionRepositoryTrait for UserSessionRepository {
             async fn create_session(&self, id: Option<Uuid>, user_id: Uuid, refresh_token_hash: String, user_agent: Option<String>, ip_address: Option<String>, expires_at: DateTime<Utc>) -> Result<UserSession, AppError>;
             async fn find_by_refresh_token_hash(&self, hash: &str) -> Result<Option<UserSession>, AppError>;
+            async fn find_by_id(&self, id: Uuid) -> Result<Option<UserSession>, AppError>;
+            async fn find_active_by_user_id(&self, user_id: Uuid) -> Result<Vec<UserSession>, AppError>;
+            async fn rotate_session(&self, parent: &UserSession, id: Uuid, refresh_token_hash: String, user_agent: Option<String>, ip_address: Option<String>, expires_at: DateTime<Utc>) -> Result<Option<UserSession>, AppError>;
             async fn delete_session(&self, id: Uuid) -> Result<(), AppError>;
+            async fn delete_family(&self, family_id: Uuid) -> Result<(), AppError>;
             async fn delete_all_sessions_for_user(&self, user_id: Uuid) -> Result<(), AppError>;
+            async fn delete_expired(&self, now: DateTime<Utc>) -> Result<u64, AppError>;
+            async fn delete_other_sessions_for_user(&self, user_id: Uuid, family_id: Uuid) -> Result<(), AppError>;
         }
     }
 
@@ -137,6 +197,123 @@ mod tests {
         }
     }
 
+    // Fake TokenDenylistRepository (records what gets denied)
+    struct FakeTokenDenylistRepository {
+        tokens: Mutex<HashSet<String>>,
+        subjects: Mutex<HashMap<String, i64>>,
+    }
+
+    impl FakeTokenDenylistRepository {
+        fn new() -> Self {
+            Self {
+                tokens: Mutex::new(HashSet::new()),
+                subjects: Mutex::new(HashMap::new()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl TokenDenylistRepositoryTrait for FakeTokenDenylistRepository {
+        async fn deny(&self, jti: &str, _: Duration) -> Result<(), AppError> {
+            self.tokens.lock().unwrap().insert(jti.to_string());
+            Ok(())
+        }
+
+        async fn is_denied(&self, jti: &str) -> Result<bool, AppError> {
+            Ok(self.tokens.lock().unwrap().contains(jti))
+        }
+
+        async fn deny_subject(&self, subject: &str, _: Duration) -> Result<(), AppError> {
+            self.subjects
+                .lock()
+                .unwrap()
+                .insert(subject.to_string(), Utc::now().timestamp_millis());
+            Ok(())
+        }
+
+        async fn subject_denied_at(&self, subject: &str) -> Result<Option<i64>, AppError> {
+            Ok(self.subjects.lock().unwrap().get(subject).copied())
+        }
+    }
+
+    // Fake UserMfaRepository (no enrollment unless one is provided)
+    struct FakeUserMfaRepository {
+        record: Mutex<Option<UserMfa>>,
+    }
+
+    impl FakeUserMfaRepository {
+        fn new() -> Self {
+            Self {
+                record: Mutex::new(None),
+            }
+        }
+
+        fn with_enabled_secret(user_id: Uuid, totp_secret: &str) -> Self {
+            Self {
+                record: Mutex::new(Some(UserMfa {
+                    id: Uuid::new_v4(),
+                    user_id,
+                    totp_secret: totp_secret.to_string(),
+                    enabled_at: Some(Utc::now()),
+                    last_used_step: None,
+                    created_at: Utc::now(),
+                    updated_at: Utc::now(),
+                })),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl UserMfaRepositoryTrait for FakeUserMfaRepository {
+        async fn find_by_user_id(&self, _: Uuid) -> Result<Option<UserMfa>, AppError> {
+            Ok(self.record.lock().unwrap().clone())
+        }
+
+        async fn upsert_pending(
+            &self,
+            user_id: Uuid,
+            totp_secret: String,
+        ) -> Result<UserMfa, AppError> {
+            let record = UserMfa {
+                id: Uuid::new_v4(),
+                user_id,
+                totp_secret,
+                enabled_at: None,
+                last_used_step: None,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+            };
+            *self.record.lock().unwrap() = Some(record.clone());
+            Ok(record)
+        }
+
+        async fn enable(&self, _: Uuid, used_step: i64) -> Result<UserMfa, AppError> {
+            let mut guard = self.record.lock().unwrap();
+            let record = guard
+                .as_mut()
+                .ok_or_else(|| AppError::NotFound("MFA enrollment not found".to_string()))?;
+            record.enabled_at = Some(Utc::now());
+            record.last_used_step = Some(used_step);
+            Ok(record.clone())
+        }
+
+        async fn update_last_used_step(&self, _: Uuid, used_step: i64) -> Result<bool, AppError> {
+            let mut guard = self.record.lock().unwrap();
+            match guard.as_mut() {
+                Some(record) if record.last_used_step.is_none_or(|last| used_step > last) => {
+                    record.last_used_step = Some(used_step);
+                    Ok(true)
+                }
+                _ => Ok(false),
+            }
+        }
+
+        async fn delete_by_user_id(&self, _: Uuid) -> Result<(), AppError> {
+            *self.record.lock().unwrap() = None;
+            Ok(())
+        }
+    }
+
     #[tokio::test]
     async fn test_login_success() {
         // Initialize config for test
@@ -165,6 +342,12 @@ mod tests {
             username: "testuser".to_string(),
             email: email.to_string(),
             password_hash: hashed_password,
+            email_verified_at: None,
+            failed_login_attempts: 0,
+            locked_until: None,
+            status: "active".to_string(),
+            status_reason: None,
+            status_expires_at: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
             deleted_at: None,
@@ -189,6 +372,10 @@ mod tests {
                     ip_address: None,
                     expires_at: Utc::now(),
                     created_at: Utc::now(),
+                    family_id: Uuid::new_v4(),
+                    parent_id: None,
+                    rotated_at: None,
+                    last_used_at: None,
                 })
             });
 
@@ -214,6 +401,10 @@ mod tests {
             Arc::new(mock_session_repo),
             Arc::new(mock_activity_repo),
             Arc::new(mock_invite_repo),
+            Arc::new(FakeUserMfaRepository::new()),
+            Arc::new(FakeUserRecoveryCodeRepository::new()),
+            Arc::new(tenant_repository(false)),
+            Arc::new(FakeTokenDenylistRepository::new()),
         );
 
         let req = LoginRequest {
@@ -231,6 +422,107 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_login_requires_verified_email_when_tenant_enforces_it() {
+        use crate::domains::common::utils::config::Config;
+        Config::init_for_test();
+
+        let raw_password = "password123";
+        let hashed_password = password::hash_password(raw_password).unwrap();
+        let tenant_id = Uuid::new_v4();
+
+        let login_usecase = |user: User| {
+            let mut mock_user_repo = MockUserRepository::new();
+            mock_user_repo
+                .expect_find_by_email_with_deleted()
+                .returning(move |_| Ok(Some(user.clone())));
+
+            let mock_tenant_repo = Arc::new(FakeUserTenantRepository::new());
+            mock_tenant_repo.set_role_response(vec!["user".to_string()]);
+
+            let mut mock_session_repo = MockUserSessionRepository::new();
+            mock_session_repo.expect_create_session().returning(
+                |id, user_id, hash, _, _, expires_at| {
+                    Ok(UserSession {
+                        id: id.unwrap_or_else(Uuid::new_v4),
+                        user_id,
+                        refresh_token_hash: hash,
+                        user_agent: None,
+                        ip_address: None,
+                        expires_at,
+                        created_at: Utc::now(),
+                        family_id: Uuid::new_v4(),
+                        parent_id: None,
+                        rotated_at: None,
+                        last_used_at: None,
+                    })
+                },
+            );
+
+            let mut mock_activity_repo = MockUserActivityLogRepository::new();
+            mock_activity_repo.expect_log_activity().returning(
+                |user_id, activity_type, status, error_message, _, _| {
+                    Ok(UserActivityLog {
+                        id: Uuid::new_v4(),
+                        user_id,
+                        activity_type,
+                        status,
+                        error_message,
+                        ip_address: None,
+                        user_agent: None,
+                        created_at: Utc::now(),
+                    })
+                },
+            );
+
+            AuthUseCase::new(
+                Arc::new(mock_user_repo),
+                Arc::new(MockUserDetailsRepository::new()),
+                mock_tenant_repo,
+                Arc::new(mock_session_repo),
+                Arc::new(mock_activity_repo),
+                Arc::new(MockInvitationCodeRepository::new()),
+                Arc::new(FakeUserMfaRepository::new()),
+                Arc::new(FakeUserRecoveryCodeRepository::new()),
+                Arc::new(tenant_repository(true)),
+                Arc::new(FakeTokenDenylistRepository::new()),
+            )
+        };
+        let req = || LoginRequest {
+            email_or_username: "verify@example.com".to_string(),
+            password: raw_password.to_string(),
+            tenant_id,
+            redirect_uri: None,
+            state: None,
+            nonce: None,
+            role: None,
+        };
+        let http_req = TestRequest::default().to_http_request();
+
+        let mut user = User {
+            id: Uuid::new_v4(),
+            username: "verifyuser".to_string(),
+            email: "verify@example.com".to_string(),
+            password_hash: hashed_password,
+            email_verified_at: None,
+            failed_login_attempts: 0,
+            locked_until: None,
+            status: "active".to_string(),
+            status_reason: None,
+            status_expires_at: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+        };
+
+        let result = login_usecase(user.clone()).login(req(), &http_req).await;
+        assert!(matches!(result, Err(AppError::Forbidden(_))));
+
+        user.email_verified_at = Some(Utc::now());
+        let result = login_usecase(user).login(req(), &http_req).await;
+        assert!(matches!(result, Ok(LoginResult::Authenticated(_, _))));
+    }
+
     #[tokio::test]
     async fn test_login_invalid_password() {
         use crate::domains::common::utils::config::Config;
@@ -254,6 +546,12 @@ mod tests {
             username: "testuser".to_string(),
             email: email.to_string(),
             password_hash: hashed_password,
+            email_verified_at: None,
+            failed_login_attempts: 0,
+            locked_until: None,
+            status: "active".to_string(),
+            status_reason: None,
+            status_expires_at: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
             deleted_at: None,
@@ -263,6 +561,11 @@ mod tests {
         mock_user_repo
             .expect_find_by_email_with_deleted()
             .returning(move |_| Ok(Some(user_clone.clone())));
+        mock_user_repo
+            .expect_increment_login_failures()
+            .with(eq(user_id))
+            .times(1)
+            .returning(|_| Ok(1));
 
         mock_activity_repo
             .expect_log_activity()
@@ -286,6 +589,10 @@ mod tests {
             Arc::new(mock_session_repo),
             Arc::new(mock_activity_repo),
             Arc::new(mock_invite_repo),
+            Arc::new(FakeUserMfaRepository::new()),
+            Arc::new(FakeUserRecoveryCodeRepository::new()),
+            Arc::new(tenant_repository(false)),
+            Arc::new(FakeTokenDenylistRepository::new()),
         );
 
         let req = LoginRequest {
@@ -304,6 +611,21 @@ mod tests {
 
     #[tokio::test]
     async fn test_register_success() {
+        let result = register_new_user(false).await;
+
+        assert!(matches!(result, Ok(RegisterResult::Authenticated(_))));
+    }
+
+    #[tokio::test]
+    async fn test_register_withholds_tokens_until_email_verified() {
+        let result = register_new_user(true).await;
+
+        assert!(matches!(result, Ok(RegisterResult::VerificationPending(_))));
+    }
+
+    async fn register_new_user(
+        require_email_verification: bool,
+    ) -> Result<RegisterResult, AppError> {
         use crate::domains::common::utils::config::Config;
         Config::init_for_test();
 
@@ -333,6 +655,12 @@ mod tests {
                 username: username.to_string(),
                 email: email.to_string(),
                 password_hash: "hash".to_string(),
+                email_verified_at: None,
+                failed_login_attempts: 0,
+                locked_until: None,
+                status: "active".to_string(),
+                status_reason: None,
+                status_expires_at: None,
                 created_at: Utc::now(),
                 updated_at: Utc::now(),
                 deleted_at: None,
@@ -378,6 +706,10 @@ mod tests {
             Arc::new(mock_session_repo),
             Arc::new(mock_activity_repo),
             Arc::new(mock_invite_repo),
+            Arc::new(FakeUserMfaRepository::new()),
+            Arc::new(FakeUserRecoveryCodeRepository::new()),
+            Arc::new(tenant_repository(require_email_verification)),
+            Arc::new(FakeTokenDenylistRepository::new()),
         );
 
         let req = crate::domains::user::dtos::auth_dto::RegisterRequest {
@@ -393,7 +725,7 @@ mod tests {
         };
         let http_req = TestRequest::default().to_http_request();
 
-        assert!(usecase.register(req, &http_req).await.is_ok());
+        usecase.register(req, &http_req).await
     }
 
     #[tokio::test]
@@ -435,6 +767,12 @@ mod tests {
                 username: username.to_string(),
                 email: email.to_string(),
                 password_hash: "hash".to_string(),
+                email_verified_at: None,
+                failed_login_attempts: 0,
+                locked_until: None,
+                status: "active".to_string(),
+                status_reason: None,
+                status_expires_at: None,
                 created_at: Utc::now(),
                 updated_at: Utc::now(),
                 deleted_at: None,
@@ -480,6 +818,10 @@ mod tests {
             Arc::new(mock_session_repo),
             Arc::new(mock_activity_repo),
             Arc::new(mock_invite_repo),
+            Arc::new(FakeUserMfaRepository::new()),
+            Arc::new(FakeUserRecoveryCodeRepository::new()),
+            Arc::new(tenant_repository(false)),
+            Arc::new(FakeTokenDenylistRepository::new()),
         );
 
         let req = crate::domains::user::dtos::auth_dto::RegisterRequest {
@@ -543,6 +885,10 @@ mod tests {
             Arc::new(mock_session_repo),
             Arc::new(mock_activity_repo),
             Arc::new(mock_invite_repo),
+            Arc::new(FakeUserMfaRepository::new()),
+            Arc::new(FakeUserRecoveryCodeRepository::new()),
+            Arc::new(tenant_repository(false)),
+            Arc::new(FakeTokenDenylistRepository::new()),
         );
 
         let req = crate::domains::user::dtos::auth_dto::RegisterRequest {
@@ -590,6 +936,12 @@ mod tests {
             username: "user".to_string(),
             email: email.to_string(),
             password_hash: password::hash_password(password).unwrap(),
+            email_verified_at: None,
+            failed_login_attempts: 0,
+            locked_until: None,
+            status: "active".to_string(),
+            status_reason: None,
+            status_expires_at: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
             deleted_at: None,
@@ -620,6 +972,10 @@ mod tests {
                     ip_address: None,
                     expires_at: Utc::now(),
                     created_at: Utc::now(),
+                    family_id: Uuid::new_v4(),
+                    parent_id: None,
+                    rotated_at: None,
+                    last_used_at: None,
                 })
             });
 
@@ -646,6 +1002,10 @@ mod tests {
             Arc::new(mock_session_repo),
             Arc::new(mock_activity_repo),
             Arc::new(mock_invite_repo),
+            Arc::new(FakeUserMfaRepository::new()),
+            Arc::new(FakeUserRecoveryCodeRepository::new()),
+            Arc::new(tenant_repository(false)),
+            Arc::new(FakeTokenDenylistRepository::new()),
         );
 
         let req = crate::domains::user::dtos::auth_dto::RegisterRequest {
@@ -692,6 +1052,12 @@ mod tests {
             username: "user".to_string(),
             email: email.to_string(),
             password_hash: password::hash_password(password).unwrap(),
+            email_verified_at: None,
+            failed_login_attempts: 0,
+            locked_until: None,
+            status: "active".to_string(),
+            status_reason: None,
+            status_expires_at: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
             deleted_at: None,
@@ -702,6 +1068,13 @@ mod tests {
             .expect_find_by_email_with_deleted()
             .returning(move |_| Ok(Some(user_clone.clone())));
 
+        // A wrong password counts towards the lockout like a failed login
+        mock_user_repo
+            .expect_increment_login_failures()
+            .with(eq(user_id))
+            .times(1)
+            .returning(|_| Ok(1));
+
         // Existing role is "user" (so role check passes)
         mock_tenant_repo.set_all_tenants_response(vec![
             crate::domains::tenant::repositories::user_tenant_repository::UserTenantInfo {
@@ -733,6 +1106,10 @@ mod tests {
             Arc::new(mock_session_repo),
             Arc::new(mock_activity_repo),
             Arc::new(mock_invite_repo),
+            Arc::new(FakeUserMfaRepository::new()),
+            Arc::new(FakeUserRecoveryCodeRepository::new()),
+            Arc::new(tenant_repository(false)),
+            Arc::new(FakeTokenDenylistRepository::new()),
         );
 
         let req = crate::domains::user::dtos::auth_dto::RegisterRequest {
@@ -779,6 +1156,12 @@ mod tests {
             username: "testuser".to_string(),
             email: email.to_string(),
             password_hash: hashed_password,
+            email_verified_at: None,
+            failed_login_attempts: 0,
+            locked_until: None,
+            status: "active".to_string(),
+            status_reason: None,
+            status_expires_at: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
             deleted_at: None,
@@ -814,6 +1197,10 @@ mod tests {
             Arc::new(mock_session_repo),
             Arc::new(mock_activity_repo),
             Arc::new(mock_invite_repo),
+            Arc::new(FakeUserMfaRepository::new()),
+            Arc::new(FakeUserRecoveryCodeRepository::new()),
+            Arc::new(tenant_repository(false)),
+            Arc::new(FakeTokenDenylistRepository::new()),
         );
 
         // REQUESTED role is "admin"
@@ -862,6 +1249,12 @@ mod tests {
             username: "user".to_string(),
             email: email.to_string(),
             password_hash: password::hash_password(password).unwrap(),
+            email_verified_at: None,
+            failed_login_attempts: 0,
+            locked_until: None,
+            status: "active".to_string(),
+            status_reason: None,
+            status_expires_at: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
             deleted_at: None,
@@ -900,6 +1293,10 @@ mod tests {
                     ip_address: None,
                     expires_at: Utc::now(),
                     created_at: Utc::now(),
+                    family_id: Uuid::new_v4(),
+                    parent_id: None,
+                    rotated_at: None,
+                    last_used_at: None,
                 })
             });
 
@@ -925,6 +1322,10 @@ mod tests {
             Arc::new(mock_session_repo),
             Arc::new(mock_activity_repo),
             Arc::new(mock_invite_repo),
+            Arc::new(FakeUserMfaRepository::new()),
+            Arc::new(FakeUserRecoveryCodeRepository::new()),
+            Arc::new(tenant_repository(false)),
+            Arc::new(FakeTokenDenylistRepository::new()),
         );
 
         let req = crate::domains::user::dtos::auth_dto::RegisterRequest {
@@ -972,6 +1373,12 @@ mod tests {
             username: "user".to_string(),
             email: email.to_string(),
             password_hash: password::hash_password(correct_password).unwrap(),
+            email_verified_at: None,
+            failed_login_attempts: 0,
+            locked_until: None,
+            status: "active".to_string(),
+            status_reason: None,
+            status_expires_at: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
             deleted_at: None,
@@ -982,6 +1389,13 @@ mod tests {
             .expect_find_by_email_with_deleted()
             .returning(move |_| Ok(Some(user_clone.clone())));
 
+        // A wrong password counts towards the lockout like a failed login
+        mock_user_repo
+            .expect_increment_login_failures()
+            .with(eq(user_id))
+            .times(1)
+            .returning(|_| Ok(1));
+
         mock_activity_repo
             .expect_log_activity()
             .returning(|_, _, _, _, _, _| {
@@ -1004,6 +1418,10 @@ mod tests {
             Arc::new(mock_session_repo),
             Arc::new(mock_activity_repo),
             Arc::new(mock_invite_repo),
+            Arc::new(FakeUserMfaRepository::new()),
+            Arc::new(FakeUserRecoveryCodeRepository::new()),
+            Arc::new(tenant_repository(false)),
+            Arc::new(FakeTokenDenylistRepository::new()),
         );
 
         let req = crate::domains::user::dtos::auth_dto::RegisterRequest {
@@ -1049,6 +1467,12 @@ mod tests {
             username: "user".to_string(),
             email: email.to_string(),
             password_hash: password::hash_password(password).unwrap(),
+            email_verified_at: None,
+            failed_login_attempts: 0,
+            locked_until: None,
+            status: "active".to_string(),
+            status_reason: None,
+            status_expires_at: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
             deleted_at: None,
@@ -1084,6 +1508,10 @@ mod tests {
             Arc::new(mock_session_repo),
             Arc::new(mock_activity_repo),
             Arc::new(mock_invite_repo),
+            Arc::new(FakeUserMfaRepository::new()),
+            Arc::new(FakeUserRecoveryCodeRepository::new()),
+            Arc::new(tenant_repository(false)),
+            Arc::new(FakeTokenDenylistRepository::new()),
         );
 
         let req = crate::domains::user::dtos::auth_dto::RegisterRequest {
@@ -1100,8 +1528,9 @@ mod tests {
         let http_req = TestRequest::default().to_http_request();
 
         let result = usecase.register(req, &http_req).await;
-        assert!(result.is_ok());
-        let response = result.unwrap();
+        let Ok(RegisterResult::Authenticated(response)) = result else {
+            panic!("expected tokens, got {:?}", result);
+        };
         assert_eq!(response.user_id, user_id);
         assert!(!response.access_token.is_empty());
     }
@@ -1129,6 +1558,12 @@ mod tests {
             username: "deleted_user".to_string(),
             email: email.to_string(),
             password_hash: password::hash_password(password).unwrap(),
+            email_verified_at: None,
+            failed_login_attempts: 0,
+            locked_until: None,
+            status: "active".to_string(),
+            status_reason: None,
+            status_expires_at: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
             deleted_at: Some(Utc::now()), // <--- DELETED
@@ -1146,6 +1581,12 @@ mod tests {
                 username: "deleted_user".to_string(),
                 email: "deleted@example.com".to_string(),
                 password_hash: "hash".to_string(),
+                email_verified_at: None,
+                failed_login_attempts: 0,
+                locked_until: None,
+                status: "active".to_string(),
+                status_reason: None,
+                status_expires_at: None,
                 created_at: Utc::now(),
                 updated_at: Utc::now(),
                 deleted_at: None, // <--- RESTORED
@@ -1163,6 +1604,10 @@ mod tests {
                     ip_address: None,
                     expires_at: Utc::now(),
                     created_at: Utc::now(),
+                    family_id: Uuid::new_v4(),
+                    parent_id: None,
+                    rotated_at: None,
+                    last_used_at: None,
                 })
             });
 
@@ -1188,6 +1633,10 @@ mod tests {
             Arc::new(mock_session_repo),
             Arc::new(mock_activity_repo),
             Arc::new(mock_invite_repo),
+            Arc::new(FakeUserMfaRepository::new()),
+            Arc::new(FakeUserRecoveryCodeRepository::new()),
+            Arc::new(tenant_repository(false)),
+            Arc::new(FakeTokenDenylistRepository::new()),
         );
 
         let req = crate::domains::user::dtos::auth_dto::RegisterRequest {
@@ -1233,6 +1682,12 @@ mod tests {
             username: existing_username.to_string(),
             email: existing_email.to_string(),
             password_hash: "hash".to_string(),
+            email_verified_at: None,
+            failed_login_attempts: 0,
+            locked_until: None,
+            status: "active".to_string(),
+            status_reason: None,
+            status_expires_at: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
             deleted_at: None,
@@ -1270,6 +1725,10 @@ mod tests {
             Arc::new(mock_session_repo),
             Arc::new(mock_activity_repo),
             Arc::new(mock_invite_repo),
+            Arc::new(FakeUserMfaRepository::new()),
+            Arc::new(FakeUserRecoveryCodeRepository::new()),
+            Arc::new(tenant_repository(false)),
+            Arc::new(FakeTokenDenylistRepository::new()),
         );
 
         let req = crate::domains::user::dtos::auth_dto::RegisterRequest {
@@ -1315,6 +1774,12 @@ mod tests {
             username: "user".to_string(),
             email: email.to_string(),
             password_hash: password::hash_password(password).unwrap(),
+            email_verified_at: None,
+            failed_login_attempts: 0,
+            locked_until: None,
+            status: "active".to_string(),
+            status_reason: None,
+            status_expires_at: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
             deleted_at: None,
@@ -1356,6 +1821,10 @@ mod tests {
             Arc::new(mock_session_repo),
             Arc::new(mock_activity_repo),
             Arc::new(mock_invite_repo),
+            Arc::new(FakeUserMfaRepository::new()),
+            Arc::new(FakeUserRecoveryCodeRepository::new()),
+            Arc::new(tenant_repository(false)),
+            Arc::new(FakeTokenDenylistRepository::new()),
         );
 
         // Requested "admin" role
@@ -1403,6 +1872,12 @@ mod tests {
             username: "multiuser".to_string(),
             email: email.to_string(),
             password_hash: password::hash_password(password).unwrap(),
+            email_verified_at: None,
+            failed_login_attempts: 0,
+            locked_until: None,
+            status: "active".to_string(),
+            status_reason: None,
+            status_expires_at: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
             deleted_at: None,
@@ -1427,6 +1902,10 @@ mod tests {
                     ip_address: None,
                     expires_at: Utc::now(),
                     created_at: Utc::now(),
+                    family_id: Uuid::new_v4(),
+                    parent_id: None,
+                    rotated_at: None,
+                    last_used_at: None,
                 })
             });
 
@@ -1452,6 +1931,10 @@ mod tests {
             Arc::new(mock_session_repo),
             Arc::new(mock_activity_repo),
             Arc::new(mock_invite_repo),
+            Arc::new(FakeUserMfaRepository::new()),
+            Arc::new(FakeUserRecoveryCodeRepository::new()),
+            Arc::new(tenant_repository(false)),
+            Arc::new(FakeTokenDenylistRepository::new()),
         );
 
         // CASE 1: Request "admin" specifically
@@ -1498,4 +1981,1336 @@ mod tests {
             _ => panic!("Expected NotFound error for wrong role"),
         }
     }
+
+    // Fake UserRecoveryCodeRepository backed by an in-memory list
+    struct FakeUserRecoveryCodeRepository {
+        codes: Mutex<Vec<UserRecoveryCode>>,
+    }
+
+    impl FakeUserRecoveryCodeRepository {
+        fn new() -> Self {
+            Self {
+                codes: Mutex::new(vec![]),
+            }
+        }
+
+        fn with_codes(user_id: Uuid, plain_codes: &[&str]) -> Self {
+            use crate::domains::common::utils::recovery_code;
+
+            let codes = plain_codes
+                .iter()
+                .map(|code| UserRecoveryCode {
+                    id: Uuid::new_v4(),
+                    user_id,
+                    code_hash: password::hash_password(&recovery_code::normalize(code)).unwrap(),
+                    used_at: None,
+                    created_at: Utc::now(),
+                })
+                .collect();
+            Self {
+                codes: Mutex::new(codes),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl UserRecoveryCodeRepositoryTrait for FakeUserRecoveryCodeRepository {
+        async fn replace_for_user(
+            &self,
+            user_id: Uuid,
+            code_hashes: Vec<String>,
+        ) -> Result<(), AppError> {
+            *self.codes.lock().unwrap() = code_hashes
+                .into_iter()
+                .map(|code_hash| UserRecoveryCode {
+                    id: Uuid::new_v4(),
+                    user_id,
+                    code_hash,
+                    used_at: None,
+                    created_at: Utc::now(),
+                })
+                .collect();
+            Ok(())
+        }
+
+        async fn find_unused_by_user_id(
+            &self,
+            user_id: Uuid,
+        ) -> Result<Vec<UserRecoveryCode>, AppError> {
+            Ok(self
+                .codes
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|c| c.user_id == user_id && c.used_at.is_none())
+                .cloned()
+                .collect())
+        }
+
+        async fn mark_used(&self, id: Uuid) -> Result<bool, AppError> {
+            let mut codes = self.codes.lock().unwrap();
+            match codes.iter_mut().find(|c| c.id == id && c.used_at.is_none()) {
+                Some(code) => {
+                    code.used_at = Some(Utc::now());
+                    Ok(true)
+                }
+                None => Ok(false),
+            }
+        }
+    }
+
+    fn mfa_test_setup(
+        raw_password: &str,
+    ) -> (
+        User,
+        MockUserRepository,
+        MockUserSessionRepository,
+        MockUserActivityLogRepository,
+    ) {
+        let user = User {
+            id: Uuid::new_v4(),
+            username: "mfauser".to_string(),
+            email: "mfa@example.com".to_string(),
+            password_hash: password::hash_password(raw_password).unwrap(),
+            email_verified_at: None,
+            failed_login_attempts: 0,
+            locked_until: None,
+            status: "active".to_string(),
+            status_reason: None,
+            status_expires_at: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+        };
+
+        let mut mock_user_repo = MockUserRepository::new();
+        let user_clone = user.clone();
+        mock_user_repo
+            .expect_find_by_email_with_deleted()
+            .returning(move |_| Ok(Some(user_clone.clone())));
+        let user_clone = user.clone();
+        mock_user_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(user_clone.clone())));
+
+        let mut mock_session_repo = MockUserSessionRepository::new();
+        mock_session_repo
+            .expect_create_session()
+            .returning(|id, user_id, _, _, _, expires_at| {
+                Ok(UserSession {
+                    id: id.unwrap_or_else(Uuid::new_v4),
+                    user_id,
+                    refresh_token_hash: "hash".to_string(),
+                    user_agent: None,
+                    ip_address: None,
+                    expires_at,
+                    created_at: Utc::now(),
+                    family_id: Uuid::new_v4(),
+                    parent_id: None,
+                    rotated_at: None,
+                    last_used_at: None,
+                })
+            });
+
+        let mut mock_activity_repo = MockUserActivityLogRepository::new();
+        mock_activity_repo.expect_log_activity().returning(
+            |user_id, activity_type, status, _, _, _| {
+                Ok(UserActivityLog {
+                    id: Uuid::new_v4(),
+                    user_id,
+                    activity_type,
+                    status,
+                    error_message: None,
+                    ip_address: None,
+                    user_agent: None,
+                    created_at: Utc::now(),
+                })
+            },
+        );
+
+        (user, mock_user_repo, mock_session_repo, mock_activity_repo)
+    }
+
+    #[tokio::test]
+    async fn test_login_with_mfa_returns_challenge_then_verify_succeeds() {
+        use crate::domains::common::utils::config::Config;
+        use crate::domains::common::utils::totp;
+        use crate::domains::user::dtos::mfa_dto::MfaVerifyRequest;
+        Config::init_for_test();
+
+        let raw_password = "password123";
+        let (user, mut mock_user_repo, mock_session_repo, mock_activity_repo) =
+            mfa_test_setup(raw_password);
+        mock_user_repo
+            .expect_increment_login_failures()
+            .with(eq(user.id))
+            .times(1)
+            .returning(|_| Ok(1));
+        let mock_tenant_repo = Arc::new(FakeUserTenantRepository::new());
+        mock_tenant_repo.set_role_response(vec!["user".to_string()]);
+
+        let secret = totp::generate_secret();
+        let tenant_id = Uuid::new_v4();
+
+        let usecase = AuthUseCase::new(
+            Arc::new(mock_user_repo),
+            Arc::new(MockUserDetailsRepository::new()),
+            mock_tenant_repo.clone(),
+            Arc::new(mock_session_repo),
+            Arc::new(mock_activity_repo),
+            Arc::new(MockInvitationCodeRepository::new()),
+            Arc::new(FakeUserMfaRepository::with_enabled_secret(user.id, &secret)),
+            Arc::new(FakeUserRecoveryCodeRepository::new()),
+            Arc::new(tenant_repository(false)),
+            Arc::new(FakeTokenDenylistRepository::new()),
+        );
+
+        let req = LoginRequest {
+            email_or_username: user.email.clone(),
+            password: raw_password.to_string(),
+            tenant_id,
+            redirect_uri: None,
+            state: None,
+            nonce: None,
+            role: None,
+        };
+        let http_req = TestRequest::default().to_http_request();
+
+        let challenge = match usecase.login(req, &http_req).await.unwrap() {
+            LoginResult::MfaRequired(challenge) => challenge,
+            LoginResult::Authenticated(..) => panic!("Expected MFA challenge"),
+        };
+
+        let code = totp::generate_code(&secret, Utc::now().timestamp() as u64).unwrap();
+        let verify_req = MfaVerifyRequest {
+            mfa_token: challenge.mfa_token.clone(),
+            code: code.clone(),
+        };
+        let (auth_response, refresh_token) = usecase
+            .verify_mfa(verify_req, tenant_id, &http_req)
+            .await
+            .unwrap();
+        assert_eq!(auth_response.user_id, user.id);
+        assert!(!refresh_token.is_empty());
+
+        let claims = JwtService::new()
+            .validate_token(&auth_response.access_token)
+            .unwrap();
+        assert!(claims.auth_time.is_some());
+        assert_eq!(claims.acr.as_deref(), Some("aal2"));
+        assert!(claims.authentication().unwrap().is_multi_factor());
+
+        // The same code cannot be replayed
+        let replay_req = MfaVerifyRequest {
+            mfa_token: challenge.mfa_token,
+            code,
+        };
+        assert!(matches!(
+            usecase.verify_mfa(replay_req, tenant_id, &http_req).await,
+            Err(AppError::Unauthorized(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_verify_mfa_rejects_invalid_code_and_wrong_tenant() {
+        use crate::domains::common::utils::config::Config;
+        use crate::domains::common::utils::jwt::JwtService;
+        use crate::domains::common::utils::totp;
+        use crate::domains::user::dtos::mfa_dto::MfaVerifyRequest;
+        Config::init_for_test();
+
+        let (user, mut mock_user_repo, mock_session_repo, mock_activity_repo) =
+            mfa_test_setup("password123");
+        mock_user_repo
+            .expect_increment_login_failures()
+            .with(eq(user.id))
+            .times(1)
+            .returning(|_| Ok(1));
+        let secret = totp::generate_secret();
+        let tenant_id = Uuid::new_v4();
+
+        let usecase = AuthUseCase::new(
+            Arc::new(mock_user_repo),
+            Arc::new(MockUserDetailsRepository::new()),
+            Arc::new(FakeUserTenantRepository::new()),
+            Arc::new(mock_session_repo),
+            Arc::new(mock_activity_repo),
+            Arc::new(MockInvitationCodeRepository::new()),
+            Arc::new(FakeUserMfaRepository::with_enabled_secret(user.id, &secret)),
+            Arc::new(FakeUserRecoveryCodeRepository::new()),
+            Arc::new(tenant_repository(false)),
+            Arc::new(FakeTokenDenylistRepository::new()),
+        );
+
+        let mfa_token = JwtService::new()
+            .generate_mfa_token(user.id, tenant_id, "user".to_string())
+            .unwrap();
+        let http_req = TestRequest::default().to_http_request();
+
+        let now = Utc::now().timestamp() as u64;
+        let stale_code = totp::generate_code(&secret, now - 10 * totp::PERIOD).unwrap();
+        let invalid = MfaVerifyRequest {
+            mfa_token: mfa_token.clone(),
+            code: stale_code,
+        };
+        assert!(matches!(
+            usecase.verify_mfa(invalid, tenant_id, &http_req).await,
+            Err(AppError::Unauthorized(_))
+        ));
+
+        let valid_code = totp::generate_code(&secret, now).unwrap();
+        let wrong_tenant = MfaVerifyRequest {
+            mfa_token,
+            code: valid_code,
+        };
+        assert!(matches!(
+            usecase
+                .verify_mfa(wrong_tenant, Uuid::new_v4(), &http_req)
+                .await,
+            Err(AppError::Unauthorized(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_verify_mfa_invalidates_challenge_after_repeated_wrong_codes() {
+        use crate::domains::common::utils::config::Config;
+        use crate::domains::common::utils::jwt::JwtService;
+        use crate::domains::common::utils::totp;
+        use crate::domains::user::dtos::mfa_dto::MfaVerifyRequest;
+        Config::init_for_test();
+
+        let (user, mut mock_user_repo, mock_session_repo, mock_activity_repo) =
+            mfa_test_setup("password123");
+        let user_id = user.id;
+        let failures = Arc::new(Mutex::new(0));
+        let counter = failures.clone();
+        mock_user_repo
+            .expect_increment_login_failures()
+            .with(eq(user_id))
+            .times(5)
+            .returning(move |_| {
+                let mut count = counter.lock().unwrap();
+                *count += 1;
+                Ok(*count)
+            });
+        mock_user_repo
+            .expect_lock_until()
+            .with(eq(user_id), always())
+            .returning(|_, _| Ok(()));
+
+        let secret = totp::generate_secret();
+        let tenant_id = Uuid::new_v4();
+        let usecase = AuthUseCase::new(
+            Arc::new(mock_user_repo),
+            Arc::new(MockUserDetailsRepository::new()),
+            Arc::new(FakeUserTenantRepository::new()),
+            Arc::new(mock_session_repo),
+            Arc::new(mock_activity_repo),
+            Arc::new(MockInvitationCodeRepository::new()),
+            Arc::new(FakeUserMfaRepository::with_enabled_secret(user.id, &secret)),
+            Arc::new(FakeUserRecoveryCodeRepository::new()),
+            Arc::new(tenant_repository(false)),
+            Arc::new(FakeTokenDenylistRepository::new()),
+        );
+
+        let mfa_token = JwtService::new()
+            .generate_mfa_token(user.id, tenant_id, "user".to_string())
+            .unwrap();
+        let http_req = TestRequest::default().to_http_request();
+        let now = Utc::now().timestamp() as u64;
+
+        for _ in 0..5 {
+            let wrong = MfaVerifyRequest {
+                mfa_token: mfa_token.clone(),
+                code: totp::generate_code(&secret, now - 10 * totp::PERIOD).unwrap(),
+            };
+            assert!(matches!(
+                usecase.verify_mfa(wrong, tenant_id, &http_req).await,
+                Err(AppError::Unauthorized(_))
+            ));
+        }
+
+        // Even the right code no longer works with this challenge
+        let valid = MfaVerifyRequest {
+            mfa_token,
+            code: totp::generate_code(&secret, now).unwrap(),
+        };
+        assert!(matches!(
+            usecase.verify_mfa(valid, tenant_id, &http_req).await,
+            Err(AppError::Unauthorized(_))
+        ));
+        assert_eq!(*failures.lock().unwrap(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_verify_mfa_recovery_consumes_code_once() {
+        use crate::domains::common::utils::config::Config;
+        use crate::domains::common::utils::jwt::JwtService;
+        use crate::domains::common::utils::totp;
+        use crate::domains::user::dtos::recovery_code_dto::MfaRecoveryRequest;
+        Config::init_for_test();
+
+        let (user, mut mock_user_repo, mock_session_repo, mock_activity_repo) =
+            mfa_test_setup("password123");
+        mock_user_repo
+            .expect_increment_login_failures()
+            .with(eq(user.id))
+            .times(1)
+            .returning(|_| Ok(1));
+        let tenant_id = Uuid::new_v4();
+        let recovery_repo = Arc::new(FakeUserRecoveryCodeRepository::with_codes(
+            user.id,
+            &["abcde-fghjk", "mnpqr-stuvw"],
+        ));
+
+        let usecase = AuthUseCase::new(
+            Arc::new(mock_user_repo),
+            Arc::new(MockUserDetailsRepository::new()),
+            Arc::new(FakeUserTenantRepository::new()),
+            Arc::new(mock_session_repo),
+            Arc::new(mock_activity_repo),
+            Arc::new(MockInvitationCodeRepository::new()),
+            Arc::new(FakeUserMfaRepository::with_enabled_secret(
+                user.id,
+                &totp::generate_secret(),
+            )),
+            recovery_repo.clone(),
+            Arc::new(tenant_repository(false)),
+            Arc::new(FakeTokenDenylistRepository::new()),
+        );
+
+        let mfa_token = JwtService::new()
+            .generate_mfa_token(user.id, tenant_id, "user".to_string())
+            .unwrap();
+        let http_req = TestRequest::default().to_http_request();
+
+        // Codes are accepted regardless of case and separators
+        let req = MfaRecoveryRequest {
+            mfa_token: mfa_token.clone(),
+            recovery_code: "ABCDEFGHJK".to_string(),
+        };
+        let (auth_response, _) = usecase
+            .verify_mfa_recovery(req, tenant_id, &http_req)
+            .await
+            .unwrap();
+        assert_eq!(auth_response.user_id, user.id);
+        assert_eq!(
+            recovery_repo
+                .find_unused_by_user_id(user.id)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+
+        let reuse = MfaRecoveryRequest {
+            mfa_token,
+            recovery_code: "abcde-fghjk".to_string(),
+        };
+        assert!(matches!(
+            usecase
+                .verify_mfa_recovery(reuse, tenant_id, &http_req)
+                .await,
+            Err(AppError::Unauthorized(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_regenerate_recovery_codes_requires_password() {
+        use crate::domains::common::utils::config::Config;
+        use crate::domains::common::utils::recovery_code;
+        Config::init_for_test();
+
+        let (user, mock_user_repo, mock_session_repo, mock_activity_repo) =
+            mfa_test_setup("password123");
+        let recovery_repo = Arc::new(FakeUserRecoveryCodeRepository::with_codes(
+            user.id,
+            &["abcde-fghjk"],
+        ));
+
+        let usecase = AuthUseCase::new(
+            Arc::new(mock_user_repo),
+            Arc::new(MockUserDetailsRepository::new()),
+            Arc::new(FakeUserTenantRepository::new()),
+            Arc::new(mock_session_repo),
+            Arc::new(mock_activity_repo),
+            Arc::new(MockInvitationCodeRepository::new()),
+            Arc::new(FakeUserMfaRepository::new()),
+            recovery_repo.clone(),
+            Arc::new(tenant_repository(false)),
+            Arc::new(FakeTokenDenylistRepository::new()),
+        );
+        let http_req = TestRequest::default().to_http_request();
+
+        assert!(matches!(
+            usecase
+                .regenerate_recovery_codes(user.id, "wrong-password", &http_req)
+                .await,
+            Err(AppError::Unauthorized(_))
+        ));
+
+        let response = usecase
+            .regenerate_recovery_codes(user.id, "password123", &http_req)
+            .await
+            .unwrap();
+        assert_eq!(response.recovery_codes.len(), recovery_code::CODE_COUNT);
+
+        // The previous set is replaced
+        let stored = recovery_repo.find_unused_by_user_id(user.id).await.unwrap();
+        assert_eq!(stored.len(), recovery_code::CODE_COUNT);
+        assert!(!password::verify_password("abcdefghjk", &stored[0].code_hash).unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_reset_with_recovery_code_resets_password_and_mfa() {
+        use crate::domains::common::utils::config::Config;
+        use crate::domains::common::utils::totp;
+        use crate::domains::user::dtos::recovery_code_dto::RecoveryResetRequest;
+        Config::init_for_test();
+
+        let (user, mut mock_user_repo, _, mock_activity_repo) = mfa_test_setup("password123");
+        let user_id = user.id;
+        let tenant_id = Uuid::new_v4();
+
+        mock_user_repo
+            .expect_update()
+            .withf(move |id, req| {
+                *id == user_id && req.password.as_deref() == Some("NewPassword456!")
+            })
+            .times(1)
+            .returning(move |_, _| Ok(user.clone()));
+
+        let mut mock_session_repo = MockUserSessionRepository::new();
+        mock_session_repo
+            .expect_delete_all_sessions_for_user()
+            .with(eq(user_id))
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let mock_tenant_repo = Arc::new(FakeUserTenantRepository::new());
+        mock_tenant_repo.set_role_response(vec!["user".to_string()]);
+        let mfa_repo = Arc::new(FakeUserMfaRepository::with_enabled_secret(
+            user_id,
+            &totp::generate_secret(),
+        ));
+
+        let denylist = Arc::new(FakeTokenDenylistRepository::new());
+        let usecase = AuthUseCase::new(
+            Arc::new(mock_user_repo),
+            Arc::new(MockUserDetailsRepository::new()),
+            mock_tenant_repo,
+            Arc::new(mock_session_repo),
+            Arc::new(mock_activity_repo),
+            Arc::new(MockInvitationCodeRepository::new()),
+            mfa_repo.clone(),
+            Arc::new(FakeUserRecoveryCodeRepository::with_codes(
+                user_id,
+                &["abcde-fghjk"],
+            )),
+            Arc::new(tenant_repository(false)),
+            denylist.clone(),
+        );
+        let http_req = TestRequest::default().to_http_request();
+
+        let wrong_code = RecoveryResetRequest {
+            email_or_username: "MFA@example.com".to_string(),
+            recovery_code: "zzzzz-zzzzz".to_string(),
+            new_password: "NewPassword456!".to_string(),
+            confirm_new_password: "NewPassword456!".to_string(),
+        };
+        assert!(matches!(
+            usecase
+                .reset_with_recovery_code(wrong_code, tenant_id, &http_req)
+                .await,
+            Err(AppError::Unauthorized(_))
+        ));
+
+        let req = RecoveryResetRequest {
+            email_or_username: "MFA@example.com".to_string(),
+            recovery_code: "abcde-fghjk".to_string(),
+            new_password: "NewPassword456!".to_string(),
+            confirm_new_password: "NewPassword456!".to_string(),
+        };
+        usecase
+            .reset_with_recovery_code(req, tenant_id, &http_req)
+            .await
+            .unwrap();
+        assert!(mfa_repo.find_by_user_id(user_id).await.unwrap().is_none());
+        assert!(denylist
+            .subject_denied_at(&user_id.to_string())
+            .await
+            .unwrap()
+            .is_some());
+    }
+
+    fn refresh_test_session(user_id: Uuid, rotated: bool) -> (String, UserSession) {
+        use crate::domains::common::utils::jwt::JwtService;
+
+        let session_id = Uuid::new_v4();
+        let refresh_token = JwtService::new()
+            .generate_refresh_token(
+                user_id,
+                Uuid::new_v4(),
+                "user".to_string(),
+                Some(session_id.to_string()),
+                None,
+            )
+            .unwrap();
+        let session = UserSession {
+            id: session_id,
+            user_id,
+            refresh_token_hash: "hash".to_string(),
+            user_agent: None,
+            ip_address: None,
+            expires_at: Utc::now() + chrono::Duration::days(7),
+            created_at: Utc::now(),
+            family_id: Uuid::new_v4(),
+            parent_id: None,
+            rotated_at: rotated.then(Utc::now),
+            last_used_at: None,
+        };
+
+        (refresh_token, session)
+    }
+
+    #[tokio::test]
+    async fn test_refresh_token_rotates_session_within_family() {
+        use crate::domains::common::utils::config::Config;
+        Config::init_for_test();
+
+        let (user, mock_user_repo, _, mock_activity_repo) = mfa_test_setup("password123");
+        let (refresh_token, session) = refresh_test_session(user.id, false);
+
+        let mut mock_session_repo = MockUserSessionRepository::new();
+        let session_clone = session.clone();
+        mock_session_repo
+            .expect_find_by_refresh_token_hash()
+            .returning(move |_| Ok(Some(session_clone.clone())));
+        let parent_id = session.id;
+        let family_id = session.family_id;
+        mock_session_repo
+            .expect_rotate_session()
+            .withf(move |parent, _, _, _, _, _| parent.id == parent_id)
+            .times(1)
+            .returning(move |parent, id, hash, _, _, expires_at| {
+                Ok(Some(UserSession {
+                    id,
+                    user_id: parent.user_id,
+                    refresh_token_hash: hash,
+                    user_agent: None,
+                    ip_address: None,
+                    expires_at,
+                    created_at: Utc::now(),
+                    family_id,
+                    parent_id: Some(parent.id),
+                    rotated_at: None,
+                    last_used_at: None,
+                }))
+            });
+        mock_session_repo.expect_delete_family().never();
+
+        let usecase = AuthUseCase::new(
+            Arc::new(mock_user_repo),
+            Arc::new(MockUserDetailsRepository::new()),
+            Arc::new(FakeUserTenantRepository::new()),
+            Arc::new(mock_session_repo),
+            Arc::new(mock_activity_repo),
+            Arc::new(MockInvitationCodeRepository::new()),
+            Arc::new(FakeUserMfaRepository::new()),
+            Arc::new(FakeUserRecoveryCodeRepository::new()),
+            Arc::new(tenant_repository(false)),
+            Arc::new(FakeTokenDenylistRepository::new()),
+        );
+
+        let (auth_response, new_refresh_token) = usecase
+            .refresh_token(&refresh_token, None, None)
+            .await
+            .unwrap();
+        assert_eq!(auth_response.user_id, user.id);
+        assert!(!auth_response.access_token.is_empty());
+        assert_ne!(new_refresh_token, refresh_token);
+    }
+
+    #[tokio::test]
+    async fn test_refresh_token_reuse_revokes_family() {
+        use crate::domains::common::utils::config::Config;
+        Config::init_for_test();
+
+        let (user, mock_user_repo, _, _) = mfa_test_setup("password123");
+        let (refresh_token, session) = refresh_test_session(user.id, true);
+
+        let mut mock_session_repo = MockUserSessionRepository::new();
+        let session_clone = session.clone();
+        mock_session_repo
+            .expect_find_by_refresh_token_hash()
+            .returning(move |_| Ok(Some(session_clone.clone())));
+        mock_session_repo.expect_rotate_session().never();
+        mock_session_repo
+            .expect_delete_family()
+            .with(eq(session.family_id))
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let logged = Arc::new(Mutex::new(Vec::new()));
+        let logged_clone = logged.clone();
+        let mut mock_activity_repo = MockUserActivityLogRepository::new();
+        mock_activity_repo.expect_log_activity().returning(
+            move |user_id, activity_type, status, _, _, _| {
+                logged_clone
+                    .lock()
+                    .unwrap()
+                    .push((activity_type.clone(), status.clone()));
+                Ok(UserActivityLog {
+                    id: Uuid::new_v4(),
+                    user_id,
+                    activity_type,
+                    status,
+                    error_message: None,
+                    ip_address: None,
+                    user_agent: None,
+                    created_at: Utc::now(),
+                })
+            },
+        );
+
+        let usecase = AuthUseCase::new(
+            Arc::new(mock_user_repo),
+            Arc::new(MockUserDetailsRepository::new()),
+            Arc::new(FakeUserTenantRepository::new()),
+            Arc::new(mock_session_repo),
+            Arc::new(mock_activity_repo),
+            Arc::new(MockInvitationCodeRepository::new()),
+            Arc::new(FakeUserMfaRepository::new()),
+            Arc::new(FakeUserRecoveryCodeRepository::new()),
+            Arc::new(tenant_repository(false)),
+            Arc::new(FakeTokenDenylistRepository::new()),
+        );
+
+        assert!(matches!(
+            usecase.refresh_token(&refresh_token, None, None).await,
+            Err(AppError::Unauthorized(_))
+        ));
+        assert_eq!(
+            *logged.lock().unwrap(),
+            vec![("refresh_token_reuse".to_string(), "failure".to_string())]
+        );
+    }
+
+    /// Logs in as `user` with `password`, with `expect_failures` setting up the
+    /// expected `set_login_failures` calls.
+    async fn login_with_lockout(
+        user: User,
+        password: &str,
+        expect_failures: impl FnOnce(&mut MockUserRepository),
+    ) -> Result<LoginResult, AppError> {
+        let email = user.email.clone();
+        let usecase = lockout_usecase(user, expect_failures);
+
+        let req = LoginRequest {
+            email_or_username: email,
+            password: password.to_string(),
+            tenant_id: Uuid::new_v4(),
+            redirect_uri: None,
+            state: None,
+            nonce: None,
+            role: None,
+        };
+        let http_req = TestRequest::default().to_http_request();
+
+        usecase.login(req, &http_req).await
+    }
+
+    fn lockout_usecase(
+        user: User,
+        expect_failures: impl FnOnce(&mut MockUserRepository),
+    ) -> AuthUseCase {
+        lockout_usecase_in_tenant(user, false, expect_failures)
+    }
+
+    fn lockout_usecase_in_tenant(
+        user: User,
+        require_email_verification: bool,
+        expect_failures: impl FnOnce(&mut MockUserRepository),
+    ) -> AuthUseCase {
+        use crate::domains::common::utils::config::Config;
+        Config::init_for_test();
+
+        let mut mock_user_repo = MockUserRepository::new();
+        mock_user_repo
+            .expect_find_by_email_with_deleted()
+            .returning(move |_| Ok(Some(user.clone())));
+        expect_failures(&mut mock_user_repo);
+
+        let mock_tenant_repo = Arc::new(FakeUserTenantRepository::new());
+        mock_tenant_repo.set_role_response(vec!["user".to_string()]);
+
+        let mut mock_session_repo = MockUserSessionRepository::new();
+        mock_session_repo
+            .expect_create_session()
+            .returning(|_, _, _, _, _, _| {
+                Ok(UserSession {
+                    id: Uuid::new_v4(),
+                    user_id: Uuid::new_v4(),
+                    refresh_token_hash: "hash".to_string(),
+                    user_agent: None,
+                    ip_address: None,
+                    expires_at: Utc::now(),
+                    created_at: Utc::now(),
+                    family_id: Uuid::new_v4(),
+                    parent_id: None,
+                    rotated_at: None,
+                    last_used_at: None,
+                })
+            });
+
+        let mut mock_activity_repo = MockUserActivityLogRepository::new();
+        mock_activity_repo.expect_log_activity().returning(
+            |user_id, activity_type, status, _, _, _| {
+                Ok(UserActivityLog {
+                    id: Uuid::new_v4(),
+                    user_id,
+                    activity_type,
+                    status,
+                    error_message: None,
+                    ip_address: None,
+                    user_agent: None,
+                    created_at: Utc::now(),
+                })
+            },
+        );
+
+        AuthUseCase::new(
+            Arc::new(mock_user_repo),
+            Arc::new(MockUserDetailsRepository::new()),
+            mock_tenant_repo,
+            Arc::new(mock_session_repo),
+            Arc::new(mock_activity_repo),
+            Arc::new(MockInvitationCodeRepository::new()),
+            Arc::new(FakeUserMfaRepository::new()),
+            Arc::new(FakeUserRecoveryCodeRepository::new()),
+            Arc::new(tenant_repository(require_email_verification)),
+            Arc::new(FakeTokenDenylistRepository::new()),
+        )
+    }
+
+    fn lockout_user(
+        raw_password: &str,
+        failed_login_attempts: i32,
+        locked_until: Option<DateTime<Utc>>,
+    ) -> User {
+        User {
+            id: Uuid::new_v4(),
+            username: "lockoutuser".to_string(),
+            email: "lockout@example.com".to_string(),
+            password_hash: password::hash_password(raw_password).unwrap(),
+            email_verified_at: None,
+            failed_login_attempts,
+            locked_until,
+            status: "active".to_string(),
+            status_reason: None,
+            status_expires_at: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_login_locked_account_rejects_correct_password() {
+        let user = lockout_user(
+            "password123",
+            5,
+            Some(Utc::now() + chrono::Duration::minutes(1)),
+        );
+
+        let result = login_with_lockout(user, "password123", |repo| {
+            repo.expect_increment_login_failures().never();
+            repo.expect_set_login_failures().never();
+        })
+        .await;
+
+        assert!(matches!(result, Err(AppError::Locked(_))));
+    }
+
+    #[tokio::test]
+    async fn test_register_link_to_locked_account_rejects_correct_password() {
+        let user = lockout_user(
+            "password123",
+            5,
+            Some(Utc::now() + chrono::Duration::minutes(1)),
+        );
+        let req = crate::domains::user::dtos::auth_dto::RegisterRequest {
+            username: user.username.clone(),
+            email: user.email.clone(),
+            password: "password123".to_string(),
+            tenant_id: Uuid::new_v4(),
+            role: "user".to_string(),
+            state: None,
+            nonce: None,
+            redirect_uri: None,
+            invitation_code: None,
+        };
+        let usecase = lockout_usecase(user, |repo| {
+            repo.expect_increment_login_failures().never();
+            repo.expect_set_login_failures().never();
+        });
+        let http_req = TestRequest::default().to_http_request();
+
+        let result = usecase.register(req, &http_req).await;
+
+        assert!(matches!(result, Err(AppError::Locked(_))));
+    }
+
+    #[tokio::test]
+    async fn test_register_link_to_suspended_account_is_forbidden() {
+        let mut user = lockout_user("password123", 0, None);
+        user.status = "suspended".to_string();
+        let req = crate::domains::user::dtos::auth_dto::RegisterRequest {
+            username: user.username.clone(),
+            email: user.email.clone(),
+            password: "password123".to_string(),
+            tenant_id: Uuid::new_v4(),
+            role: "user".to_string(),
+            state: None,
+            nonce: None,
+            redirect_uri: None,
+            invitation_code: None,
+        };
+        let usecase = lockout_usecase(user, |repo| {
+            repo.expect_increment_login_failures().never();
+        });
+        let http_req = TestRequest::default().to_http_request();
+
+        let result = usecase.register(req, &http_req).await;
+
+        assert!(matches!(result, Err(AppError::Forbidden(_))));
+    }
+
+    #[tokio::test]
+    async fn test_register_link_to_unverified_account_withholds_tokens() {
+        let user = lockout_user("password123", 0, None);
+        let user_id = user.id;
+        let req = crate::domains::user::dtos::auth_dto::RegisterRequest {
+            username: user.username.clone(),
+            email: user.email.clone(),
+            password: "password123".to_string(),
+            tenant_id: Uuid::new_v4(),
+            role: "user".to_string(),
+            state: None,
+            nonce: None,
+            redirect_uri: None,
+            invitation_code: None,
+        };
+        let usecase = lockout_usecase_in_tenant(user, true, |_| {});
+        let http_req = TestRequest::default().to_http_request();
+
+        let result = usecase.register(req, &http_req).await;
+
+        assert!(matches!(
+            result,
+            Ok(RegisterResult::VerificationPending(id)) if id == user_id
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_login_failure_at_threshold_locks_account() {
+        // Default policy locks for 60 seconds after 5 failures
+        let user = lockout_user("password123", 4, None);
+        let user_id = user.id;
+
+        let result = login_with_lockout(user, "wrongpassword", |repo| {
+            repo.expect_increment_login_failures()
+                .with(eq(user_id))
+                .times(1)
+                .returning(|_| Ok(5));
+            repo.expect_lock_until()
+                .withf(move |id, until| {
+                    let expected = Utc::now() + chrono::Duration::seconds(60);
+                    *id == user_id && (*until - expected).num_seconds().abs() <= 1
+                })
+                .times(1)
+                .returning(|_, _| Ok(()));
+        })
+        .await;
+
+        assert!(matches!(result, Err(AppError::Unauthorized(_))));
+    }
+
+    #[tokio::test]
+    async fn test_login_failure_after_expired_lock_doubles_lock() {
+        let user = lockout_user(
+            "password123",
+            5,
+            Some(Utc::now() - chrono::Duration::seconds(1)),
+        );
+
+        let result = login_with_lockout(user, "wrongpassword", |repo| {
+            // The count comes from the database, not from the loaded row
+            repo.expect_increment_login_failures()
+                .times(1)
+                .returning(|_| Ok(6));
+            repo.expect_lock_until()
+                .withf(|_, until| {
+                    let expected = Utc::now() + chrono::Duration::seconds(120);
+                    (*until - expected).num_seconds().abs() <= 1
+                })
+                .times(1)
+                .returning(|_, _| Ok(()));
+        })
+        .await;
+
+        assert!(matches!(result, Err(AppError::Unauthorized(_))));
+    }
+
+    #[tokio::test]
+    async fn test_login_success_resets_failed_attempts() {
+        let user = lockout_user(
+            "password123",
+            3,
+            Some(Utc::now() - chrono::Duration::seconds(1)),
+        );
+        let user_id = user.id;
+
+        let result = login_with_lockout(user, "password123", |repo| {
+            repo.expect_set_login_failures()
+                .with(eq(user_id), eq(0), eq(None))
+                .times(1)
+                .returning(|_, _, _| Ok(()));
+        })
+        .await;
+
+        assert!(matches!(result, Ok(LoginResult::Authenticated(_, _))));
+    }
+
+    #[tokio::test]
+    async fn test_login_suspended_user_forbidden() {
+        let mut user = lockout_user("password123", 0, None);
+        user.status = "suspended".to_string();
+        user.status_expires_at = Some(Utc::now() + chrono::Duration::days(1));
+
+        let result = login_with_lockout(user, "password123", |_| {}).await;
+
+        assert!(matches!(result, Err(AppError::Forbidden(msg)) if msg == "Account is suspended"));
+    }
+
+    #[tokio::test]
+    async fn test_login_banned_user_wrong_password_unauthorized() {
+        let mut user = lockout_user("password123", 0, None);
+        user.status = "banned".to_string();
+
+        let result = login_with_lockout(user, "wrongpassword", |repo| {
+            repo.expect_increment_login_failures().returning(|_| Ok(1));
+        })
+        .await;
+
+        assert!(matches!(result, Err(AppError::Unauthorized(_))));
+    }
+
+    #[tokio::test]
+    async fn test_login_after_suspension_expired_succeeds() {
+        let mut user = lockout_user("password123", 0, None);
+        user.status = "suspended".to_string();
+        user.status_expires_at = Some(Utc::now() - chrono::Duration::seconds(1));
+
+        let result = login_with_lockout(user, "password123", |_| {}).await;
+
+        assert!(matches!(result, Ok(LoginResult::Authenticated(_, _))));
+    }
+
+    #[test]
+    fn test_ensure_active_pending_user_forbidden() {
+        let mut user = lockout_user("password123", 0, None);
+        user.status = "pending".to_string();
+
+        assert!(matches!(
+            AuthUseCase::ensure_active(&user),
+            Err(AppError::Forbidden(msg)) if msg == "Account is pending approval"
+        ));
+    }
+
+    fn request_with_access_token(user_id: Uuid) -> (actix_web::HttpRequest, Claims) {
+        use actix_web::cookie::Cookie;
+        use actix_web::HttpMessage;
+
+        let jwt = JwtService::new();
+        let token = jwt
+            .generate_access_token(user_id, Uuid::new_v4(), "user".to_string())
+            .unwrap();
+        let claims = jwt.validate_token(&token).unwrap();
+
+        let http_req = TestRequest::default()
+            .cookie(Cookie::new("refresh_token", "refresh"))
+            .to_http_request();
+        http_req.extensions_mut().insert(claims.clone());
+
+        (http_req, claims)
+    }
+
+    #[tokio::test]
+    async fn test_logout_denies_access_token() {
+        use crate::domains::common::utils::config::Config;
+        Config::init_for_test();
+
+        let (user, mock_user_repo, _, mock_activity_repo) = mfa_test_setup("password123");
+        let mut mock_session_repo = MockUserSessionRepository::new();
+        mock_session_repo
+            .expect_find_by_refresh_token_hash()
+            .returning(|_| Ok(None));
+        let denylist = Arc::new(FakeTokenDenylistRepository::new());
+
+        let usecase = AuthUseCase::new(
+            Arc::new(mock_user_repo),
+            Arc::new(MockUserDetailsRepository::new()),
+            Arc::new(FakeUserTenantRepository::new()),
+            Arc::new(mock_session_repo),
+            Arc::new(mock_activity_repo),
+            Arc::new(MockInvitationCodeRepository::new()),
+            Arc::new(FakeUserMfaRepository::new()),
+            Arc::new(FakeUserRecoveryCodeRepository::new()),
+            Arc::new(tenant_repository(false)),
+            denylist.clone(),
+        );
+
+        let (http_req, claims) = request_with_access_token(user.id);
+        usecase.logout(user.id, &http_req).await.unwrap();
+
+        assert!(denylist.is_revoked(&claims).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_change_password_denies_issued_access_tokens() {
+        use crate::domains::common::utils::config::Config;
+        Config::init_for_test();
+
+        let (user, mut mock_user_repo, _, mock_activity_repo) = mfa_test_setup("password123");
+        let user_clone = user.clone();
+        mock_user_repo
+            .expect_update()
+            .times(1)
+            .returning(move |_, _| Ok(user_clone.clone()));
+        let mut mock_session_repo = MockUserSessionRepository::new();
+        mock_session_repo
+            .expect_delete_all_sessions_for_user()
+            .with(eq(user.id))
+            .times(1)
+            .returning(|_| Ok(()));
+        let denylist = Arc::new(FakeTokenDenylistRepository::new());
+
+        let usecase = AuthUseCase::new(
+            Arc::new(mock_user_repo),
+            Arc::new(MockUserDetailsRepository::new()),
+            Arc::new(FakeUserTenantRepository::new()),
+            Arc::new(mock_session_repo),
+            Arc::new(mock_activity_repo),
+            Arc::new(MockInvitationCodeRepository::new()),
+            Arc::new(FakeUserMfaRepository::new()),
+            Arc::new(FakeUserRecoveryCodeRepository::new()),
+            Arc::new(tenant_repository(false)),
+            denylist.clone(),
+        );
+
+        let (http_req, claims) = request_with_access_token(user.id);
+        let req = ChangePasswordRequest {
+            old_password: "password123".to_string(),
+            new_password: "NewPassword456!".to_string(),
+            confirm_new_password: "NewPassword456!".to_string(),
+        };
+        usecase
+            .change_password(user.id, req, &http_req)
+            .await
+            .unwrap();
+
+        assert!(denylist
+            .is_denied(claims.jti.as_deref().unwrap())
+            .await
+            .unwrap());
+        let denied_at = denylist
+            .subject_denied_at(&user.id.to_string())
+            .await
+            .unwrap()
+            .unwrap();
+
+        // Another token issued up to the denial is denied too
+        let earlier = Claims {
+            jti: None,
+            iat: denied_at / 1000,
+            iat_ms: Some(denied_at),
+            ..claims.clone()
+        };
+        assert!(denylist.is_revoked(&earlier).await.unwrap());
+
+        // A token issued right after, within the same second, stays valid
+        let later = Claims {
+            jti: None,
+            iat: denied_at / 1000,
+            iat_ms: Some(denied_at + 1),
+            ..claims.clone()
+        };
+        assert!(!denylist.is_revoked(&later).await.unwrap());
+
+        // Without `iat_ms` the whole second of the denial is denied
+        let legacy = Claims {
+            jti: None,
+            iat: denied_at / 1000,
+            iat_ms: None,
+            ..claims
+        };
+        assert!(denylist.is_revoked(&legacy).await.unwrap());
+    }
+
+    /// User and activity type of each activity log entry.
+    type ActivityLog = Arc<Mutex<Vec<(Option<Uuid>, String)>>>;
+
+    fn impersonation_usecase(roles: Vec<String>) -> (AuthUseCase, User, ActivityLog) {
+        let (user, mock_user_repo, mock_session_repo, _) = mfa_test_setup("password123");
+        let user_tenant_repo = FakeUserTenantRepository::new();
+        user_tenant_repo.set_role_response(roles);
+
+        let logged: ActivityLog = Arc::default();
+        let store = logged.clone();
+        let mut mock_activity_repo = MockUserActivityLogRepository::new();
+        mock_activity_repo.expect_log_activity().returning(
+            move |user_id, activity_type, status, _, _, _| {
+                store.lock().unwrap().push((user_id, activity_type.clone()));
+                Ok(UserActivityLog {
+                    id: Uuid::new_v4(),
+                    user_id,
+                    activity_type,
+                    status,
+                    error_message: None,
+                    ip_address: None,
+                    user_agent: None,
+                    created_at: Utc::now(),
+                })
+            },
+        );
+
+        let usecase = AuthUseCase::new(
+            Arc::new(mock_user_repo),
+            Arc::new(MockUserDetailsRepository::new()),
+            Arc::new(user_tenant_repo),
+            Arc::new(mock_session_repo),
+            Arc::new(mock_activity_repo),
+            Arc::new(MockInvitationCodeRepository::new()),
+            Arc::new(FakeUserMfaRepository::new()),
+            Arc::new(FakeUserRecoveryCodeRepository::new()),
+            Arc::new(tenant_repository(false)),
+            Arc::new(FakeTokenDenylistRepository::new()),
+        );
+
+        (usecase, user, logged)
+    }
+
+    #[tokio::test]
+    async fn test_impersonate_issues_token_and_logs_both_users() {
+        use crate::domains::common::utils::config::Config;
+        Config::init_for_test();
+
+        let (usecase, user, logged) = impersonation_usecase(vec!["admin".to_string()]);
+        let (admin_id, tenant_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let http_req = TestRequest::default().to_http_request();
+
+        let response = usecase
+            .impersonate(admin_id, user.id, tenant_id, &http_req)
+            .await
+            .unwrap();
+
+        let jwt = JwtService::new();
+        let claims = jwt.validate_token(&response.access_token).unwrap();
+        assert_eq!(claims.sub, user.id.to_string());
+        assert_eq!(claims.tenant_id, tenant_id.to_string());
+        assert_eq!(claims.impersonator, Some(admin_id.to_string()));
+        assert_eq!(response.expires_in, jwt.get_impersonation_token_expiry());
+
+        let logged = logged.lock().unwrap();
+        assert!(logged.contains(&(Some(admin_id), "impersonate".to_string())));
+        assert!(logged.contains(&(Some(user.id), "impersonated".to_string())));
+    }
+
+    #[tokio::test]
+    async fn test_impersonate_requires_admin() {
+        use crate::domains::common::utils::config::Config;
+        Config::init_for_test();
+
+        let (usecase, user, logged) = impersonation_usecase(vec!["user".to_string()]);
+        let admin_id = Uuid::new_v4();
+        let http_req = TestRequest::default().to_http_request();
+
+        let result = usecase
+            .impersonate(admin_id, user.id, Uuid::new_v4(), &http_req)
+            .await;
+
+        assert!(matches!(result, Err(AppError::Forbidden(_))));
+        let logged = logged.lock().unwrap();
+        assert_eq!(*logged, vec![(Some(admin_id), "impersonate".to_string())]);
+    }
+
+    #[tokio::test]
+    async fn test_reauthenticate_refreshes_auth_time_with_second_factor() {
+        use crate::domains::common::utils::config::Config;
+        use crate::domains::common::utils::totp;
+        use crate::domains::user::dtos::auth_dto::ReauthenticateRequest;
+        Config::init_for_test();
+
+        let (user, mut mock_user_repo, mock_session_repo, mock_activity_repo) =
+            mfa_test_setup("password123");
+        mock_user_repo
+            .expect_increment_login_failures()
+            .with(eq(user.id))
+            .times(1)
+            .returning(|_| Ok(1));
+        let secret = totp::generate_secret();
+
+        let usecase = AuthUseCase::new(
+            Arc::new(mock_user_repo),
+            Arc::new(MockUserDetailsRepository::new()),
+            Arc::new(FakeUserTenantRepository::new()),
+            Arc::new(mock_session_repo),
+            Arc::new(mock_activity_repo),
+            Arc::new(MockInvitationCodeRepository::new()),
+            Arc::new(FakeUserMfaRepository::with_enabled_secret(user.id, &secret)),
+            Arc::new(FakeUserRecoveryCodeRepository::new()),
+            Arc::new(tenant_repository(false)),
+            Arc::new(FakeTokenDenylistRepository::new()),
+        );
+
+        let (http_req, claims) = request_with_access_token(user.id);
+        assert!(claims.authentication().is_none());
+
+        let wrong_password = ReauthenticateRequest {
+            password: "wrong".to_string(),
+            code: None,
+        };
+        assert!(matches!(
+            usecase.reauthenticate(wrong_password, &http_req).await,
+            Err(AppError::Unauthorized(_))
+        ));
+
+        // MFA users must present a code as well
+        let missing_code = ReauthenticateRequest {
+            password: "password123".to_string(),
+            code: None,
+        };
+        assert!(matches!(
+            usecase.reauthenticate(missing_code, &http_req).await,
+            Err(AppError::Unauthorized(_))
+        ));
+
+        let code = totp::generate_code(&secret, Utc::now().timestamp() as u64).unwrap();
+        let req = ReauthenticateRequest {
+            password: "password123".to_string(),
+            code: Some(code),
+        };
+        let response = usecase.reauthenticate(req, &http_req).await.unwrap();
+
+        let refreshed = JwtService::new()
+            .validate_token(&response.access_token)
+            .unwrap();
+        assert_eq!(refreshed.sub, claims.sub);
+        assert_eq!(refreshed.tenant_id, claims.tenant_id);
+        assert_eq!(refreshed.role, claims.role);
+        let authentication = refreshed.authentication().unwrap();
+        assert!(authentication.time >= claims.iat);
+        assert!(authentication.is_multi_factor());
+    }
 }
@@ -0,0 +1,205 @@
+use crate::domains::auth::usecases::auth_usecase::AuthUseCase;
+use crate::domains::common::errors::{AppError, ValidationDetail};
+use crate::domains::common::infrastructures::email_templates::EmailTemplates;
+use crate::domains::common::infrastructures::mailer::Mailer;
+use crate::domains::common::utils::config::Config;
+use crate::domains::common::utils::request_helper;
+use crate::domains::tenant::repositories::user_tenant_repository::UserTenantRepositoryTrait;
+use crate::domains::user::dtos::email_verification_dto::{
+    ResendVerificationRequest, VerifyEmailRequest,
+};
+use crate::domains::user::entities::user::Model as User;
+use crate::domains::user::entities::user_token::PURPOSE_EMAIL_VERIFICATION;
+use crate::domains::user::repositories::user_repository::UserRepositoryTrait;
+use crate::domains::user::repositories::user_token_repository::UserTokenRepositoryTrait;
+use chrono::Utc;
+use std::sync::Arc;
+
+/// Proof of ownership for the email address a user registered with.
+///
+/// A link carrying a random single-use token is emailed after registration
+/// and on request. Redeeming it sets `email_verified_at` on the user. Whether
+/// unverified users may log in is decided per tenant by `AuthUseCase`.
+pub struct EmailVerificationUseCase {
+    repository: Arc<dyn UserRepositoryTrait>,
+    user_tenant_repository: Arc<dyn UserTenantRepositoryTrait>,
+    token_repository: Arc<dyn UserTokenRepositoryTrait>,
+    mailer: Arc<dyn Mailer>,
+    templates: Arc<EmailTemplates>,
+    auth_usecase: Arc<AuthUseCase>,
+}
+
+impl EmailVerificationUseCase {
+    /// Creates a new EmailVerificationUseCase instance.
+    pub fn new(
+        repository: Arc<dyn UserRepositoryTrait>,
+        user_tenant_repository: Arc<dyn UserTenantRepositoryTrait>,
+        token_repository: Arc<dyn UserTokenRepositoryTrait>,
+        mailer: Arc<dyn Mailer>,
+        templates: Arc<EmailTemplates>,
+        auth_usecase: Arc<AuthUseCase>,
+    ) -> Self {
+        Self {
+            repository,
+            user_tenant_repository,
+            token_repository,
+            mailer,
+            templates,
+            auth_usecase,
+        }
+    }
+
+    /// Emails a verification link to a freshly registered user.
+    ///
+    /// Does nothing if the user is unknown or already verified.
+    pub async fn send_verification_email(
+        &self,
+        user_id: uuid::Uuid,
+        tenant_id: uuid::Uuid,
+        http_req: &actix_web::HttpRequest,
+    ) -> Result<(), AppError> {
+        let Some(user) = self
+            .repository
+            .find_by_id(user_id)
+            .await?
+            .filter(|u| u.email_verified_at.is_none())
+        else {
+            return Ok(());
+        };
+
+        self.send_link(&user, tenant_id, http_req).await
+    }
+
+    /// Emails a new verification link if the address belongs to an unverified
+    /// member of the tenant.
+    ///
+    /// Always succeeds for well-formed input so that the endpoint cannot be
+    /// used to enumerate accounts. A new link invalidates any previous one.
+    pub async fn resend_verification(
+        &self,
+        req: ResendVerificationRequest,
+        tenant_id: uuid::Uuid,
+        http_req: &actix_web::HttpRequest,
+    ) -> Result<(), AppError> {
+        let email = req.email.trim().to_lowercase();
+        if email.is_empty() {
+            return Err(AppError::ValidationError(
+                "Email is required".to_string(),
+                Some(vec![ValidationDetail {
+                    field: "email".to_string(),
+                    message: "Email is required".to_string(),
+                }]),
+            ));
+        }
+
+        let Some(user) = self
+            .repository
+            .find_by_email_with_deleted(&email)
+            .await?
+            .filter(|u| u.deleted_at.is_none() && u.email_verified_at.is_none())
+        else {
+            return Ok(());
+        };
+
+        let roles = self
+            .user_tenant_repository
+            .get_user_roles_in_tenant(user.id, tenant_id)
+            .await?;
+        if roles.is_empty() {
+            return Ok(());
+        }
+
+        self.send_link(&user, tenant_id, http_req).await
+    }
+
+    /// Marks the user's email as verified using the token from a verification link.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::BadRequest` if the token is unknown, expired, already
+    /// used or was issued for another tenant.
+    pub async fn verify_email(
+        &self,
+        req: VerifyEmailRequest,
+        tenant_id: uuid::Uuid,
+        http_req: &actix_web::HttpRequest,
+    ) -> Result<(), AppError> {
+        let (ip_address, user_agent) = request_helper::extract_client_info(http_req);
+
+        let invalid = || AppError::BadRequest("Invalid or expired verification token".to_string());
+
+        let token = self
+            .token_repository
+            .consume(
+                PURPOSE_EMAIL_VERIFICATION,
+                &request_helper::hash_token(req.token.trim()),
+            )
+            .await?
+            .filter(|t| t.tenant_id == tenant_id)
+            .ok_or_else(invalid)?;
+
+        let user = self
+            .repository
+            .find_by_id(token.user_id)
+            .await?
+            .ok_or_else(invalid)?;
+
+        self.repository.mark_email_verified(user.id).await?;
+
+        self.auth_usecase
+            .log_activity_success(Some(user.id), "email_verify", ip_address, user_agent)
+            .await;
+
+        Ok(())
+    }
+
+    /// Issues a verification token for the user and emails the link.
+    ///
+    /// The email is sent in the background, so that registration and resend
+    /// responses depend neither on the mail transport nor on whether the
+    /// address is registered.
+    async fn send_link(
+        &self,
+        user: &User,
+        tenant_id: uuid::Uuid,
+        http_req: &actix_web::HttpRequest,
+    ) -> Result<(), AppError> {
+        let (ip_address, user_agent) = request_helper::extract_client_info(http_req);
+        let config = Config::get();
+
+        let token = request_helper::generate_token();
+        let expires_at =
+            Utc::now() + chrono::Duration::seconds(config.email_verification_token_expiry);
+        self.token_repository
+            .issue(
+                user.id,
+                tenant_id,
+                PURPOSE_EMAIL_VERIFICATION,
+                request_helper::hash_token(&token),
+                expires_at,
+            )
+            .await?;
+
+        let rendered = self.templates.render(
+            "email_verification",
+            tenant_id,
+            request_helper::preferred_locale(http_req).as_deref(),
+            &user.email,
+            &serde_json::json!({
+                "username": user.username,
+                "link": format!("{}?token={}", config.email_verification_url, token),
+                "expires_in_hours": config.email_verification_token_expiry / 3600,
+            }),
+        );
+
+        self.auth_usecase.send_email_in_background(
+            self.mailer.clone(),
+            rendered,
+            user.id,
+            "email_verification_request",
+            (ip_address, user_agent),
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,190 @@
+#[cfg(test)]
+mod tests {
+    use crate::domains::auth::usecases::email_verification_usecase::EmailVerificationUseCase;
+    use crate::domains::auth::usecases::test_support::*;
+    use crate::domains::common::errors::AppError;
+    use crate::domains::common::infrastructures::email_templates::EmailTemplates;
+    use crate::domains::common::utils::config::Config;
+    use crate::domains::user::dtos::email_verification_dto::{
+        ResendVerificationRequest, VerifyEmailRequest,
+    };
+    use crate::domains::user::entities::user::Model as User;
+
+    use actix_web::test::TestRequest;
+    use chrono::Utc;
+    use std::sync::{Arc, Mutex};
+    use uuid::Uuid;
+
+    struct VerificationTestContext {
+        usecase: EmailVerificationUseCase,
+        user: Arc<Mutex<User>>,
+        tenant_id: Uuid,
+        mailer: Arc<FakeMailer>,
+    }
+
+    fn verification_test_setup() -> VerificationTestContext {
+        Config::init_for_test();
+
+        let user = Arc::new(Mutex::new(test_user("verifyuser", "verify@example.com")));
+        let tenant_id = Uuid::new_v4();
+
+        let user_repo = {
+            let mut mock = MockUserRepository::new();
+            let state = user.clone();
+            mock.expect_find_by_email_with_deleted()
+                .returning(move |email| {
+                    Ok(Some(state.lock().unwrap().clone()).filter(|u| u.email == email))
+                });
+            let state = user.clone();
+            mock.expect_find_by_id().returning(move |id| {
+                Ok(Some(state.lock().unwrap().clone()).filter(|u| u.id == id))
+            });
+            let state = user.clone();
+            mock.expect_mark_email_verified().returning(move |_| {
+                let mut user = state.lock().unwrap();
+                user.email_verified_at = Some(Utc::now());
+                Ok(user.clone())
+            });
+            Arc::new(mock)
+        };
+        let user_tenant_repo = Arc::new(user_tenant_repository(tenant_id));
+
+        let auth_usecase = auth_usecase(
+            user_repo.clone(),
+            user_tenant_repo.clone(),
+            Arc::new(MockUserSessionRepository::new()),
+            MockUserMfaRepository::new(),
+            tenant_repository(true, false),
+            MockTokenDenylistRepository::new(),
+        );
+
+        let mailer = Arc::new(FakeMailer::default());
+        let usecase = EmailVerificationUseCase::new(
+            user_repo,
+            user_tenant_repo,
+            Arc::new(FakeUserTokenRepository::default()),
+            mailer.clone(),
+            Arc::new(EmailTemplates::new(None, "en").unwrap()),
+            auth_usecase,
+        );
+
+        VerificationTestContext {
+            usecase,
+            user,
+            tenant_id,
+            mailer,
+        }
+    }
+
+    fn verify_request(token: &str) -> VerifyEmailRequest {
+        VerifyEmailRequest {
+            token: token.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_email_verification_flow_marks_user_verified_and_token_is_single_use() {
+        let ctx = verification_test_setup();
+        let http_req = TestRequest::default().to_http_request();
+        let user_id = ctx.user.lock().unwrap().id;
+
+        ctx.usecase
+            .send_verification_email(user_id, ctx.tenant_id, &http_req)
+            .await
+            .unwrap();
+
+        ctx.mailer.wait_for(1).await;
+        {
+            let sent = ctx.mailer.sent.lock().unwrap();
+            assert_eq!(sent.len(), 1);
+            assert_eq!(sent[0].to, "verify@example.com");
+            assert!(sent[0]
+                .text_body
+                .contains(&Config::get().email_verification_url));
+        }
+        let token = ctx.mailer.last_link_token();
+
+        ctx.usecase
+            .verify_email(verify_request(&token), ctx.tenant_id, &http_req)
+            .await
+            .unwrap();
+        assert!(ctx.user.lock().unwrap().email_verified_at.is_some());
+
+        let result = ctx
+            .usecase
+            .verify_email(verify_request(&token), ctx.tenant_id, &http_req)
+            .await;
+        assert!(matches!(result, Err(AppError::BadRequest(_))));
+
+        // Verified users are not sent another link
+        ctx.usecase
+            .send_verification_email(user_id, ctx.tenant_id, &http_req)
+            .await
+            .unwrap();
+        assert_eq!(ctx.mailer.sent.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_resend_verification_is_indistinguishable_for_unknown_emails() {
+        let ctx = verification_test_setup();
+        let http_req = TestRequest::default().to_http_request();
+        let resend = |email: &str| ResendVerificationRequest {
+            email: email.to_string(),
+        };
+
+        let result = ctx
+            .usecase
+            .resend_verification(resend("nobody@example.com"), ctx.tenant_id, &http_req)
+            .await;
+        assert!(result.is_ok());
+
+        // A registered user outside the tenant is treated the same way
+        let result = ctx
+            .usecase
+            .resend_verification(resend("verify@example.com"), Uuid::new_v4(), &http_req)
+            .await;
+        assert!(result.is_ok());
+        assert!(ctx.mailer.sent.lock().unwrap().is_empty());
+
+        ctx.usecase
+            .resend_verification(resend("Verify@Example.com"), ctx.tenant_id, &http_req)
+            .await
+            .unwrap();
+        ctx.mailer.wait_for(1).await;
+        assert_eq!(ctx.mailer.sent.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_verify_email_rejects_superseded_and_foreign_tenant_tokens() {
+        let ctx = verification_test_setup();
+        let http_req = TestRequest::default().to_http_request();
+        let user_id = ctx.user.lock().unwrap().id;
+
+        ctx.usecase
+            .send_verification_email(user_id, ctx.tenant_id, &http_req)
+            .await
+            .unwrap();
+        ctx.mailer.wait_for(1).await;
+        let first_token = ctx.mailer.last_link_token();
+        ctx.usecase
+            .send_verification_email(user_id, ctx.tenant_id, &http_req)
+            .await
+            .unwrap();
+        ctx.mailer.wait_for(2).await;
+        let second_token = ctx.mailer.last_link_token();
+
+        // Sending a new link invalidates the previous one
+        let result = ctx
+            .usecase
+            .verify_email(verify_request(&first_token), ctx.tenant_id, &http_req)
+            .await;
+        assert!(matches!(result, Err(AppError::BadRequest(_))));
+
+        let result = ctx
+            .usecase
+            .verify_email(verify_request(&second_token), Uuid::new_v4(), &http_req)
+            .await;
+        assert!(matches!(result, Err(AppError::BadRequest(_))));
+        assert!(ctx.user.lock().unwrap().email_verified_at.is_none());
+    }
+}
@@ -1,4 +1,30 @@
 pub mod auth_usecase;
+pub mod email_verification_usecase;
+pub mod oidc_usecase;
+pub mod passkey_usecase;
+pub mod password_reset_usecase;
+pub mod passwordless_usecase;
+pub mod session_usecase;
+pub mod signing_key_usecase;
+pub mod token_usecase;
 
 #[cfg(test)]
 mod auth_usecase_test;
+#[cfg(test)]
+mod email_verification_usecase_test;
+#[cfg(test)]
+mod oidc_usecase_test;
+#[cfg(test)]
+mod passkey_usecase_test;
+#[cfg(test)]
+mod password_reset_usecase_test;
+#[cfg(test)]
+mod passwordless_usecase_test;
+#[cfg(test)]
+mod session_usecase_test;
+#[cfg(test)]
+mod signing_key_usecase_test;
+#[cfg(test)]
+mod test_support;
+#[cfg(test)]
+mod token_usecase_test;
@@ -0,0 +1,1172 @@
+use crate::domains::auth::dtos::oidc_dto::{
+    AuthorizeQuery, DeviceAuthorizationRequest, DeviceAuthorizationResponse,
+    DeviceVerificationResponse, OpenIdConfiguration, TokenRequest, TokenResponse, UserInfoResponse,
+};
+use crate::domains::auth::repositories::authorization_code_repository::{
+    AuthorizationCode, AuthorizationCodeRepositoryTrait, AuthorizationRequest,
+};
+use crate::domains::auth::repositories::device_code_repository::{
+    DeviceAuthorization, DeviceAuthorizationStatus, DeviceCodeRepositoryTrait,
+};
+use crate::domains::auth::repositories::token_denylist_repository::TokenDenylistRepositoryTrait;
+use crate::domains::common::errors::AppError;
+use crate::domains::common::utils::config::Config;
+use crate::domains::common::utils::jwt::{Actor, Claims, IdTokenClaims, JwtService};
+use crate::domains::common::utils::{request_helper, user_code};
+use crate::domains::common::validators::sso_validator::{
+    validate_redirect_uri_origin, validate_redirect_uri_whitelist,
+};
+use crate::domains::tenant::entities::client::{
+    Model as Client, GRANT_TYPE_AUTHORIZATION_CODE, GRANT_TYPE_CLIENT_CREDENTIALS,
+    GRANT_TYPE_DEVICE_CODE, GRANT_TYPE_TOKEN_EXCHANGE,
+};
+use crate::domains::tenant::repositories::client_repository::ClientRepositoryTrait;
+use crate::domains::tenant::repositories::tenant_repository::TenantRepositoryTrait;
+use crate::domains::user::entities::user::Model as User;
+use crate::domains::user::repositories::user_repository::UserRepositoryTrait;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::Utc;
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Scopes the provider understands. Other requested scopes are ignored.
+pub const SUPPORTED_SCOPES: [&str; 3] = ["openid", "profile", "email"];
+
+/// How long a pending authorization request waits for the user to sign in, in seconds.
+const AUTHORIZATION_REQUEST_TTL_SECS: u64 = 600;
+
+/// Maximum length of the `state` and `nonce` parameters.
+const MAX_PARAM_LENGTH: usize = 512;
+
+/// Token type identifiers of the token exchange grant (RFC 8693 section 3).
+const TOKEN_TYPE_ACCESS_TOKEN: &str = "urn:ietf:params:oauth:token-type:access_token";
+const TOKEN_TYPE_JWT: &str = "urn:ietf:params:oauth:token-type:jwt";
+
+/// Seconds added to a device's polling interval each time it polls too fast
+/// (RFC 8628 section 3.5).
+const DEVICE_SLOW_DOWN_SECS: u64 = 5;
+
+/// OpenID Connect provider: authorization code flow with PKCE.
+///
+/// `/oauth/authorize` validates the client's request and sends the browser to
+/// the login page. Once the user has signed in there, the page completes the
+/// request and the browser returns to the client with a single-use code,
+/// which the client exchanges at `/oauth/token` for an access token and an
+/// ID token.
+///
+/// Devices without a browser use the device authorization grant instead:
+/// they show a user code, the user approves it on the web app after signing
+/// in, and the device polls `/oauth/token` until it receives its tokens.
+///
+/// Services holding a user's access token can exchange it for a narrower
+/// token naming them as actor, to call other services for the user.
+///
+/// Clients are the applications registered by tenants. A tenant without
+/// registered clients acts as a public client itself, identified by its
+/// tenant ID and limited to the global allowed origins.
+pub struct OidcUseCase {
+    code_repository: Arc<dyn AuthorizationCodeRepositoryTrait>,
+    device_code_repository: Arc<dyn DeviceCodeRepositoryTrait>,
+    client_repository: Arc<dyn ClientRepositoryTrait>,
+    tenant_repository: Arc<dyn TenantRepositoryTrait>,
+    user_repository: Arc<dyn UserRepositoryTrait>,
+    token_denylist_repository: Arc<dyn TokenDenylistRepositoryTrait>,
+    jwt_service: JwtService,
+}
+
+impl OidcUseCase {
+    /// Creates a new OidcUseCase instance.
+    pub fn new(
+        code_repository: Arc<dyn AuthorizationCodeRepositoryTrait>,
+        device_code_repository: Arc<dyn DeviceCodeRepositoryTrait>,
+        client_repository: Arc<dyn ClientRepositoryTrait>,
+        tenant_repository: Arc<dyn TenantRepositoryTrait>,
+        user_repository: Arc<dyn UserRepositoryTrait>,
+        token_denylist_repository: Arc<dyn TokenDenylistRepositoryTrait>,
+    ) -> Self {
+        Self {
+            code_repository,
+            device_code_repository,
+            client_repository,
+            tenant_repository,
+            user_repository,
+            token_denylist_repository,
+            jwt_service: JwtService::new(),
+        }
+    }
+
+    /// Validates an authorization request and returns where to send the browser.
+    ///
+    /// That is the login page for a valid request, or the client's
+    /// `redirect_uri` with an `error` parameter otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::OAuth` with `invalid_request` or `invalid_client`
+    /// when `client_id` is unusable, or `AppError::Forbidden` when
+    /// `redirect_uri` is not registered for the client, as the client cannot
+    /// be redirected to then.
+    pub async fn authorize(&self, query: AuthorizeQuery) -> Result<String, AppError> {
+        let client = self.resolve_client(query.client_id.as_deref()).await?;
+        let redirect_uri = query.redirect_uri.clone().ok_or_else(|| {
+            AppError::OAuth("invalid_request", "redirect_uri is required".to_string())
+        })?;
+        // Never redirect to a URI the client did not register
+        client.validate_redirect_uri(&redirect_uri)?;
+
+        let request = match Self::validate_request(&client, &redirect_uri, &query) {
+            Ok(request) => request,
+            Err(AppError::OAuth(error, description)) => {
+                return Ok(Self::redirect_with(
+                    &redirect_uri,
+                    &[
+                        ("error", error),
+                        ("error_description", &description),
+                        ("state", query.state.as_deref().unwrap_or_default()),
+                    ],
+                ));
+            }
+            Err(e) => return Err(e),
+        };
+
+        let id = request_helper::generate_token();
+        self.code_repository
+            .save_request(
+                &id,
+                request,
+                Duration::from_secs(AUTHORIZATION_REQUEST_TTL_SECS),
+            )
+            .await?;
+
+        Ok(Self::redirect_with(
+            &Config::get().oidc_login_url,
+            &[("authorization_request", &id)],
+        ))
+    }
+
+    /// Issues an authorization code for the signed-in user and returns the
+    /// client redirect carrying it.
+    ///
+    /// `claims` are those of the access token the login page obtained.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::NotFound` if the request expired or was already
+    /// completed, `AppError::Forbidden` if the user signed in to another tenant
+    /// or is no longer active.
+    pub async fn complete_authorization(
+        &self,
+        request_id: &str,
+        claims: &Claims,
+    ) -> Result<String, AppError> {
+        let request = self
+            .code_repository
+            .take_request(request_id)
+            .await?
+            .ok_or_else(|| {
+                AppError::NotFound("Authorization request expired or not found".to_string())
+            })?;
+
+        if claims.tenant_id != request.tenant_id.to_string() {
+            return Err(AppError::Forbidden(
+                "Signed in to a different tenant than the client".to_string(),
+            ));
+        }
+        let user_id = Uuid::parse_str(&claims.sub)
+            .map_err(|_| AppError::Unauthorized("Invalid user ID".to_string()))?;
+        self.active_user(user_id)
+            .await?
+            .ok_or_else(|| AppError::Forbidden("Account is not active".to_string()))?;
+
+        let code = request_helper::generate_token();
+        let redirect = Self::redirect_with(
+            &request.redirect_uri,
+            &[
+                ("code", &code),
+                ("state", request.state.as_deref().unwrap_or_default()),
+            ],
+        );
+        let grant = AuthorizationCode {
+            request,
+            user_id,
+            role: claims.role.clone(),
+            auth_time: claims.auth_time.unwrap_or(claims.iat),
+        };
+        self.code_repository
+            .save_code(
+                &code,
+                grant,
+                Duration::from_secs(Config::get().oidc_code_expiry),
+            )
+            .await?;
+
+        Ok(redirect)
+    }
+
+    /// Device authorization endpoint: issues a device code for the device to
+    /// poll with and a user code for the user to approve on the web app.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::OAuth` with `invalid_client`, `unauthorized_client`
+    /// or `invalid_scope`.
+    pub async fn device_authorization(
+        &self,
+        req: DeviceAuthorizationRequest,
+    ) -> Result<DeviceAuthorizationResponse, AppError> {
+        let client = self
+            .authenticate_client(req.client_id.as_deref(), req.client_secret.as_deref())
+            .await?;
+        let client = match client {
+            OAuthClient::Registered(client) if client.allows_grant_type(GRANT_TYPE_DEVICE_CODE) => {
+                client
+            }
+            _ => {
+                return Err(AppError::OAuth(
+                    "unauthorized_client",
+                    "Client may not use the device_code grant".to_string(),
+                ))
+            }
+        };
+        let scope = granted_scope(&client, req.scope.as_deref())?;
+
+        let config = Config::get();
+        let device_code = request_helper::generate_token();
+        let mut display_code = user_code::generate_code();
+        // Two pending devices must never share a user code
+        while self
+            .device_code_repository
+            .find_by_user_code(&user_code::normalize(&display_code))
+            .await?
+            .is_some()
+        {
+            display_code = user_code::generate_code();
+        }
+
+        self.device_code_repository
+            .save(&DeviceAuthorization {
+                device_code_hash: request_helper::hash_token(&device_code),
+                user_code: user_code::normalize(&display_code),
+                tenant_id: client.tenant_id,
+                client_id: client.client_id.clone(),
+                scope,
+                status: DeviceAuthorizationStatus::Pending,
+                interval: config.oidc_device_poll_interval,
+                last_polled_at: None,
+                expires_at: Utc::now().timestamp() + config.oidc_device_code_expiry as i64,
+            })
+            .await?;
+
+        log::info!(
+            "Issued device code to client {} of tenant {}",
+            client.client_id,
+            client.tenant_id
+        );
+
+        Ok(DeviceAuthorizationResponse {
+            device_code,
+            verification_uri_complete: Self::redirect_with(
+                &config.oidc_device_verification_url,
+                &[("user_code", &display_code)],
+            ),
+            user_code: display_code,
+            verification_uri: config.oidc_device_verification_url.clone(),
+            expires_in: config.oidc_device_code_expiry,
+            interval: config.oidc_device_poll_interval,
+        })
+    }
+
+    /// Describes the device authorization of `user_code` to the signed-in
+    /// user, before they approve or deny it.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::NotFound` if the code expired or does not exist,
+    /// `AppError::Forbidden` if the user signed in to another tenant than the
+    /// client's.
+    pub async fn find_device_authorization(
+        &self,
+        user_code: &str,
+        claims: &Claims,
+    ) -> Result<DeviceVerificationResponse, AppError> {
+        let authorization = self.device_authorization_for(user_code, claims).await?;
+        self.describe_device_authorization(&authorization).await
+    }
+
+    /// Records the signed-in user's decision on the device authorization of
+    /// `user_code`. The device receives tokens for the user on its next poll
+    /// if `approve` is true.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::NotFound` if the code expired or does not exist,
+    /// `AppError::Conflict` if it was already decided, `AppError::Forbidden`
+    /// if the user signed in to another tenant than the client's or is no
+    /// longer active.
+    pub async fn decide_device_authorization(
+        &self,
+        user_code: &str,
+        approve: bool,
+        claims: &Claims,
+    ) -> Result<DeviceVerificationResponse, AppError> {
+        let mut authorization = self.device_authorization_for(user_code, claims).await?;
+        if authorization.status != DeviceAuthorizationStatus::Pending {
+            return Err(AppError::Conflict(
+                "Device code was already used".to_string(),
+            ));
+        }
+
+        let user_id = Uuid::parse_str(&claims.sub)
+            .map_err(|_| AppError::Unauthorized("Invalid user ID".to_string()))?;
+        self.active_user(user_id)
+            .await?
+            .ok_or_else(|| AppError::Forbidden("Account is not active".to_string()))?;
+
+        authorization.status = if approve {
+            DeviceAuthorizationStatus::Approved {
+                user_id,
+                role: claims.role.clone(),
+                auth_time: claims.auth_time.unwrap_or(claims.iat),
+            }
+        } else {
+            DeviceAuthorizationStatus::Denied
+        };
+        self.device_code_repository.save(&authorization).await?;
+
+        log::info!(
+            "User {} {} device of client {}",
+            user_id,
+            if approve { "approved" } else { "denied" },
+            authorization.client_id
+        );
+
+        self.describe_device_authorization(&authorization).await
+    }
+
+    /// Token endpoint: exchanges an authorization code, an approved device
+    /// code or a user's access token (token exchange) for tokens, or issues an
+    /// access token to a machine client (client credentials grant).
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::OAuth` with `invalid_client`,
+    /// `unsupported_grant_type`, `unauthorized_client`, `invalid_request`,
+    /// `invalid_grant` or `invalid_scope` as defined by RFC 6749, with
+    /// `authorization_pending`, `slow_down`, `access_denied` or
+    /// `expired_token` while polling with a device code (RFC 8628), or with
+    /// `invalid_target` for an unknown token exchange audience (RFC 8693).
+    pub async fn exchange_token(&self, req: TokenRequest) -> Result<TokenResponse, AppError> {
+        let client = self
+            .authenticate_client(req.client_id.as_deref(), req.client_secret.as_deref())
+            .await?;
+
+        if req.grant_type != GRANT_TYPE_AUTHORIZATION_CODE
+            && req.grant_type != GRANT_TYPE_CLIENT_CREDENTIALS
+            && req.grant_type != GRANT_TYPE_DEVICE_CODE
+            && req.grant_type != GRANT_TYPE_TOKEN_EXCHANGE
+        {
+            return Err(AppError::OAuth(
+                "unsupported_grant_type",
+                format!("Unsupported grant_type: {}", req.grant_type),
+            ));
+        }
+        if !client.allows_grant_type(&req.grant_type) {
+            return Err(AppError::OAuth(
+                "unauthorized_client",
+                format!("Client may not use the {} grant", req.grant_type),
+            ));
+        }
+
+        match req.grant_type.as_str() {
+            GRANT_TYPE_CLIENT_CREDENTIALS => {
+                self.client_credentials_grant(&client, req.scope.as_deref())
+            }
+            GRANT_TYPE_DEVICE_CODE => self.device_code_grant(&client, &req).await,
+            GRANT_TYPE_TOKEN_EXCHANGE => self.token_exchange_grant(&client, &req).await,
+            _ => self.authorization_code_grant(&client, &req).await,
+        }
+    }
+
+    /// Client credentials grant: the token is issued to the client itself.
+    ///
+    /// Without a `scope` parameter every scope of the client is granted.
+    fn client_credentials_grant(
+        &self,
+        client: &OAuthClient,
+        scope: Option<&str>,
+    ) -> Result<TokenResponse, AppError> {
+        // Only confidential registered clients can hold this grant, and they
+        // were authenticated by `exchange_token`
+        let client = match client {
+            OAuthClient::Registered(client) if !client.is_public() => client,
+            _ => {
+                return Err(AppError::OAuth(
+                    "unauthorized_client",
+                    "Client may not use the client_credentials grant".to_string(),
+                ))
+            }
+        };
+
+        let scope = granted_scope(client, scope)?;
+
+        let access_token = self
+            .jwt_service
+            .generate_client_token(&client.client_id, client.tenant_id, scope.clone())
+            .map_err(|e| AppError::InternalError(format!("Failed to generate token: {}", e)))?;
+
+        log::info!(
+            "Issued client credentials token to client {} of tenant {}",
+            client.client_id,
+            client.tenant_id
+        );
+
+        Ok(TokenResponse {
+            access_token,
+            token_type: "Bearer".to_string(),
+            expires_in: self.jwt_service.get_access_token_expiry(),
+            id_token: None,
+            scope,
+            issued_token_type: None,
+        })
+    }
+
+    /// Authorization code grant: redeems the code after checking PKCE.
+    async fn authorization_code_grant(
+        &self,
+        client: &OAuthClient,
+        req: &TokenRequest,
+    ) -> Result<TokenResponse, AppError> {
+        let (code, verifier) = match (req.code.as_deref(), req.code_verifier.as_deref()) {
+            (Some(code), Some(verifier)) => (code, verifier),
+            _ => {
+                return Err(AppError::OAuth(
+                    "invalid_request",
+                    "code and code_verifier are required".to_string(),
+                ))
+            }
+        };
+
+        // Redeeming deletes the code, so a failed attempt burns it as well
+        let grant = self
+            .code_repository
+            .take_code(code)
+            .await?
+            .ok_or_else(|| invalid_grant("Invalid or expired authorization code"))?;
+        let request = &grant.request;
+
+        if client.client_id() != request.client_id {
+            return Err(invalid_grant("Code was issued to another client"));
+        }
+        if req.redirect_uri.as_deref() != Some(request.redirect_uri.as_str()) {
+            return Err(invalid_grant("redirect_uri does not match"));
+        }
+        if !is_valid_pkce_value(verifier) || pkce_challenge(verifier) != request.code_challenge {
+            return Err(invalid_grant("PKCE verification failed"));
+        }
+
+        let user = self
+            .active_user(grant.user_id)
+            .await?
+            .ok_or_else(|| invalid_grant("Account is not active"))?;
+
+        let access_token = self
+            .jwt_service
+            .generate_scoped_access_token(
+                user.id,
+                request.tenant_id,
+                grant.role.clone(),
+                Some(request.scope.clone()),
+            )
+            .map_err(|e| AppError::InternalError(format!("Failed to generate token: {}", e)))?;
+
+        let id_token = self.id_token(
+            &user,
+            &request.client_id,
+            &request.scope,
+            grant.auth_time,
+            request.nonce.clone(),
+        )?;
+
+        log::info!(
+            "Issued OIDC tokens for user {} to client {}",
+            user.id,
+            request.client_id
+        );
+
+        Ok(TokenResponse {
+            access_token,
+            token_type: "Bearer".to_string(),
+            expires_in: self.jwt_service.get_access_token_expiry(),
+            id_token: Some(id_token),
+            scope: request.scope.clone(),
+            issued_token_type: None,
+        })
+    }
+
+    /// Device authorization grant: answers a device polling with its device
+    /// code, and issues tokens once the user has approved it.
+    ///
+    /// Devices polling faster than their interval get `slow_down` and must
+    /// wait longer from then on.
+    async fn device_code_grant(
+        &self,
+        client: &OAuthClient,
+        req: &TokenRequest,
+    ) -> Result<TokenResponse, AppError> {
+        let device_code = req.device_code.as_deref().ok_or_else(|| {
+            AppError::OAuth("invalid_request", "device_code is required".to_string())
+        })?;
+
+        let mut authorization = self
+            .device_code_repository
+            .find_by_device_code(device_code)
+            .await?
+            .ok_or_else(|| {
+                AppError::OAuth(
+                    "expired_token",
+                    "Device code expired or not found".to_string(),
+                )
+            })?;
+        if client.client_id() != authorization.client_id {
+            return Err(invalid_grant("Device code was issued to another client"));
+        }
+
+        let now = Utc::now().timestamp();
+        let too_fast = authorization
+            .last_polled_at
+            .is_some_and(|polled_at| now - polled_at < authorization.interval as i64);
+        authorization.last_polled_at = Some(now);
+        if too_fast {
+            authorization.interval += DEVICE_SLOW_DOWN_SECS;
+            self.device_code_repository.save(&authorization).await?;
+            return Err(AppError::OAuth(
+                "slow_down",
+                format!("Poll at most every {} seconds", authorization.interval),
+            ));
+        }
+
+        let (user_id, role, auth_time) = match authorization.status.clone() {
+            DeviceAuthorizationStatus::Pending => {
+                self.device_code_repository.save(&authorization).await?;
+                return Err(AppError::OAuth(
+                    "authorization_pending",
+                    "The user has not approved the device yet".to_string(),
+                ));
+            }
+            DeviceAuthorizationStatus::Denied => {
+                self.device_code_repository.delete(&authorization).await?;
+                return Err(AppError::OAuth(
+                    "access_denied",
+                    "The user denied the device".to_string(),
+                ));
+            }
+            DeviceAuthorizationStatus::Approved {
+                user_id,
+                role,
+                auth_time,
+            } => (user_id, role, auth_time),
+        };
+        // Tokens are issued once, like authorization codes are redeemed once
+        self.device_code_repository.delete(&authorization).await?;
+
+        let user = self
+            .active_user(user_id)
+            .await?
+            .ok_or_else(|| invalid_grant("Account is not active"))?;
+
+        // Always scoped, so the device cannot use the first-party API
+        let access_token = self
+            .jwt_service
+            .generate_scoped_access_token(
+                user.id,
+                authorization.tenant_id,
+                role,
+                Some(authorization.scope.clone()),
+            )
+            .map_err(|e| AppError::InternalError(format!("Failed to generate token: {}", e)))?;
+        let id_token = if authorization.scope.split(' ').any(|s| s == "openid") {
+            Some(self.id_token(
+                &user,
+                &authorization.client_id,
+                &authorization.scope,
+                auth_time,
+                None,
+            )?)
+        } else {
+            None
+        };
+
+        log::info!(
+            "Issued device tokens for user {} to client {}",
+            user.id,
+            authorization.client_id
+        );
+
+        Ok(TokenResponse {
+            access_token,
+            token_type: "Bearer".to_string(),
+            expires_in: self.jwt_service.get_access_token_expiry(),
+            id_token,
+            scope: authorization.scope,
+            issued_token_type: None,
+        })
+    }
+
+    /// Token exchange grant: issues a token for the user of `subject_token`,
+    /// with the client as actor, an optional audience and at most the scopes
+    /// of both the client and the subject token.
+    async fn token_exchange_grant(
+        &self,
+        client: &OAuthClient,
+        req: &TokenRequest,
+    ) -> Result<TokenResponse, AppError> {
+        // Only confidential registered clients can hold this grant, and they
+        // were authenticated by `exchange_token`
+        let client = match client {
+            OAuthClient::Registered(client) if !client.is_public() => client,
+            _ => {
+                return Err(AppError::OAuth(
+                    "unauthorized_client",
+                    "Client may not use the token-exchange grant".to_string(),
+                ))
+            }
+        };
+
+        let subject_token = req.subject_token.as_deref().ok_or_else(|| {
+            AppError::OAuth("invalid_request", "subject_token is required".to_string())
+        })?;
+        if !matches!(
+            req.subject_token_type.as_deref(),
+            Some(TOKEN_TYPE_ACCESS_TOKEN | TOKEN_TYPE_JWT)
+        ) {
+            return Err(AppError::OAuth(
+                "invalid_request",
+                "subject_token_type must be an access token".to_string(),
+            ));
+        }
+        if req
+            .requested_token_type
+            .as_deref()
+            .is_some_and(|t| t != TOKEN_TYPE_ACCESS_TOKEN)
+        {
+            return Err(AppError::OAuth(
+                "invalid_request",
+                "Only access tokens can be requested".to_string(),
+            ));
+        }
+
+        let subject = self
+            .jwt_service
+            .validate_token(subject_token)
+            .map_err(|_| invalid_grant("Invalid or expired subject_token"))?;
+        if subject.token_type != "access" || subject.tenant_id != client.tenant_id.to_string() {
+            return Err(invalid_grant(
+                "subject_token is not a user access token of the client's tenant",
+            ));
+        }
+        if self.token_denylist_repository.is_revoked(&subject).await? {
+            return Err(invalid_grant("subject_token was revoked"));
+        }
+        let user_id =
+            Uuid::parse_str(&subject.sub).map_err(|_| invalid_grant("Invalid subject"))?;
+        if self.active_user(user_id).await?.is_none() {
+            return Err(invalid_grant("Account is not active"));
+        }
+
+        if let Some(audience) = req.audience.as_deref() {
+            let known = self
+                .client_repository
+                .find_by_client_id(audience)
+                .await?
+                .is_some_and(|c| c.tenant_id == client.tenant_id);
+            if !known {
+                return Err(AppError::OAuth(
+                    "invalid_target",
+                    format!("Unknown audience: {}", audience),
+                ));
+            }
+        }
+
+        // The new token is never wider than the client or the subject token
+        let within_subject = |s: &&str| subject.scope.is_none() || subject.has_scope(s);
+        let client_scope = granted_scope(client, req.scope.as_deref())?;
+        let scopes: Vec<&str> = client_scope.split_whitespace().collect();
+        if req.scope.is_some() {
+            if let Some(denied) = scopes.iter().find(|s| !within_subject(s)) {
+                return Err(AppError::OAuth(
+                    "invalid_scope",
+                    format!("subject_token was not granted the {} scope", denied),
+                ));
+            }
+        }
+        let scope = scopes
+            .into_iter()
+            .filter(within_subject)
+            .collect::<Vec<_>>()
+            .join(" ");
+        if scope.is_empty() {
+            return Err(AppError::OAuth(
+                "invalid_scope",
+                "No scope of the client was granted to subject_token".to_string(),
+            ));
+        }
+
+        let actor = Actor {
+            sub: client.client_id.clone(),
+            act: subject.act.clone().map(Box::new),
+        };
+        let access_token = self
+            .jwt_service
+            .generate_delegated_token(&subject, scope.clone(), req.audience.clone(), actor)
+            .map_err(|e| AppError::InternalError(format!("Failed to generate token: {}", e)))?;
+
+        log::info!(
+            "Client {} exchanged a token of user {} for audience {}",
+            client.client_id,
+            subject.sub,
+            req.audience.as_deref().unwrap_or("-")
+        );
+
+        Ok(TokenResponse {
+            access_token,
+            token_type: "Bearer".to_string(),
+            expires_in: (subject.exp - Utc::now().timestamp())
+                .min(self.jwt_service.get_access_token_expiry()),
+            id_token: None,
+            scope,
+            issued_token_type: Some(TOKEN_TYPE_ACCESS_TOKEN.to_string()),
+        })
+    }
+
+    /// Signs an ID token for `user` with the claims allowed by `scope`.
+    fn id_token(
+        &self,
+        user: &User,
+        client_id: &str,
+        scope: &str,
+        auth_time: i64,
+        nonce: Option<String>,
+    ) -> Result<String, AppError> {
+        let now = Utc::now().timestamp();
+        let has_scope = |s: &str| scope.split(' ').any(|granted| granted == s);
+
+        self.jwt_service
+            .generate_id_token(&IdTokenClaims {
+                iss: issuer(),
+                sub: user.id.to_string(),
+                aud: client_id.to_string(),
+                exp: now + self.jwt_service.get_access_token_expiry(),
+                iat: now,
+                auth_time,
+                nonce,
+                preferred_username: has_scope("profile").then(|| user.username.clone()),
+                email: has_scope("email").then(|| user.email.clone()),
+                email_verified: has_scope("email").then(|| user.email_verified_at.is_some()),
+            })
+            .map_err(|e| AppError::InternalError(format!("Failed to generate token: {}", e)))
+    }
+
+    /// UserInfo endpoint: returns the claims allowed by the token's scopes.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::OAuth` with `insufficient_scope` if the token was not
+    /// issued with the `openid` scope, or `invalid_token` if the user no
+    /// longer exists or is not active.
+    pub async fn userinfo(&self, claims: &Claims) -> Result<UserInfoResponse, AppError> {
+        if !claims.has_scope("openid") {
+            return Err(AppError::OAuth(
+                "insufficient_scope",
+                "Token was not issued with the openid scope".to_string(),
+            ));
+        }
+
+        let user = match Uuid::parse_str(&claims.sub) {
+            Ok(user_id) => self.active_user(user_id).await?,
+            Err(_) => None,
+        }
+        .ok_or_else(|| AppError::OAuth("invalid_token", "Unknown user".to_string()))?;
+
+        Ok(UserInfoResponse {
+            sub: user.id.to_string(),
+            preferred_username: claims.has_scope("profile").then(|| user.username.clone()),
+            email: claims.has_scope("email").then(|| user.email.clone()),
+            email_verified: claims
+                .has_scope("email")
+                .then(|| user.email_verified_at.is_some()),
+        })
+    }
+
+    /// Provider metadata for `/.well-known/openid-configuration`.
+    ///
+    /// All tenants share the issuer, so there is a single document. The
+    /// signing algorithms are those of the keys currently in the key ring.
+    pub fn discovery(&self) -> OpenIdConfiguration {
+        let issuer = issuer();
+        let url = |path: &str| format!("{}{}", issuer, path);
+        let strings = |values: &[&str]| values.iter().map(|v| v.to_string()).collect();
+
+        OpenIdConfiguration {
+            authorization_endpoint: url("/oauth/authorize"),
+            token_endpoint: url("/oauth/token"),
+            userinfo_endpoint: url("/oauth/userinfo"),
+            jwks_uri: url("/.well-known/jwks.json"),
+            end_session_endpoint: url("/auth/sso/logout"),
+            introspection_endpoint: url("/oauth/introspect"),
+            revocation_endpoint: url("/oauth/revoke"),
+            device_authorization_endpoint: url("/oauth/device_authorization"),
+            login_endpoint: url("/auth/login"),
+            refresh_endpoint: url("/auth/refresh"),
+            verify_endpoint: url("/auth/verify"),
+            logout_endpoint: url("/auth/logout"),
+            scopes_supported: strings(&SUPPORTED_SCOPES),
+            response_types_supported: strings(&["code"]),
+            response_modes_supported: strings(&["query"]),
+            grant_types_supported: strings(&[
+                GRANT_TYPE_AUTHORIZATION_CODE,
+                GRANT_TYPE_CLIENT_CREDENTIALS,
+                GRANT_TYPE_DEVICE_CODE,
+                GRANT_TYPE_TOKEN_EXCHANGE,
+            ]),
+            subject_types_supported: strings(&["public"]),
+            id_token_signing_alg_values_supported: self
+                .jwt_service
+                .signing_algorithms()
+                .iter()
+                .map(|a| format!("{:?}", a))
+                .collect(),
+            token_endpoint_auth_methods_supported: strings(&[
+                "none",
+                "client_secret_basic",
+                "client_secret_post",
+            ]),
+            // Callers may also use the tenant API key (X-API-Key)
+            introspection_endpoint_auth_methods_supported: strings(&[
+                "client_secret_basic",
+                "client_secret_post",
+            ]),
+            revocation_endpoint_auth_methods_supported: strings(&[
+                "client_secret_basic",
+                "client_secret_post",
+            ]),
+            code_challenge_methods_supported: strings(&["S256"]),
+            claims_supported: strings(&[
+                "iss",
+                "sub",
+                "aud",
+                "exp",
+                "iat",
+                "auth_time",
+                "nonce",
+                "preferred_username",
+                "email",
+                "email_verified",
+            ]),
+            issuer,
+        }
+    }
+
+    fn validate_request(
+        client: &OAuthClient,
+        redirect_uri: &str,
+        query: &AuthorizeQuery,
+    ) -> Result<AuthorizationRequest, AppError> {
+        if query.response_type.as_deref() != Some("code") {
+            return Err(AppError::OAuth(
+                "unsupported_response_type",
+                "Only response_type=code is supported".to_string(),
+            ));
+        }
+        if !client.allows_grant_type(GRANT_TYPE_AUTHORIZATION_CODE) {
+            return Err(AppError::OAuth(
+                "unauthorized_client",
+                "Client may not use the authorization_code grant".to_string(),
+            ));
+        }
+
+        if [&query.state, &query.nonce]
+            .iter()
+            .any(|p| p.as_ref().is_some_and(|v| v.len() > MAX_PARAM_LENGTH))
+        {
+            return Err(AppError::OAuth(
+                "invalid_request",
+                format!(
+                    "state and nonce are limited to {} characters",
+                    MAX_PARAM_LENGTH
+                ),
+            ));
+        }
+
+        let requested = query.scope.as_deref().unwrap_or_default();
+        let scope: Vec<&str> = SUPPORTED_SCOPES
+            .into_iter()
+            .filter(|s| requested.split(' ').any(|r| r == *s))
+            .filter(|s| client.allows_scope(s))
+            .collect();
+        if !scope.contains(&"openid") {
+            return Err(AppError::OAuth(
+                "invalid_scope",
+                "The openid scope is required and must be allowed for the client".to_string(),
+            ));
+        }
+
+        let code_challenge = match (
+            query.code_challenge.as_deref(),
+            query.code_challenge_method.as_deref(),
+        ) {
+            (Some(challenge), Some("S256")) if is_valid_pkce_value(challenge) => challenge,
+            _ => {
+                return Err(AppError::OAuth(
+                    "invalid_request",
+                    "PKCE with code_challenge_method=S256 is required".to_string(),
+                ))
+            }
+        };
+
+        Ok(AuthorizationRequest {
+            tenant_id: client.tenant_id(),
+            client_id: client.client_id(),
+            redirect_uri: redirect_uri.to_string(),
+            scope: scope.join(" "),
+            state: query.state.clone(),
+            nonce: query.nonce.clone(),
+            code_challenge: code_challenge.to_string(),
+        })
+    }
+
+    /// Looks up the client named by `client_id`.
+    ///
+    /// A tenant ID is accepted as client as long as the tenant has not
+    /// registered any clients.
+    async fn resolve_client(&self, client_id: Option<&str>) -> Result<OAuthClient, AppError> {
+        let client_id = client_id.ok_or_else(|| {
+            AppError::OAuth("invalid_request", "client_id is required".to_string())
+        })?;
+        let unknown = || AppError::OAuth("invalid_client", "Unknown client_id".to_string());
+
+        let client = match self.client_repository.find_by_client_id(client_id).await? {
+            Some(client) => OAuthClient::Registered(Box::new(client)),
+            None => {
+                let tenant_id = Uuid::parse_str(client_id).map_err(|_| unknown())?;
+                if !self
+                    .client_repository
+                    .find_by_tenant(tenant_id)
+                    .await?
+                    .is_empty()
+                {
+                    return Err(unknown());
+                }
+                OAuthClient::Tenant(tenant_id)
+            }
+        };
+
+        if self
+            .tenant_repository
+            .find_by_id(client.tenant_id())
+            .await?
+            .is_none()
+        {
+            return Err(unknown());
+        }
+        Ok(client)
+    }
+
+    /// Resolves the client of a token or device authorization request and
+    /// checks its secret.
+    async fn authenticate_client(
+        &self,
+        client_id: Option<&str>,
+        client_secret: Option<&str>,
+    ) -> Result<OAuthClient, AppError> {
+        let client = self.resolve_client(client_id).await.map_err(|e| match e {
+            AppError::OAuth(_, description) => AppError::OAuth("invalid_client", description),
+            e => e,
+        })?;
+        client.authenticate(client_secret)?;
+        Ok(client)
+    }
+
+    /// Returns the pending or decided device authorization of `user_code`,
+    /// provided the user signed in to the client's tenant.
+    async fn device_authorization_for(
+        &self,
+        user_code: &str,
+        claims: &Claims,
+    ) -> Result<DeviceAuthorization, AppError> {
+        let authorization = self
+            .device_code_repository
+            .find_by_user_code(&user_code::normalize(user_code))
+            .await?
+            .ok_or_else(|| AppError::NotFound("Device code expired or not found".to_string()))?;
+
+        if claims.tenant_id != authorization.tenant_id.to_string() {
+            return Err(AppError::Forbidden(
+                "Signed in to a different tenant than the device".to_string(),
+            ));
+        }
+        Ok(authorization)
+    }
+
+    async fn describe_device_authorization(
+        &self,
+        authorization: &DeviceAuthorization,
+    ) -> Result<DeviceVerificationResponse, AppError> {
+        let client_name = self
+            .client_repository
+            .find_by_client_id(&authorization.client_id)
+            .await?
+            .map(|c| c.name)
+            .unwrap_or_else(|| authorization.client_id.clone());
+
+        Ok(DeviceVerificationResponse {
+            client_id: authorization.client_id.clone(),
+            client_name,
+            scope: authorization.scope.clone(),
+            status: match authorization.status {
+                DeviceAuthorizationStatus::Pending => "pending",
+                DeviceAuthorizationStatus::Approved { .. } => "approved",
+                DeviceAuthorizationStatus::Denied => "denied",
+            }
+            .to_string(),
+        })
+    }
+
+    async fn active_user(&self, user_id: Uuid) -> Result<Option<User>, AppError> {
+        Ok(self
+            .user_repository
+            .find_by_id(user_id)
+            .await?
+            .filter(|u| u.is_active()))
+    }
+
+    /// Appends query parameters to `base`, skipping empty values.
+    fn redirect_with(base: &str, params: &[(&str, &str)]) -> String {
+        match url::Url::parse(base) {
+            Ok(mut url) => {
+                url.query_pairs_mut()
+                    .extend_pairs(params.iter().filter(|(_, v)| !v.is_empty()));
+                url.to_string()
+            }
+            // Relative login page, e.g. "/login"
+            Err(_) => {
+                let query = url::form_urlencoded::Serializer::new(String::new())
+                    .extend_pairs(params.iter().filter(|(_, v)| !v.is_empty()))
+                    .finish();
+                let separator = if base.contains('?') { '&' } else { '?' };
+                format!("{}{}{}", base, separator, query)
+            }
+        }
+    }
+}
+
+/// Client of an OAuth request.
+enum OAuthClient {
+    /// Application registered by a tenant.
+    Registered(Box<Client>),
+    /// Tenant without registered clients, acting as a public client.
+    Tenant(Uuid),
+}
+
+impl OAuthClient {
+    fn tenant_id(&self) -> Uuid {
+        match self {
+            Self::Registered(client) => client.tenant_id,
+            Self::Tenant(tenant_id) => *tenant_id,
+        }
+    }
+
+    fn client_id(&self) -> String {
+        match self {
+            Self::Registered(client) => client.client_id.clone(),
+            Self::Tenant(tenant_id) => tenant_id.to_string(),
+        }
+    }
+
+    fn allows_grant_type(&self, grant_type: &str) -> bool {
+        match self {
+            Self::Registered(client) => client.allows_grant_type(grant_type),
+            Self::Tenant(_) => grant_type == GRANT_TYPE_AUTHORIZATION_CODE,
+        }
+    }
+
+    fn allows_scope(&self, scope: &str) -> bool {
+        match self {
+            Self::Registered(client) => client.scopes().iter().any(|s| s == scope),
+            Self::Tenant(_) => true,
+        }
+    }
+
+    fn validate_redirect_uri(&self, redirect_uri: &str) -> Result<(), AppError> {
+        let redirect_uri = Some(redirect_uri.to_string());
+        match self {
+            Self::Registered(client) => {
+                validate_redirect_uri_whitelist(&redirect_uri, &client.redirect_uris())
+            }
+            Self::Tenant(_) => {
+                validate_redirect_uri_origin(&redirect_uri, &Config::get().allowed_origins)
+            }
+        }
+    }
+
+    /// Confidential clients must present their secret; public clients rely on PKCE.
+    fn authenticate(&self, client_secret: Option<&str>) -> Result<(), AppError> {
+        match self {
+            Self::Registered(client) if !client.is_public() => {
+                if client_secret.is_some_and(|secret| client.verify_secret(secret)) {
+                    Ok(())
+                } else {
+                    Err(AppError::OAuth(
+                        "invalid_client",
+                        "Client authentication failed".to_string(),
+                    ))
+                }
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Scopes granted to a registered client asking for `scope`, all of its
+/// scopes when it asks for none.
+fn granted_scope(client: &Client, scope: Option<&str>) -> Result<String, AppError> {
+    let allowed = client.scopes();
+    let scopes: Vec<String> = match scope {
+        Some(scope) => {
+            let requested: Vec<String> = scope.split_whitespace().map(str::to_string).collect();
+            if let Some(denied) = requested.iter().find(|s| !allowed.contains(s)) {
+                return Err(AppError::OAuth(
+                    "invalid_scope",
+                    format!("Client may not request the {} scope", denied),
+                ));
+            }
+            requested
+        }
+        None => allowed,
+    };
+    Ok(scopes.join(" "))
+}
+
+/// Issuer identifier of the provider, the service `ENDPOINT`.
+pub fn issuer() -> String {
+    Config::get().endpoint.trim_end_matches('/').to_string()
+}
+
+/// PKCE S256 transform: BASE64URL(SHA256(code_verifier)).
+fn pkce_challenge(verifier: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes()))
+}
+
+/// PKCE verifiers and challenges are 43 to 128 unreserved characters (RFC 7636).
+fn is_valid_pkce_value(value: &str) -> bool {
+    (43..=128).contains(&value.len())
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '_' | '~'))
+}
+
+fn invalid_grant(description: &str) -> AppError {
+    AppError::OAuth("invalid_grant", description.to_string())
+}
@@ -0,0 +1,1088 @@
+#[cfg(test)]
+mod tests {
+    use crate::domains::auth::dtos::oidc_dto::{
+        AuthorizeQuery, DeviceAuthorizationRequest, TokenRequest,
+    };
+    use crate::domains::auth::repositories::authorization_code_repository::{
+        AuthorizationCode, AuthorizationCodeRepositoryTrait, AuthorizationRequest,
+    };
+    use crate::domains::auth::repositories::device_code_repository::{
+        DeviceAuthorization, DeviceCodeRepositoryTrait,
+    };
+    use crate::domains::auth::repositories::token_denylist_repository::TokenDenylistRepositoryTrait;
+    use crate::domains::auth::usecases::oidc_usecase::OidcUseCase;
+    use crate::domains::common::errors::AppError;
+    use crate::domains::common::utils::config::Config;
+    use crate::domains::common::utils::jwt::{Claims, IdTokenClaims, JwtService};
+    use crate::domains::common::utils::request_helper;
+    use crate::domains::tenant::entities::client::Model as Client;
+    use crate::domains::tenant::entities::tenant::Model as Tenant;
+    use crate::domains::tenant::repositories::client_repository::ClientRepositoryTrait;
+    use crate::domains::tenant::repositories::tenant_repository::TenantRepositoryTrait;
+    use crate::domains::user::entities::user::Model as User;
+    use crate::domains::user::repositories::user_repository::UserRepositoryTrait;
+
+    use async_trait::async_trait;
+    use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+    use base64::Engine;
+    use chrono::{DateTime, Utc};
+    use mockall::mock;
+    use sha2::{Digest, Sha256};
+    use std::collections::{HashMap, HashSet};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+    use uuid::Uuid;
+
+    mock! {
+        pub UserRepository {}
+        #[async_trait]
+        impl UserRepositoryTrait for UserRepository {
+            async fn create(&self, user: crate::domains::user::dtos::user_dto::CreateUserRequest) -> Result<User, AppError>;
+            async fn find_by_id(&self, id: Uuid) -> Result<Option<User>, AppError>;
+            async fn find_by_username(&self, username: &str) -> Result<Option<User>, AppError>;
+            async fn find_all(&self) -> Result<Vec<User>, AppError>;
+            async fn update(&self, id: Uuid, user: crate::domains::user::dtos::user_dto::UpdateUserRequest) -> Result<User, AppError>;
+            async fn delete(&self, id: Uuid) -> Result<(), AppError>;
+            async fn find_by_email_with_deleted(&self, email: &str) -> Result<Option<User>, AppError>;
+            async fn find_by_username_with_deleted(&self, username: &str) -> Result<Option<User>, AppError>;
+            async fn restore(&self, id: Uuid, req: crate::domains::user::dtos::user_dto::CreateUserRequest) -> Result<User, AppError>;
+            async fn mark_email_verified(&self, id: Uuid) -> Result<User, AppError>;
+            async fn set_login_failures(&self, id: Uuid, failed_login_attempts: i32, locked_until: Option<DateTime<Utc>>) -> Result<(), AppError>;
+            async fn increment_login_failures(&self, id: Uuid) -> Result<i32, AppError>;
+            async fn lock_until(&self, id: Uuid, locked_until: DateTime<Utc>) -> Result<(), AppError>;
+            async fn set_status(&self, id: Uuid, status: String, reason: Option<String>, expires_at: Option<DateTime<Utc>>) -> Result<User, AppError>;
+            async fn purge_deleted_before(&self, cutoff: DateTime<Utc>) -> Result<u64, AppError>;
+        }
+    }
+
+    mock! {
+        pub TenantRepository {}
+        #[async_trait]
+        impl TenantRepositoryTrait for TenantRepository {
+            async fn create(&self, tenant: crate::domains::tenant::dtos::tenant_dto::CreateTenantRequest) -> Result<Tenant, AppError>;
+            async fn find_by_id(&self, id: Uuid) -> Result<Option<Tenant>, AppError>;
+            async fn find_by_name(&self, name: &str) -> Result<Option<Tenant>, AppError>;
+            async fn find_by_name_with_deleted(&self, name: &str) -> Result<Option<Tenant>, AppError>;
+            async fn find_all(&self) -> Result<Vec<Tenant>, AppError>;
+            async fn update(&self, id: Uuid, tenant: crate::domains::tenant::dtos::tenant_dto::UpdateTenantRequest) -> Result<Tenant, AppError>;
+            async fn delete(&self, id: Uuid) -> Result<(), AppError>;
+            async fn restore(&self, id: Uuid) -> Result<(), AppError>;
+            async fn purge_deleted_before(&self, cutoff: DateTime<Utc>) -> Result<u64, AppError>;
+        }
+    }
+
+    mock! {
+        pub AuthorizationCodeRepository {}
+        #[async_trait]
+        impl AuthorizationCodeRepositoryTrait for AuthorizationCodeRepository {
+            async fn save_request(&self, id: &str, request: AuthorizationRequest, ttl: Duration) -> Result<(), AppError>;
+            async fn take_request(&self, id: &str) -> Result<Option<AuthorizationRequest>, AppError>;
+            async fn save_code(&self, code: &str, grant: AuthorizationCode, ttl: Duration) -> Result<(), AppError>;
+            async fn take_code(&self, code: &str) -> Result<Option<AuthorizationCode>, AppError>;
+        }
+    }
+
+    mock! {
+        pub DeviceCodeRepository {}
+        #[async_trait]
+        impl DeviceCodeRepositoryTrait for DeviceCodeRepository {
+            async fn save(&self, authorization: &DeviceAuthorization) -> Result<(), AppError>;
+            async fn find_by_device_code(&self, device_code: &str) -> Result<Option<DeviceAuthorization>, AppError>;
+            async fn find_by_user_code(&self, user_code: &str) -> Result<Option<DeviceAuthorization>, AppError>;
+            async fn delete(&self, authorization: &DeviceAuthorization) -> Result<(), AppError>;
+        }
+    }
+
+    mock! {
+        pub TokenDenylistRepository {}
+        #[async_trait]
+        impl TokenDenylistRepositoryTrait for TokenDenylistRepository {
+            async fn deny(&self, jti: &str, ttl: Duration) -> Result<(), AppError>;
+            async fn is_denied(&self, jti: &str) -> Result<bool, AppError>;
+            async fn deny_subject(&self, subject: &str, ttl: Duration) -> Result<(), AppError>;
+            async fn subject_denied_at(&self, subject: &str) -> Result<Option<i64>, AppError>;
+        }
+    }
+
+    mock! {
+        pub ClientRepository {}
+        #[async_trait]
+        impl ClientRepositoryTrait for ClientRepository {
+            async fn create(&self, client: Client) -> Result<Client, AppError>;
+            async fn find_by_client_id(&self, client_id: &str) -> Result<Option<Client>, AppError>;
+            async fn find_by_tenant(&self, tenant_id: Uuid) -> Result<Vec<Client>, AppError>;
+            async fn update(&self, client: Client) -> Result<Client, AppError>;
+            async fn delete(&self, id: Uuid) -> Result<(), AppError>;
+        }
+    }
+
+    const REDIRECT_URI: &str = "http://localhost:3000/callback";
+    const VERIFIER: &str = "dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk";
+    const CLIENT_SECRET: &str = "registered-client-secret";
+
+    struct OidcTestContext {
+        usecase: OidcUseCase,
+        user: User,
+        tenant_id: Uuid,
+        /// Stored device authorizations, keyed by device code hash.
+        devices: Arc<Mutex<HashMap<String, DeviceAuthorization>>>,
+        /// Revoked access token IDs.
+        denied: Arc<Mutex<HashSet<String>>>,
+    }
+
+    fn challenge(verifier: &str) -> String {
+        URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes()))
+    }
+
+    fn authorize_query(tenant_id: Uuid) -> AuthorizeQuery {
+        AuthorizeQuery {
+            response_type: Some("code".to_string()),
+            client_id: Some(tenant_id.to_string()),
+            redirect_uri: Some(REDIRECT_URI.to_string()),
+            scope: Some("openid email offline_access".to_string()),
+            state: Some("xyz".to_string()),
+            nonce: Some("n-0S6_WzA2Mj".to_string()),
+            code_challenge: Some(challenge(VERIFIER)),
+            code_challenge_method: Some("S256".to_string()),
+        }
+    }
+
+    fn access_claims(user_id: Uuid, tenant_id: Uuid, scope: Option<&str>) -> Claims {
+        Claims {
+            sub: user_id.to_string(),
+            tenant_id: tenant_id.to_string(),
+            role: "user".to_string(),
+            exp: Utc::now().timestamp() + 900,
+            iat: Utc::now().timestamp(),
+            iat_ms: None,
+            token_type: "access".to_string(),
+            jti: None,
+            scope: scope.map(str::to_string),
+            aud: None,
+            act: None,
+            impersonator: None,
+            auth_time: None,
+            amr: None,
+            acr: None,
+        }
+    }
+
+    fn query_param(url: &str, name: &str) -> Option<String> {
+        url::Url::parse(url)
+            .unwrap()
+            .query_pairs()
+            .find(|(k, _)| k == name)
+            .map(|(_, v)| v.into_owned())
+    }
+
+    /// Confidential client registered by the tenant for `REDIRECT_URI`.
+    fn registered_client(tenant_id: Uuid) -> Client {
+        Client {
+            id: Uuid::new_v4(),
+            tenant_id,
+            client_id: "registered-client".to_string(),
+            client_secret_hash: Some(request_helper::hash_token(CLIENT_SECRET)),
+            name: "Registered App".to_string(),
+            redirect_uris: serde_json::json!([REDIRECT_URI]),
+            grant_types: serde_json::json!(["authorization_code"]),
+            scopes: serde_json::json!(["openid", "email"]),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    /// Confidential machine client allowed to use the client credentials grant.
+    fn machine_client(tenant_id: Uuid) -> Client {
+        Client {
+            client_id: "machine-client".to_string(),
+            name: "Reporting Service".to_string(),
+            redirect_uris: serde_json::json!([]),
+            grant_types: serde_json::json!(["client_credentials"]),
+            scopes: serde_json::json!(["reports:read", "reports:write"]),
+            ..registered_client(tenant_id)
+        }
+    }
+
+    fn client_credentials_request(scope: Option<&str>) -> TokenRequest {
+        TokenRequest {
+            grant_type: "client_credentials".to_string(),
+            code: None,
+            redirect_uri: None,
+            client_id: Some("machine-client".to_string()),
+            client_secret: Some(CLIENT_SECRET.to_string()),
+            code_verifier: None,
+            scope: scope.map(str::to_string),
+            device_code: None,
+            subject_token: None,
+            subject_token_type: None,
+            audience: None,
+            requested_token_type: None,
+        }
+    }
+
+    /// Public CLI client allowed to use the device authorization grant.
+    fn device_client(tenant_id: Uuid) -> Client {
+        Client {
+            client_id: "device-client".to_string(),
+            client_secret_hash: None,
+            name: "Fleet CLI".to_string(),
+            redirect_uris: serde_json::json!([]),
+            grant_types: serde_json::json!(["urn:ietf:params:oauth:grant-type:device_code"]),
+            scopes: serde_json::json!(["openid", "profile", "mqtt"]),
+            ..registered_client(tenant_id)
+        }
+    }
+
+    fn device_token_request(device_code: &str) -> TokenRequest {
+        TokenRequest {
+            grant_type: "urn:ietf:params:oauth:grant-type:device_code".to_string(),
+            code: None,
+            redirect_uri: None,
+            client_id: Some("device-client".to_string()),
+            client_secret: None,
+            code_verifier: None,
+            scope: None,
+            device_code: Some(device_code.to_string()),
+            subject_token: None,
+            subject_token_type: None,
+            audience: None,
+            requested_token_type: None,
+        }
+    }
+
+    /// Confidential API gateway allowed to exchange user tokens for backend
+    /// services.
+    fn gateway_client(tenant_id: Uuid) -> Client {
+        Client {
+            client_id: "gateway-client".to_string(),
+            name: "API Gateway".to_string(),
+            redirect_uris: serde_json::json!([]),
+            grant_types: serde_json::json!(["urn:ietf:params:oauth:grant-type:token-exchange"]),
+            scopes: serde_json::json!(["orders:read", "orders:write", "mqtt"]),
+            ..registered_client(tenant_id)
+        }
+    }
+
+    fn token_exchange_request(subject_token: &str, scope: Option<&str>) -> TokenRequest {
+        TokenRequest {
+            grant_type: "urn:ietf:params:oauth:grant-type:token-exchange".to_string(),
+            code: None,
+            redirect_uri: None,
+            client_id: Some("gateway-client".to_string()),
+            client_secret: Some(CLIENT_SECRET.to_string()),
+            code_verifier: None,
+            scope: scope.map(str::to_string),
+            device_code: None,
+            subject_token: Some(subject_token.to_string()),
+            subject_token_type: Some("urn:ietf:params:oauth:token-type:access_token".to_string()),
+            audience: Some("machine-client".to_string()),
+            requested_token_type: None,
+        }
+    }
+
+    /// Builds the use case over an in-memory request and code store, for a
+    /// tenant without registered clients.
+    fn oidc_test_setup() -> OidcTestContext {
+        oidc_test_setup_with_clients(false)
+    }
+
+    /// Same as `oidc_test_setup`, with the tenant optionally having
+    /// registered `registered_client`, `machine_client`, `device_client` and
+    /// `gateway_client`.
+    fn oidc_test_setup_with_clients(with_client: bool) -> OidcTestContext {
+        Config::init_for_test();
+
+        let user = User {
+            id: Uuid::new_v4(),
+            username: "oidcuser".to_string(),
+            email: "oidc@example.com".to_string(),
+            password_hash: "unused".to_string(),
+            email_verified_at: Some(Utc::now()),
+            failed_login_attempts: 0,
+            locked_until: None,
+            status: "active".to_string(),
+            status_reason: None,
+            status_expires_at: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+        };
+        let tenant_id = Uuid::new_v4();
+
+        let mut user_repo = MockUserRepository::new();
+        let user_clone = user.clone();
+        user_repo
+            .expect_find_by_id()
+            .returning(move |id| Ok((id == user_clone.id).then(|| user_clone.clone())));
+
+        let mut tenant_repo = MockTenantRepository::new();
+        tenant_repo.expect_find_by_id().returning(move |id| {
+            Ok((id == tenant_id).then(|| Tenant {
+                id: tenant_id,
+                name: "OIDC Tenant".to_string(),
+                description: None,
+                api_key: None,
+                require_email_verification: false,
+                passwordless_login: false,
+                rate_limits: None,
+                deleted_at: None,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+            }))
+        });
+
+        let requests: Arc<Mutex<HashMap<String, AuthorizationRequest>>> = Arc::default();
+        let codes: Arc<Mutex<HashMap<String, AuthorizationCode>>> = Arc::default();
+        let mut code_repo = MockAuthorizationCodeRepository::new();
+        let store = requests.clone();
+        code_repo
+            .expect_save_request()
+            .returning(move |id, request, _| {
+                store.lock().unwrap().insert(id.to_string(), request);
+                Ok(())
+            });
+        let store = requests;
+        code_repo
+            .expect_take_request()
+            .returning(move |id| Ok(store.lock().unwrap().remove(id)));
+        let store = codes.clone();
+        code_repo
+            .expect_save_code()
+            .returning(move |code, grant, _| {
+                store.lock().unwrap().insert(code.to_string(), grant);
+                Ok(())
+            });
+        let store = codes;
+        code_repo
+            .expect_take_code()
+            .returning(move |code| Ok(store.lock().unwrap().remove(code)));
+
+        let clients: Vec<Client> = if with_client {
+            vec![
+                registered_client(tenant_id),
+                machine_client(tenant_id),
+                device_client(tenant_id),
+                gateway_client(tenant_id),
+            ]
+        } else {
+            Vec::new()
+        };
+        let mut client_repo = MockClientRepository::new();
+        let registered = clients.clone();
+        client_repo
+            .expect_find_by_client_id()
+            .returning(move |id| Ok(registered.iter().find(|c| c.client_id == id).cloned()));
+        client_repo.expect_find_by_tenant().returning(move |id| {
+            Ok(clients
+                .iter()
+                .filter(|c| c.tenant_id == id)
+                .cloned()
+                .collect())
+        });
+
+        let devices: Arc<Mutex<HashMap<String, DeviceAuthorization>>> = Arc::default();
+        let mut device_repo = MockDeviceCodeRepository::new();
+        let store = devices.clone();
+        device_repo.expect_save().returning(move |authorization| {
+            store.lock().unwrap().insert(
+                authorization.device_code_hash.clone(),
+                authorization.clone(),
+            );
+            Ok(())
+        });
+        let store = devices.clone();
+        device_repo
+            .expect_find_by_device_code()
+            .returning(move |device_code| {
+                let hash = request_helper::hash_token(device_code);
+                Ok(store.lock().unwrap().get(&hash).cloned())
+            });
+        let store = devices.clone();
+        device_repo
+            .expect_find_by_user_code()
+            .returning(move |user_code| {
+                Ok(store
+                    .lock()
+                    .unwrap()
+                    .values()
+                    .find(|a| a.user_code == user_code)
+                    .cloned())
+            });
+        let store = devices.clone();
+        device_repo.expect_delete().returning(move |authorization| {
+            store
+                .lock()
+                .unwrap()
+                .remove(&authorization.device_code_hash);
+            Ok(())
+        });
+
+        let denied: Arc<Mutex<HashSet<String>>> = Arc::default();
+        let mut denylist_repo = MockTokenDenylistRepository::new();
+        let store = denied.clone();
+        denylist_repo
+            .expect_is_denied()
+            .returning(move |jti| Ok(store.lock().unwrap().contains(jti)));
+        denylist_repo
+            .expect_subject_denied_at()
+            .returning(|_| Ok(None));
+
+        OidcTestContext {
+            usecase: OidcUseCase::new(
+                Arc::new(code_repo),
+                Arc::new(device_repo),
+                Arc::new(client_repo),
+                Arc::new(tenant_repo),
+                Arc::new(user_repo),
+                Arc::new(denylist_repo),
+            ),
+            user,
+            tenant_id,
+            devices,
+            denied,
+        }
+    }
+
+    /// Runs /authorize and the login page completion, returning the code.
+    async fn issue_code(ctx: &OidcTestContext) -> String {
+        let location = ctx
+            .usecase
+            .authorize(authorize_query(ctx.tenant_id))
+            .await
+            .unwrap();
+        let request_id = query_param(&location, "authorization_request").unwrap();
+
+        let redirect = ctx
+            .usecase
+            .complete_authorization(
+                &request_id,
+                &access_claims(ctx.user.id, ctx.tenant_id, None),
+            )
+            .await
+            .unwrap();
+        assert!(redirect.starts_with(REDIRECT_URI));
+        assert_eq!(query_param(&redirect, "state").as_deref(), Some("xyz"));
+
+        query_param(&redirect, "code").unwrap()
+    }
+
+    fn token_request(ctx: &OidcTestContext, code: &str, verifier: &str) -> TokenRequest {
+        TokenRequest {
+            grant_type: "authorization_code".to_string(),
+            code: Some(code.to_string()),
+            redirect_uri: Some(REDIRECT_URI.to_string()),
+            client_id: Some(ctx.tenant_id.to_string()),
+            client_secret: None,
+            code_verifier: Some(verifier.to_string()),
+            scope: None,
+            device_code: None,
+            subject_token: None,
+            subject_token_type: None,
+            audience: None,
+            requested_token_type: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_authorize_redirects_to_login_page() {
+        let ctx = oidc_test_setup();
+
+        let location = ctx
+            .usecase
+            .authorize(authorize_query(ctx.tenant_id))
+            .await
+            .unwrap();
+
+        assert!(location.starts_with(&Config::get().oidc_login_url));
+        assert!(query_param(&location, "authorization_request").is_some());
+    }
+
+    #[tokio::test]
+    async fn test_authorize_without_pkce_redirects_with_error() {
+        let ctx = oidc_test_setup();
+        let mut query = authorize_query(ctx.tenant_id);
+        query.code_challenge_method = Some("plain".to_string());
+
+        let location = ctx.usecase.authorize(query).await.unwrap();
+
+        assert!(location.starts_with(REDIRECT_URI));
+        assert_eq!(
+            query_param(&location, "error").as_deref(),
+            Some("invalid_request")
+        );
+        assert_eq!(query_param(&location, "state").as_deref(), Some("xyz"));
+    }
+
+    #[tokio::test]
+    async fn test_authorize_unknown_client_is_rejected() {
+        let ctx = oidc_test_setup();
+
+        let result = ctx.usecase.authorize(authorize_query(Uuid::new_v4())).await;
+
+        assert!(matches!(result, Err(AppError::OAuth("invalid_client", _))));
+    }
+
+    #[tokio::test]
+    async fn test_code_exchange_issues_id_token_with_nonce() {
+        let ctx = oidc_test_setup();
+        let code = issue_code(&ctx).await;
+
+        let response = ctx
+            .usecase
+            .exchange_token(token_request(&ctx, &code, VERIFIER))
+            .await
+            .unwrap();
+
+        // Unsupported scopes are dropped
+        assert_eq!(response.scope, "openid email");
+        assert_eq!(response.token_type, "Bearer");
+
+        let access = JwtService::new()
+            .validate_token(&response.access_token)
+            .unwrap();
+        assert_eq!(access.sub, ctx.user.id.to_string());
+        assert!(access.has_scope("email"));
+
+        let key = JwtService::key_ring().active();
+        let mut validation = jsonwebtoken::Validation::new(key.algorithm());
+        validation.set_audience(&[ctx.tenant_id.to_string()]);
+        let id_token = jsonwebtoken::decode::<IdTokenClaims>(
+            response.id_token.as_ref().unwrap(),
+            key.decoding_key(),
+            &validation,
+        )
+        .unwrap()
+        .claims;
+        assert_eq!(id_token.nonce.as_deref(), Some("n-0S6_WzA2Mj"));
+        assert_eq!(id_token.iss, Config::get().endpoint.trim_end_matches('/'));
+        assert_eq!(id_token.email.as_deref(), Some("oidc@example.com"));
+        assert_eq!(id_token.preferred_username, None);
+    }
+
+    #[tokio::test]
+    async fn test_code_is_single_use() {
+        let ctx = oidc_test_setup();
+        let code = issue_code(&ctx).await;
+
+        ctx.usecase
+            .exchange_token(token_request(&ctx, &code, VERIFIER))
+            .await
+            .unwrap();
+        let replay = ctx
+            .usecase
+            .exchange_token(token_request(&ctx, &code, VERIFIER))
+            .await;
+
+        assert!(matches!(replay, Err(AppError::OAuth("invalid_grant", _))));
+    }
+
+    #[tokio::test]
+    async fn test_wrong_code_verifier_is_rejected() {
+        let ctx = oidc_test_setup();
+        let code = issue_code(&ctx).await;
+        let wrong = "x".repeat(43);
+
+        let result = ctx
+            .usecase
+            .exchange_token(token_request(&ctx, &code, &wrong))
+            .await;
+
+        assert!(matches!(result, Err(AppError::OAuth("invalid_grant", _))));
+    }
+
+    #[tokio::test]
+    async fn test_complete_authorization_for_other_tenant_is_forbidden() {
+        let ctx = oidc_test_setup();
+        let location = ctx
+            .usecase
+            .authorize(authorize_query(ctx.tenant_id))
+            .await
+            .unwrap();
+        let request_id = query_param(&location, "authorization_request").unwrap();
+
+        let result = ctx
+            .usecase
+            .complete_authorization(
+                &request_id,
+                &access_claims(ctx.user.id, Uuid::new_v4(), None),
+            )
+            .await;
+
+        assert!(matches!(result, Err(AppError::Forbidden(_))));
+    }
+
+    #[tokio::test]
+    async fn test_userinfo_requires_openid_scope() {
+        let ctx = oidc_test_setup();
+
+        let result = ctx
+            .usecase
+            .userinfo(&access_claims(ctx.user.id, ctx.tenant_id, None))
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(AppError::OAuth("insufficient_scope", _))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_userinfo_returns_scoped_claims() {
+        let ctx = oidc_test_setup();
+
+        let userinfo = ctx
+            .usecase
+            .userinfo(&access_claims(
+                ctx.user.id,
+                ctx.tenant_id,
+                Some("openid profile"),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(userinfo.sub, ctx.user.id.to_string());
+        assert_eq!(userinfo.preferred_username.as_deref(), Some("oidcuser"));
+        assert_eq!(userinfo.email, None);
+    }
+
+    #[tokio::test]
+    async fn test_discovery_is_derived_from_endpoint() {
+        let ctx = oidc_test_setup();
+        let issuer = Config::get().endpoint.trim_end_matches('/').to_string();
+
+        let metadata = ctx.usecase.discovery();
+
+        assert_eq!(metadata.issuer, issuer);
+        assert_eq!(metadata.token_endpoint, format!("{}/oauth/token", issuer));
+        assert_eq!(metadata.login_endpoint, format!("{}/auth/login", issuer));
+        assert_eq!(
+            metadata.end_session_endpoint,
+            format!("{}/auth/sso/logout", issuer)
+        );
+        assert!(metadata.scopes_supported.contains(&"openid".to_string()));
+        assert_eq!(metadata.code_challenge_methods_supported, vec!["S256"]);
+        assert!(!metadata.id_token_signing_alg_values_supported.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_tenant_with_registered_clients_is_not_a_client() {
+        let ctx = oidc_test_setup_with_clients(true);
+
+        let result = ctx.usecase.authorize(authorize_query(ctx.tenant_id)).await;
+
+        assert!(matches!(result, Err(AppError::OAuth("invalid_client", _))));
+    }
+
+    #[tokio::test]
+    async fn test_registered_client_rejects_unregistered_redirect_uri() {
+        let ctx = oidc_test_setup_with_clients(true);
+        let mut query = authorize_query(ctx.tenant_id);
+        query.client_id = Some("registered-client".to_string());
+        query.redirect_uri = Some("http://localhost:3000/other".to_string());
+
+        let result = ctx.usecase.authorize(query).await;
+
+        assert!(matches!(result, Err(AppError::Forbidden(_))));
+    }
+
+    #[tokio::test]
+    async fn test_confidential_client_must_authenticate() {
+        let ctx = oidc_test_setup_with_clients(true);
+        let mut query = authorize_query(ctx.tenant_id);
+        query.client_id = Some("registered-client".to_string());
+        query.scope = Some("openid profile email".to_string());
+        let location = ctx.usecase.authorize(query).await.unwrap();
+        let request_id = query_param(&location, "authorization_request").unwrap();
+        let redirect = ctx
+            .usecase
+            .complete_authorization(
+                &request_id,
+                &access_claims(ctx.user.id, ctx.tenant_id, None),
+            )
+            .await
+            .unwrap();
+        let code = query_param(&redirect, "code").unwrap();
+
+        let mut request = token_request(&ctx, &code, VERIFIER);
+        request.client_id = Some("registered-client".to_string());
+        request.client_secret = Some("wrong-secret".to_string());
+        let result = ctx.usecase.exchange_token(request.clone()).await;
+        assert!(matches!(result, Err(AppError::OAuth("invalid_client", _))));
+
+        request.client_secret = Some(CLIENT_SECRET.to_string());
+        let response = ctx.usecase.exchange_token(request).await.unwrap();
+        // profile is not among the client's allowed scopes
+        assert_eq!(response.scope, "openid email");
+    }
+
+    #[tokio::test]
+    async fn test_client_credentials_issues_client_token() {
+        let ctx = oidc_test_setup_with_clients(true);
+
+        let response = ctx
+            .usecase
+            .exchange_token(client_credentials_request(None))
+            .await
+            .unwrap();
+
+        assert!(response.id_token.is_none());
+        assert_eq!(response.scope, "reports:read reports:write");
+        let claims = JwtService::new()
+            .validate_token(&response.access_token)
+            .unwrap();
+        assert!(claims.is_client());
+        assert_eq!(claims.sub, "machine-client");
+        assert_eq!(claims.tenant_id, ctx.tenant_id.to_string());
+        assert!(claims.has_scope("reports:read"));
+    }
+
+    #[tokio::test]
+    async fn test_client_credentials_rejects_unregistered_scope() {
+        let ctx = oidc_test_setup_with_clients(true);
+
+        let result = ctx
+            .usecase
+            .exchange_token(client_credentials_request(Some("reports:read admin")))
+            .await;
+        assert!(matches!(result, Err(AppError::OAuth("invalid_scope", _))));
+
+        let response = ctx
+            .usecase
+            .exchange_token(client_credentials_request(Some("reports:read")))
+            .await
+            .unwrap();
+        assert_eq!(response.scope, "reports:read");
+    }
+
+    #[tokio::test]
+    async fn test_client_credentials_requires_grant_and_secret() {
+        let ctx = oidc_test_setup_with_clients(true);
+
+        let mut request = client_credentials_request(None);
+        request.client_secret = None;
+        let result = ctx.usecase.exchange_token(request).await;
+        assert!(matches!(result, Err(AppError::OAuth("invalid_client", _))));
+
+        // registered-client is only allowed the authorization code grant
+        let mut request = client_credentials_request(None);
+        request.client_id = Some("registered-client".to_string());
+        let result = ctx.usecase.exchange_token(request).await;
+        assert!(matches!(
+            result,
+            Err(AppError::OAuth("unauthorized_client", _))
+        ));
+    }
+
+    /// Starts a device authorization for `device_client`, returning the
+    /// device code and the user code shown to the user.
+    async fn start_device_flow(ctx: &OidcTestContext) -> (String, String) {
+        let response = ctx
+            .usecase
+            .device_authorization(DeviceAuthorizationRequest {
+                client_id: Some("device-client".to_string()),
+                client_secret: None,
+                scope: Some("openid profile".to_string()),
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(
+            response.verification_uri,
+            Config::get().oidc_device_verification_url
+        );
+        assert_eq!(
+            query_param(&response.verification_uri_complete, "user_code"),
+            Some(response.user_code.clone())
+        );
+        assert_eq!(response.interval, Config::get().oidc_device_poll_interval);
+
+        (response.device_code, response.user_code)
+    }
+
+    /// Lets the device poll again without waiting for its interval.
+    fn rewind_last_poll(ctx: &OidcTestContext) {
+        for authorization in ctx.devices.lock().unwrap().values_mut() {
+            authorization.last_polled_at = authorization
+                .last_polled_at
+                .map(|t| t - authorization.interval as i64);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_device_flow_issues_tokens_once_approved() {
+        let ctx = oidc_test_setup_with_clients(true);
+        let (device_code, user_code) = start_device_flow(&ctx).await;
+
+        let result = ctx
+            .usecase
+            .exchange_token(device_token_request(&device_code))
+            .await;
+        assert!(matches!(
+            result,
+            Err(AppError::OAuth("authorization_pending", _))
+        ));
+
+        // Users may type the code in lowercase and without the dash
+        let typed = user_code.replace('-', "").to_lowercase();
+        let claims = access_claims(ctx.user.id, ctx.tenant_id, None);
+        let device = ctx
+            .usecase
+            .find_device_authorization(&typed, &claims)
+            .await
+            .unwrap();
+        assert_eq!(device.client_name, "Fleet CLI");
+        assert_eq!(device.status, "pending");
+        ctx.usecase
+            .decide_device_authorization(&typed, true, &claims)
+            .await
+            .unwrap();
+
+        rewind_last_poll(&ctx);
+        let response = ctx
+            .usecase
+            .exchange_token(device_token_request(&device_code))
+            .await
+            .unwrap();
+        assert_eq!(response.scope, "openid profile");
+        assert!(response.id_token.is_some());
+        let access = JwtService::new()
+            .validate_token(&response.access_token)
+            .unwrap();
+        assert_eq!(access.sub, ctx.user.id.to_string());
+        assert_eq!(access.tenant_id, ctx.tenant_id.to_string());
+        // Limited to the granted scopes, not a first-party API token
+        assert!(access.is_scoped());
+        assert_eq!(access.scope.as_deref(), Some("openid profile"));
+
+        let result = ctx
+            .usecase
+            .exchange_token(device_token_request(&device_code))
+            .await;
+        assert!(matches!(result, Err(AppError::OAuth("expired_token", _))));
+    }
+
+    #[tokio::test]
+    async fn test_device_polling_too_fast_slows_down() {
+        let ctx = oidc_test_setup_with_clients(true);
+        let (device_code, _) = start_device_flow(&ctx).await;
+
+        let result = ctx
+            .usecase
+            .exchange_token(device_token_request(&device_code))
+            .await;
+        assert!(matches!(
+            result,
+            Err(AppError::OAuth("authorization_pending", _))
+        ));
+
+        let result = ctx
+            .usecase
+            .exchange_token(device_token_request(&device_code))
+            .await;
+        assert!(matches!(result, Err(AppError::OAuth("slow_down", _))));
+        let interval = ctx
+            .devices
+            .lock()
+            .unwrap()
+            .values()
+            .next()
+            .unwrap()
+            .interval;
+        assert_eq!(interval, Config::get().oidc_device_poll_interval + 5);
+    }
+
+    #[tokio::test]
+    async fn test_device_denied_by_user() {
+        let ctx = oidc_test_setup_with_clients(true);
+        let (device_code, user_code) = start_device_flow(&ctx).await;
+        let claims = access_claims(ctx.user.id, ctx.tenant_id, None);
+
+        ctx.usecase
+            .decide_device_authorization(&user_code, false, &claims)
+            .await
+            .unwrap();
+        let result = ctx
+            .usecase
+            .decide_device_authorization(&user_code, true, &claims)
+            .await;
+        assert!(matches!(result, Err(AppError::Conflict(_))));
+
+        let result = ctx
+            .usecase
+            .exchange_token(device_token_request(&device_code))
+            .await;
+        assert!(matches!(result, Err(AppError::OAuth("access_denied", _))));
+    }
+
+    #[tokio::test]
+    async fn test_device_approval_is_bound_to_client_tenant() {
+        let ctx = oidc_test_setup_with_clients(true);
+        let (_, user_code) = start_device_flow(&ctx).await;
+
+        let claims = access_claims(ctx.user.id, Uuid::new_v4(), None);
+        let result = ctx
+            .usecase
+            .decide_device_authorization(&user_code, true, &claims)
+            .await;
+
+        assert!(matches!(result, Err(AppError::Forbidden(_))));
+    }
+
+    #[tokio::test]
+    async fn test_device_authorization_requires_grant() {
+        let ctx = oidc_test_setup_with_clients(true);
+
+        let result = ctx
+            .usecase
+            .device_authorization(DeviceAuthorizationRequest {
+                client_id: Some("registered-client".to_string()),
+                client_secret: Some(CLIENT_SECRET.to_string()),
+                scope: None,
+            })
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(AppError::OAuth("unauthorized_client", _))
+        ));
+    }
+
+    fn subject_token(ctx: &OidcTestContext, scope: Option<&str>) -> String {
+        JwtService::new()
+            .generate_scoped_access_token(
+                ctx.user.id,
+                ctx.tenant_id,
+                "user".to_string(),
+                scope.map(str::to_string),
+            )
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_token_exchange_issues_narrower_token_with_actor() {
+        let ctx = oidc_test_setup_with_clients(true);
+        let subject = subject_token(&ctx, Some("openid orders:read mqtt"));
+
+        let response = ctx
+            .usecase
+            .exchange_token(token_exchange_request(&subject, Some("orders:read")))
+            .await
+            .unwrap();
+
+        assert_eq!(response.scope, "orders:read");
+        assert!(response.id_token.is_none());
+        assert_eq!(
+            response.issued_token_type.as_deref(),
+            Some("urn:ietf:params:oauth:token-type:access_token")
+        );
+        let claims = JwtService::new()
+            .validate_token(&response.access_token)
+            .unwrap();
+        assert_eq!(claims.sub, ctx.user.id.to_string());
+        assert_eq!(claims.tenant_id, ctx.tenant_id.to_string());
+        assert_eq!(claims.aud.as_deref(), Some("machine-client"));
+        assert_eq!(claims.act.unwrap().sub, "gateway-client");
+    }
+
+    #[tokio::test]
+    async fn test_token_exchange_keeps_only_scopes_of_subject() {
+        let ctx = oidc_test_setup_with_clients(true);
+        let subject = subject_token(&ctx, Some("openid orders:read mqtt"));
+
+        let response = ctx
+            .usecase
+            .exchange_token(token_exchange_request(&subject, None))
+            .await
+            .unwrap();
+        assert_eq!(response.scope, "orders:read mqtt");
+
+        let result = ctx
+            .usecase
+            .exchange_token(token_exchange_request(&subject, Some("orders:write")))
+            .await;
+        assert!(matches!(result, Err(AppError::OAuth("invalid_scope", _))));
+    }
+
+    #[tokio::test]
+    async fn test_token_exchange_chains_actors() {
+        let ctx = oidc_test_setup_with_clients(true);
+        let subject = subject_token(&ctx, None);
+        let first = ctx
+            .usecase
+            .exchange_token(token_exchange_request(&subject, None))
+            .await
+            .unwrap();
+
+        let second = ctx
+            .usecase
+            .exchange_token(token_exchange_request(&first.access_token, None))
+            .await
+            .unwrap();
+
+        let act = JwtService::new()
+            .validate_token(&second.access_token)
+            .unwrap()
+            .act
+            .unwrap();
+        assert_eq!(act.sub, "gateway-client");
+        assert_eq!(act.act.unwrap().sub, "gateway-client");
+    }
+
+    #[tokio::test]
+    async fn test_token_exchange_rejects_revoked_and_foreign_subjects() {
+        let ctx = oidc_test_setup_with_clients(true);
+
+        let subject = subject_token(&ctx, None);
+        let jti = JwtService::new().validate_token(&subject).unwrap().jti;
+        ctx.denied.lock().unwrap().insert(jti.unwrap());
+        let result = ctx
+            .usecase
+            .exchange_token(token_exchange_request(&subject, None))
+            .await;
+        assert!(matches!(result, Err(AppError::OAuth("invalid_grant", _))));
+
+        let foreign = JwtService::new()
+            .generate_access_token(ctx.user.id, Uuid::new_v4(), "user".to_string())
+            .unwrap();
+        let result = ctx
+            .usecase
+            .exchange_token(token_exchange_request(&foreign, None))
+            .await;
+        assert!(matches!(result, Err(AppError::OAuth("invalid_grant", _))));
+    }
+
+    #[tokio::test]
+    async fn test_token_exchange_rejects_unknown_audience() {
+        let ctx = oidc_test_setup_with_clients(true);
+        let subject = subject_token(&ctx, None);
+
+        let result = ctx
+            .usecase
+            .exchange_token(TokenRequest {
+                audience: Some("unknown-service".to_string()),
+                ..token_exchange_request(&subject, None)
+            })
+            .await;
+
+        assert!(matches!(result, Err(AppError::OAuth("invalid_target", _))));
+    }
+
+    #[tokio::test]
+    async fn test_token_exchange_requires_grant() {
+        let ctx = oidc_test_setup_with_clients(true);
+        let subject = subject_token(&ctx, None);
+
+        let result = ctx
+            .usecase
+            .exchange_token(TokenRequest {
+                client_id: Some("machine-client".to_string()),
+                ..token_exchange_request(&subject, None)
+            })
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(AppError::OAuth("unauthorized_client", _))
+        ));
+    }
+}
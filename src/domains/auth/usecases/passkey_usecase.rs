@@ -0,0 +1,588 @@
+use crate::domains::auth::repositories::webauthn_challenge_repository::{
+    WebauthnChallenge, WebauthnChallengeRepositoryTrait,
+};
+use crate::domains::auth::usecases::auth_usecase::AuthUseCase;
+use crate::domains::common::errors::AppError;
+use crate::domains::common::utils::config::Config;
+use crate::domains::common::utils::jwt::{Authentication, AMR_HARDWARE_KEY, AMR_MULTI_FACTOR};
+use crate::domains::common::utils::{request_helper, webauthn};
+use crate::domains::tenant::repositories::user_tenant_repository::UserTenantRepositoryTrait;
+use crate::domains::user::dtos::auth_dto::AuthResponse;
+use crate::domains::user::dtos::passkey_dto::{
+    AuthenticatorSelection, CredentialDescriptor, PasskeyLoginOptions, PasskeyLoginOptionsRequest,
+    PasskeyLoginRequest, PasskeyRegisterRequest, PasskeyRegistrationOptions, PasskeyResponse,
+    PasskeyUserEntity, PubKeyCredParam, RelyingParty,
+};
+use crate::domains::user::entities::user::Model as User;
+use crate::domains::user::entities::user_passkey::Model as UserPasskey;
+use crate::domains::user::repositories::user_passkey_repository::UserPasskeyRepositoryTrait;
+use crate::domains::user::repositories::user_repository::UserRepositoryTrait;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// How long a WebAuthn challenge stays valid, in seconds.
+const CHALLENGE_TTL_SECS: u64 = 300;
+
+const CEREMONY_REGISTRATION: &str = "registration";
+const CEREMONY_AUTHENTICATION: &str = "authentication";
+
+/// Passkey (WebAuthn) registration and login.
+///
+/// Registration attaches an ES256 credential to the signed-in user. Login
+/// verifies an assertion and issues the same access/refresh pair as a password
+/// login through `AuthUseCase`. A passkey with user verification already
+/// proves two factors, so it is not followed by a TOTP challenge.
+pub struct PasskeyUseCase {
+    repository: Arc<dyn UserRepositoryTrait>,
+    user_tenant_repository: Arc<dyn UserTenantRepositoryTrait>,
+    passkey_repository: Arc<dyn UserPasskeyRepositoryTrait>,
+    challenge_repository: Arc<dyn WebauthnChallengeRepositoryTrait>,
+    auth_usecase: Arc<AuthUseCase>,
+}
+
+impl PasskeyUseCase {
+    /// Creates a new PasskeyUseCase instance.
+    pub fn new(
+        repository: Arc<dyn UserRepositoryTrait>,
+        user_tenant_repository: Arc<dyn UserTenantRepositoryTrait>,
+        passkey_repository: Arc<dyn UserPasskeyRepositoryTrait>,
+        challenge_repository: Arc<dyn WebauthnChallengeRepositoryTrait>,
+        auth_usecase: Arc<AuthUseCase>,
+    ) -> Self {
+        Self {
+            repository,
+            user_tenant_repository,
+            passkey_repository,
+            challenge_repository,
+            auth_usecase,
+        }
+    }
+
+    /// Starts registering a passkey for the authenticated user.
+    ///
+    /// # Returns
+    ///
+    /// Returns the options to pass to `navigator.credentials.create()`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::NotFound` if the user does not exist.
+    pub async fn registration_options(
+        &self,
+        user_id: uuid::Uuid,
+        tenant_id: uuid::Uuid,
+    ) -> Result<PasskeyRegistrationOptions, AppError> {
+        let config = Config::get();
+        let user: User = self
+            .repository
+            .find_by_id(user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+        let exclude_credentials = self
+            .passkey_repository
+            .find_by_user_id(user_id)
+            .await?
+            .into_iter()
+            .map(|passkey| Self::credential_descriptor(passkey.credential_id))
+            .collect();
+
+        let challenge = webauthn::generate_challenge();
+        self.challenge_repository
+            .save_challenge(
+                &challenge,
+                WebauthnChallenge {
+                    ceremony: CEREMONY_REGISTRATION.to_string(),
+                    user_id: Some(user_id),
+                    tenant_id,
+                },
+                Duration::from_secs(CHALLENGE_TTL_SECS),
+            )
+            .await?;
+
+        Ok(PasskeyRegistrationOptions {
+            challenge,
+            rp: RelyingParty {
+                id: config.webauthn_rp_id.clone(),
+                name: config.webauthn_rp_name.clone(),
+            },
+            user: PasskeyUserEntity {
+                id: Self::user_handle(user.id),
+                name: user.username.clone(),
+                display_name: user.username,
+            },
+            pub_key_cred_params: vec![PubKeyCredParam {
+                credential_type: "public-key".to_string(),
+                alg: webauthn::COSE_ALG_ES256,
+            }],
+            timeout: CHALLENGE_TTL_SECS * 1000,
+            attestation: "none".to_string(),
+            authenticator_selection: AuthenticatorSelection {
+                resident_key: "preferred".to_string(),
+                user_verification: "required".to_string(),
+            },
+            exclude_credentials,
+        })
+    }
+
+    /// Verifies a registration response and stores the new credential.
+    ///
+    /// # Errors
+    ///
+    /// - `AppError::BadRequest` if the response is malformed, was not issued for
+    ///   this user, or fails origin, RP ID, flag or attestation checks
+    /// - `AppError::Conflict` if the credential is already registered
+    pub async fn register(
+        &self,
+        user_id: uuid::Uuid,
+        tenant_id: uuid::Uuid,
+        req: PasskeyRegisterRequest,
+        http_req: &actix_web::HttpRequest,
+    ) -> Result<PasskeyResponse, AppError> {
+        let (ip_address, user_agent) = request_helper::extract_client_info(http_req);
+
+        let result = self.verify_registration(user_id, tenant_id, req).await;
+        match &result {
+            Ok(_) => {
+                self.auth_usecase
+                    .log_activity_success(Some(user_id), "passkey_register", ip_address, user_agent)
+                    .await
+            }
+            Err(err) => {
+                self.auth_usecase
+                    .log_activity_failure(
+                        Some(user_id),
+                        "passkey_register",
+                        err,
+                        ip_address,
+                        user_agent,
+                    )
+                    .await
+            }
+        }
+
+        result
+    }
+
+    /// Starts a passkey login.
+    ///
+    /// When a known user is named, their credentials are listed in
+    /// `allowCredentials`. Unknown users get the same response as an anonymous
+    /// request so that the endpoint cannot be used to enumerate accounts.
+    pub async fn login_options(
+        &self,
+        tenant_id: uuid::Uuid,
+        req: PasskeyLoginOptionsRequest,
+    ) -> Result<PasskeyLoginOptions, AppError> {
+        let user = match req.email_or_username.as_deref() {
+            Some(identifier) if !identifier.is_empty() => self.find_user(identifier).await?,
+            _ => None,
+        };
+
+        let allow_credentials = match &user {
+            Some(user) => self
+                .passkey_repository
+                .find_by_user_id(user.id)
+                .await?
+                .into_iter()
+                .map(|passkey| Self::credential_descriptor(passkey.credential_id))
+                .collect(),
+            None => vec![],
+        };
+
+        let challenge = webauthn::generate_challenge();
+        self.challenge_repository
+            .save_challenge(
+                &challenge,
+                WebauthnChallenge {
+                    ceremony: CEREMONY_AUTHENTICATION.to_string(),
+                    user_id: user.map(|u| u.id),
+                    tenant_id,
+                },
+                Duration::from_secs(CHALLENGE_TTL_SECS),
+            )
+            .await?;
+
+        Ok(PasskeyLoginOptions {
+            challenge,
+            rp_id: Config::get().webauthn_rp_id.clone(),
+            timeout: CHALLENGE_TTL_SECS * 1000,
+            user_verification: "required".to_string(),
+            allow_credentials,
+        })
+    }
+
+    /// Verifies a passkey assertion and issues session tokens.
+    ///
+    /// # Returns
+    ///
+    /// Returns a tuple of `(AuthResponse, refresh_token)`.
+    ///
+    /// # Errors
+    ///
+    /// - `AppError::BadRequest` if the response is malformed
+    /// - `AppError::Unauthorized` if the assertion is invalid or the user is not in the tenant
+    /// - `AppError::NotFound` if a requested role is not held by the user
+    pub async fn login(
+        &self,
+        tenant_id: uuid::Uuid,
+        req: PasskeyLoginRequest,
+        http_req: &actix_web::HttpRequest,
+    ) -> Result<(AuthResponse, String), AppError> {
+        let (ip_address, user_agent) = request_helper::extract_client_info(http_req);
+
+        let (passkey, sign_count) = match self.verify_assertion(tenant_id, &req).await {
+            Ok(verified) => verified,
+            Err((user_id, err)) => {
+                self.auth_usecase
+                    .log_activity_failure(user_id, "passkey_login", &err, ip_address, user_agent)
+                    .await;
+                return Err(err);
+            }
+        };
+
+        self.passkey_repository
+            .update_usage(passkey.id, sign_count)
+            .await?;
+
+        let user: User = self
+            .repository
+            .find_by_id(passkey.user_id)
+            .await?
+            .ok_or_else(|| AppError::Unauthorized("Unauthorized".to_string()))?;
+
+        let roles = self
+            .user_tenant_repository
+            .get_user_roles_in_tenant(user.id, tenant_id)
+            .await?;
+
+        if roles.is_empty() {
+            return Err(AppError::Unauthorized(
+                "User not authorized for this tenant".to_string(),
+            ));
+        }
+
+        let Some(role) = AuthUseCase::select_role(&roles, req.role.as_deref()) else {
+            let err = AppError::NotFound("User not found".to_string());
+            self.auth_usecase
+                .log_activity_failure(
+                    Some(user.id),
+                    "login_role_mismatch",
+                    &err,
+                    ip_address,
+                    user_agent,
+                )
+                .await;
+            return Err(err);
+        };
+
+        if let Err(err) = AuthUseCase::ensure_active(&user) {
+            self.auth_usecase
+                .log_activity_failure(Some(user.id), "passkey_login", &err, ip_address, user_agent)
+                .await;
+            return Err(err);
+        }
+
+        if let Err(err) = self
+            .auth_usecase
+            .ensure_email_verified(&user, tenant_id)
+            .await
+        {
+            self.auth_usecase
+                .log_activity_failure(Some(user.id), "passkey_login", &err, ip_address, user_agent)
+                .await;
+            return Err(err);
+        }
+
+        let tokens = self
+            .auth_usecase
+            .issue_session_tokens(
+                user.id,
+                tenant_id,
+                role,
+                &Authentication::now(&[AMR_HARDWARE_KEY, AMR_MULTI_FACTOR]),
+                ip_address.clone(),
+                user_agent.clone(),
+            )
+            .await?;
+
+        self.auth_usecase
+            .log_activity_success(
+                Some(user.id),
+                "passkey_login",
+                ip_address.clone(),
+                user_agent.clone(),
+            )
+            .await;
+        self.auth_usecase
+            .log_activity_success(Some(user.id), "login", ip_address, user_agent)
+            .await;
+
+        Ok(tokens)
+    }
+
+    /// Lists the authenticated user's passkeys.
+    pub async fn list_passkeys(
+        &self,
+        user_id: uuid::Uuid,
+    ) -> Result<Vec<PasskeyResponse>, AppError> {
+        Ok(self
+            .passkey_repository
+            .find_by_user_id(user_id)
+            .await?
+            .into_iter()
+            .map(Self::to_passkey_response)
+            .collect())
+    }
+
+    /// Removes one of the authenticated user's passkeys.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::NotFound` if the user has no passkey with this ID.
+    pub async fn delete_passkey(
+        &self,
+        user_id: uuid::Uuid,
+        passkey_id: uuid::Uuid,
+        http_req: &actix_web::HttpRequest,
+    ) -> Result<(), AppError> {
+        let (ip_address, user_agent) = request_helper::extract_client_info(http_req);
+
+        if !self.passkey_repository.delete(user_id, passkey_id).await? {
+            return Err(AppError::NotFound("Passkey not found".to_string()));
+        }
+
+        self.auth_usecase
+            .log_activity_success(Some(user_id), "passkey_delete", ip_address, user_agent)
+            .await;
+
+        Ok(())
+    }
+
+    async fn verify_registration(
+        &self,
+        user_id: uuid::Uuid,
+        tenant_id: uuid::Uuid,
+        req: PasskeyRegisterRequest,
+    ) -> Result<PasskeyResponse, AppError> {
+        let invalid =
+            |message: &str| AppError::BadRequest(format!("Invalid WebAuthn response: {}", message));
+        let config = Config::get();
+        let credential = req.credential;
+
+        if credential.credential_type != "public-key" {
+            return Err(invalid("unexpected credential type"));
+        }
+
+        let client_data_json = webauthn::base64url_decode(&credential.response.client_data_json)?;
+        let client_data = webauthn::parse_client_data(&client_data_json)?;
+        if client_data.ceremony_type != "webauthn.create" {
+            return Err(invalid("unexpected ceremony type"));
+        }
+
+        // Consume the challenge first so that a failed attempt cannot be retried
+        let expected = WebauthnChallenge {
+            ceremony: CEREMONY_REGISTRATION.to_string(),
+            user_id: Some(user_id),
+            tenant_id,
+        };
+        if self
+            .challenge_repository
+            .take_challenge(&client_data.challenge)
+            .await?
+            != Some(expected)
+        {
+            return Err(invalid("unknown or expired challenge"));
+        }
+
+        if !config.webauthn_origins.contains(&client_data.origin) {
+            return Err(invalid("origin not allowed"));
+        }
+
+        let attestation_object =
+            webauthn::base64url_decode(&credential.response.attestation_object)?;
+        let attestation = webauthn::parse_attestation_object(&attestation_object)?;
+        let auth_data = webauthn::parse_authenticator_data(&attestation.auth_data)?;
+
+        if auth_data.rp_id_hash != webauthn::rp_id_hash(&config.webauthn_rp_id) {
+            return Err(invalid("RP ID mismatch"));
+        }
+        if !auth_data.user_present() || !auth_data.user_verified() {
+            return Err(invalid("user verification required"));
+        }
+
+        let attested = auth_data
+            .attested_credential
+            .ok_or_else(|| invalid("missing attested credential"))?;
+        webauthn::parse_es256_public_key(&attested.public_key)?;
+        webauthn::verify_attestation(
+            &attestation,
+            &webauthn::client_data_hash(&client_data_json),
+            &attested.public_key,
+        )?;
+
+        let credential_id = webauthn::base64url_encode(&attested.credential_id);
+        if self
+            .passkey_repository
+            .find_by_credential_id(&credential_id)
+            .await?
+            .is_some()
+        {
+            return Err(AppError::Conflict(
+                "Passkey is already registered".to_string(),
+            ));
+        }
+
+        let name = req
+            .name
+            .map(|n| n.trim().chars().take(100).collect::<String>())
+            .filter(|n| !n.is_empty());
+
+        let passkey = self
+            .passkey_repository
+            .create(
+                user_id,
+                credential_id,
+                attested.public_key,
+                auth_data.sign_count as i64,
+                name,
+            )
+            .await?;
+
+        Ok(Self::to_passkey_response(passkey))
+    }
+
+    /// Runs the assertion checks and returns the matched passkey with its new
+    /// signature counter.
+    ///
+    /// On failure, the error is paired with the credential owner when known, for logging.
+    async fn verify_assertion(
+        &self,
+        tenant_id: uuid::Uuid,
+        req: &PasskeyLoginRequest,
+    ) -> Result<(UserPasskey, i64), (Option<uuid::Uuid>, AppError)> {
+        let invalid =
+            |message: &str| AppError::BadRequest(format!("Invalid WebAuthn response: {}", message));
+        let rejected = || AppError::Unauthorized("Invalid passkey".to_string());
+        let config = Config::get();
+        let credential = &req.credential;
+
+        if credential.credential_type != "public-key" {
+            return Err((None, invalid("unexpected credential type")));
+        }
+
+        let client_data_json = webauthn::base64url_decode(&credential.response.client_data_json)
+            .map_err(|e| (None, e))?;
+        let client_data = webauthn::parse_client_data(&client_data_json).map_err(|e| (None, e))?;
+        if client_data.ceremony_type != "webauthn.get" {
+            return Err((None, invalid("unexpected ceremony type")));
+        }
+
+        let challenge = self
+            .challenge_repository
+            .take_challenge(&client_data.challenge)
+            .await
+            .map_err(|e| (None, e))?
+            .filter(|c| c.ceremony == CEREMONY_AUTHENTICATION && c.tenant_id == tenant_id)
+            .ok_or_else(|| (None, rejected()))?;
+
+        if !config.webauthn_origins.contains(&client_data.origin) {
+            return Err((None, rejected()));
+        }
+
+        // Re-encode so that padded IDs match the stored form
+        let credential_id = webauthn::base64url_decode(&credential.id)
+            .map(|id| webauthn::base64url_encode(&id))
+            .map_err(|e| (None, e))?;
+        let passkey = self
+            .passkey_repository
+            .find_by_credential_id(&credential_id)
+            .await
+            .map_err(|e| (None, e))?
+            .ok_or_else(|| (None, rejected()))?;
+        let owner = Some(passkey.user_id);
+
+        // The challenge may be bound to a named user, and the authenticator may
+        // report the user handle it stored at registration
+        if challenge.user_id.is_some_and(|id| id != passkey.user_id) {
+            return Err((owner, rejected()));
+        }
+        if let Some(user_handle) = &credential.response.user_handle {
+            if !user_handle.is_empty() && *user_handle != Self::user_handle(passkey.user_id) {
+                return Err((owner, rejected()));
+            }
+        }
+
+        let authenticator_data =
+            webauthn::base64url_decode(&credential.response.authenticator_data)
+                .map_err(|e| (owner, e))?;
+        let auth_data =
+            webauthn::parse_authenticator_data(&authenticator_data).map_err(|e| (owner, e))?;
+
+        if auth_data.rp_id_hash != webauthn::rp_id_hash(&config.webauthn_rp_id)
+            || !auth_data.user_present()
+            || !auth_data.user_verified()
+        {
+            return Err((owner, rejected()));
+        }
+
+        let signature =
+            webauthn::base64url_decode(&credential.response.signature).map_err(|e| (owner, e))?;
+        let mut signed = authenticator_data;
+        signed.extend_from_slice(&webauthn::client_data_hash(&client_data_json));
+        if !webauthn::verify_signature(&passkey.public_key, &signed, &signature)
+            .map_err(|e| (owner, e))?
+        {
+            return Err((owner, rejected()));
+        }
+
+        // A counter that does not increase suggests a cloned authenticator.
+        // Authenticators that do not implement counters always report zero.
+        let sign_count = auth_data.sign_count as i64;
+        if (sign_count != 0 || passkey.sign_count != 0) && sign_count <= passkey.sign_count {
+            return Err((owner, rejected()));
+        }
+
+        Ok((passkey, sign_count))
+    }
+
+    /// Looks up a non-deleted user by email or username.
+    async fn find_user(&self, identifier: &str) -> Result<Option<User>, AppError> {
+        let user = match self
+            .repository
+            .find_by_email_with_deleted(&identifier.to_lowercase())
+            .await?
+        {
+            Some(user) => Some(user),
+            None => {
+                self.repository
+                    .find_by_username_with_deleted(identifier)
+                    .await?
+            }
+        };
+
+        Ok(user.filter(|u| u.deleted_at.is_none()))
+    }
+
+    /// Encodes a user ID as the WebAuthn user handle.
+    fn user_handle(user_id: uuid::Uuid) -> String {
+        webauthn::base64url_encode(user_id.as_bytes())
+    }
+
+    fn credential_descriptor(credential_id: String) -> CredentialDescriptor {
+        CredentialDescriptor {
+            credential_type: "public-key".to_string(),
+            id: credential_id,
+        }
+    }
+
+    fn to_passkey_response(passkey: UserPasskey) -> PasskeyResponse {
+        PasskeyResponse {
+            id: passkey.id,
+            credential_id: passkey.credential_id,
+            name: passkey.name,
+            created_at: passkey.created_at,
+            last_used_at: passkey.last_used_at,
+        }
+    }
+}
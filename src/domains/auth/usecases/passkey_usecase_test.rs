@@ -0,0 +1,618 @@
+#[cfg(test)]
+mod tests {
+    use crate::domains::auth::repositories::invitation_code_repository::InvitationCodeRepositoryTrait;
+    use crate::domains::auth::repositories::token_denylist_repository::TokenDenylistRepositoryTrait;
+    use crate::domains::auth::repositories::webauthn_challenge_repository::{
+        WebauthnChallenge, WebauthnChallengeRepositoryTrait,
+    };
+    use crate::domains::auth::usecases::auth_usecase::AuthUseCase;
+    use crate::domains::auth::usecases::passkey_usecase::PasskeyUseCase;
+    use crate::domains::common::errors::AppError;
+    use crate::domains::common::utils::config::Config;
+    use crate::domains::common::utils::webauthn::base64url_encode;
+    use crate::domains::common::utils::webauthn_test::SoftwareAuthenticator;
+    use crate::domains::tenant::entities::tenant::Model as Tenant;
+    use crate::domains::tenant::repositories::tenant_repository::TenantRepositoryTrait;
+    use crate::domains::tenant::repositories::user_tenant_repository::{
+        UserTenantInfo, UserTenantRepositoryTrait,
+    };
+    use crate::domains::user::dtos::passkey_dto::{
+        AssertionResponse, AttestationResponse, AuthenticationCredential,
+        PasskeyLoginOptionsRequest, PasskeyLoginRequest, PasskeyRegisterRequest,
+        RegistrationCredential,
+    };
+    use crate::domains::user::entities::user::Model as User;
+    use crate::domains::user::entities::user_activity_log::Model as UserActivityLog;
+    use crate::domains::user::entities::user_details::Model as UserDetails;
+    use crate::domains::user::entities::user_mfa::Model as UserMfa;
+    use crate::domains::user::entities::user_passkey::Model as UserPasskey;
+    use crate::domains::user::entities::user_recovery_code::Model as UserRecoveryCode;
+    use crate::domains::user::entities::user_session::Model as UserSession;
+    use crate::domains::user::repositories::user_activity_log_repository::UserActivityLogRepositoryTrait;
+    use crate::domains::user::repositories::user_details_repository::UserDetailsRepositoryTrait;
+    use crate::domains::user::repositories::user_mfa_repository::UserMfaRepositoryTrait;
+    use crate::domains::user::repositories::user_passkey_repository::UserPasskeyRepositoryTrait;
+    use crate::domains::user::repositories::user_recovery_code_repository::UserRecoveryCodeRepositoryTrait;
+    use crate::domains::user::repositories::user_repository::UserRepositoryTrait;
+    use crate::domains::user::repositories::user_session_repository::UserSessionRepositoryTrait;
+
+    use actix_web::test::TestRequest;
+    use async_trait::async_trait;
+    use chrono::{DateTime, Utc};
+    use mockall::mock;
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+    use uuid::Uuid;
+
+    mock! {
+        pub TokenDenylistRepository {}
+        #[async_trait]
+        impl TokenDenylistRepositoryTrait for TokenDenylistRepository {
+            async fn deny(&self, jti: &str, ttl: std::time::Duration) -> Result<(), AppError>;
+            async fn is_denied(&self, jti: &str) -> Result<bool, AppError>;
+            async fn deny_subject(&self, subject: &str, ttl: std::time::Duration) -> Result<(), AppError>;
+            async fn subject_denied_at(&self, subject: &str) -> Result<Option<i64>, AppError>;
+        }
+    }
+
+    mock! {
+        pub UserRepository {}
+        #[async_trait]
+        impl UserRepositoryTrait for UserRepository {
+            async fn create(&self, user: crate::domains::user::dtos::user_dto::CreateUserRequest) -> Result<User, AppError>;
+            async fn find_by_id(&self, id: Uuid) -> Result<Option<User>, AppError>;
+            async fn find_by_username(&self, username: &str) -> Result<Option<User>, AppError>;
+            async fn find_all(&self) -> Result<Vec<User>, AppError>;
+            async fn update(&self, id: Uuid, user: crate::domains::user::dtos::user_dto::UpdateUserRequest) -> Result<User, AppError>;
+            async fn delete(&self, id: Uuid) -> Result<(), AppError>;
+            async fn find_by_email_with_deleted(&self, email: &str) -> Result<Option<User>, AppError>;
+            async fn find_by_username_with_deleted(&self, username: &str) -> Result<Option<User>, AppError>;
+            async fn restore(&self, id: Uuid, req: crate::domains::user::dtos::user_dto::CreateUserRequest) -> Result<User, AppError>;
+            async fn mark_email_verified(&self, id: Uuid) -> Result<User, AppError>;
+            async fn set_login_failures(&self, id: Uuid, failed_login_attempts: i32, locked_until: Option<DateTime<Utc>>) -> Result<(), AppError>;
+            async fn increment_login_failures(&self, id: Uuid) -> Result<i32, AppError>;
+            async fn lock_until(&self, id: Uuid, locked_until: DateTime<Utc>) -> Result<(), AppError>;
+            async fn set_status(&self, id: Uuid, status: String, reason: Option<String>, expires_at: Option<DateTime<Utc>>) -> Result<User, AppError>;
+            async fn purge_deleted_before(&self, cutoff: DateTime<Utc>) -> Result<u64, AppError>;
+        }
+    }
+
+    // Mocking TenantRepositoryTrait
+    mock! {
+        pub TenantRepository {}
+        #[async_trait]
+        impl TenantRepositoryTrait for TenantRepository {
+            async fn create(&self, tenant: crate::domains::tenant::dtos::tenant_dto::CreateTenantRequest) -> Result<Tenant, AppError>;
+            async fn find_by_id(&self, id: Uuid) -> Result<Option<Tenant>, AppError>;
+            async fn find_by_name(&self, name: &str) -> Result<Option<Tenant>, AppError>;
+            async fn find_by_name_with_deleted(&self, name: &str) -> Result<Option<Tenant>, AppError>;
+            async fn find_all(&self) -> Result<Vec<Tenant>, AppError>;
+            async fn update(&self, id: Uuid, tenant: crate::domains::tenant::dtos::tenant_dto::UpdateTenantRequest) -> Result<Tenant, AppError>;
+            async fn delete(&self, id: Uuid) -> Result<(), AppError>;
+            async fn restore(&self, id: Uuid) -> Result<(), AppError>;
+            async fn purge_deleted_before(&self, cutoff: DateTime<Utc>) -> Result<u64, AppError>;
+        }
+    }
+
+    /// Builds a tenant repository whose tenants have the given email verification policy.
+    fn tenant_repository(require_email_verification: bool) -> MockTenantRepository {
+        let mut repo = MockTenantRepository::new();
+        repo.expect_find_by_id().returning(move |id| {
+            Ok(Some(Tenant {
+                id,
+                name: "test-tenant".to_string(),
+                description: None,
+                api_key: None,
+                require_email_verification,
+                passwordless_login: false,
+                rate_limits: None,
+                deleted_at: None,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+            }))
+        });
+        repo
+    }
+
+    mock! {
+        pub UserDetailsRepository {}
+        #[async_trait]
+        impl UserDetailsRepositoryTrait for UserDetailsRepository {
+            async fn create(&self, user_id: Uuid) -> Result<UserDetails, AppError>;
+            async fn find_by_user_id(&self, user_id: Uuid) -> Result<Option<UserDetails>, AppError>;
+            async fn update(&self, user_id: Uuid, full_name: Option<String>, phone_number: Option<String>, address: Option<String>, date_of_birth: Option<chrono::NaiveDate>) -> Result<UserDetails, AppError>;
+            async fn update_profile_picture(&self, user_id: Uuid, profile_picture_url: String) -> Result<UserDetails, AppError>;
+        }
+    }
+
+    mock! {
+        pub UserTenantRepository {}
+        #[async_trait]
+        impl UserTenantRepositoryTrait for UserTenantRepository {
+            async fn add_user_to_tenant(&self, user_id: Uuid, tenant_id: Uuid, role: String) -> Result<(), AppError>;
+            async fn get_user_roles_in_tenant(&self, user_id: Uuid, tenant_id: Uuid) -> Result<Vec<String>, AppError>;
+            async fn get_all_tenants_for_user(&self, user_id: Uuid) -> Result<Vec<UserTenantInfo>, AppError>;
+        }
+    }
+
+    mock! {
+        pub UserSessionRepository {}
+        #[async_trait]
+        impl UserSessionRepositoryTrait for UserSessionRepository {
+            async fn create_session(&self, id: Option<Uuid>, user_id: Uuid, refresh_token_hash: String, user_agent: Option<String>, ip_address: Option<String>, expires_at: DateTime<Utc>) -> Result<UserSession, AppError>;
+            async fn find_by_refresh_token_hash(&self, hash: &str) -> Result<Option<UserSession>, AppError>;
+            async fn find_by_id(&self, id: Uuid) -> Result<Option<UserSession>, AppError>;
+            async fn find_active_by_user_id(&self, user_id: Uuid) -> Result<Vec<UserSession>, AppError>;
+            async fn rotate_session(&self, parent: &UserSession, id: Uuid, refresh_token_hash: String, user_agent: Option<String>, ip_address: Option<String>, expires_at: DateTime<Utc>) -> Result<Option<UserSession>, AppError>;
+            async fn delete_session(&self, id: Uuid) -> Result<(), AppError>;
+            async fn delete_family(&self, family_id: Uuid) -> Result<(), AppError>;
+            async fn delete_all_sessions_for_user(&self, user_id: Uuid) -> Result<(), AppError>;
+            async fn delete_expired(&self, now: DateTime<Utc>) -> Result<u64, AppError>;
+            async fn delete_other_sessions_for_user(&self, user_id: Uuid, family_id: Uuid) -> Result<(), AppError>;
+        }
+    }
+
+    mock! {
+        pub UserActivityLogRepository {}
+        #[async_trait]
+        impl UserActivityLogRepositoryTrait for UserActivityLogRepository {
+            async fn log_activity(&self, user_id: Option<Uuid>, activity_type: String, status: String, error_message: Option<String>, ip_address: Option<String>, user_agent: Option<String>) -> Result<UserActivityLog, AppError>;
+        }
+    }
+
+    mock! {
+        pub InvitationCodeRepository {}
+        #[async_trait]
+        impl InvitationCodeRepositoryTrait for InvitationCodeRepository {
+            async fn save_code(&self, code: String, ttl: std::time::Duration) -> Result<(), AppError>;
+            async fn validate_and_delete_code(&self, code: &str) -> Result<bool, AppError>;
+        }
+    }
+
+    mock! {
+        pub UserMfaRepository {}
+        #[async_trait]
+        impl UserMfaRepositoryTrait for UserMfaRepository {
+            async fn find_by_user_id(&self, user_id: Uuid) -> Result<Option<UserMfa>, AppError>;
+            async fn upsert_pending(&self, user_id: Uuid, totp_secret: String) -> Result<UserMfa, AppError>;
+            async fn enable(&self, user_id: Uuid, used_step: i64) -> Result<UserMfa, AppError>;
+            async fn update_last_used_step(&self, user_id: Uuid, used_step: i64) -> Result<bool, AppError>;
+            async fn delete_by_user_id(&self, user_id: Uuid) -> Result<(), AppError>;
+        }
+    }
+
+    mock! {
+        pub UserRecoveryCodeRepository {}
+        #[async_trait]
+        impl UserRecoveryCodeRepositoryTrait for UserRecoveryCodeRepository {
+            async fn replace_for_user(&self, user_id: Uuid, code_hashes: Vec<String>) -> Result<(), AppError>;
+            async fn find_unused_by_user_id(&self, user_id: Uuid) -> Result<Vec<UserRecoveryCode>, AppError>;
+            async fn mark_used(&self, id: Uuid) -> Result<bool, AppError>;
+        }
+    }
+
+    // Fake UserPasskeyRepository backed by a Vec
+    struct FakeUserPasskeyRepository {
+        passkeys: Mutex<Vec<UserPasskey>>,
+    }
+
+    impl FakeUserPasskeyRepository {
+        fn new() -> Self {
+            Self {
+                passkeys: Mutex::new(vec![]),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl UserPasskeyRepositoryTrait for FakeUserPasskeyRepository {
+        async fn create(
+            &self,
+            user_id: Uuid,
+            credential_id: String,
+            public_key: Vec<u8>,
+            sign_count: i64,
+            name: Option<String>,
+        ) -> Result<UserPasskey, AppError> {
+            let passkey = UserPasskey {
+                id: Uuid::new_v4(),
+                user_id,
+                credential_id,
+                public_key,
+                sign_count,
+                name,
+                created_at: Utc::now(),
+                last_used_at: None,
+            };
+            self.passkeys.lock().unwrap().push(passkey.clone());
+            Ok(passkey)
+        }
+
+        async fn find_by_credential_id(
+            &self,
+            credential_id: &str,
+        ) -> Result<Option<UserPasskey>, AppError> {
+            Ok(self
+                .passkeys
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|p| p.credential_id == credential_id)
+                .cloned())
+        }
+
+        async fn find_by_user_id(&self, user_id: Uuid) -> Result<Vec<UserPasskey>, AppError> {
+            Ok(self
+                .passkeys
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|p| p.user_id == user_id)
+                .cloned()
+                .collect())
+        }
+
+        async fn update_usage(&self, id: Uuid, sign_count: i64) -> Result<(), AppError> {
+            if let Some(passkey) = self
+                .passkeys
+                .lock()
+                .unwrap()
+                .iter_mut()
+                .find(|p| p.id == id)
+            {
+                passkey.sign_count = sign_count;
+                passkey.last_used_at = Some(Utc::now());
+            }
+            Ok(())
+        }
+
+        async fn delete(&self, user_id: Uuid, id: Uuid) -> Result<bool, AppError> {
+            let mut passkeys = self.passkeys.lock().unwrap();
+            let before = passkeys.len();
+            passkeys.retain(|p| !(p.id == id && p.user_id == user_id));
+            Ok(passkeys.len() < before)
+        }
+    }
+
+    // Fake WebauthnChallengeRepository backed by a HashMap (TTL ignored)
+    struct FakeWebauthnChallengeRepository {
+        challenges: Mutex<HashMap<String, WebauthnChallenge>>,
+    }
+
+    #[async_trait]
+    impl WebauthnChallengeRepositoryTrait for FakeWebauthnChallengeRepository {
+        async fn save_challenge(
+            &self,
+            challenge: &str,
+            state: WebauthnChallenge,
+            _: Duration,
+        ) -> Result<(), AppError> {
+            self.challenges
+                .lock()
+                .unwrap()
+                .insert(challenge.to_string(), state);
+            Ok(())
+        }
+
+        async fn take_challenge(
+            &self,
+            challenge: &str,
+        ) -> Result<Option<WebauthnChallenge>, AppError> {
+            Ok(self.challenges.lock().unwrap().remove(challenge))
+        }
+    }
+
+    struct PasskeyTestContext {
+        usecase: PasskeyUseCase,
+        user: User,
+        tenant_id: Uuid,
+        passkey_repo: Arc<FakeUserPasskeyRepository>,
+    }
+
+    fn passkey_test_setup() -> PasskeyTestContext {
+        Config::init_for_test();
+
+        let user = User {
+            id: Uuid::new_v4(),
+            username: "passkeyuser".to_string(),
+            email: "passkey@example.com".to_string(),
+            password_hash: "unused".to_string(),
+            email_verified_at: None,
+            failed_login_attempts: 0,
+            locked_until: None,
+            status: "active".to_string(),
+            status_reason: None,
+            status_expires_at: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+        };
+        let tenant_id = Uuid::new_v4();
+
+        let user_repo = {
+            let mut mock = MockUserRepository::new();
+            let user_clone = user.clone();
+            mock.expect_find_by_id()
+                .returning(move |id| Ok(Some(user_clone.clone()).filter(|u| u.id == id)));
+            let user_clone = user.clone();
+            mock.expect_find_by_email_with_deleted()
+                .returning(move |email| Ok(Some(user_clone.clone()).filter(|u| u.email == email)));
+            let user_clone = user.clone();
+            mock.expect_find_by_username_with_deleted()
+                .returning(move |name| Ok(Some(user_clone.clone()).filter(|u| u.username == name)));
+            Arc::new(mock)
+        };
+
+        let user_tenant_repo = {
+            let mut mock = MockUserTenantRepository::new();
+            mock.expect_get_user_roles_in_tenant()
+                .returning(move |_, tenant| {
+                    Ok(if tenant == tenant_id {
+                        vec!["user".to_string()]
+                    } else {
+                        vec![]
+                    })
+                });
+            Arc::new(mock)
+        };
+
+        let mut session_repo = MockUserSessionRepository::new();
+        session_repo
+            .expect_create_session()
+            .returning(|id, user_id, _, _, _, expires_at| {
+                Ok(UserSession {
+                    id: id.unwrap_or_else(Uuid::new_v4),
+                    user_id,
+                    refresh_token_hash: "hash".to_string(),
+                    user_agent: None,
+                    ip_address: None,
+                    expires_at,
+                    created_at: Utc::now(),
+                    family_id: Uuid::new_v4(),
+                    parent_id: None,
+                    rotated_at: None,
+                    last_used_at: None,
+                })
+            });
+
+        let mut activity_repo = MockUserActivityLogRepository::new();
+        activity_repo
+            .expect_log_activity()
+            .returning(|user_id, activity_type, status, _, _, _| {
+                Ok(UserActivityLog {
+                    id: Uuid::new_v4(),
+                    user_id,
+                    activity_type,
+                    status,
+                    error_message: None,
+                    ip_address: None,
+                    user_agent: None,
+                    created_at: Utc::now(),
+                })
+            });
+
+        let auth_usecase = Arc::new(AuthUseCase::new(
+            user_repo.clone(),
+            Arc::new(MockUserDetailsRepository::new()),
+            user_tenant_repo.clone(),
+            Arc::new(session_repo),
+            Arc::new(activity_repo),
+            Arc::new(MockInvitationCodeRepository::new()),
+            Arc::new(MockUserMfaRepository::new()),
+            Arc::new(MockUserRecoveryCodeRepository::new()),
+            Arc::new(tenant_repository(false)),
+            Arc::new(MockTokenDenylistRepository::new()),
+        ));
+
+        let passkey_repo = Arc::new(FakeUserPasskeyRepository::new());
+        let usecase = PasskeyUseCase::new(
+            user_repo,
+            user_tenant_repo,
+            passkey_repo.clone(),
+            Arc::new(FakeWebauthnChallengeRepository {
+                challenges: Mutex::new(HashMap::new()),
+            }),
+            auth_usecase,
+        );
+
+        PasskeyTestContext {
+            usecase,
+            user,
+            tenant_id,
+            passkey_repo,
+        }
+    }
+
+    fn origin() -> String {
+        Config::get().webauthn_origins[0].clone()
+    }
+
+    /// Runs the registration ceremony for `authenticator` against the use case.
+    async fn register(
+        ctx: &PasskeyTestContext,
+        authenticator: &SoftwareAuthenticator,
+        origin: &str,
+    ) -> Result<(), AppError> {
+        let options = ctx
+            .usecase
+            .registration_options(ctx.user.id, ctx.tenant_id)
+            .await?;
+        let (client_data, attestation_object) =
+            authenticator.make_credential(&options.rp.id, origin, &options.challenge, true);
+
+        let req = PasskeyRegisterRequest {
+            credential: RegistrationCredential {
+                id: base64url_encode(&authenticator.credential_id),
+                response: AttestationResponse {
+                    client_data_json: base64url_encode(&client_data),
+                    attestation_object: base64url_encode(&attestation_object),
+                },
+                credential_type: "public-key".to_string(),
+            },
+            name: Some("Laptop".to_string()),
+        };
+        let http_req = TestRequest::default().to_http_request();
+
+        ctx.usecase
+            .register(ctx.user.id, ctx.tenant_id, req, &http_req)
+            .await
+            .map(|_| ())
+    }
+
+    fn assertion_request(
+        authenticator: &SoftwareAuthenticator,
+        client_data: &[u8],
+        auth_data: &[u8],
+        signature: &[u8],
+    ) -> PasskeyLoginRequest {
+        PasskeyLoginRequest {
+            credential: AuthenticationCredential {
+                id: base64url_encode(&authenticator.credential_id),
+                response: AssertionResponse {
+                    client_data_json: base64url_encode(client_data),
+                    authenticator_data: base64url_encode(auth_data),
+                    signature: base64url_encode(signature),
+                    user_handle: None,
+                },
+                credential_type: "public-key".to_string(),
+            },
+            role: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_passkey_register_and_login() {
+        let ctx = passkey_test_setup();
+        let mut authenticator = SoftwareAuthenticator::new();
+        register(&ctx, &authenticator, &origin()).await.unwrap();
+
+        let stored = ctx.passkey_repo.find_by_user_id(ctx.user.id).await.unwrap();
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].name.as_deref(), Some("Laptop"));
+
+        // Naming the user lists their credential
+        let options = ctx
+            .usecase
+            .login_options(
+                ctx.tenant_id,
+                PasskeyLoginOptionsRequest {
+                    email_or_username: Some("passkeyuser".to_string()),
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(options.allow_credentials.len(), 1);
+        assert_eq!(options.allow_credentials[0].id, stored[0].credential_id);
+
+        let (client_data, auth_data, signature) =
+            authenticator.get_assertion(&options.rp_id, &origin(), &options.challenge);
+        let mut req = assertion_request(&authenticator, &client_data, &auth_data, &signature);
+        req.credential.response.user_handle = Some(base64url_encode(ctx.user.id.as_bytes()));
+        let http_req = TestRequest::default().to_http_request();
+
+        let (auth_response, refresh_token) = ctx
+            .usecase
+            .login(ctx.tenant_id, req, &http_req)
+            .await
+            .unwrap();
+        assert_eq!(auth_response.user_id, ctx.user.id);
+        assert!(!auth_response.access_token.is_empty());
+        assert!(!refresh_token.is_empty());
+
+        let stored = ctx.passkey_repo.find_by_user_id(ctx.user.id).await.unwrap();
+        assert_eq!(stored[0].sign_count, 1);
+        assert!(stored[0].last_used_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_passkey_register_rejects_foreign_origin() {
+        let ctx = passkey_test_setup();
+        let authenticator = SoftwareAuthenticator::new();
+
+        let result = register(&ctx, &authenticator, "https://evil.example").await;
+        assert!(matches!(result, Err(AppError::BadRequest(_))));
+        assert!(ctx
+            .passkey_repo
+            .find_by_user_id(ctx.user.id)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_passkey_login_challenge_is_single_use() {
+        let ctx = passkey_test_setup();
+        let mut authenticator = SoftwareAuthenticator::new();
+        register(&ctx, &authenticator, &origin()).await.unwrap();
+
+        let options = ctx
+            .usecase
+            .login_options(ctx.tenant_id, PasskeyLoginOptionsRequest::default())
+            .await
+            .unwrap();
+        assert!(options.allow_credentials.is_empty());
+
+        let http_req = TestRequest::default().to_http_request();
+        let (client_data, auth_data, signature) =
+            authenticator.get_assertion(&options.rp_id, &origin(), &options.challenge);
+        let req = assertion_request(&authenticator, &client_data, &auth_data, &signature);
+        assert!(ctx
+            .usecase
+            .login(ctx.tenant_id, req.clone(), &http_req)
+            .await
+            .is_ok());
+
+        // Replaying the same assertion fails because the challenge was consumed
+        let result = ctx.usecase.login(ctx.tenant_id, req, &http_req).await;
+        assert!(matches!(result, Err(AppError::Unauthorized(_))));
+    }
+
+    #[tokio::test]
+    async fn test_passkey_login_rejects_other_tenant_and_counter_regression() {
+        let ctx = passkey_test_setup();
+        let mut authenticator = SoftwareAuthenticator::new();
+        register(&ctx, &authenticator, &origin()).await.unwrap();
+        let http_req = TestRequest::default().to_http_request();
+
+        // A challenge issued for one tenant cannot be redeemed in another
+        let options = ctx
+            .usecase
+            .login_options(ctx.tenant_id, PasskeyLoginOptionsRequest::default())
+            .await
+            .unwrap();
+        let (client_data, auth_data, signature) =
+            authenticator.get_assertion(&options.rp_id, &origin(), &options.challenge);
+        let req = assertion_request(&authenticator, &client_data, &auth_data, &signature);
+        let result = ctx.usecase.login(Uuid::new_v4(), req, &http_req).await;
+        assert!(matches!(result, Err(AppError::Unauthorized(_))));
+
+        // Record a login with a high counter, then present a lower one
+        let options = ctx
+            .usecase
+            .login_options(ctx.tenant_id, PasskeyLoginOptionsRequest::default())
+            .await
+            .unwrap();
+        authenticator.sign_count = 10;
+        let (client_data, auth_data, signature) =
+            authenticator.get_assertion(&options.rp_id, &origin(), &options.challenge);
+        let req = assertion_request(&authenticator, &client_data, &auth_data, &signature);
+        assert!(ctx
+            .usecase
+            .login(ctx.tenant_id, req, &http_req)
+            .await
+            .is_ok());
+
+        let options = ctx
+            .usecase
+            .login_options(ctx.tenant_id, PasskeyLoginOptionsRequest::default())
+            .await
+            .unwrap();
+        authenticator.sign_count = 3;
+        let (client_data, auth_data, signature) =
+            authenticator.get_assertion(&options.rp_id, &origin(), &options.challenge);
+        let req = assertion_request(&authenticator, &client_data, &auth_data, &signature);
+        let result = ctx.usecase.login(ctx.tenant_id, req, &http_req).await;
+        assert!(matches!(result, Err(AppError::Unauthorized(_))));
+    }
+}
@@ -0,0 +1,202 @@
+use crate::domains::auth::usecases::auth_usecase::AuthUseCase;
+use crate::domains::common::errors::{AppError, ValidationDetail};
+use crate::domains::common::infrastructures::email_templates::EmailTemplates;
+use crate::domains::common::infrastructures::mailer::Mailer;
+use crate::domains::common::utils::config::Config;
+use crate::domains::common::utils::request_helper;
+use crate::domains::tenant::repositories::user_tenant_repository::UserTenantRepositoryTrait;
+use crate::domains::user::dtos::password_reset_dto::{ForgotPasswordRequest, ResetPasswordRequest};
+use crate::domains::user::dtos::user_dto::UpdateUserRequest;
+use crate::domains::user::entities::user_token::PURPOSE_PASSWORD_RESET;
+use crate::domains::user::repositories::user_repository::UserRepositoryTrait;
+use crate::domains::user::repositories::user_session_repository::UserSessionRepositoryTrait;
+use crate::domains::user::repositories::user_token_repository::UserTokenRepositoryTrait;
+use crate::domains::user::validators::user_validator;
+use chrono::Utc;
+use std::sync::Arc;
+
+/// Self-service "forgot password" flow.
+///
+/// A reset link carrying a random single-use token is emailed to the user.
+/// Only the token hash is stored. Redeeming it sets a new password and
+/// revokes every session and issued access token of the user.
+pub struct PasswordResetUseCase {
+    repository: Arc<dyn UserRepositoryTrait>,
+    user_tenant_repository: Arc<dyn UserTenantRepositoryTrait>,
+    session_repository: Arc<dyn UserSessionRepositoryTrait>,
+    token_repository: Arc<dyn UserTokenRepositoryTrait>,
+    mailer: Arc<dyn Mailer>,
+    templates: Arc<EmailTemplates>,
+    auth_usecase: Arc<AuthUseCase>,
+}
+
+impl PasswordResetUseCase {
+    /// Creates a new PasswordResetUseCase instance.
+    pub fn new(
+        repository: Arc<dyn UserRepositoryTrait>,
+        user_tenant_repository: Arc<dyn UserTenantRepositoryTrait>,
+        session_repository: Arc<dyn UserSessionRepositoryTrait>,
+        token_repository: Arc<dyn UserTokenRepositoryTrait>,
+        mailer: Arc<dyn Mailer>,
+        templates: Arc<EmailTemplates>,
+        auth_usecase: Arc<AuthUseCase>,
+    ) -> Self {
+        Self {
+            repository,
+            user_tenant_repository,
+            session_repository,
+            token_repository,
+            mailer,
+            templates,
+            auth_usecase,
+        }
+    }
+
+    /// Emails a password reset link if the address belongs to a member of the tenant.
+    ///
+    /// Always succeeds for well-formed input, whether or not the email is
+    /// registered, so that the endpoint cannot be used to enumerate accounts.
+    /// Issuing a new link invalidates any previous unused one.
+    pub async fn forgot_password(
+        &self,
+        req: ForgotPasswordRequest,
+        tenant_id: uuid::Uuid,
+        http_req: &actix_web::HttpRequest,
+    ) -> Result<(), AppError> {
+        let (ip_address, user_agent) = request_helper::extract_client_info(http_req);
+        let config = Config::get();
+
+        let email = req.email.trim().to_lowercase();
+        if email.is_empty() {
+            return Err(AppError::ValidationError(
+                "Email is required".to_string(),
+                Some(vec![ValidationDetail {
+                    field: "email".to_string(),
+                    message: "Email is required".to_string(),
+                }]),
+            ));
+        }
+
+        let Some(user) = self
+            .repository
+            .find_by_email_with_deleted(&email)
+            .await?
+            .filter(|u| u.deleted_at.is_none())
+        else {
+            return Ok(());
+        };
+
+        let roles = self
+            .user_tenant_repository
+            .get_user_roles_in_tenant(user.id, tenant_id)
+            .await?;
+        if roles.is_empty() {
+            return Ok(());
+        }
+
+        let token = request_helper::generate_token();
+        let expires_at = Utc::now() + chrono::Duration::seconds(config.password_reset_token_expiry);
+        self.token_repository
+            .issue(
+                user.id,
+                tenant_id,
+                PURPOSE_PASSWORD_RESET,
+                request_helper::hash_token(&token),
+                expires_at,
+            )
+            .await?;
+
+        let rendered = self.templates.render(
+            "password_reset",
+            tenant_id,
+            request_helper::preferred_locale(http_req).as_deref(),
+            &user.email,
+            &serde_json::json!({
+                "username": user.username,
+                "link": format!("{}?token={}", config.password_reset_url, token),
+                "expires_in_minutes": config.password_reset_token_expiry / 60,
+            }),
+        );
+
+        // Sent in the background so the response time is the same for unknown emails
+        self.auth_usecase.send_email_in_background(
+            self.mailer.clone(),
+            rendered,
+            user.id,
+            "password_reset_request",
+            (ip_address, user_agent),
+        );
+
+        Ok(())
+    }
+
+    /// Sets a new password using a reset token, revokes all sessions and
+    /// access tokens and clears any login lockout.
+    ///
+    /// # Errors
+    ///
+    /// - `AppError::ValidationError` if the new password is invalid or does not match
+    /// - `AppError::BadRequest` if the token is unknown, expired, already used or
+    ///   was issued for another tenant
+    pub async fn reset_password(
+        &self,
+        req: ResetPasswordRequest,
+        tenant_id: uuid::Uuid,
+        http_req: &actix_web::HttpRequest,
+    ) -> Result<(), AppError> {
+        let (ip_address, user_agent) = request_helper::extract_client_info(http_req);
+
+        if req.new_password != req.confirm_new_password {
+            return Err(AppError::ValidationError(
+                "Passwords do not match".to_string(),
+                Some(vec![ValidationDetail {
+                    field: "confirm_new_password".to_string(),
+                    message: "Passwords do not match".to_string(),
+                }]),
+            ));
+        }
+        user_validator::validate_password(&req.new_password, "new_password")?;
+
+        let invalid = || AppError::BadRequest("Invalid or expired reset token".to_string());
+
+        let token = self
+            .token_repository
+            .consume(
+                PURPOSE_PASSWORD_RESET,
+                &request_helper::hash_token(req.token.trim()),
+            )
+            .await?
+            .filter(|t| t.tenant_id == tenant_id)
+            .ok_or_else(invalid)?;
+
+        let user = self
+            .repository
+            .find_by_id(token.user_id)
+            .await?
+            .ok_or_else(invalid)?;
+
+        let update_req = UpdateUserRequest {
+            username: None,
+            email: None,
+            password: Some(req.new_password),
+        };
+        self.repository.update(user.id, update_req).await?;
+
+        // Completing an emailed reset proves control of the account, so it
+        // also lifts a login lockout
+        if user.failed_login_attempts > 0 || user.locked_until.is_some() {
+            self.repository.set_login_failures(user.id, 0, None).await?;
+        }
+
+        self.session_repository
+            .delete_all_sessions_for_user(user.id)
+            .await?;
+        self.auth_usecase.deny_issued_tokens(user.id).await?;
+
+        self.auth_usecase
+            .log_activity_success(Some(user.id), "password_reset", ip_address, user_agent)
+            .await;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,213 @@
+#[cfg(test)]
+mod tests {
+    use crate::domains::auth::usecases::password_reset_usecase::PasswordResetUseCase;
+    use crate::domains::auth::usecases::test_support::*;
+    use crate::domains::common::errors::AppError;
+    use crate::domains::common::infrastructures::email_templates::EmailTemplates;
+    use crate::domains::common::utils::config::Config;
+    use crate::domains::user::dtos::password_reset_dto::{
+        ForgotPasswordRequest, ResetPasswordRequest,
+    };
+    use crate::domains::user::entities::user::Model as User;
+
+    use actix_web::test::TestRequest;
+    use std::sync::{Arc, Mutex};
+    use uuid::Uuid;
+
+    struct ResetTestContext {
+        usecase: PasswordResetUseCase,
+        user: User,
+        tenant_id: Uuid,
+        mailer: Arc<FakeMailer>,
+        password_updates: Arc<Mutex<Vec<String>>>,
+    }
+
+    fn reset_test_setup(expected_revocations: usize) -> ResetTestContext {
+        Config::init_for_test();
+
+        let user = test_user("resetuser", "reset@example.com");
+        let tenant_id = Uuid::new_v4();
+        let password_updates = Arc::new(Mutex::new(vec![]));
+
+        let user_repo = {
+            let mut mock = MockUserRepository::new();
+            let user_clone = user.clone();
+            mock.expect_find_by_email_with_deleted()
+                .returning(move |email| Ok(Some(user_clone.clone()).filter(|u| u.email == email)));
+            let user_clone = user.clone();
+            mock.expect_find_by_id()
+                .returning(move |id| Ok(Some(user_clone.clone()).filter(|u| u.id == id)));
+            let user_clone = user.clone();
+            let updates = password_updates.clone();
+            mock.expect_update().returning(move |_, req| {
+                updates.lock().unwrap().push(req.password.unwrap());
+                Ok(user_clone.clone())
+            });
+            Arc::new(mock)
+        };
+        let user_tenant_repo = Arc::new(user_tenant_repository(tenant_id));
+
+        let mut session_repo = MockUserSessionRepository::new();
+        let user_id = user.id;
+        session_repo
+            .expect_delete_all_sessions_for_user()
+            .withf(move |id| *id == user_id)
+            .times(expected_revocations)
+            .returning(|_| Ok(()));
+        let session_repo = Arc::new(session_repo);
+
+        let mut denylist_repo = MockTokenDenylistRepository::new();
+        denylist_repo
+            .expect_deny_subject()
+            .withf(move |subject, _| subject == user_id.to_string())
+            .times(expected_revocations)
+            .returning(|_, _| Ok(()));
+
+        let auth_usecase = auth_usecase(
+            user_repo.clone(),
+            user_tenant_repo.clone(),
+            session_repo.clone(),
+            MockUserMfaRepository::new(),
+            tenant_repository(false, false),
+            denylist_repo,
+        );
+
+        let mailer = Arc::new(FakeMailer::default());
+        let usecase = PasswordResetUseCase::new(
+            user_repo,
+            user_tenant_repo,
+            session_repo,
+            Arc::new(FakeUserTokenRepository::default()),
+            mailer.clone(),
+            Arc::new(EmailTemplates::new(None, "en").unwrap()),
+            auth_usecase,
+        );
+
+        ResetTestContext {
+            usecase,
+            user,
+            tenant_id,
+            mailer,
+            password_updates,
+        }
+    }
+
+    fn reset_request(token: &str) -> ResetPasswordRequest {
+        ResetPasswordRequest {
+            token: token.to_string(),
+            new_password: "NewPassword123!".to_string(),
+            confirm_new_password: "NewPassword123!".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_password_reset_flow_revokes_sessions_and_tokens_and_is_single_use() {
+        let ctx = reset_test_setup(1);
+        let http_req = TestRequest::default().to_http_request();
+
+        ctx.usecase
+            .forgot_password(
+                ForgotPasswordRequest {
+                    email: "Reset@Example.com".to_string(),
+                },
+                ctx.tenant_id,
+                &http_req,
+            )
+            .await
+            .unwrap();
+
+        ctx.mailer.wait_for(1).await;
+        {
+            let sent = ctx.mailer.sent.lock().unwrap();
+            assert_eq!(sent.len(), 1);
+            assert_eq!(sent[0].to, ctx.user.email);
+            assert!(sent[0]
+                .text_body
+                .contains(&Config::get().password_reset_url));
+        }
+        let token = ctx.mailer.last_link_token();
+
+        ctx.usecase
+            .reset_password(reset_request(&token), ctx.tenant_id, &http_req)
+            .await
+            .unwrap();
+        assert_eq!(
+            *ctx.password_updates.lock().unwrap(),
+            vec!["NewPassword123!".to_string()]
+        );
+
+        let result = ctx
+            .usecase
+            .reset_password(reset_request(&token), ctx.tenant_id, &http_req)
+            .await;
+        assert!(matches!(result, Err(AppError::BadRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn test_forgot_password_unknown_email_is_indistinguishable() {
+        let ctx = reset_test_setup(0);
+        let http_req = TestRequest::default().to_http_request();
+
+        let result = ctx
+            .usecase
+            .forgot_password(
+                ForgotPasswordRequest {
+                    email: "nobody@example.com".to_string(),
+                },
+                ctx.tenant_id,
+                &http_req,
+            )
+            .await;
+        assert!(result.is_ok());
+
+        // A registered user outside the tenant is treated the same way
+        let result = ctx
+            .usecase
+            .forgot_password(
+                ForgotPasswordRequest {
+                    email: ctx.user.email.clone(),
+                },
+                Uuid::new_v4(),
+                &http_req,
+            )
+            .await;
+        assert!(result.is_ok());
+        assert!(ctx.mailer.sent.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_reset_password_rejects_superseded_and_foreign_tenant_tokens() {
+        let ctx = reset_test_setup(0);
+        let http_req = TestRequest::default().to_http_request();
+        let forgot = || ForgotPasswordRequest {
+            email: ctx.user.email.clone(),
+        };
+
+        ctx.usecase
+            .forgot_password(forgot(), ctx.tenant_id, &http_req)
+            .await
+            .unwrap();
+        ctx.mailer.wait_for(1).await;
+        let first_token = ctx.mailer.last_link_token();
+        ctx.usecase
+            .forgot_password(forgot(), ctx.tenant_id, &http_req)
+            .await
+            .unwrap();
+        ctx.mailer.wait_for(2).await;
+        let second_token = ctx.mailer.last_link_token();
+
+        // Requesting a new link invalidates the previous one
+        let result = ctx
+            .usecase
+            .reset_password(reset_request(&first_token), ctx.tenant_id, &http_req)
+            .await;
+        assert!(matches!(result, Err(AppError::BadRequest(_))));
+
+        let result = ctx
+            .usecase
+            .reset_password(reset_request(&second_token), Uuid::new_v4(), &http_req)
+            .await;
+        assert!(matches!(result, Err(AppError::BadRequest(_))));
+        assert!(ctx.password_updates.lock().unwrap().is_empty());
+    }
+}
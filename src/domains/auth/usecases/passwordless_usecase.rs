@@ -0,0 +1,346 @@
+use crate::domains::auth::repositories::passwordless_repository::{
+    PasswordlessChallenge, PasswordlessRepositoryTrait,
+};
+use crate::domains::auth::usecases::auth_usecase::AuthUseCase;
+use crate::domains::common::errors::{AppError, ValidationDetail};
+use crate::domains::common::infrastructures::email_templates::EmailTemplates;
+use crate::domains::common::infrastructures::mailer::Mailer;
+use crate::domains::common::utils::config::Config;
+use crate::domains::common::utils::jwt::{Authentication, AMR_OTP};
+use crate::domains::common::utils::request_helper;
+use crate::domains::tenant::repositories::tenant_repository::TenantRepositoryTrait;
+use crate::domains::tenant::repositories::user_tenant_repository::UserTenantRepositoryTrait;
+use crate::domains::user::dtos::auth_dto::LoginResult;
+use crate::domains::user::dtos::passwordless_dto::{
+    PasswordlessConsumeRequest, PasswordlessRequest,
+};
+use crate::domains::user::repositories::user_repository::UserRepositoryTrait;
+use chrono::Utc;
+use rand::Rng;
+use std::sync::Arc;
+
+/// Passwordless sign-in for tenants that enable `passwordless_login`.
+///
+/// A link and a 6-digit code are emailed to the user. Either one completes the
+/// sign-in once, and only in the browser that requested it: the request sets
+/// a device secret cookie that the challenge is bound to. Wrong codes count
+/// towards an attempt limit after which the challenge is dropped.
+pub struct PasswordlessUseCase {
+    repository: Arc<dyn UserRepositoryTrait>,
+    user_tenant_repository: Arc<dyn UserTenantRepositoryTrait>,
+    tenant_repository: Arc<dyn TenantRepositoryTrait>,
+    passwordless_repository: Arc<dyn PasswordlessRepositoryTrait>,
+    mailer: Arc<dyn Mailer>,
+    templates: Arc<EmailTemplates>,
+    auth_usecase: Arc<AuthUseCase>,
+}
+
+impl PasswordlessUseCase {
+    /// Creates a new PasswordlessUseCase instance.
+    pub fn new(
+        repository: Arc<dyn UserRepositoryTrait>,
+        user_tenant_repository: Arc<dyn UserTenantRepositoryTrait>,
+        tenant_repository: Arc<dyn TenantRepositoryTrait>,
+        passwordless_repository: Arc<dyn PasswordlessRepositoryTrait>,
+        mailer: Arc<dyn Mailer>,
+        templates: Arc<EmailTemplates>,
+        auth_usecase: Arc<AuthUseCase>,
+    ) -> Self {
+        Self {
+            repository,
+            user_tenant_repository,
+            tenant_repository,
+            passwordless_repository,
+            mailer,
+            templates,
+            auth_usecase,
+        }
+    }
+
+    /// Emails a sign-in link and code if the address belongs to a member of the tenant.
+    ///
+    /// Returns the device secret to set as a cookie. It is returned whether or
+    /// not the email is registered, so that the endpoint cannot be used to
+    /// enumerate accounts. A new request replaces the user's pending one.
+    ///
+    /// # Errors
+    ///
+    /// - `AppError::ValidationError` if the email is empty
+    /// - `AppError::Forbidden` if the tenant has not enabled passwordless sign-in
+    pub async fn request(
+        &self,
+        req: PasswordlessRequest,
+        tenant_id: uuid::Uuid,
+        http_req: &actix_web::HttpRequest,
+    ) -> Result<String, AppError> {
+        let (ip_address, user_agent) = request_helper::extract_client_info(http_req);
+        let config = Config::get();
+
+        let email = req.email.trim().to_lowercase();
+        if email.is_empty() {
+            return Err(AppError::ValidationError(
+                "Email is required".to_string(),
+                Some(vec![ValidationDetail {
+                    field: "email".to_string(),
+                    message: "Email is required".to_string(),
+                }]),
+            ));
+        }
+
+        self.ensure_enabled(tenant_id).await?;
+
+        let device_secret = request_helper::generate_token();
+
+        let Some(user) = self
+            .repository
+            .find_by_email_with_deleted(&email)
+            .await?
+            .filter(|u| u.deleted_at.is_none())
+        else {
+            return Ok(device_secret);
+        };
+
+        let roles = self
+            .user_tenant_repository
+            .get_user_roles_in_tenant(user.id, tenant_id)
+            .await?;
+        if roles.is_empty() {
+            return Ok(device_secret);
+        }
+
+        let link_token = request_helper::generate_token();
+        let code = format!("{:06}", rand::rng().random_range(0..1_000_000));
+        self.passwordless_repository
+            .save(&PasswordlessChallenge {
+                device_hash: request_helper::hash_token(&device_secret),
+                link_hash: request_helper::hash_token(&link_token),
+                code_hash: Self::hash_code(&device_secret, &code),
+                user_id: user.id,
+                tenant_id,
+                attempts: 0,
+                expires_at: Utc::now().timestamp() + config.passwordless_token_expiry,
+            })
+            .await?;
+
+        let rendered = self.templates.render(
+            "passwordless_login",
+            tenant_id,
+            request_helper::preferred_locale(http_req).as_deref(),
+            &user.email,
+            &serde_json::json!({
+                "username": user.username,
+                "link": format!("{}?token={}", config.passwordless_login_url, link_token),
+                "code": code,
+                "expires_in_minutes": config.passwordless_token_expiry / 60,
+            }),
+        );
+
+        // Sent in the background so the response time is the same for unknown emails
+        self.auth_usecase.send_email_in_background(
+            self.mailer.clone(),
+            rendered,
+            user.id,
+            "passwordless_request",
+            (ip_address, user_agent),
+        );
+
+        Ok(device_secret)
+    }
+
+    /// Completes a passwordless sign-in with the emailed link token or code.
+    ///
+    /// `device_secret` is the cookie set by `request`. On success the
+    /// challenge is consumed, the email counts as verified and the session is
+    /// created like a password login, including the MFA challenge for users
+    /// with a second factor.
+    ///
+    /// # Errors
+    ///
+    /// - `AppError::ValidationError` if neither a token nor a code is given
+    /// - `AppError::Forbidden` if the tenant has not enabled passwordless
+    ///   sign-in, the link is opened on another device, or the account is not active
+    /// - `AppError::BadRequest` if the token or code is unknown, expired, already
+    ///   used or wrong
+    /// - `AppError::NotFound` if a requested role is not held by the user
+    pub async fn consume(
+        &self,
+        req: PasswordlessConsumeRequest,
+        tenant_id: uuid::Uuid,
+        device_secret: Option<&str>,
+        http_req: &actix_web::HttpRequest,
+    ) -> Result<LoginResult, AppError> {
+        let (ip_address, user_agent) = request_helper::extract_client_info(http_req);
+
+        self.ensure_enabled(tenant_id).await?;
+
+        let invalid = || AppError::BadRequest("Invalid or expired sign-in code".to_string());
+        let other_device = || {
+            AppError::Forbidden(
+                "Sign-in must be completed on the device that requested it".to_string(),
+            )
+        };
+        let device_secret = device_secret.filter(|s| !s.is_empty());
+
+        let token = req
+            .token
+            .as_deref()
+            .map(str::trim)
+            .filter(|t| !t.is_empty());
+        let code = req.code.as_deref().map(str::trim).filter(|c| !c.is_empty());
+
+        let challenge = match (token, code) {
+            (Some(token), _) => {
+                let challenge = self
+                    .passwordless_repository
+                    .find_by_link(token)
+                    .await?
+                    .ok_or_else(invalid)?;
+
+                // The link stays valid for the requesting device
+                let same_device = device_secret.is_some_and(|secret| {
+                    request_helper::hash_token(secret) == challenge.device_hash
+                });
+                if !same_device {
+                    let err = other_device();
+                    self.auth_usecase
+                        .log_activity_failure(
+                            Some(challenge.user_id),
+                            "passwordless_login",
+                            &err,
+                            ip_address,
+                            user_agent,
+                        )
+                        .await;
+                    return Err(err);
+                }
+                challenge
+            }
+            (None, Some(code)) => {
+                let device_secret = device_secret.ok_or_else(other_device)?;
+                let mut challenge = self
+                    .passwordless_repository
+                    .find_by_device(device_secret)
+                    .await?
+                    .ok_or_else(invalid)?;
+
+                if Self::hash_code(device_secret, code) != challenge.code_hash {
+                    challenge.attempts += 1;
+                    if challenge.attempts >= Config::get().passwordless_max_attempts {
+                        self.passwordless_repository.delete(&challenge).await?;
+                    } else {
+                        self.passwordless_repository.save(&challenge).await?;
+                    }
+
+                    let err = invalid();
+                    self.auth_usecase
+                        .log_activity_failure(
+                            Some(challenge.user_id),
+                            "passwordless_login",
+                            &err,
+                            ip_address,
+                            user_agent,
+                        )
+                        .await;
+                    return Err(err);
+                }
+                challenge
+            }
+            (None, None) => {
+                return Err(AppError::ValidationError(
+                    "Token or code is required".to_string(),
+                    Some(vec![ValidationDetail {
+                        field: "code".to_string(),
+                        message: "Token or code is required".to_string(),
+                    }]),
+                ));
+            }
+        };
+
+        if challenge.tenant_id != tenant_id || challenge.expires_at <= Utc::now().timestamp() {
+            return Err(invalid());
+        }
+        self.passwordless_repository.delete(&challenge).await?;
+
+        let user = self
+            .repository
+            .find_by_id(challenge.user_id)
+            .await?
+            .ok_or_else(invalid)?;
+
+        if let Err(err) = AuthUseCase::ensure_active(&user) {
+            self.auth_usecase
+                .log_activity_failure(
+                    Some(user.id),
+                    "passwordless_login",
+                    &err,
+                    ip_address,
+                    user_agent,
+                )
+                .await;
+            return Err(err);
+        }
+
+        let roles = self
+            .user_tenant_repository
+            .get_user_roles_in_tenant(user.id, tenant_id)
+            .await?;
+
+        if roles.is_empty() {
+            return Err(AppError::Unauthorized(
+                "User not authorized for this tenant".to_string(),
+            ));
+        }
+
+        let Some(role) = AuthUseCase::select_role(&roles, req.role.as_deref()) else {
+            let err = AppError::NotFound("User not found".to_string());
+            self.auth_usecase
+                .log_activity_failure(
+                    Some(user.id),
+                    "login_role_mismatch",
+                    &err,
+                    ip_address,
+                    user_agent,
+                )
+                .await;
+            return Err(err);
+        };
+
+        // Receiving the email proves control of the address
+        if user.email_verified_at.is_none() {
+            self.repository.mark_email_verified(user.id).await?;
+        }
+
+        self.auth_usecase
+            .start_session(
+                user.id,
+                tenant_id,
+                role,
+                &Authentication::now(&[AMR_OTP]),
+                ip_address,
+                user_agent,
+            )
+            .await
+    }
+
+    /// Rejects tenants that have not enabled passwordless sign-in.
+    async fn ensure_enabled(&self, tenant_id: uuid::Uuid) -> Result<(), AppError> {
+        let enabled = self
+            .tenant_repository
+            .find_by_id(tenant_id)
+            .await?
+            .is_some_and(|tenant| tenant.passwordless_login);
+        if !enabled {
+            return Err(AppError::Forbidden(
+                "Passwordless sign-in is not enabled for this tenant".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Hashes a code with the device secret, so a stored hash cannot be
+    /// reversed by trying all codes without the secret.
+    fn hash_code(device_secret: &str, code: &str) -> String {
+        request_helper::hash_token(&format!("{}:{}", device_secret, code))
+    }
+}
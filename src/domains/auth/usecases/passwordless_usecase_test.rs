@@ -0,0 +1,387 @@
+#[cfg(test)]
+mod tests {
+    use crate::domains::auth::repositories::passwordless_repository::{
+        PasswordlessChallenge, PasswordlessRepositoryTrait,
+    };
+    use crate::domains::auth::usecases::passwordless_usecase::PasswordlessUseCase;
+    use crate::domains::auth::usecases::test_support::*;
+    use crate::domains::common::errors::AppError;
+    use crate::domains::common::infrastructures::email_templates::EmailTemplates;
+    use crate::domains::common::utils::config::Config;
+    use crate::domains::common::utils::jwt::{JwtService, AMR_OTP};
+    use crate::domains::common::utils::request_helper;
+    use crate::domains::user::dtos::auth_dto::LoginResult;
+    use crate::domains::user::dtos::passwordless_dto::{
+        PasswordlessConsumeRequest, PasswordlessRequest,
+    };
+    use crate::domains::user::entities::user::Model as User;
+    use crate::domains::user::entities::user_session::Model as UserSession;
+
+    use actix_web::test::TestRequest;
+    use async_trait::async_trait;
+    use chrono::Utc;
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+    use uuid::Uuid;
+
+    // Fake PasswordlessRepository backed by a map keyed by device hash
+    #[derive(Default)]
+    struct FakePasswordlessRepository {
+        challenges: Mutex<HashMap<String, PasswordlessChallenge>>,
+    }
+
+    #[async_trait]
+    impl PasswordlessRepositoryTrait for FakePasswordlessRepository {
+        async fn save(&self, challenge: &PasswordlessChallenge) -> Result<(), AppError> {
+            let mut challenges = self.challenges.lock().unwrap();
+            challenges.retain(|device_hash, c| {
+                c.user_id != challenge.user_id || *device_hash == challenge.device_hash
+            });
+            challenges.insert(challenge.device_hash.clone(), challenge.clone());
+            Ok(())
+        }
+
+        async fn find_by_device(
+            &self,
+            device_secret: &str,
+        ) -> Result<Option<PasswordlessChallenge>, AppError> {
+            let device_hash = request_helper::hash_token(device_secret);
+            Ok(self.challenges.lock().unwrap().get(&device_hash).cloned())
+        }
+
+        async fn find_by_link(
+            &self,
+            link_token: &str,
+        ) -> Result<Option<PasswordlessChallenge>, AppError> {
+            let link_hash = request_helper::hash_token(link_token);
+            Ok(self
+                .challenges
+                .lock()
+                .unwrap()
+                .values()
+                .find(|c| c.link_hash == link_hash)
+                .cloned())
+        }
+
+        async fn delete(&self, challenge: &PasswordlessChallenge) -> Result<(), AppError> {
+            self.challenges
+                .lock()
+                .unwrap()
+                .remove(&challenge.device_hash);
+            Ok(())
+        }
+    }
+
+    struct PasswordlessTestContext {
+        usecase: PasswordlessUseCase,
+        user: User,
+        tenant_id: Uuid,
+        mailer: Arc<FakeMailer>,
+    }
+
+    fn passwordless_test_setup(enabled: bool) -> PasswordlessTestContext {
+        Config::init_for_test();
+
+        let user = test_user("magicuser", "magic@example.com");
+        let tenant_id = Uuid::new_v4();
+
+        let user_repo = {
+            let mut mock = MockUserRepository::new();
+            let user_clone = user.clone();
+            mock.expect_find_by_email_with_deleted()
+                .returning(move |email| Ok(Some(user_clone.clone()).filter(|u| u.email == email)));
+            let user_clone = user.clone();
+            mock.expect_find_by_id()
+                .returning(move |id| Ok(Some(user_clone.clone()).filter(|u| u.id == id)));
+            let user_clone = user.clone();
+            mock.expect_mark_email_verified().returning(move |_| {
+                Ok(User {
+                    email_verified_at: Some(Utc::now()),
+                    ..user_clone.clone()
+                })
+            });
+            Arc::new(mock)
+        };
+        let user_tenant_repo = Arc::new(user_tenant_repository(tenant_id));
+
+        let mut session_repo = MockUserSessionRepository::new();
+        session_repo
+            .expect_create_session()
+            .returning(|id, user_id, _, _, _, expires_at| {
+                Ok(UserSession {
+                    id: id.unwrap_or_else(Uuid::new_v4),
+                    user_id,
+                    refresh_token_hash: "hash".to_string(),
+                    user_agent: None,
+                    ip_address: None,
+                    expires_at,
+                    created_at: Utc::now(),
+                    family_id: Uuid::new_v4(),
+                    parent_id: None,
+                    rotated_at: None,
+                    last_used_at: None,
+                })
+            });
+
+        let mut mfa_repo = MockUserMfaRepository::new();
+        mfa_repo.expect_find_by_user_id().returning(|_| Ok(None));
+
+        let auth_usecase = auth_usecase(
+            user_repo.clone(),
+            user_tenant_repo.clone(),
+            Arc::new(session_repo),
+            mfa_repo,
+            tenant_repository(false, enabled),
+            MockTokenDenylistRepository::new(),
+        );
+
+        let mailer = Arc::new(FakeMailer::default());
+        let usecase = PasswordlessUseCase::new(
+            user_repo,
+            user_tenant_repo,
+            Arc::new(tenant_repository(false, enabled)),
+            Arc::new(FakePasswordlessRepository::default()),
+            mailer.clone(),
+            Arc::new(EmailTemplates::new(None, "en").unwrap()),
+            auth_usecase,
+        );
+
+        PasswordlessTestContext {
+            usecase,
+            user,
+            tenant_id,
+            mailer,
+        }
+    }
+
+    /// Extracts the link token and the code from the last sent email.
+    fn secrets_from_last_email(mailer: &FakeMailer) -> (String, String) {
+        let sent = mailer.sent.lock().unwrap();
+        let body = &sent.last().unwrap().text_body;
+        let start = body.find("?token=").unwrap() + "?token=".len();
+        let token = body[start..]
+            .chars()
+            .take_while(|c| c.is_ascii_alphanumeric() || *c == '-' || *c == '_')
+            .collect();
+        let start = body.find("sign-in page: ").unwrap() + "sign-in page: ".len();
+        let code = body[start..]
+            .chars()
+            .take_while(|c| c.is_ascii_digit())
+            .collect();
+        (token, code)
+    }
+
+    fn request(email: &str) -> PasswordlessRequest {
+        PasswordlessRequest {
+            email: email.to_string(),
+        }
+    }
+
+    fn with_token(token: &str) -> PasswordlessConsumeRequest {
+        PasswordlessConsumeRequest {
+            token: Some(token.to_string()),
+            code: None,
+            role: None,
+        }
+    }
+
+    fn with_code(code: &str) -> PasswordlessConsumeRequest {
+        PasswordlessConsumeRequest {
+            token: None,
+            code: Some(code.to_string()),
+            role: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_passwordless_link_signs_in_once_on_requesting_device() {
+        let ctx = passwordless_test_setup(true);
+        let http_req = TestRequest::default().to_http_request();
+
+        let device = ctx
+            .usecase
+            .request(request("Magic@Example.com"), ctx.tenant_id, &http_req)
+            .await
+            .unwrap();
+        ctx.mailer.wait_for(1).await;
+        {
+            let sent = ctx.mailer.sent.lock().unwrap();
+            assert_eq!(sent.len(), 1);
+            assert_eq!(sent[0].to, ctx.user.email);
+            assert!(sent[0]
+                .text_body
+                .contains(&Config::get().passwordless_login_url));
+        }
+        let (token, code) = secrets_from_last_email(&ctx.mailer);
+        assert_eq!(code.len(), 6);
+
+        // Opening the link in another browser does not use it up
+        let other_device = request_helper::generate_token();
+        for device in [None, Some(other_device.as_str())] {
+            let result = ctx
+                .usecase
+                .consume(with_token(&token), ctx.tenant_id, device, &http_req)
+                .await;
+            assert!(matches!(result, Err(AppError::Forbidden(_))));
+        }
+
+        let result = ctx
+            .usecase
+            .consume(with_token(&token), ctx.tenant_id, Some(&device), &http_req)
+            .await
+            .unwrap();
+        let LoginResult::Authenticated(auth_response, refresh_token) = result else {
+            panic!("Expected session tokens");
+        };
+        assert_eq!(auth_response.user_id, ctx.user.id);
+        assert!(!refresh_token.is_empty());
+        let claims = JwtService::new()
+            .validate_token(&auth_response.access_token)
+            .unwrap();
+        assert_eq!(claims.tenant_id, ctx.tenant_id.to_string());
+        assert_eq!(claims.amr, Some(vec![AMR_OTP.to_string()]));
+
+        // Both the link and the code are single-use
+        let result = ctx
+            .usecase
+            .consume(with_token(&token), ctx.tenant_id, Some(&device), &http_req)
+            .await;
+        assert!(matches!(result, Err(AppError::BadRequest(_))));
+        let result = ctx
+            .usecase
+            .consume(with_code(&code), ctx.tenant_id, Some(&device), &http_req)
+            .await;
+        assert!(matches!(result, Err(AppError::BadRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn test_passwordless_code_requires_device_and_wrong_codes_are_counted() {
+        let ctx = passwordless_test_setup(true);
+        let http_req = TestRequest::default().to_http_request();
+
+        let device = ctx
+            .usecase
+            .request(request(&ctx.user.email), ctx.tenant_id, &http_req)
+            .await
+            .unwrap();
+        ctx.mailer.wait_for(1).await;
+        let (_, code) = secrets_from_last_email(&ctx.mailer);
+        let wrong = if code == "000000" { "000001" } else { "000000" };
+
+        let result = ctx
+            .usecase
+            .consume(with_code(&code), ctx.tenant_id, None, &http_req)
+            .await;
+        assert!(matches!(result, Err(AppError::Forbidden(_))));
+
+        let result = ctx
+            .usecase
+            .consume(with_code(wrong), ctx.tenant_id, Some(&device), &http_req)
+            .await;
+        assert!(matches!(result, Err(AppError::BadRequest(_))));
+
+        // A wrong guess below the limit keeps the challenge
+        let result = ctx
+            .usecase
+            .consume(with_code(&code), ctx.tenant_id, Some(&device), &http_req)
+            .await;
+        assert!(matches!(result, Ok(LoginResult::Authenticated(..))));
+
+        // Reaching the limit drops the challenge, even for the right code
+        let device = ctx
+            .usecase
+            .request(request(&ctx.user.email), ctx.tenant_id, &http_req)
+            .await
+            .unwrap();
+        ctx.mailer.wait_for(2).await;
+        let (_, code) = secrets_from_last_email(&ctx.mailer);
+        let wrong = if code == "000000" { "000001" } else { "000000" };
+        for _ in 0..Config::get().passwordless_max_attempts {
+            let result = ctx
+                .usecase
+                .consume(with_code(wrong), ctx.tenant_id, Some(&device), &http_req)
+                .await;
+            assert!(matches!(result, Err(AppError::BadRequest(_))));
+        }
+        let result = ctx
+            .usecase
+            .consume(with_code(&code), ctx.tenant_id, Some(&device), &http_req)
+            .await;
+        assert!(matches!(result, Err(AppError::BadRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn test_passwordless_new_request_and_other_tenant_invalidate_challenge() {
+        let ctx = passwordless_test_setup(true);
+        let http_req = TestRequest::default().to_http_request();
+
+        let first_device = ctx
+            .usecase
+            .request(request(&ctx.user.email), ctx.tenant_id, &http_req)
+            .await
+            .unwrap();
+        ctx.mailer.wait_for(1).await;
+        let (first_token, _) = secrets_from_last_email(&ctx.mailer);
+        let device = ctx
+            .usecase
+            .request(request(&ctx.user.email), ctx.tenant_id, &http_req)
+            .await
+            .unwrap();
+        ctx.mailer.wait_for(2).await;
+        let (token, _) = secrets_from_last_email(&ctx.mailer);
+
+        let result = ctx
+            .usecase
+            .consume(
+                with_token(&first_token),
+                ctx.tenant_id,
+                Some(&first_device),
+                &http_req,
+            )
+            .await;
+        assert!(matches!(result, Err(AppError::BadRequest(_))));
+
+        let result = ctx
+            .usecase
+            .consume(with_token(&token), Uuid::new_v4(), Some(&device), &http_req)
+            .await;
+        assert!(matches!(result, Err(AppError::BadRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn test_passwordless_request_unknown_email_is_indistinguishable() {
+        let ctx = passwordless_test_setup(true);
+        let http_req = TestRequest::default().to_http_request();
+
+        let result = ctx
+            .usecase
+            .request(request("nobody@example.com"), ctx.tenant_id, &http_req)
+            .await;
+        assert!(result.is_ok_and(|device| !device.is_empty()));
+        assert!(ctx.mailer.sent.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_passwordless_requires_tenant_opt_in() {
+        let ctx = passwordless_test_setup(false);
+        let http_req = TestRequest::default().to_http_request();
+
+        let result = ctx
+            .usecase
+            .request(request(&ctx.user.email), ctx.tenant_id, &http_req)
+            .await;
+        assert!(matches!(result, Err(AppError::Forbidden(_))));
+
+        let result = ctx
+            .usecase
+            .consume(
+                with_code("123456"),
+                ctx.tenant_id,
+                Some("device"),
+                &http_req,
+            )
+            .await;
+        assert!(matches!(result, Err(AppError::Forbidden(_))));
+        assert!(ctx.mailer.sent.lock().unwrap().is_empty());
+    }
+}
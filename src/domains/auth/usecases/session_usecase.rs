@@ -0,0 +1,143 @@
+use crate::domains::auth::usecases::auth_usecase::AuthUseCase;
+use crate::domains::common::errors::AppError;
+use crate::domains::common::utils::request_helper;
+use crate::domains::user::dtos::session_dto::SessionResponse;
+use crate::domains::user::entities::user_session::Model as UserSession;
+use crate::domains::user::repositories::user_session_repository::UserSessionRepositoryTrait;
+use std::sync::Arc;
+
+/// Lets users review the devices they are signed in on and sign them out.
+///
+/// A device is the latest session of a refresh token family. Revoking it
+/// deletes the whole family, so neither its current nor any earlier refresh
+/// token can be used again.
+pub struct SessionUseCase {
+    session_repository: Arc<dyn UserSessionRepositoryTrait>,
+    auth_usecase: Arc<AuthUseCase>,
+}
+
+impl SessionUseCase {
+    /// Creates a new SessionUseCase instance.
+    pub fn new(
+        session_repository: Arc<dyn UserSessionRepositoryTrait>,
+        auth_usecase: Arc<AuthUseCase>,
+    ) -> Self {
+        Self {
+            session_repository,
+            auth_usecase,
+        }
+    }
+
+    /// Lists the authenticated user's active sessions, most recently used first.
+    ///
+    /// The session identified by the request's refresh token cookie is flagged
+    /// as `current`.
+    pub async fn list_sessions(
+        &self,
+        user_id: uuid::Uuid,
+        http_req: &actix_web::HttpRequest,
+    ) -> Result<Vec<SessionResponse>, AppError> {
+        let current_id = self.current_session(user_id, http_req).await?.map(|s| s.id);
+
+        let mut sessions: Vec<SessionResponse> = self
+            .session_repository
+            .find_active_by_user_id(user_id)
+            .await?
+            .into_iter()
+            .map(|s| SessionResponse {
+                current: Some(s.id) == current_id,
+                id: s.id,
+                user_agent: s.user_agent,
+                ip_address: s.ip_address,
+                created_at: s.created_at,
+                last_used_at: s.last_used_at,
+                expires_at: s.expires_at,
+            })
+            .collect();
+        sessions.sort_by_key(|s| std::cmp::Reverse(s.last_used_at.unwrap_or(s.created_at)));
+
+        Ok(sessions)
+    }
+
+    /// Signs the authenticated user out of one device.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::NotFound` if the user has no active session with this ID.
+    pub async fn revoke_session(
+        &self,
+        user_id: uuid::Uuid,
+        session_id: uuid::Uuid,
+        http_req: &actix_web::HttpRequest,
+    ) -> Result<(), AppError> {
+        let (ip_address, user_agent) = request_helper::extract_client_info(http_req);
+
+        let session = self
+            .session_repository
+            .find_by_id(session_id)
+            .await?
+            .filter(|s| s.user_id == user_id && s.rotated_at.is_none())
+            .ok_or_else(|| AppError::NotFound("Session not found".to_string()))?;
+
+        self.session_repository
+            .delete_family(session.family_id)
+            .await?;
+
+        self.auth_usecase
+            .log_activity_success(Some(user_id), "session_revoke", ip_address, user_agent)
+            .await;
+
+        Ok(())
+    }
+
+    /// Signs the authenticated user out of every device except the current one.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Unauthorized` if the request carries no refresh token
+    /// cookie for one of the user's sessions.
+    pub async fn revoke_other_sessions(
+        &self,
+        user_id: uuid::Uuid,
+        http_req: &actix_web::HttpRequest,
+    ) -> Result<(), AppError> {
+        let (ip_address, user_agent) = request_helper::extract_client_info(http_req);
+
+        let current = self
+            .current_session(user_id, http_req)
+            .await?
+            .ok_or_else(|| AppError::Unauthorized("Refresh token not found".to_string()))?;
+
+        self.session_repository
+            .delete_other_sessions_for_user(user_id, current.family_id)
+            .await?;
+
+        self.auth_usecase
+            .log_activity_success(
+                Some(user_id),
+                "session_revoke_others",
+                ip_address,
+                user_agent,
+            )
+            .await;
+
+        Ok(())
+    }
+
+    /// Resolves the user's unrotated session from the refresh token cookie.
+    async fn current_session(
+        &self,
+        user_id: uuid::Uuid,
+        http_req: &actix_web::HttpRequest,
+    ) -> Result<Option<UserSession>, AppError> {
+        let Some(cookie) = http_req.cookie("refresh_token") else {
+            return Ok(None);
+        };
+
+        Ok(self
+            .session_repository
+            .find_by_refresh_token_hash(&request_helper::hash_token(cookie.value()))
+            .await?
+            .filter(|s| s.user_id == user_id && s.rotated_at.is_none()))
+    }
+}
@@ -0,0 +1,485 @@
+#[cfg(test)]
+mod tests {
+    use crate::domains::auth::repositories::invitation_code_repository::InvitationCodeRepositoryTrait;
+    use crate::domains::auth::repositories::token_denylist_repository::TokenDenylistRepositoryTrait;
+    use crate::domains::auth::usecases::auth_usecase::AuthUseCase;
+    use crate::domains::auth::usecases::session_usecase::SessionUseCase;
+    use crate::domains::common::errors::AppError;
+    use crate::domains::common::utils::request_helper;
+    use crate::domains::tenant::entities::tenant::Model as Tenant;
+    use crate::domains::tenant::repositories::tenant_repository::TenantRepositoryTrait;
+    use crate::domains::tenant::repositories::user_tenant_repository::{
+        UserTenantInfo, UserTenantRepositoryTrait,
+    };
+    use crate::domains::user::entities::user::Model as User;
+    use crate::domains::user::entities::user_activity_log::Model as UserActivityLog;
+    use crate::domains::user::entities::user_details::Model as UserDetails;
+    use crate::domains::user::entities::user_mfa::Model as UserMfa;
+    use crate::domains::user::entities::user_recovery_code::Model as UserRecoveryCode;
+    use crate::domains::user::entities::user_session::Model as UserSession;
+    use crate::domains::user::repositories::user_activity_log_repository::UserActivityLogRepositoryTrait;
+    use crate::domains::user::repositories::user_details_repository::UserDetailsRepositoryTrait;
+    use crate::domains::user::repositories::user_mfa_repository::UserMfaRepositoryTrait;
+    use crate::domains::user::repositories::user_recovery_code_repository::UserRecoveryCodeRepositoryTrait;
+    use crate::domains::user::repositories::user_repository::UserRepositoryTrait;
+    use crate::domains::user::repositories::user_session_repository::UserSessionRepositoryTrait;
+
+    use actix_web::cookie::Cookie;
+    use actix_web::test::TestRequest;
+    use async_trait::async_trait;
+    use chrono::{DateTime, Duration, Utc};
+    use mockall::mock;
+    use std::sync::{Arc, Mutex};
+    use uuid::Uuid;
+
+    mock! {
+        pub TokenDenylistRepository {}
+        #[async_trait]
+        impl TokenDenylistRepositoryTrait for TokenDenylistRepository {
+            async fn deny(&self, jti: &str, ttl: std::time::Duration) -> Result<(), AppError>;
+            async fn is_denied(&self, jti: &str) -> Result<bool, AppError>;
+            async fn deny_subject(&self, subject: &str, ttl: std::time::Duration) -> Result<(), AppError>;
+            async fn subject_denied_at(&self, subject: &str) -> Result<Option<i64>, AppError>;
+        }
+    }
+
+    mock! {
+        pub UserRepository {}
+        #[async_trait]
+        impl UserRepositoryTrait for UserRepository {
+            async fn create(&self, user: crate::domains::user::dtos::user_dto::CreateUserRequest) -> Result<User, AppError>;
+            async fn find_by_id(&self, id: Uuid) -> Result<Option<User>, AppError>;
+            async fn find_by_username(&self, username: &str) -> Result<Option<User>, AppError>;
+            async fn find_all(&self) -> Result<Vec<User>, AppError>;
+            async fn update(&self, id: Uuid, user: crate::domains::user::dtos::user_dto::UpdateUserRequest) -> Result<User, AppError>;
+            async fn delete(&self, id: Uuid) -> Result<(), AppError>;
+            async fn find_by_email_with_deleted(&self, email: &str) -> Result<Option<User>, AppError>;
+            async fn find_by_username_with_deleted(&self, username: &str) -> Result<Option<User>, AppError>;
+            async fn restore(&self, id: Uuid, req: crate::domains::user::dtos::user_dto::CreateUserRequest) -> Result<User, AppError>;
+            async fn mark_email_verified(&self, id: Uuid) -> Result<User, AppError>;
+            async fn set_login_failures(&self, id: Uuid, failed_login_attempts: i32, locked_until: Option<DateTime<Utc>>) -> Result<(), AppError>;
+            async fn increment_login_failures(&self, id: Uuid) -> Result<i32, AppError>;
+            async fn lock_until(&self, id: Uuid, locked_until: DateTime<Utc>) -> Result<(), AppError>;
+            async fn set_status(&self, id: Uuid, status: String, reason: Option<String>, expires_at: Option<DateTime<Utc>>) -> Result<User, AppError>;
+            async fn purge_deleted_before(&self, cutoff: DateTime<Utc>) -> Result<u64, AppError>;
+        }
+    }
+
+    // Mocking TenantRepositoryTrait
+    mock! {
+        pub TenantRepository {}
+        #[async_trait]
+        impl TenantRepositoryTrait for TenantRepository {
+            async fn create(&self, tenant: crate::domains::tenant::dtos::tenant_dto::CreateTenantRequest) -> Result<Tenant, AppError>;
+            async fn find_by_id(&self, id: Uuid) -> Result<Option<Tenant>, AppError>;
+            async fn find_by_name(&self, name: &str) -> Result<Option<Tenant>, AppError>;
+            async fn find_by_name_with_deleted(&self, name: &str) -> Result<Option<Tenant>, AppError>;
+            async fn find_all(&self) -> Result<Vec<Tenant>, AppError>;
+            async fn update(&self, id: Uuid, tenant: crate::domains::tenant::dtos::tenant_dto::UpdateTenantRequest) -> Result<Tenant, AppError>;
+            async fn delete(&self, id: Uuid) -> Result<(), AppError>;
+            async fn restore(&self, id: Uuid) -> Result<(), AppError>;
+            async fn purge_deleted_before(&self, cutoff: DateTime<Utc>) -> Result<u64, AppError>;
+        }
+    }
+
+    /// Builds a tenant repository whose tenants have the given email verification policy.
+    fn tenant_repository(require_email_verification: bool) -> MockTenantRepository {
+        let mut repo = MockTenantRepository::new();
+        repo.expect_find_by_id().returning(move |id| {
+            Ok(Some(Tenant {
+                id,
+                name: "test-tenant".to_string(),
+                description: None,
+                api_key: None,
+                require_email_verification,
+                passwordless_login: false,
+                rate_limits: None,
+                deleted_at: None,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+            }))
+        });
+        repo
+    }
+
+    mock! {
+        pub UserDetailsRepository {}
+        #[async_trait]
+        impl UserDetailsRepositoryTrait for UserDetailsRepository {
+            async fn create(&self, user_id: Uuid) -> Result<UserDetails, AppError>;
+            async fn find_by_user_id(&self, user_id: Uuid) -> Result<Option<UserDetails>, AppError>;
+            async fn update(&self, user_id: Uuid, full_name: Option<String>, phone_number: Option<String>, address: Option<String>, date_of_birth: Option<chrono::NaiveDate>) -> Result<UserDetails, AppError>;
+            async fn update_profile_picture(&self, user_id: Uuid, profile_picture_url: String) -> Result<UserDetails, AppError>;
+        }
+    }
+
+    mock! {
+        pub UserTenantRepository {}
+        #[async_trait]
+        impl UserTenantRepositoryTrait for UserTenantRepository {
+            async fn add_user_to_tenant(&self, user_id: Uuid, tenant_id: Uuid, role: String) -> Result<(), AppError>;
+            async fn get_user_roles_in_tenant(&self, user_id: Uuid, tenant_id: Uuid) -> Result<Vec<String>, AppError>;
+            async fn get_all_tenants_for_user(&self, user_id: Uuid) -> Result<Vec<UserTenantInfo>, AppError>;
+        }
+    }
+
+    mock! {
+        pub UserActivityLogRepository {}
+        #[async_trait]
+        impl UserActivityLogRepositoryTrait for UserActivityLogRepository {
+            async fn log_activity(&self, user_id: Option<Uuid>, activity_type: String, status: String, error_message: Option<String>, ip_address: Option<String>, user_agent: Option<String>) -> Result<UserActivityLog, AppError>;
+        }
+    }
+
+    mock! {
+        pub InvitationCodeRepository {}
+        #[async_trait]
+        impl InvitationCodeRepositoryTrait for InvitationCodeRepository {
+            async fn save_code(&self, code: String, ttl: std::time::Duration) -> Result<(), AppError>;
+            async fn validate_and_delete_code(&self, code: &str) -> Result<bool, AppError>;
+        }
+    }
+
+    mock! {
+        pub UserMfaRepository {}
+        #[async_trait]
+        impl UserMfaRepositoryTrait for UserMfaRepository {
+            async fn find_by_user_id(&self, user_id: Uuid) -> Result<Option<UserMfa>, AppError>;
+            async fn upsert_pending(&self, user_id: Uuid, totp_secret: String) -> Result<UserMfa, AppError>;
+            async fn enable(&self, user_id: Uuid, used_step: i64) -> Result<UserMfa, AppError>;
+            async fn update_last_used_step(&self, user_id: Uuid, used_step: i64) -> Result<bool, AppError>;
+            async fn delete_by_user_id(&self, user_id: Uuid) -> Result<(), AppError>;
+        }
+    }
+
+    mock! {
+        pub UserRecoveryCodeRepository {}
+        #[async_trait]
+        impl UserRecoveryCodeRepositoryTrait for UserRecoveryCodeRepository {
+            async fn replace_for_user(&self, user_id: Uuid, code_hashes: Vec<String>) -> Result<(), AppError>;
+            async fn find_unused_by_user_id(&self, user_id: Uuid) -> Result<Vec<UserRecoveryCode>, AppError>;
+            async fn mark_used(&self, id: Uuid) -> Result<bool, AppError>;
+        }
+    }
+
+    // Fake UserSessionRepository backed by a Vec
+    struct FakeUserSessionRepository {
+        sessions: Mutex<Vec<UserSession>>,
+    }
+
+    #[async_trait]
+    impl UserSessionRepositoryTrait for FakeUserSessionRepository {
+        async fn create_session(
+            &self,
+            id: Option<Uuid>,
+            user_id: Uuid,
+            refresh_token_hash: String,
+            user_agent: Option<String>,
+            ip_address: Option<String>,
+            expires_at: DateTime<Utc>,
+        ) -> Result<UserSession, AppError> {
+            let id = id.unwrap_or_else(Uuid::new_v4);
+            let session = UserSession {
+                id,
+                user_id,
+                refresh_token_hash,
+                user_agent,
+                ip_address,
+                expires_at,
+                created_at: Utc::now(),
+                family_id: id,
+                parent_id: None,
+                rotated_at: None,
+                last_used_at: Some(Utc::now()),
+            };
+            self.sessions.lock().unwrap().push(session.clone());
+            Ok(session)
+        }
+
+        async fn find_by_refresh_token_hash(
+            &self,
+            hash: &str,
+        ) -> Result<Option<UserSession>, AppError> {
+            let sessions = self.sessions.lock().unwrap();
+            Ok(sessions
+                .iter()
+                .find(|s| s.refresh_token_hash == hash)
+                .cloned())
+        }
+
+        async fn find_by_id(&self, id: Uuid) -> Result<Option<UserSession>, AppError> {
+            let sessions = self.sessions.lock().unwrap();
+            Ok(sessions.iter().find(|s| s.id == id).cloned())
+        }
+
+        async fn find_active_by_user_id(
+            &self,
+            user_id: Uuid,
+        ) -> Result<Vec<UserSession>, AppError> {
+            let sessions = self.sessions.lock().unwrap();
+            Ok(sessions
+                .iter()
+                .filter(|s| {
+                    s.user_id == user_id && s.rotated_at.is_none() && s.expires_at > Utc::now()
+                })
+                .cloned()
+                .collect())
+        }
+
+        async fn rotate_session(
+            &self,
+            parent: &UserSession,
+            id: Uuid,
+            refresh_token_hash: String,
+            user_agent: Option<String>,
+            ip_address: Option<String>,
+            expires_at: DateTime<Utc>,
+        ) -> Result<Option<UserSession>, AppError> {
+            let mut sessions = self.sessions.lock().unwrap();
+            let Some(stored) = sessions
+                .iter_mut()
+                .find(|s| s.id == parent.id && s.rotated_at.is_none())
+            else {
+                return Ok(None);
+            };
+            stored.rotated_at = Some(Utc::now());
+            let session = UserSession {
+                id,
+                user_id: parent.user_id,
+                refresh_token_hash,
+                user_agent,
+                ip_address,
+                expires_at,
+                created_at: parent.created_at,
+                family_id: parent.family_id,
+                parent_id: Some(parent.id),
+                rotated_at: None,
+                last_used_at: Some(Utc::now()),
+            };
+            sessions.push(session.clone());
+            Ok(Some(session))
+        }
+
+        async fn delete_session(&self, id: Uuid) -> Result<(), AppError> {
+            self.sessions.lock().unwrap().retain(|s| s.id != id);
+            Ok(())
+        }
+
+        async fn delete_family(&self, family_id: Uuid) -> Result<(), AppError> {
+            self.sessions
+                .lock()
+                .unwrap()
+                .retain(|s| s.family_id != family_id);
+            Ok(())
+        }
+
+        async fn delete_all_sessions_for_user(&self, user_id: Uuid) -> Result<(), AppError> {
+            self.sessions
+                .lock()
+                .unwrap()
+                .retain(|s| s.user_id != user_id);
+            Ok(())
+        }
+
+        async fn delete_expired(&self, now: DateTime<Utc>) -> Result<u64, AppError> {
+            let mut sessions = self.sessions.lock().unwrap();
+            let before = sessions.len();
+            sessions.retain(|s| s.expires_at >= now);
+            Ok((before - sessions.len()) as u64)
+        }
+
+        async fn delete_other_sessions_for_user(
+            &self,
+            user_id: Uuid,
+            family_id: Uuid,
+        ) -> Result<(), AppError> {
+            self.sessions
+                .lock()
+                .unwrap()
+                .retain(|s| s.user_id != user_id || s.family_id == family_id);
+            Ok(())
+        }
+    }
+
+    struct SessionTestContext {
+        usecase: SessionUseCase,
+        session_repo: Arc<FakeUserSessionRepository>,
+        user_id: Uuid,
+    }
+
+    fn session_test_setup() -> SessionTestContext {
+        let mut activity_repo = MockUserActivityLogRepository::new();
+        activity_repo
+            .expect_log_activity()
+            .returning(|user_id, activity_type, status, _, _, _| {
+                Ok(UserActivityLog {
+                    id: Uuid::new_v4(),
+                    user_id,
+                    activity_type,
+                    status,
+                    error_message: None,
+                    ip_address: None,
+                    user_agent: None,
+                    created_at: Utc::now(),
+                })
+            });
+
+        let session_repo = Arc::new(FakeUserSessionRepository {
+            sessions: Mutex::new(vec![]),
+        });
+        let auth_usecase = Arc::new(AuthUseCase::new(
+            Arc::new(MockUserRepository::new()),
+            Arc::new(MockUserDetailsRepository::new()),
+            Arc::new(MockUserTenantRepository::new()),
+            session_repo.clone(),
+            Arc::new(activity_repo),
+            Arc::new(MockInvitationCodeRepository::new()),
+            Arc::new(MockUserMfaRepository::new()),
+            Arc::new(MockUserRecoveryCodeRepository::new()),
+            Arc::new(tenant_repository(false)),
+            Arc::new(MockTokenDenylistRepository::new()),
+        ));
+
+        SessionTestContext {
+            usecase: SessionUseCase::new(session_repo.clone(), auth_usecase),
+            session_repo,
+            user_id: Uuid::new_v4(),
+        }
+    }
+
+    /// Creates a session for `user_id` whose refresh token is `token`.
+    async fn sign_in(repo: &FakeUserSessionRepository, user_id: Uuid, token: &str) -> UserSession {
+        repo.create_session(
+            None,
+            user_id,
+            request_helper::hash_token(token),
+            Some("test-agent".to_string()),
+            None,
+            Utc::now() + Duration::days(7),
+        )
+        .await
+        .unwrap()
+    }
+
+    fn request_with_cookie(token: &str) -> actix_web::HttpRequest {
+        TestRequest::default()
+            .cookie(Cookie::new("refresh_token", token.to_string()))
+            .to_http_request()
+    }
+
+    #[tokio::test]
+    async fn test_list_sessions_flags_current_and_hides_rotated() {
+        let ctx = session_test_setup();
+        let laptop = sign_in(&ctx.session_repo, ctx.user_id, "laptop-token").await;
+        let phone = sign_in(&ctx.session_repo, ctx.user_id, "phone-token").await;
+        sign_in(&ctx.session_repo, Uuid::new_v4(), "someone-else").await;
+
+        // Refreshing the phone replaces its session with a successor
+        let rotated = ctx
+            .session_repo
+            .rotate_session(
+                &phone,
+                Uuid::new_v4(),
+                request_helper::hash_token("phone-token-2"),
+                None,
+                None,
+                phone.expires_at,
+            )
+            .await
+            .unwrap()
+            .unwrap();
+
+        let sessions = ctx
+            .usecase
+            .list_sessions(ctx.user_id, &request_with_cookie("laptop-token"))
+            .await
+            .unwrap();
+
+        assert_eq!(sessions.len(), 2);
+        // Most recently used first
+        assert_eq!(sessions[0].id, rotated.id);
+        assert_eq!(sessions[0].created_at, phone.created_at);
+        assert!(!sessions[0].current);
+        assert_eq!(sessions[1].id, laptop.id);
+        assert!(sessions[1].current);
+    }
+
+    #[tokio::test]
+    async fn test_revoke_session_deletes_family_of_own_session_only() {
+        let ctx = session_test_setup();
+        let phone = sign_in(&ctx.session_repo, ctx.user_id, "phone-token").await;
+        ctx.session_repo
+            .rotate_session(
+                &phone,
+                Uuid::new_v4(),
+                request_helper::hash_token("phone-token-2"),
+                None,
+                None,
+                phone.expires_at,
+            )
+            .await
+            .unwrap();
+        let other = sign_in(&ctx.session_repo, Uuid::new_v4(), "other-token").await;
+        let http_req = TestRequest::default().to_http_request();
+
+        // Another user's session cannot be revoked
+        assert!(matches!(
+            ctx.usecase
+                .revoke_session(ctx.user_id, other.id, &http_req)
+                .await,
+            Err(AppError::NotFound(_))
+        ));
+
+        // Nor can a rotated session, only the device's current one
+        assert!(matches!(
+            ctx.usecase
+                .revoke_session(ctx.user_id, phone.id, &http_req)
+                .await,
+            Err(AppError::NotFound(_))
+        ));
+
+        let current = ctx
+            .usecase
+            .list_sessions(ctx.user_id, &http_req)
+            .await
+            .unwrap();
+        ctx.usecase
+            .revoke_session(ctx.user_id, current[0].id, &http_req)
+            .await
+            .unwrap();
+
+        let remaining = ctx.session_repo.sessions.lock().unwrap().clone();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].id, other.id);
+    }
+
+    #[tokio::test]
+    async fn test_revoke_other_sessions_keeps_current_session() {
+        let ctx = session_test_setup();
+        let laptop = sign_in(&ctx.session_repo, ctx.user_id, "laptop-token").await;
+        sign_in(&ctx.session_repo, ctx.user_id, "phone-token").await;
+        sign_in(&ctx.session_repo, ctx.user_id, "tablet-token").await;
+
+        // The current session must be known
+        assert!(matches!(
+            ctx.usecase
+                .revoke_other_sessions(ctx.user_id, &TestRequest::default().to_http_request())
+                .await,
+            Err(AppError::Unauthorized(_))
+        ));
+
+        ctx.usecase
+            .revoke_other_sessions(ctx.user_id, &request_with_cookie("laptop-token"))
+            .await
+            .unwrap();
+
+        let sessions = ctx
+            .usecase
+            .list_sessions(ctx.user_id, &request_with_cookie("laptop-token"))
+            .await
+            .unwrap();
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].id, laptop.id);
+        assert!(sessions[0].current);
+    }
+}
@@ -0,0 +1,233 @@
+use crate::domains::auth::dtos::signing_key_dto::SigningKeyResponse;
+use crate::domains::auth::entities::jwt_signing_key::{
+    Model as JwtSigningKey, KEY_STATUS_ACTIVE, KEY_STATUS_VERIFICATION_ONLY,
+};
+use crate::domains::auth::repositories::jwt_signing_key_repository::JwtSigningKeyRepositoryTrait;
+use crate::domains::common::errors::AppError;
+use crate::domains::common::utils::config::Config;
+use crate::domains::common::utils::jwt_keys::{parse_algorithm, JwtKey, KeyRing};
+use crate::domains::common::utils::key_encryption::KeyEncryption;
+use chrono::{Duration, Utc};
+use jsonwebtoken::Algorithm;
+use std::path::Path;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Manages the JWT key ring stored in the database.
+///
+/// Rotation creates a new active key and demotes the previous one to
+/// verification-only, so tokens it signed stay valid. A verification-only key
+/// is retired once the longest-lived token it could have signed has expired.
+///
+/// Private key material is stored encrypted with a key derived from
+/// `SECRET_KEY`.
+pub struct SigningKeyUseCase {
+    repository: Arc<dyn JwtSigningKeyRepositoryTrait>,
+    key_ring: Arc<KeyRing>,
+    encryption: KeyEncryption,
+    token_lifetime: Duration,
+}
+
+impl SigningKeyUseCase {
+    /// Creates a new SigningKeyUseCase instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `repository` - Repository for stored signing keys
+    /// * `key_ring` - In-memory ring used by `JwtService`
+    pub fn new(repository: Arc<dyn JwtSigningKeyRepositoryTrait>, key_ring: Arc<KeyRing>) -> Self {
+        let config = Config::get();
+
+        Self {
+            repository,
+            key_ring,
+            encryption: KeyEncryption::new(&config.secret_key),
+            token_lifetime: Duration::seconds(
+                config.refresh_token_expiry.max(config.access_token_expiry),
+            ),
+        }
+    }
+
+    /// Loads the key ring at startup.
+    ///
+    /// When there is no active key, or it does not use `JWT_ALGORITHM`, the
+    /// configured key becomes the active key: `JWT_SECRET` for `HS256`, or the
+    /// key at `JWT_PRIVATE_KEY_PATH` (generated if missing) otherwise. Tokens
+    /// issued before the ring existed therefore keep validating. Otherwise the
+    /// stored ring wins, and a warning is logged if the configured key is not
+    /// part of it.
+    ///
+    /// Keys stored in plaintext by earlier versions are encrypted.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::InternalError` if the configured key cannot be loaded
+    /// or a stored key is invalid or cannot be decrypted.
+    pub async fn initialize(&self) -> Result<(), AppError> {
+        let config = Config::get();
+        let keys = self.repository.find_unretired().await?;
+        let active = keys.iter().find(|k| k.status == KEY_STATUS_ACTIVE);
+        let algorithm = algorithm_name(config.jwt_algorithm);
+
+        for key in keys
+            .iter()
+            .filter(|k| !KeyEncryption::is_sealed(&k.private_key))
+        {
+            let sealed = self.encryption.seal(&key.private_key)?;
+            self.repository
+                .update_private_key(key.kid.clone(), sealed)
+                .await?;
+            log::info!("Encrypted stored JWT signing key {}", key.kid);
+        }
+
+        if let Some(active) = active.filter(|k| k.algorithm == algorithm) {
+            if !self.holds_configured_key(&keys)? {
+                log::warn!(
+                    "The configured JWT signing key is not in the key ring, tokens are signed \
+                     with stored key {}. Changing JWT_SECRET or JWT_PRIVATE_KEY_PATH does not \
+                     replace stored keys, rotate them with POST /auth/internal/keys/rotate",
+                    active.kid
+                );
+            }
+        } else {
+            let (key, material) = match config.jwt_algorithm {
+                Algorithm::HS256 => {
+                    let kid = Uuid::new_v4().simple().to_string();
+                    let key = JwtKey::from_stored(Algorithm::HS256, &kid, &config.jwt_secret)?;
+                    (key, config.jwt_secret.clone())
+                }
+                other => JwtKey::load_or_generate(other, Path::new(&config.jwt_private_key_path))?,
+            };
+            let kid = key.kid().unwrap_or_default().to_string();
+
+            self.repository
+                .activate(kid.clone(), algorithm, self.encryption.seal(&material)?)
+                .await?;
+            log::info!("Seeded JWT key ring with configured key {}", kid);
+        }
+
+        self.reload().await?;
+        Ok(())
+    }
+
+    /// Generates a new active key with `JWT_ALGORITHM`.
+    ///
+    /// The previous active key keeps verifying tokens until they expire.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::InternalError` if key generation fails or
+    /// `AppError::DatabaseError` if the key cannot be stored.
+    pub async fn rotate(&self) -> Result<SigningKeyResponse, AppError> {
+        let algorithm = Config::get().jwt_algorithm;
+        let (key, material) = JwtKey::generate(algorithm)?;
+        let kid = key.kid().unwrap_or_default().to_string();
+
+        let stored = self
+            .repository
+            .activate(
+                kid,
+                algorithm_name(algorithm),
+                self.encryption.seal(&material)?,
+            )
+            .await?;
+        self.reload().await?;
+
+        log::info!("Rotated JWT signing key, new active key {}", stored.kid);
+        Ok(self.to_response(stored))
+    }
+
+    /// Lists every signing key, newest first, without private material.
+    pub async fn list_keys(&self) -> Result<Vec<SigningKeyResponse>, AppError> {
+        let keys = self.repository.find_all().await?;
+        Ok(keys.into_iter().map(|k| self.to_response(k)).collect())
+    }
+
+    /// Retires expired verification-only keys and rebuilds the in-memory ring
+    /// from the database, picking up rotations made by other instances.
+    ///
+    /// Returns the number of keys retired.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::InternalError` if there is no active key or a stored
+    /// key is invalid.
+    pub async fn reload(&self) -> Result<u64, AppError> {
+        let retired = self
+            .repository
+            .retire_deactivated_before(Utc::now() - self.token_lifetime)
+            .await?;
+
+        let mut active = None;
+        let mut verification_only = Vec::new();
+        for stored in self.repository.find_unretired().await? {
+            let algorithm = parse_algorithm(&stored.algorithm).ok_or_else(|| {
+                AppError::InternalError(format!("Unknown algorithm for key {}", stored.kid))
+            })?;
+            let material = self.encryption.open(&stored.private_key)?;
+            let key = JwtKey::from_stored(algorithm, &stored.kid, &material)?;
+
+            match stored.status.as_str() {
+                KEY_STATUS_ACTIVE if active.is_none() => active = Some(key),
+                KEY_STATUS_VERIFICATION_ONLY => verification_only.push(key),
+                _ => {}
+            }
+        }
+
+        let active = active
+            .ok_or_else(|| AppError::InternalError("No active JWT signing key".to_string()))?;
+        self.key_ring.replace(active, verification_only);
+
+        Ok(retired)
+    }
+
+    /// Returns true if the key configured by `JWT_SECRET` or
+    /// `JWT_PRIVATE_KEY_PATH` is one of `keys`.
+    fn holds_configured_key(&self, keys: &[JwtSigningKey]) -> Result<bool, AppError> {
+        let config = Config::get();
+        let algorithm = algorithm_name(config.jwt_algorithm);
+        let path = Path::new(&config.jwt_private_key_path);
+
+        let configured_kid = match config.jwt_algorithm {
+            Algorithm::HS256 => None,
+            // Only compare an existing key file, a missing one would be generated
+            other if path.exists() => JwtKey::load_or_generate(other, path)?
+                .0
+                .kid()
+                .map(str::to_string),
+            _ => return Ok(true),
+        };
+
+        for key in keys.iter().filter(|k| k.algorithm == algorithm) {
+            let matches = match &configured_kid {
+                Some(kid) => key.kid == *kid,
+                None => self.encryption.open(&key.private_key)? == config.jwt_secret,
+            };
+            if matches {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    fn to_response(&self, key: JwtSigningKey) -> SigningKeyResponse {
+        let retires_at = match key.status.as_str() {
+            KEY_STATUS_VERIFICATION_ONLY => key.deactivated_at.map(|at| at + self.token_lifetime),
+            _ => None,
+        };
+
+        SigningKeyResponse {
+            kid: key.kid,
+            algorithm: key.algorithm,
+            status: key.status,
+            created_at: key.created_at,
+            deactivated_at: key.deactivated_at,
+            retires_at,
+            retired_at: key.retired_at,
+        }
+    }
+}
+
+fn algorithm_name(algorithm: Algorithm) -> String {
+    format!("{:?}", algorithm)
+}
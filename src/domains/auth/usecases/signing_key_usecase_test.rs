@@ -0,0 +1,289 @@
+#[cfg(test)]
+mod tests {
+    use crate::domains::auth::entities::jwt_signing_key::{
+        Model as JwtSigningKey, KEY_STATUS_ACTIVE, KEY_STATUS_RETIRED, KEY_STATUS_VERIFICATION_ONLY,
+    };
+    use crate::domains::auth::repositories::jwt_signing_key_repository::JwtSigningKeyRepositoryTrait;
+    use crate::domains::auth::usecases::signing_key_usecase::SigningKeyUseCase;
+    use crate::domains::common::errors::AppError;
+    use crate::domains::common::utils::config::Config;
+    use crate::domains::common::utils::jwt::JwtService;
+    use crate::domains::common::utils::jwt_keys::{JwtKey, KeyRing};
+    use crate::domains::common::utils::key_encryption::KeyEncryption;
+
+    use async_trait::async_trait;
+    use chrono::{DateTime, Duration, Utc};
+    use jsonwebtoken::Algorithm;
+    use mockall::mock;
+    use std::sync::{Arc, Mutex};
+    use uuid::Uuid;
+
+    mock! {
+        pub JwtSigningKeyRepository {}
+        #[async_trait]
+        impl JwtSigningKeyRepositoryTrait for JwtSigningKeyRepository {
+            async fn find_all(&self) -> Result<Vec<JwtSigningKey>, AppError>;
+            async fn find_unretired(&self) -> Result<Vec<JwtSigningKey>, AppError>;
+            async fn activate(&self, kid: String, algorithm: String, private_key: String) -> Result<JwtSigningKey, AppError>;
+            async fn retire_deactivated_before(&self, cutoff: DateTime<Utc>) -> Result<u64, AppError>;
+            async fn update_private_key(&self, kid: String, private_key: String) -> Result<(), AppError>;
+        }
+    }
+
+    fn setup_env() {
+        Config::init_for_test();
+    }
+
+    fn stored_key(
+        status: &str,
+        secret: &str,
+        deactivated_at: Option<DateTime<Utc>>,
+    ) -> JwtSigningKey {
+        JwtSigningKey {
+            kid: Uuid::new_v4().simple().to_string(),
+            algorithm: "HS256".to_string(),
+            private_key: secret.to_string(),
+            status: status.to_string(),
+            created_at: Utc::now(),
+            deactivated_at,
+            retired_at: None,
+        }
+    }
+
+    /// Mock repository backed by an in-memory key list, mirroring the
+    /// demotion done by the SeaORM implementation.
+    fn repository_with(keys: Arc<Mutex<Vec<JwtSigningKey>>>) -> MockJwtSigningKeyRepository {
+        let mut repo = MockJwtSigningKeyRepository::new();
+
+        let state = keys.clone();
+        repo.expect_find_unretired().returning(move || {
+            let mut keys: Vec<_> = state
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|k| k.status != KEY_STATUS_RETIRED)
+                .cloned()
+                .collect();
+            keys.reverse();
+            Ok(keys)
+        });
+
+        let state = keys.clone();
+        repo.expect_find_all().returning(move || {
+            let mut keys = state.lock().unwrap().clone();
+            keys.reverse();
+            Ok(keys)
+        });
+
+        let state = keys.clone();
+        repo.expect_activate()
+            .returning(move |kid, algorithm, private_key| {
+                let mut keys = state.lock().unwrap();
+                for key in keys.iter_mut().filter(|k| k.status == KEY_STATUS_ACTIVE) {
+                    key.status = KEY_STATUS_VERIFICATION_ONLY.to_string();
+                    key.deactivated_at = Some(Utc::now());
+                }
+                let key = JwtSigningKey {
+                    kid,
+                    algorithm,
+                    private_key,
+                    status: KEY_STATUS_ACTIVE.to_string(),
+                    created_at: Utc::now(),
+                    deactivated_at: None,
+                    retired_at: None,
+                };
+                keys.push(key.clone());
+                Ok(key)
+            });
+
+        let state = keys.clone();
+        repo.expect_update_private_key()
+            .returning(move |kid, private_key| {
+                for key in state.lock().unwrap().iter_mut().filter(|k| k.kid == kid) {
+                    key.private_key = private_key.clone();
+                }
+                Ok(())
+            });
+
+        let state = keys;
+        repo.expect_retire_deactivated_before()
+            .returning(move |cutoff| {
+                let mut retired = 0;
+                for key in state.lock().unwrap().iter_mut() {
+                    if key.status == KEY_STATUS_VERIFICATION_ONLY
+                        && key.deactivated_at.is_some_and(|at| at < cutoff)
+                    {
+                        key.status = KEY_STATUS_RETIRED.to_string();
+                        key.private_key = String::new();
+                        key.retired_at = Some(Utc::now());
+                        retired += 1;
+                    }
+                }
+                Ok(retired)
+            });
+
+        repo
+    }
+
+    fn encryption() -> KeyEncryption {
+        KeyEncryption::new(&Config::get().secret_key)
+    }
+
+    fn new_ring() -> Arc<KeyRing> {
+        Arc::new(KeyRing::new(JwtKey::from_secret(b"placeholder")))
+    }
+
+    #[tokio::test]
+    async fn test_initialize_seeds_configured_secret() {
+        setup_env();
+        let keys = Arc::new(Mutex::new(Vec::new()));
+        let ring = new_ring();
+        let usecase = SigningKeyUseCase::new(Arc::new(repository_with(keys.clone())), ring.clone());
+
+        // Token issued before the key ring existed, signed with JWT_SECRET and no kid
+        let legacy_token =
+            JwtService::with_key(JwtKey::from_secret(Config::get().jwt_secret.as_bytes()))
+                .generate_access_token(Uuid::new_v4(), Uuid::new_v4(), "user".to_string())
+                .unwrap();
+
+        usecase.initialize().await.unwrap();
+
+        let keys = keys.lock().unwrap();
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].algorithm, "HS256");
+        assert!(KeyEncryption::is_sealed(&keys[0].private_key));
+        assert_eq!(
+            encryption().open(&keys[0].private_key).unwrap(),
+            Config::get().jwt_secret
+        );
+        assert_eq!(ring.active().kid(), Some(keys[0].kid.as_str()));
+        assert!(JwtService::with_key_ring(ring)
+            .validate_token(&legacy_token)
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_initialize_keeps_existing_active_key_and_encrypts_it() {
+        setup_env();
+        // Stored in plaintext, as by versions before encryption at rest
+        let existing = stored_key(KEY_STATUS_ACTIVE, "stored-secret", None);
+        let kid = existing.kid.clone();
+        let keys = Arc::new(Mutex::new(vec![existing]));
+        let ring = new_ring();
+        let usecase = SigningKeyUseCase::new(Arc::new(repository_with(keys.clone())), ring.clone());
+
+        usecase.initialize().await.unwrap();
+
+        let keys = keys.lock().unwrap();
+        assert_eq!(keys.len(), 1);
+        assert!(KeyEncryption::is_sealed(&keys[0].private_key));
+        assert_eq!(
+            encryption().open(&keys[0].private_key).unwrap(),
+            "stored-secret"
+        );
+        assert_eq!(ring.active().kid(), Some(kid.as_str()));
+
+        let token = JwtService::with_key(
+            JwtKey::from_stored(Algorithm::HS256, &kid, "stored-secret").unwrap(),
+        )
+        .generate_access_token(Uuid::new_v4(), Uuid::new_v4(), "user".to_string())
+        .unwrap();
+        assert!(JwtService::with_key_ring(ring)
+            .validate_token(&token)
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_rotate_keeps_tokens_of_previous_key_valid() {
+        setup_env();
+        let keys = Arc::new(Mutex::new(vec![stored_key(
+            KEY_STATUS_ACTIVE,
+            "old-secret",
+            None,
+        )]));
+        let ring = new_ring();
+        let usecase = SigningKeyUseCase::new(Arc::new(repository_with(keys.clone())), ring.clone());
+        usecase.reload().await.unwrap();
+
+        let service = JwtService::with_key_ring(ring.clone());
+        let old_token = service
+            .generate_access_token(Uuid::new_v4(), Uuid::new_v4(), "user".to_string())
+            .unwrap();
+
+        let rotated = usecase.rotate().await.unwrap();
+        let new_token = service
+            .generate_access_token(Uuid::new_v4(), Uuid::new_v4(), "user".to_string())
+            .unwrap();
+
+        assert_eq!(rotated.status, KEY_STATUS_ACTIVE);
+        assert!(KeyEncryption::is_sealed(
+            &keys.lock().unwrap().last().unwrap().private_key
+        ));
+        assert_eq!(ring.active().kid(), Some(rotated.kid.as_str()));
+        assert_eq!(
+            jsonwebtoken::decode_header(&new_token).unwrap().kid,
+            Some(rotated.kid)
+        );
+        assert!(service.validate_token(&old_token).is_ok());
+        assert!(service.validate_token(&new_token).is_ok());
+
+        let listed = usecase.list_keys().await.unwrap();
+        assert_eq!(listed.len(), 2);
+        assert_eq!(listed[1].status, KEY_STATUS_VERIFICATION_ONLY);
+        assert!(listed[1].retires_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_reload_retires_keys_past_token_lifetime() {
+        setup_env();
+        let lifetime = Config::get()
+            .refresh_token_expiry
+            .max(Config::get().access_token_expiry);
+        let expired = stored_key(
+            KEY_STATUS_VERIFICATION_ONLY,
+            "expired-secret",
+            Some(Utc::now() - Duration::seconds(lifetime + 60)),
+        );
+        let recent = stored_key(
+            KEY_STATUS_VERIFICATION_ONLY,
+            "recent-secret",
+            Some(Utc::now() - Duration::seconds(60)),
+        );
+        let active = stored_key(KEY_STATUS_ACTIVE, "active-secret", None);
+
+        let expired_token = JwtService::with_key(
+            JwtKey::from_stored(Algorithm::HS256, &expired.kid, "expired-secret").unwrap(),
+        )
+        .generate_access_token(Uuid::new_v4(), Uuid::new_v4(), "user".to_string())
+        .unwrap();
+        let recent_token = JwtService::with_key(
+            JwtKey::from_stored(Algorithm::HS256, &recent.kid, "recent-secret").unwrap(),
+        )
+        .generate_access_token(Uuid::new_v4(), Uuid::new_v4(), "user".to_string())
+        .unwrap();
+
+        let keys = Arc::new(Mutex::new(vec![expired, recent, active]));
+        let ring = new_ring();
+        let usecase = SigningKeyUseCase::new(Arc::new(repository_with(keys.clone())), ring.clone());
+
+        let retired = usecase.reload().await.unwrap();
+        let service = JwtService::with_key_ring(ring);
+
+        assert_eq!(retired, 1);
+        assert_eq!(keys.lock().unwrap()[0].status, KEY_STATUS_RETIRED);
+        assert!(keys.lock().unwrap()[0].private_key.is_empty());
+        assert!(service.validate_token(&expired_token).is_err());
+        assert!(service.validate_token(&recent_token).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_reload_without_active_key_fails() {
+        setup_env();
+        let keys = Arc::new(Mutex::new(Vec::new()));
+        let usecase = SigningKeyUseCase::new(Arc::new(repository_with(keys)), new_ring());
+
+        let result = usecase.reload().await;
+
+        assert!(matches!(result, Err(AppError::InternalError(_))));
+    }
+}
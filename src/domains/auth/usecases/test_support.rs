@@ -0,0 +1,348 @@
+//! Mocks and fakes shared by the tests of the use cases that email links to
+//! users: password reset, email verification and passwordless sign-in.
+
+use crate::domains::auth::repositories::invitation_code_repository::InvitationCodeRepositoryTrait;
+use crate::domains::auth::repositories::token_denylist_repository::TokenDenylistRepositoryTrait;
+use crate::domains::auth::usecases::auth_usecase::AuthUseCase;
+use crate::domains::common::errors::AppError;
+use crate::domains::common::infrastructures::mailer::{EmailMessage, Mailer};
+use crate::domains::tenant::entities::tenant::Model as Tenant;
+use crate::domains::tenant::repositories::tenant_repository::TenantRepositoryTrait;
+use crate::domains::tenant::repositories::user_tenant_repository::{
+    UserTenantInfo, UserTenantRepositoryTrait,
+};
+use crate::domains::user::entities::user::Model as User;
+use crate::domains::user::entities::user_activity_log::Model as UserActivityLog;
+use crate::domains::user::entities::user_details::Model as UserDetails;
+use crate::domains::user::entities::user_mfa::Model as UserMfa;
+use crate::domains::user::entities::user_recovery_code::Model as UserRecoveryCode;
+use crate::domains::user::entities::user_session::Model as UserSession;
+use crate::domains::user::entities::user_token::Model as UserToken;
+use crate::domains::user::repositories::user_activity_log_repository::UserActivityLogRepositoryTrait;
+use crate::domains::user::repositories::user_details_repository::UserDetailsRepositoryTrait;
+use crate::domains::user::repositories::user_mfa_repository::UserMfaRepositoryTrait;
+use crate::domains::user::repositories::user_recovery_code_repository::UserRecoveryCodeRepositoryTrait;
+use crate::domains::user::repositories::user_repository::UserRepositoryTrait;
+use crate::domains::user::repositories::user_session_repository::UserSessionRepositoryTrait;
+use crate::domains::user::repositories::user_token_repository::UserTokenRepositoryTrait;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use mockall::mock;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+mock! {
+    pub TokenDenylistRepository {}
+    #[async_trait]
+    impl TokenDenylistRepositoryTrait for TokenDenylistRepository {
+        async fn deny(&self, jti: &str, ttl: std::time::Duration) -> Result<(), AppError>;
+        async fn is_denied(&self, jti: &str) -> Result<bool, AppError>;
+        async fn deny_subject(&self, subject: &str, ttl: std::time::Duration) -> Result<(), AppError>;
+        async fn subject_denied_at(&self, subject: &str) -> Result<Option<i64>, AppError>;
+    }
+}
+
+mock! {
+    pub UserRepository {}
+    #[async_trait]
+    impl UserRepositoryTrait for UserRepository {
+        async fn create(&self, user: crate::domains::user::dtos::user_dto::CreateUserRequest) -> Result<User, AppError>;
+        async fn find_by_id(&self, id: Uuid) -> Result<Option<User>, AppError>;
+        async fn find_by_username(&self, username: &str) -> Result<Option<User>, AppError>;
+        async fn find_all(&self) -> Result<Vec<User>, AppError>;
+        async fn update(&self, id: Uuid, user: crate::domains::user::dtos::user_dto::UpdateUserRequest) -> Result<User, AppError>;
+        async fn delete(&self, id: Uuid) -> Result<(), AppError>;
+        async fn find_by_email_with_deleted(&self, email: &str) -> Result<Option<User>, AppError>;
+        async fn find_by_username_with_deleted(&self, username: &str) -> Result<Option<User>, AppError>;
+        async fn restore(&self, id: Uuid, req: crate::domains::user::dtos::user_dto::CreateUserRequest) -> Result<User, AppError>;
+        async fn mark_email_verified(&self, id: Uuid) -> Result<User, AppError>;
+        async fn set_login_failures(&self, id: Uuid, failed_login_attempts: i32, locked_until: Option<DateTime<Utc>>) -> Result<(), AppError>;
+        async fn increment_login_failures(&self, id: Uuid) -> Result<i32, AppError>;
+        async fn lock_until(&self, id: Uuid, locked_until: DateTime<Utc>) -> Result<(), AppError>;
+        async fn set_status(&self, id: Uuid, status: String, reason: Option<String>, expires_at: Option<DateTime<Utc>>) -> Result<User, AppError>;
+        async fn purge_deleted_before(&self, cutoff: DateTime<Utc>) -> Result<u64, AppError>;
+    }
+}
+
+mock! {
+    pub TenantRepository {}
+    #[async_trait]
+    impl TenantRepositoryTrait for TenantRepository {
+        async fn create(&self, tenant: crate::domains::tenant::dtos::tenant_dto::CreateTenantRequest) -> Result<Tenant, AppError>;
+        async fn find_by_id(&self, id: Uuid) -> Result<Option<Tenant>, AppError>;
+        async fn find_by_name(&self, name: &str) -> Result<Option<Tenant>, AppError>;
+        async fn find_by_name_with_deleted(&self, name: &str) -> Result<Option<Tenant>, AppError>;
+        async fn find_all(&self) -> Result<Vec<Tenant>, AppError>;
+        async fn update(&self, id: Uuid, tenant: crate::domains::tenant::dtos::tenant_dto::UpdateTenantRequest) -> Result<Tenant, AppError>;
+        async fn delete(&self, id: Uuid) -> Result<(), AppError>;
+        async fn restore(&self, id: Uuid) -> Result<(), AppError>;
+        async fn purge_deleted_before(&self, cutoff: DateTime<Utc>) -> Result<u64, AppError>;
+    }
+}
+
+mock! {
+    pub UserDetailsRepository {}
+    #[async_trait]
+    impl UserDetailsRepositoryTrait for UserDetailsRepository {
+        async fn create(&self, user_id: Uuid) -> Result<UserDetails, AppError>;
+        async fn find_by_user_id(&self, user_id: Uuid) -> Result<Option<UserDetails>, AppError>;
+        async fn update(&self, user_id: Uuid, full_name: Option<String>, phone_number: Option<String>, address: Option<String>, date_of_birth: Option<chrono::NaiveDate>) -> Result<UserDetails, AppError>;
+        async fn update_profile_picture(&self, user_id: Uuid, profile_picture_url: String) -> Result<UserDetails, AppError>;
+    }
+}
+
+mock! {
+    pub UserTenantRepository {}
+    #[async_trait]
+    impl UserTenantRepositoryTrait for UserTenantRepository {
+        async fn add_user_to_tenant(&self, user_id: Uuid, tenant_id: Uuid, role: String) -> Result<(), AppError>;
+        async fn get_user_roles_in_tenant(&self, user_id: Uuid, tenant_id: Uuid) -> Result<Vec<String>, AppError>;
+        async fn get_all_tenants_for_user(&self, user_id: Uuid) -> Result<Vec<UserTenantInfo>, AppError>;
+    }
+}
+
+mock! {
+    pub UserSessionRepository {}
+    #[async_trait]
+    impl UserSessionRepositoryTrait for UserSessionRepository {
+        async fn create_session(&self, id: Option<Uuid>, user_id: Uuid, refresh_token_hash: String, user_agent: Option<String>, ip_address: Option<String>, expires_at: DateTime<Utc>) -> Result<UserSession, AppError>;
+        async fn find_by_refresh_token_hash(&self, hash: &str) -> Result<Option<UserSession>, AppError>;
+        async fn find_by_id(&self, id: Uuid) -> Result<Option<UserSession>, AppError>;
+        async fn find_active_by_user_id(&self, user_id: Uuid) -> Result<Vec<UserSession>, AppError>;
+        async fn rotate_session(&self, parent: &UserSession, id: Uuid, refresh_token_hash: String, user_agent: Option<String>, ip_address: Option<String>, expires_at: DateTime<Utc>) -> Result<Option<UserSession>, AppError>;
+        async fn delete_session(&self, id: Uuid) -> Result<(), AppError>;
+        async fn delete_family(&self, family_id: Uuid) -> Result<(), AppError>;
+        async fn delete_all_sessions_for_user(&self, user_id: Uuid) -> Result<(), AppError>;
+        async fn delete_expired(&self, now: DateTime<Utc>) -> Result<u64, AppError>;
+        async fn delete_other_sessions_for_user(&self, user_id: Uuid, family_id: Uuid) -> Result<(), AppError>;
+    }
+}
+
+mock! {
+    pub UserActivityLogRepository {}
+    #[async_trait]
+    impl UserActivityLogRepositoryTrait for UserActivityLogRepository {
+        async fn log_activity(&self, user_id: Option<Uuid>, activity_type: String, status: String, error_message: Option<String>, ip_address: Option<String>, user_agent: Option<String>) -> Result<UserActivityLog, AppError>;
+    }
+}
+
+mock! {
+    pub InvitationCodeRepository {}
+    #[async_trait]
+    impl InvitationCodeRepositoryTrait for InvitationCodeRepository {
+        async fn save_code(&self, code: String, ttl: std::time::Duration) -> Result<(), AppError>;
+        async fn validate_and_delete_code(&self, code: &str) -> Result<bool, AppError>;
+    }
+}
+
+mock! {
+    pub UserMfaRepository {}
+    #[async_trait]
+    impl UserMfaRepositoryTrait for UserMfaRepository {
+        async fn find_by_user_id(&self, user_id: Uuid) -> Result<Option<UserMfa>, AppError>;
+        async fn upsert_pending(&self, user_id: Uuid, totp_secret: String) -> Result<UserMfa, AppError>;
+        async fn enable(&self, user_id: Uuid, used_step: i64) -> Result<UserMfa, AppError>;
+        async fn update_last_used_step(&self, user_id: Uuid, used_step: i64) -> Result<bool, AppError>;
+        async fn delete_by_user_id(&self, user_id: Uuid) -> Result<(), AppError>;
+    }
+}
+
+mock! {
+    pub UserRecoveryCodeRepository {}
+    #[async_trait]
+    impl UserRecoveryCodeRepositoryTrait for UserRecoveryCodeRepository {
+        async fn replace_for_user(&self, user_id: Uuid, code_hashes: Vec<String>) -> Result<(), AppError>;
+        async fn find_unused_by_user_id(&self, user_id: Uuid) -> Result<Vec<UserRecoveryCode>, AppError>;
+        async fn mark_used(&self, id: Uuid) -> Result<bool, AppError>;
+    }
+}
+
+/// Builds a tenant repository whose tenants have the given policies.
+pub fn tenant_repository(
+    require_email_verification: bool,
+    passwordless_login: bool,
+) -> MockTenantRepository {
+    let mut repo = MockTenantRepository::new();
+    repo.expect_find_by_id().returning(move |id| {
+        Ok(Some(Tenant {
+            id,
+            name: "test-tenant".to_string(),
+            description: None,
+            api_key: None,
+            require_email_verification,
+            passwordless_login,
+            rate_limits: None,
+            deleted_at: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }))
+    });
+    repo
+}
+
+/// Builds a user-tenant repository in which every user has the `user` role
+/// in `tenant_id` and no role anywhere else.
+pub fn user_tenant_repository(tenant_id: Uuid) -> MockUserTenantRepository {
+    let mut repo = MockUserTenantRepository::new();
+    repo.expect_get_user_roles_in_tenant()
+        .returning(move |_, tenant| {
+            Ok(if tenant == tenant_id {
+                vec!["user".to_string()]
+            } else {
+                vec![]
+            })
+        });
+    repo
+}
+
+/// Builds an activity log repository that accepts every entry.
+pub fn activity_log_repository() -> MockUserActivityLogRepository {
+    let mut repo = MockUserActivityLogRepository::new();
+    repo.expect_log_activity()
+        .returning(|user_id, activity_type, status, _, _, _| {
+            Ok(UserActivityLog {
+                id: Uuid::new_v4(),
+                user_id,
+                activity_type,
+                status,
+                error_message: None,
+                ip_address: None,
+                user_agent: None,
+                created_at: Utc::now(),
+            })
+        });
+    repo
+}
+
+/// Returns an active user with an unverified email.
+pub fn test_user(username: &str, email: &str) -> User {
+    User {
+        id: Uuid::new_v4(),
+        username: username.to_string(),
+        email: email.to_string(),
+        password_hash: "hash".to_string(),
+        email_verified_at: None,
+        failed_login_attempts: 0,
+        locked_until: None,
+        status: "active".to_string(),
+        status_reason: None,
+        status_expires_at: None,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+        deleted_at: None,
+    }
+}
+
+/// Builds the `AuthUseCase` the email use cases log activity and issue
+/// tokens through. Repositories it is not expected to use are empty mocks.
+pub fn auth_usecase(
+    user_repo: Arc<MockUserRepository>,
+    user_tenant_repo: Arc<MockUserTenantRepository>,
+    session_repo: Arc<MockUserSessionRepository>,
+    mfa_repo: MockUserMfaRepository,
+    tenant_repo: MockTenantRepository,
+    denylist_repo: MockTokenDenylistRepository,
+) -> Arc<AuthUseCase> {
+    Arc::new(AuthUseCase::new(
+        user_repo,
+        Arc::new(MockUserDetailsRepository::new()),
+        user_tenant_repo,
+        session_repo,
+        Arc::new(activity_log_repository()),
+        Arc::new(MockInvitationCodeRepository::new()),
+        Arc::new(mfa_repo),
+        Arc::new(MockUserRecoveryCodeRepository::new()),
+        Arc::new(tenant_repo),
+        Arc::new(denylist_repo),
+    ))
+}
+
+/// Fake UserTokenRepository backed by a Vec.
+#[derive(Default)]
+pub struct FakeUserTokenRepository {
+    tokens: Mutex<Vec<UserToken>>,
+}
+
+#[async_trait]
+impl UserTokenRepositoryTrait for FakeUserTokenRepository {
+    async fn issue(
+        &self,
+        user_id: Uuid,
+        tenant_id: Uuid,
+        purpose: &str,
+        token_hash: String,
+        expires_at: DateTime<Utc>,
+    ) -> Result<UserToken, AppError> {
+        let mut tokens = self.tokens.lock().unwrap();
+        tokens.retain(|t| !(t.user_id == user_id && t.purpose == purpose && t.used_at.is_none()));
+        let token = UserToken {
+            id: Uuid::new_v4(),
+            user_id,
+            tenant_id,
+            purpose: purpose.to_string(),
+            token_hash,
+            expires_at,
+            used_at: None,
+            created_at: Utc::now(),
+        };
+        tokens.push(token.clone());
+        Ok(token)
+    }
+
+    async fn consume(
+        &self,
+        purpose: &str,
+        token_hash: &str,
+    ) -> Result<Option<UserToken>, AppError> {
+        let mut tokens = self.tokens.lock().unwrap();
+        let token = tokens.iter_mut().find(|t| {
+            t.token_hash == token_hash
+                && t.purpose == purpose
+                && t.used_at.is_none()
+                && t.expires_at > Utc::now()
+        });
+        Ok(token.map(|t| {
+            t.used_at = Some(Utc::now());
+            t.clone()
+        }))
+    }
+}
+
+/// Mailer that keeps sent messages for inspection.
+#[derive(Default)]
+pub struct FakeMailer {
+    pub sent: Mutex<Vec<EmailMessage>>,
+}
+
+#[async_trait]
+impl Mailer for FakeMailer {
+    async fn send(&self, message: EmailMessage) -> Result<(), AppError> {
+        self.sent.lock().unwrap().push(message);
+        Ok(())
+    }
+}
+
+impl FakeMailer {
+    /// Waits until `count` emails were sent, as they are sent in the background.
+    pub async fn wait_for(&self, count: usize) {
+        for _ in 0..100 {
+            if self.sent.lock().unwrap().len() >= count {
+                return;
+            }
+            tokio::task::yield_now().await;
+        }
+        panic!("expected {} sent emails", count);
+    }
+
+    /// Extracts the token from the link in the last sent email.
+    pub fn last_link_token(&self) -> String {
+        let sent = self.sent.lock().unwrap();
+        let body = &sent.last().unwrap().text_body;
+        let start = body.find("?token=").unwrap() + "?token=".len();
+        body[start..]
+            .chars()
+            .take_while(|c| c.is_ascii_alphanumeric() || *c == '-' || *c == '_')
+            .collect()
+    }
+}
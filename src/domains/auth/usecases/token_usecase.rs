@@ -0,0 +1,215 @@
+use crate::domains::auth::dtos::oidc_dto::TokenIntrospectionResponse;
+use crate::domains::auth::repositories::token_denylist_repository::TokenDenylistRepositoryTrait;
+use crate::domains::auth::usecases::oidc_usecase::issuer;
+use crate::domains::common::errors::AppError;
+use crate::domains::common::utils::jwt::{Claims, JwtService};
+use crate::domains::common::utils::request_helper;
+use crate::domains::tenant::entities::client::GRANT_TYPE_CLIENT_CREDENTIALS;
+use crate::domains::tenant::repositories::client_repository::ClientRepositoryTrait;
+use crate::domains::user::entities::user::Model as User;
+use crate::domains::user::entities::user_session::Model as UserSession;
+use crate::domains::user::repositories::user_repository::UserRepositoryTrait;
+use crate::domains::user::repositories::user_session_repository::UserSessionRepositoryTrait;
+use chrono::Utc;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Token introspection (RFC 7662) and revocation (RFC 7009) for resource
+/// servers and clients.
+///
+/// Callers act for one tenant, either through its API key or as one of its
+/// confidential clients, and only see and revoke tokens of that tenant.
+/// Revoked refresh tokens lose their session; revoked access tokens are
+/// denied until they expire.
+pub struct TokenUseCase {
+    client_repository: Arc<dyn ClientRepositoryTrait>,
+    user_repository: Arc<dyn UserRepositoryTrait>,
+    session_repository: Arc<dyn UserSessionRepositoryTrait>,
+    denylist_repository: Arc<dyn TokenDenylistRepositoryTrait>,
+    jwt_service: JwtService,
+}
+
+/// A token that passed signature and expiry checks.
+enum ValidatedToken {
+    Access(Claims),
+    Refresh(Claims, UserSession),
+}
+
+impl TokenUseCase {
+    /// Creates a new TokenUseCase instance.
+    pub fn new(
+        client_repository: Arc<dyn ClientRepositoryTrait>,
+        user_repository: Arc<dyn UserRepositoryTrait>,
+        session_repository: Arc<dyn UserSessionRepositoryTrait>,
+        denylist_repository: Arc<dyn TokenDenylistRepositoryTrait>,
+    ) -> Self {
+        Self {
+            client_repository,
+            user_repository,
+            session_repository,
+            denylist_repository,
+            jwt_service: JwtService::new(),
+        }
+    }
+
+    /// Authenticates a confidential client and returns its tenant.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::OAuth` with `invalid_client` if the client is
+    /// unknown, public or the secret does not match.
+    pub async fn authenticate_client(
+        &self,
+        client_id: Option<&str>,
+        client_secret: Option<&str>,
+    ) -> Result<Uuid, AppError> {
+        let client = match client_id {
+            Some(client_id) => self.client_repository.find_by_client_id(client_id).await?,
+            None => None,
+        };
+
+        match (client, client_secret) {
+            (Some(client), Some(secret)) if !client.is_public() && client.verify_secret(secret) => {
+                Ok(client.tenant_id)
+            }
+            _ => Err(AppError::OAuth(
+                "invalid_client",
+                "Client authentication failed".to_string(),
+            )),
+        }
+    }
+
+    /// Describes `token` for a caller of `tenant_id`.
+    ///
+    /// Tokens that are invalid, expired, revoked, of another tenant or whose
+    /// user or client is gone are reported as inactive.
+    pub async fn introspect(
+        &self,
+        tenant_id: Uuid,
+        token: &str,
+    ) -> Result<TokenIntrospectionResponse, AppError> {
+        let (claims, token_use) = match self.validate(tenant_id, token).await? {
+            Some(ValidatedToken::Access(claims)) => (claims, "access_token"),
+            Some(ValidatedToken::Refresh(claims, _)) => (claims, "refresh_token"),
+            None => return Ok(TokenIntrospectionResponse::default()),
+        };
+
+        let username = match Uuid::parse_str(&claims.sub) {
+            Ok(user_id) if !claims.is_client() => {
+                self.active_user(user_id).await?.map(|u| u.username)
+            }
+            _ => None,
+        };
+
+        Ok(TokenIntrospectionResponse {
+            active: true,
+            scope: claims.scope.clone(),
+            client_id: claims.is_client().then(|| claims.sub.clone()),
+            username,
+            token_type: (token_use == "access_token").then(|| "Bearer".to_string()),
+            token_use: Some(token_use.to_string()),
+            exp: Some(claims.exp),
+            iat: Some(claims.iat),
+            sub: Some(claims.sub),
+            iss: Some(issuer()),
+            tenant_id: Some(claims.tenant_id),
+            role: Some(claims.role),
+            aud: claims.aud,
+            act: claims.act,
+            impersonator: claims.impersonator,
+        })
+    }
+
+    /// Revokes `token` for a caller of `tenant_id`.
+    ///
+    /// Revoking a refresh token signs its device out, like deleting the
+    /// session. Unknown tokens and tokens of other tenants are ignored, as
+    /// RFC 7009 asks, so callers cannot probe for valid tokens.
+    pub async fn revoke(&self, tenant_id: Uuid, token: &str) -> Result<(), AppError> {
+        match self.validate(tenant_id, token).await? {
+            Some(ValidatedToken::Access(claims)) => {
+                self.denylist_repository.deny_token(&claims).await?;
+                log::info!(
+                    "Revoked access token of {} in tenant {}",
+                    claims.sub,
+                    tenant_id
+                );
+            }
+            Some(ValidatedToken::Refresh(claims, session)) => {
+                self.session_repository
+                    .delete_family(session.family_id)
+                    .await?;
+                log::info!(
+                    "Revoked refresh token of user {} in tenant {}",
+                    claims.sub,
+                    tenant_id
+                );
+            }
+            None => {}
+        }
+
+        Ok(())
+    }
+
+    /// Returns the token if it is currently valid for `tenant_id`.
+    async fn validate(
+        &self,
+        tenant_id: Uuid,
+        token: &str,
+    ) -> Result<Option<ValidatedToken>, AppError> {
+        let Ok(claims) = self.jwt_service.validate_token(token) else {
+            return Ok(None);
+        };
+        if claims.tenant_id != tenant_id.to_string() {
+            return Ok(None);
+        }
+
+        if claims.is_client() {
+            let active = !self.denylist_repository.is_revoked(&claims).await?
+                && self
+                    .client_repository
+                    .find_by_client_id(&claims.sub)
+                    .await?
+                    .is_some_and(|c| {
+                        c.tenant_id == tenant_id
+                            && c.allows_grant_type(GRANT_TYPE_CLIENT_CREDENTIALS)
+                    });
+            return Ok(active.then_some(ValidatedToken::Access(claims)));
+        }
+
+        let Ok(user_id) = Uuid::parse_str(&claims.sub) else {
+            return Ok(None);
+        };
+        if self.active_user(user_id).await?.is_none() {
+            return Ok(None);
+        }
+
+        match claims.token_type.as_str() {
+            "access" => {
+                // Same rules as the auth middleware: signature, expiry, user
+                // status and the denylist, which revocations and sign-outs fill
+                let active = !self.denylist_repository.is_revoked(&claims).await?;
+                Ok(active.then_some(ValidatedToken::Access(claims)))
+            }
+            "refresh" => {
+                let session = self
+                    .session_repository
+                    .find_by_refresh_token_hash(&request_helper::hash_token(token))
+                    .await?
+                    .filter(|s| {
+                        s.user_id == user_id && s.rotated_at.is_none() && s.expires_at > Utc::now()
+                    });
+                Ok(session.map(|s| ValidatedToken::Refresh(claims, s)))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    async fn active_user(&self, user_id: Uuid) -> Result<Option<User>, AppError> {
+        Ok(self
+            .user_repository
+            .find_by_id(user_id)
+            .await?
+            .filter(|u| u.is_active()))
+    }
+}
@@ -0,0 +1,351 @@
+#[cfg(test)]
+mod tests {
+    use crate::domains::auth::repositories::token_denylist_repository::TokenDenylistRepositoryTrait;
+    use crate::domains::auth::usecases::token_usecase::TokenUseCase;
+    use crate::domains::common::errors::AppError;
+    use crate::domains::common::utils::config::Config;
+    use crate::domains::common::utils::jwt::JwtService;
+    use crate::domains::common::utils::request_helper;
+    use crate::domains::tenant::entities::client::Model as Client;
+    use crate::domains::tenant::repositories::client_repository::ClientRepositoryTrait;
+    use crate::domains::user::entities::user::Model as User;
+    use crate::domains::user::entities::user_session::Model as UserSession;
+    use crate::domains::user::repositories::user_repository::UserRepositoryTrait;
+    use crate::domains::user::repositories::user_session_repository::UserSessionRepositoryTrait;
+
+    use async_trait::async_trait;
+    use chrono::{DateTime, Duration as ChronoDuration, Utc};
+    use mockall::mock;
+    use std::collections::HashSet;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+    use uuid::Uuid;
+
+    mock! {
+        pub UserRepository {}
+        #[async_trait]
+        impl UserRepositoryTrait for UserRepository {
+            async fn create(&self, user: crate::domains::user::dtos::user_dto::CreateUserRequest) -> Result<User, AppError>;
+            async fn find_by_id(&self, id: Uuid) -> Result<Option<User>, AppError>;
+            async fn find_by_username(&self, username: &str) -> Result<Option<User>, AppError>;
+            async fn find_all(&self) -> Result<Vec<User>, AppError>;
+            async fn update(&self, id: Uuid, user: crate::domains::user::dtos::user_dto::UpdateUserRequest) -> Result<User, AppError>;
+            async fn delete(&self, id: Uuid) -> Result<(), AppError>;
+            async fn find_by_email_with_deleted(&self, email: &str) -> Result<Option<User>, AppError>;
+            async fn find_by_username_with_deleted(&self, username: &str) -> Result<Option<User>, AppError>;
+            async fn restore(&self, id: Uuid, req: crate::domains::user::dtos::user_dto::CreateUserRequest) -> Result<User, AppError>;
+            async fn mark_email_verified(&self, id: Uuid) -> Result<User, AppError>;
+            async fn set_login_failures(&self, id: Uuid, failed_login_attempts: i32, locked_until: Option<DateTime<Utc>>) -> Result<(), AppError>;
+            async fn increment_login_failures(&self, id: Uuid) -> Result<i32, AppError>;
+            async fn lock_until(&self, id: Uuid, locked_until: DateTime<Utc>) -> Result<(), AppError>;
+            async fn set_status(&self, id: Uuid, status: String, reason: Option<String>, expires_at: Option<DateTime<Utc>>) -> Result<User, AppError>;
+            async fn purge_deleted_before(&self, cutoff: DateTime<Utc>) -> Result<u64, AppError>;
+        }
+    }
+
+    mock! {
+        pub ClientRepository {}
+        #[async_trait]
+        impl ClientRepositoryTrait for ClientRepository {
+            async fn create(&self, client: Client) -> Result<Client, AppError>;
+            async fn find_by_client_id(&self, client_id: &str) -> Result<Option<Client>, AppError>;
+            async fn find_by_tenant(&self, tenant_id: Uuid) -> Result<Vec<Client>, AppError>;
+            async fn update(&self, client: Client) -> Result<Client, AppError>;
+            async fn delete(&self, id: Uuid) -> Result<(), AppError>;
+        }
+    }
+
+    mock! {
+        pub UserSessionRepository {}
+        #[async_trait]
+        impl UserSessionRepositoryTrait for UserSessionRepository {
+            async fn create_session(&self, id: Option<Uuid>, user_id: Uuid, refresh_token_hash: String, user_agent: Option<String>, ip_address: Option<String>, expires_at: DateTime<Utc>) -> Result<UserSession, AppError>;
+            async fn find_by_refresh_token_hash(&self, hash: &str) -> Result<Option<UserSession>, AppError>;
+            async fn find_by_id(&self, id: Uuid) -> Result<Option<UserSession>, AppError>;
+            async fn find_active_by_user_id(&self, user_id: Uuid) -> Result<Vec<UserSession>, AppError>;
+            async fn rotate_session(&self, parent: &UserSession, id: Uuid, refresh_token_hash: String, user_agent: Option<String>, ip_address: Option<String>, expires_at: DateTime<Utc>) -> Result<Option<UserSession>, AppError>;
+            async fn delete_session(&self, id: Uuid) -> Result<(), AppError>;
+            async fn delete_family(&self, family_id: Uuid) -> Result<(), AppError>;
+            async fn delete_all_sessions_for_user(&self, user_id: Uuid) -> Result<(), AppError>;
+            async fn delete_expired(&self, now: DateTime<Utc>) -> Result<u64, AppError>;
+            async fn delete_other_sessions_for_user(&self, user_id: Uuid, family_id: Uuid) -> Result<(), AppError>;
+        }
+    }
+
+    mock! {
+        pub TokenDenylistRepository {}
+        #[async_trait]
+        impl TokenDenylistRepositoryTrait for TokenDenylistRepository {
+            async fn deny(&self, jti: &str, ttl: Duration) -> Result<(), AppError>;
+            async fn is_denied(&self, jti: &str) -> Result<bool, AppError>;
+            async fn deny_subject(&self, subject: &str, ttl: Duration) -> Result<(), AppError>;
+            async fn subject_denied_at(&self, subject: &str) -> Result<Option<i64>, AppError>;
+        }
+    }
+
+    const CLIENT_SECRET: &str = "machine-client-secret";
+
+    struct TokenTestContext {
+        usecase: TokenUseCase,
+        jwt: JwtService,
+        user: User,
+        tenant_id: Uuid,
+        refresh_token: String,
+    }
+
+    /// Builds the use case for a user with one session, whose refresh token
+    /// is `refresh_token`, and a machine client of the same tenant.
+    fn token_test_setup() -> TokenTestContext {
+        Config::init_for_test();
+        let jwt = JwtService::new();
+        let tenant_id = Uuid::new_v4();
+
+        let user = User {
+            id: Uuid::new_v4(),
+            username: "tokenuser".to_string(),
+            email: "token@example.com".to_string(),
+            password_hash: "unused".to_string(),
+            email_verified_at: Some(Utc::now()),
+            failed_login_attempts: 0,
+            locked_until: None,
+            status: "active".to_string(),
+            status_reason: None,
+            status_expires_at: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+        };
+        let mut user_repo = MockUserRepository::new();
+        let stored = user.clone();
+        user_repo
+            .expect_find_by_id()
+            .returning(move |id| Ok((id == stored.id).then(|| stored.clone())));
+
+        let client = Client {
+            id: Uuid::new_v4(),
+            tenant_id,
+            client_id: "machine-client".to_string(),
+            client_secret_hash: Some(request_helper::hash_token(CLIENT_SECRET)),
+            name: "Reporting Service".to_string(),
+            redirect_uris: serde_json::json!([]),
+            grant_types: serde_json::json!(["client_credentials"]),
+            scopes: serde_json::json!(["reports:read"]),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        let mut client_repo = MockClientRepository::new();
+        client_repo
+            .expect_find_by_client_id()
+            .returning(move |id| Ok((id == client.client_id).then(|| client.clone())));
+
+        let refresh_token = jwt
+            .generate_refresh_token(user.id, tenant_id, "user".to_string(), None, None)
+            .unwrap();
+        let session_id = Uuid::new_v4();
+        let sessions = Arc::new(Mutex::new(vec![UserSession {
+            id: session_id,
+            user_id: user.id,
+            refresh_token_hash: request_helper::hash_token(&refresh_token),
+            user_agent: None,
+            ip_address: None,
+            expires_at: Utc::now() + ChronoDuration::days(1),
+            created_at: Utc::now(),
+            family_id: session_id,
+            parent_id: None,
+            rotated_at: None,
+            last_used_at: None,
+        }]));
+        let mut session_repo = MockUserSessionRepository::new();
+        let state = sessions.clone();
+        session_repo
+            .expect_find_by_refresh_token_hash()
+            .returning(move |hash| {
+                Ok(state
+                    .lock()
+                    .unwrap()
+                    .iter()
+                    .find(|s| s.refresh_token_hash == hash)
+                    .cloned())
+            });
+        let state = sessions;
+        session_repo
+            .expect_delete_family()
+            .returning(move |family_id| {
+                state.lock().unwrap().retain(|s| s.family_id != family_id);
+                Ok(())
+            });
+
+        let denied: Arc<Mutex<HashSet<String>>> = Arc::default();
+        let mut denylist_repo = MockTokenDenylistRepository::new();
+        let state = denied.clone();
+        denylist_repo.expect_deny().returning(move |jti, _| {
+            state.lock().unwrap().insert(jti.to_string());
+            Ok(())
+        });
+        let state = denied;
+        denylist_repo
+            .expect_is_denied()
+            .returning(move |jti| Ok(state.lock().unwrap().contains(jti)));
+        denylist_repo
+            .expect_subject_denied_at()
+            .returning(|_| Ok(None));
+
+        TokenTestContext {
+            usecase: TokenUseCase::new(
+                Arc::new(client_repo),
+                Arc::new(user_repo),
+                Arc::new(session_repo),
+                Arc::new(denylist_repo),
+            ),
+            jwt,
+            user,
+            tenant_id,
+            refresh_token,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_introspect_active_access_token() {
+        let ctx = token_test_setup();
+        let token = ctx
+            .jwt
+            .generate_access_token(ctx.user.id, ctx.tenant_id, "admin".to_string())
+            .unwrap();
+
+        let response = ctx.usecase.introspect(ctx.tenant_id, &token).await.unwrap();
+
+        assert!(response.active);
+        assert_eq!(response.sub, Some(ctx.user.id.to_string()));
+        assert_eq!(response.username.as_deref(), Some("tokenuser"));
+        assert_eq!(response.role.as_deref(), Some("admin"));
+        assert_eq!(response.token_use.as_deref(), Some("access_token"));
+        assert!(response.client_id.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_introspect_hides_tokens_of_other_tenants() {
+        let ctx = token_test_setup();
+        let token = ctx
+            .jwt
+            .generate_access_token(ctx.user.id, ctx.tenant_id, "user".to_string())
+            .unwrap();
+
+        let response = ctx
+            .usecase
+            .introspect(Uuid::new_v4(), &token)
+            .await
+            .unwrap();
+        assert!(!response.active);
+        assert!(response.sub.is_none());
+
+        let response = ctx
+            .usecase
+            .introspect(ctx.tenant_id, "not-a-token")
+            .await
+            .unwrap();
+        assert!(!response.active);
+    }
+
+    #[tokio::test]
+    async fn test_revoked_access_token_is_inactive() {
+        let ctx = token_test_setup();
+        let token = ctx
+            .jwt
+            .generate_access_token(ctx.user.id, ctx.tenant_id, "user".to_string())
+            .unwrap();
+
+        // Revoking from another tenant has no effect
+        ctx.usecase.revoke(Uuid::new_v4(), &token).await.unwrap();
+        assert!(
+            ctx.usecase
+                .introspect(ctx.tenant_id, &token)
+                .await
+                .unwrap()
+                .active
+        );
+
+        ctx.usecase.revoke(ctx.tenant_id, &token).await.unwrap();
+        assert!(
+            !ctx.usecase
+                .introspect(ctx.tenant_id, &token)
+                .await
+                .unwrap()
+                .active
+        );
+    }
+
+    #[tokio::test]
+    async fn test_revoking_refresh_token_ends_session() {
+        let ctx = token_test_setup();
+        let access_token = ctx
+            .jwt
+            .generate_access_token(ctx.user.id, ctx.tenant_id, "user".to_string())
+            .unwrap();
+
+        let response = ctx
+            .usecase
+            .introspect(ctx.tenant_id, &ctx.refresh_token)
+            .await
+            .unwrap();
+        assert!(response.active);
+        assert_eq!(response.token_use.as_deref(), Some("refresh_token"));
+
+        ctx.usecase
+            .revoke(ctx.tenant_id, &ctx.refresh_token)
+            .await
+            .unwrap();
+
+        let response = ctx
+            .usecase
+            .introspect(ctx.tenant_id, &ctx.refresh_token)
+            .await
+            .unwrap();
+        assert!(!response.active);
+        // Access tokens stay valid until they expire or are revoked
+        // themselves, as they do for the auth middleware
+        let response = ctx
+            .usecase
+            .introspect(ctx.tenant_id, &access_token)
+            .await
+            .unwrap();
+        assert!(response.active);
+    }
+
+    #[tokio::test]
+    async fn test_introspect_client_token() {
+        let ctx = token_test_setup();
+        let token = ctx
+            .jwt
+            .generate_client_token("machine-client", ctx.tenant_id, "reports:read".to_string())
+            .unwrap();
+
+        let response = ctx.usecase.introspect(ctx.tenant_id, &token).await.unwrap();
+
+        assert!(response.active);
+        assert_eq!(response.client_id.as_deref(), Some("machine-client"));
+        assert_eq!(response.scope.as_deref(), Some("reports:read"));
+        assert!(response.username.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_authenticate_client() {
+        let ctx = token_test_setup();
+
+        let tenant_id = ctx
+            .usecase
+            .authenticate_client(Some("machine-client"), Some(CLIENT_SECRET))
+            .await
+            .unwrap();
+        assert_eq!(tenant_id, ctx.tenant_id);
+
+        for (client_id, secret) in [
+            (Some("machine-client"), Some("wrong-secret")),
+            (Some("machine-client"), None),
+            (Some("unknown-client"), Some(CLIENT_SECRET)),
+            (None, None),
+        ] {
+            let result = ctx.usecase.authenticate_client(client_id, secret).await;
+            assert!(matches!(result, Err(AppError::OAuth("invalid_client", _))));
+        }
+    }
+}
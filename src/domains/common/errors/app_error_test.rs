@@ -39,6 +39,38 @@ fn test_app_error_status_codes() {
         AppError::PayloadTooLarge("".into()).status_code(),
         StatusCode::PAYLOAD_TOO_LARGE
     );
+    assert_eq!(
+        AppError::Locked("".into()).status_code(),
+        StatusCode::LOCKED
+    );
+}
+
+#[test]
+fn test_oauth_error_status_codes() {
+    assert_eq!(
+        AppError::OAuth("invalid_grant", "".into()).status_code(),
+        StatusCode::BAD_REQUEST
+    );
+    assert_eq!(
+        AppError::OAuth("invalid_client", "".into()).status_code(),
+        StatusCode::UNAUTHORIZED
+    );
+    assert_eq!(
+        AppError::OAuth("insufficient_scope", "".into()).status_code(),
+        StatusCode::FORBIDDEN
+    );
+}
+
+#[actix_web::test]
+async fn test_oauth_error_response_body() {
+    let response = AppError::OAuth("invalid_grant", "Code expired".into()).error_response();
+    let body = actix_web::body::to_bytes(response.into_body())
+        .await
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(json["error"], "invalid_grant");
+    assert_eq!(json["error_description"], "Code expired");
 }
 
 #[test]
@@ -38,6 +38,9 @@ pub enum AppError {
     Conflict(String),
     Forbidden(String),
     PayloadTooLarge(String),
+    Locked(String),
+    /// OAuth 2.0 / OpenID Connect error code (RFC 6749 section 5.2) and description.
+    OAuth(&'static str, String),
 }
 
 impl fmt::Display for AppError {
@@ -52,12 +55,21 @@ impl fmt::Display for AppError {
             AppError::Conflict(msg) => write!(f, "{}", msg),
             AppError::Forbidden(msg) => write!(f, "{}", msg),
             AppError::PayloadTooLarge(msg) => write!(f, "{}", msg),
+            AppError::Locked(msg) => write!(f, "{}", msg),
+            AppError::OAuth(_, msg) => write!(f, "{}", msg),
         }
     }
 }
 
 impl std::error::Error for AppError {}
 
+/// Error body of the OAuth endpoints, in the format client libraries expect.
+#[derive(Serialize)]
+struct OAuthErrorResponse<'a> {
+    error: &'a str,
+    error_description: String,
+}
+
 #[derive(Serialize)]
 struct ErrorResponse {
     status: bool,
@@ -78,11 +90,25 @@ impl ResponseError for AppError {
             AppError::Conflict(_) => StatusCode::CONFLICT,
             AppError::Forbidden(_) => StatusCode::FORBIDDEN,
             AppError::PayloadTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+            AppError::Locked(_) => StatusCode::LOCKED,
+            AppError::OAuth("invalid_client", _) => StatusCode::UNAUTHORIZED,
+            AppError::OAuth("access_denied" | "insufficient_scope", _) => StatusCode::FORBIDDEN,
+            AppError::OAuth("server_error", _) => StatusCode::INTERNAL_SERVER_ERROR,
+            AppError::OAuth(_, _) => StatusCode::BAD_REQUEST,
         }
     }
 
     fn error_response(&self) -> HttpResponse {
         let status_code = self.status_code();
+        if let AppError::OAuth(error, description) = self {
+            return HttpResponse::build(status_code)
+                .insert_header(("Cache-Control", "no-store"))
+                .json(OAuthErrorResponse {
+                    error,
+                    error_description: description.clone(),
+                });
+        }
+
         let details = match self {
             AppError::ValidationError(_, d) => d.clone(),
             _ => None,
@@ -0,0 +1,246 @@
+//! Email templates rendered per tenant and locale.
+//!
+//! Templates are Handlebars files laid out as
+//! `{scope}/{locale}/{name}.{part}.hbs`, where `scope` is a tenant ID or
+//! `default` and `part` is `subject`, `txt` or `html`. The built-in English
+//! templates in `templates/email` are always available; files under
+//! `MAIL_TEMPLATE_DIR` override them or add tenants and locales.
+
+use crate::domains::common::errors::AppError;
+use crate::domains::common::infrastructures::mailer::EmailMessage;
+use crate::domains::common::utils::config::Config;
+use handlebars::Handlebars;
+use log::debug;
+use std::path::Path;
+use uuid::Uuid;
+
+/// Scope holding templates that apply to every tenant.
+const DEFAULT_SCOPE: &str = "default";
+
+/// Templates shipped with the crate, keyed like files under `MAIL_TEMPLATE_DIR`.
+const BUILTIN_TEMPLATES: &[(&str, &str)] = &[
+    (
+        "default/en/password_reset.subject",
+        include_str!("../../../../templates/email/default/en/password_reset.subject.hbs"),
+    ),
+    (
+        "default/en/password_reset.txt",
+        include_str!("../../../../templates/email/default/en/password_reset.txt.hbs"),
+    ),
+    (
+        "default/en/password_reset.html",
+        include_str!("../../../../templates/email/default/en/password_reset.html.hbs"),
+    ),
+    (
+        "default/en/email_verification.subject",
+        include_str!("../../../../templates/email/default/en/email_verification.subject.hbs"),
+    ),
+    (
+        "default/en/email_verification.txt",
+        include_str!("../../../../templates/email/default/en/email_verification.txt.hbs"),
+    ),
+    (
+        "default/en/email_verification.html",
+        include_str!("../../../../templates/email/default/en/email_verification.html.hbs"),
+    ),
+    (
+        "default/en/passwordless_login.subject",
+        include_str!("../../../../templates/email/default/en/passwordless_login.subject.hbs"),
+    ),
+    (
+        "default/en/passwordless_login.txt",
+        include_str!("../../../../templates/email/default/en/passwordless_login.txt.hbs"),
+    ),
+    (
+        "default/en/passwordless_login.html",
+        include_str!("../../../../templates/email/default/en/passwordless_login.html.hbs"),
+    ),
+];
+
+/// Registry of email templates.
+///
+/// Subjects and plaintext parts are rendered without escaping, HTML parts
+/// with Handlebars' HTML escaping.
+pub struct EmailTemplates {
+    text: Handlebars<'static>,
+    html: Handlebars<'static>,
+    default_locale: String,
+}
+
+impl EmailTemplates {
+    /// Creates a registry with the built-in templates plus any found in `dir`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::InternalError` if `dir` cannot be read or a template fails to parse.
+    pub fn new(dir: Option<&Path>, default_locale: &str) -> Result<Self, AppError> {
+        let mut text = Handlebars::new();
+        text.register_escape_fn(handlebars::no_escape);
+        let mut templates = Self {
+            text,
+            html: Handlebars::new(),
+            default_locale: normalize_locale(default_locale),
+        };
+
+        for (key, source) in BUILTIN_TEMPLATES {
+            templates.register(key, source)?;
+        }
+        if let Some(dir) = dir {
+            templates.load_dir(dir)?;
+        }
+
+        Ok(templates)
+    }
+
+    /// Creates a registry from `MAIL_TEMPLATE_DIR` and `MAIL_DEFAULT_LOCALE`.
+    pub fn from_config(config: &Config) -> Result<Self, AppError> {
+        Self::new(
+            config.mail_template_dir.as_deref().map(Path::new),
+            &config.mail_default_locale,
+        )
+    }
+
+    /// Renders the template `name` for a recipient.
+    ///
+    /// The tenant's template is preferred over the default one, first in the
+    /// requested locale, then its base language (`pt` for `pt-BR`), then the
+    /// default locale. The first match with a plaintext part is used together
+    /// with its subject and optional HTML part.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::InternalError` if no template matches or rendering fails.
+    pub fn render(
+        &self,
+        name: &str,
+        tenant_id: Uuid,
+        locale: Option<&str>,
+        to: &str,
+        data: &serde_json::Value,
+    ) -> Result<EmailMessage, AppError> {
+        let prefix = self
+            .candidates(name, tenant_id, locale)
+            .into_iter()
+            .find(|prefix| self.text.has_template(&format!("{}.txt", prefix)))
+            .ok_or_else(|| {
+                AppError::InternalError(format!("Email template '{}' not found", name))
+            })?;
+        debug!("Rendering email template {}", prefix);
+
+        let render_err = |e: handlebars::RenderError| {
+            AppError::InternalError(format!("Failed to render email template {}: {}", prefix, e))
+        };
+
+        let subject_key = format!("{}.subject", prefix);
+        if !self.text.has_template(&subject_key) {
+            return Err(AppError::InternalError(format!(
+                "Email template {} has no subject",
+                prefix
+            )));
+        }
+        let subject = self.text.render(&subject_key, data).map_err(render_err)?;
+        let text_body = self
+            .text
+            .render(&format!("{}.txt", prefix), data)
+            .map_err(render_err)?;
+
+        let html_key = format!("{}.html", prefix);
+        let html_body = if self.html.has_template(&html_key) {
+            Some(self.html.render(&html_key, data).map_err(render_err)?)
+        } else {
+            None
+        };
+
+        Ok(EmailMessage {
+            to: to.to_string(),
+            subject: subject.trim().to_string(),
+            text_body,
+            html_body,
+        })
+    }
+
+    /// Lists template prefixes (`{scope}/{locale}/{name}`) in lookup order.
+    fn candidates(&self, name: &str, tenant_id: Uuid, locale: Option<&str>) -> Vec<String> {
+        let mut locales = Vec::new();
+        if let Some(locale) = locale.map(normalize_locale).filter(|l| !l.is_empty()) {
+            let base = locale.split_once('-').map(|(base, _)| base.to_string());
+            locales.push(locale);
+            locales.extend(base);
+        }
+        locales.push(self.default_locale.clone());
+
+        let tenant_scope = tenant_id.to_string();
+        let mut prefixes: Vec<String> = Vec::new();
+        for locale in &locales {
+            for scope in [tenant_scope.as_str(), DEFAULT_SCOPE] {
+                let prefix = format!("{}/{}/{}", scope, locale, name);
+                if !prefixes.contains(&prefix) {
+                    prefixes.push(prefix);
+                }
+            }
+        }
+        prefixes
+    }
+
+    /// Registers every `{scope}/{locale}/{name}.{part}.hbs` file below `dir`.
+    fn load_dir(&mut self, dir: &Path) -> Result<(), AppError> {
+        let read_dir = |path: &Path| {
+            std::fs::read_dir(path).map_err(|e| {
+                AppError::InternalError(format!(
+                    "Failed to read email templates from {}: {}",
+                    path.display(),
+                    e
+                ))
+            })
+        };
+
+        for scope in read_dir(dir)?.flatten() {
+            if !scope.path().is_dir() {
+                continue;
+            }
+            for locale in read_dir(&scope.path())?.flatten() {
+                if !locale.path().is_dir() {
+                    continue;
+                }
+                for file in read_dir(&locale.path())?.flatten() {
+                    let file_name = file.file_name().to_string_lossy().to_string();
+                    let Some(stem) = file_name.strip_suffix(".hbs") else {
+                        continue;
+                    };
+                    let source = std::fs::read_to_string(file.path()).map_err(|e| {
+                        AppError::InternalError(format!(
+                            "Failed to read email template {}: {}",
+                            file.path().display(),
+                            e
+                        ))
+                    })?;
+                    let key = format!(
+                        "{}/{}/{}",
+                        scope.file_name().to_string_lossy(),
+                        normalize_locale(&locale.file_name().to_string_lossy()),
+                        stem
+                    );
+                    self.register(&key, &source)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn register(&mut self, key: &str, source: &str) -> Result<(), AppError> {
+        let registry = if key.ends_with(".html") {
+            &mut self.html
+        } else {
+            &mut self.text
+        };
+        registry
+            .register_template_string(key, source)
+            .map_err(|e| AppError::InternalError(format!("Invalid email template {}: {}", key, e)))
+    }
+}
+
+/// Lowercases a locale tag and uses `-` as separator (`pt_BR` -> `pt-br`).
+fn normalize_locale(locale: &str) -> String {
+    locale.trim().to_lowercase().replace('_', "-")
+}
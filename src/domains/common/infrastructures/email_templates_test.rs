@@ -0,0 +1,118 @@
+use super::email_templates::*;
+use serde_json::json;
+use std::fs;
+use tempfile::tempdir;
+use uuid::Uuid;
+
+fn write_template(root: &std::path::Path, scope: &str, locale: &str, file: &str, body: &str) {
+    let dir = root.join(scope).join(locale);
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join(file), body).unwrap();
+}
+
+#[test]
+fn test_builtin_templates_render_text_and_html() {
+    let templates = EmailTemplates::new(None, "en").unwrap();
+    let data = json!({
+        "username": "<b>john</b>",
+        "link": "https://app.example.com/reset-password?token=abc",
+        "expires_in_minutes": 60,
+    });
+
+    let message = templates
+        .render(
+            "password_reset",
+            Uuid::new_v4(),
+            None,
+            "john@example.com",
+            &data,
+        )
+        .unwrap();
+
+    assert_eq!(message.to, "john@example.com");
+    assert_eq!(message.subject, "Reset your password");
+    assert!(message.text_body.contains("Hi <b>john</b>,"));
+    assert!(message
+        .text_body
+        .contains("https://app.example.com/reset-password?token=abc"));
+
+    // HTML parts are escaped, plaintext parts are not
+    let html = message.html_body.unwrap();
+    assert!(html.contains("&lt;b&gt;john&lt;/b&gt;"));
+    assert!(!html.contains("<b>john</b>"));
+}
+
+#[test]
+fn test_tenant_and_locale_overrides_take_precedence() {
+    let dir = tempdir().unwrap();
+    let tenant_id = Uuid::new_v4();
+    let other_tenant = Uuid::new_v4();
+
+    write_template(
+        dir.path(),
+        &tenant_id.to_string(),
+        "en",
+        "password_reset.subject.hbs",
+        "Acme password reset",
+    );
+    write_template(
+        dir.path(),
+        &tenant_id.to_string(),
+        "en",
+        "password_reset.txt.hbs",
+        "Acme: {{link}}",
+    );
+    write_template(
+        dir.path(),
+        "default",
+        "pt",
+        "password_reset.subject.hbs",
+        "Redefinir senha",
+    );
+    write_template(
+        dir.path(),
+        "default",
+        "pt",
+        "password_reset.txt.hbs",
+        "Olá {{username}}",
+    );
+
+    let templates = EmailTemplates::new(Some(dir.path()), "en").unwrap();
+    let data = json!({ "username": "john", "link": "https://x" });
+    let render = |tenant: Uuid, locale: Option<&str>| {
+        templates
+            .render("password_reset", tenant, locale, "john@example.com", &data)
+            .unwrap()
+    };
+
+    // Tenant template without an HTML part
+    let message = render(tenant_id, None);
+    assert_eq!(message.subject, "Acme password reset");
+    assert_eq!(message.text_body, "Acme: https://x");
+    assert!(message.html_body.is_none());
+
+    // Regional locale falls back to its base language before the default locale
+    let message = render(tenant_id, Some("pt-BR"));
+    assert_eq!(message.subject, "Redefinir senha");
+    assert_eq!(message.text_body, "Olá john");
+
+    // Other tenants get the built-in template
+    let message = render(other_tenant, Some("de"));
+    assert_eq!(message.subject, "Reset your password");
+    assert!(message.html_body.is_some());
+}
+
+#[test]
+fn test_unknown_template_is_an_error() {
+    let templates = EmailTemplates::new(None, "en").unwrap();
+
+    let result = templates.render(
+        "does_not_exist",
+        Uuid::new_v4(),
+        Some("en"),
+        "john@example.com",
+        &json!({}),
+    );
+
+    assert!(result.is_err());
+}
@@ -0,0 +1,77 @@
+//! Outbound email delivery.
+//!
+//! Use cases depend on the `Mailer` trait so that the transport can be swapped
+//! without touching business logic. The transport is chosen with `MAIL_TRANSPORT`:
+//! `smtp` delivers through `SmtpMailer`, `outbox` (the default) hands messages
+//! to `OutboxMailer` for development and tests.
+
+use crate::domains::common::errors::AppError;
+use crate::domains::common::infrastructures::outbox_mailer::OutboxMailer;
+use crate::domains::common::infrastructures::smtp_mailer::SmtpMailer;
+use crate::domains::common::utils::config::Config;
+use async_trait::async_trait;
+use lettre::message::{Mailbox, MultiPart, SinglePart};
+use lettre::Message;
+use std::sync::Arc;
+
+/// An email ready to be delivered, with a plaintext and an optional HTML part.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmailMessage {
+    pub to: String,
+    pub subject: String,
+    pub text_body: String,
+    pub html_body: Option<String>,
+}
+
+impl EmailMessage {
+    /// Builds the MIME message sent by the transports.
+    ///
+    /// Messages with an HTML part are sent as `multipart/alternative` so that
+    /// clients without HTML support show the plaintext part.
+    pub fn to_mime(&self, from: &Mailbox) -> Result<Message, AppError> {
+        let to: Mailbox = self
+            .to
+            .parse()
+            .map_err(|e| AppError::InternalError(format!("Invalid recipient address: {}", e)))?;
+
+        let builder = Message::builder()
+            .from(from.clone())
+            .to(to)
+            .subject(self.subject.clone());
+
+        let message = match &self.html_body {
+            Some(html) => builder.multipart(MultiPart::alternative_plain_html(
+                self.text_body.clone(),
+                html.clone(),
+            )),
+            None => builder.singlepart(SinglePart::plain(self.text_body.clone())),
+        };
+
+        message.map_err(|e| AppError::InternalError(format!("Failed to build email: {}", e)))
+    }
+}
+
+#[async_trait]
+pub trait Mailer: Send + Sync {
+    async fn send(&self, message: EmailMessage) -> Result<(), AppError>;
+}
+
+/// Creates the mailer selected by `MAIL_TRANSPORT`.
+///
+/// # Errors
+///
+/// Returns `AppError::InternalError` if the sender address or SMTP settings are invalid.
+pub fn from_config(config: &Config) -> Result<Arc<dyn Mailer>, AppError> {
+    let from: Mailbox = config
+        .mail_from
+        .parse()
+        .map_err(|e| AppError::InternalError(format!("Invalid MAIL_FROM address: {}", e)))?;
+
+    match config.mail_transport.as_str() {
+        "smtp" => Ok(Arc::new(SmtpMailer::new(config, from)?)),
+        _ => Ok(Arc::new(OutboxMailer::new(
+            from,
+            config.mail_outbox_dir.clone().map(Into::into),
+        ))),
+    }
+}
@@ -1,9 +1,20 @@
+pub mod email_templates;
+#[cfg(test)]
+mod email_templates_test;
+pub mod mailer;
+pub mod outbox_mailer;
+#[cfg(test)]
+mod outbox_mailer_test;
 pub mod postgres_connection;
 #[cfg(test)]
 mod postgres_connection_test;
+pub mod rate_limiter;
+#[cfg(test)]
+mod rate_limiter_test;
 pub mod rocksdb_connection;
 #[cfg(test)]
 mod rocksdb_connection_test;
+pub mod smtp_mailer;
 pub mod sqlite_connection;
 #[cfg(test)]
 mod sqlite_connection_test;
@@ -0,0 +1,62 @@
+//! Development email transport.
+
+use crate::domains::common::errors::AppError;
+use crate::domains::common::infrastructures::mailer::{EmailMessage, Mailer};
+use async_trait::async_trait;
+use chrono::Utc;
+use lettre::message::Mailbox;
+use log::info;
+use std::path::PathBuf;
+
+/// Mailer that keeps messages local instead of delivering them.
+///
+/// With `MAIL_OUTBOX_DIR` set, each message is written there as an `.eml`
+/// file that can be opened in a mail client. Otherwise the plaintext part is
+/// written to the application log.
+///
+/// Message bodies may contain single-use links, so this must not be used in production.
+pub struct OutboxMailer {
+    from: Mailbox,
+    dir: Option<PathBuf>,
+}
+
+impl OutboxMailer {
+    /// Creates a new OutboxMailer writing to `dir`, or to the log if `None`.
+    pub fn new(from: Mailbox, dir: Option<PathBuf>) -> Self {
+        Self { from, dir }
+    }
+}
+
+#[async_trait]
+impl Mailer for OutboxMailer {
+    async fn send(&self, message: EmailMessage) -> Result<(), AppError> {
+        // Built even when only logging so that invalid messages fail like they would over SMTP
+        let mime = message.to_mime(&self.from)?;
+
+        let Some(dir) = &self.dir else {
+            info!(
+                "📧 Email to {} | {}\n{}",
+                message.to, message.subject, message.text_body
+            );
+            return Ok(());
+        };
+
+        let path = dir.join(format!(
+            "{}-{}.eml",
+            Utc::now().format("%Y%m%dT%H%M%S%.3f"),
+            uuid::Uuid::new_v4()
+        ));
+
+        std::fs::create_dir_all(dir)
+            .and_then(|_| std::fs::write(&path, mime.formatted()))
+            .map_err(|e| AppError::InternalError(format!("Failed to write outbox email: {}", e)))?;
+
+        info!(
+            "📧 Email to {} | {} written to {}",
+            message.to,
+            message.subject,
+            path.display()
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,53 @@
+use super::mailer::{EmailMessage, Mailer};
+use super::outbox_mailer::*;
+use std::fs;
+use tempfile::tempdir;
+
+#[tokio::test]
+async fn test_outbox_mailer_writes_multipart_eml() {
+    let dir = tempdir().unwrap();
+    let mailer = OutboxMailer::new(
+        "Auth <no-reply@example.com>".parse().unwrap(),
+        Some(dir.path().join("outbox")),
+    );
+
+    mailer
+        .send(EmailMessage {
+            to: "john@example.com".to_string(),
+            subject: "Verify your email address".to_string(),
+            text_body: "Plain body".to_string(),
+            html_body: Some("<p>HTML body</p>".to_string()),
+        })
+        .await
+        .unwrap();
+
+    let files: Vec<_> = fs::read_dir(dir.path().join("outbox"))
+        .unwrap()
+        .flatten()
+        .collect();
+    assert_eq!(files.len(), 1);
+    assert!(files[0].file_name().to_string_lossy().ends_with(".eml"));
+
+    let eml = fs::read_to_string(files[0].path()).unwrap();
+    assert!(eml.contains("To: john@example.com"));
+    assert!(eml.contains("Subject: Verify your email address"));
+    assert!(eml.contains("multipart/alternative"));
+    assert!(eml.contains("Plain body"));
+    assert!(eml.contains("<p>HTML body</p>"));
+}
+
+#[tokio::test]
+async fn test_outbox_mailer_rejects_invalid_recipient() {
+    let mailer = OutboxMailer::new("no-reply@example.com".parse().unwrap(), None);
+
+    let result = mailer
+        .send(EmailMessage {
+            to: "not an address".to_string(),
+            subject: "Subject".to_string(),
+            text_body: "Body".to_string(),
+            html_body: None,
+        })
+        .await;
+
+    assert!(result.is_err());
+}
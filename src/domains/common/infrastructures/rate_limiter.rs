@@ -0,0 +1,258 @@
+//! Token bucket rate limiter backed by `RocksDbCache`.
+//!
+//! Every rule (`login`, `login_ip`, ...) has a default limit from the
+//! configuration which tenants can override. A bucket holds up to
+//! `max_requests` tokens and refills continuously, so a client can burst up to
+//! the limit and then makes one request per `window_secs / max_requests`.
+
+use crate::domains::common::infrastructures::rocksdb_connection::RocksDbCache;
+use crate::domains::common::utils::config::Config;
+use crate::domains::common::utils::request_helper;
+use crate::domains::tenant::repositories::tenant_repository::TenantRepositoryTrait;
+use log::{error, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use uuid::Uuid;
+
+/// Login attempts per login identifier.
+pub const LOGIN: &str = "login";
+/// Login attempts per client IP.
+pub const LOGIN_IP: &str = "login_ip";
+/// Registrations per client IP.
+pub const REGISTER: &str = "register";
+/// Token refreshes per client IP.
+pub const REFRESH: &str = "refresh";
+/// MQTT credential checks per MQTT username.
+pub const MQTT_CHECK: &str = "mqtt_check";
+
+/// How long a tenant's overrides are cached. Changes to `rate_limits` take
+/// effect within this time.
+const OVERRIDES_CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// Names of all rules, the keys accepted in `RateLimitOverrides`.
+pub const RATE_LIMIT_RULES: [&str; 5] = [LOGIN, LOGIN_IP, REGISTER, REFRESH, MQTT_CHECK];
+
+/// Allows `max_requests` per `window_secs`. Zero requests disables the limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RateLimit {
+    pub max_requests: u32,
+    pub window_secs: u64,
+}
+
+impl RateLimit {
+    /// Parses `{max_requests}/{window_secs}`, e.g. `10/300`.
+    pub fn parse(spec: &str) -> Option<Self> {
+        let (max_requests, window_secs) = spec.trim().split_once('/')?;
+        let limit = Self {
+            max_requests: max_requests.trim().parse().ok()?,
+            window_secs: window_secs.trim().parse().ok()?,
+        };
+        (limit.window_secs > 0).then_some(limit)
+    }
+
+    fn is_disabled(&self) -> bool {
+        self.max_requests == 0 || self.window_secs == 0
+    }
+}
+
+/// Per-tenant rate limits keyed by rule name, stored in `tenants.rate_limits`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct RateLimitOverrides(pub BTreeMap<String, RateLimit>);
+
+impl RateLimitOverrides {
+    /// Reads the overrides stored on a tenant. Invalid JSON counts as no overrides.
+    pub fn from_json(value: Option<serde_json::Value>) -> Self {
+        value
+            .and_then(|value| serde_json::from_value(value).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn get(&self, rule: &str) -> Option<RateLimit> {
+        self.0.get(rule).copied()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &RateLimit)> {
+        self.0.iter()
+    }
+}
+
+/// Returned when a bucket is empty.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimited {
+    /// Seconds until the next request is allowed.
+    pub retry_after: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+struct TokenBucket {
+    tokens: f64,
+    updated_at_ms: u64,
+}
+
+pub struct RateLimiter {
+    cache: Arc<RocksDbCache>,
+    tenant_repository: Arc<dyn TenantRepositoryTrait>,
+    defaults: HashMap<&'static str, RateLimit>,
+    enabled: bool,
+    // Serializes bucket updates, RocksDB has no atomic read-modify-write
+    lock: Mutex<()>,
+}
+
+impl RateLimiter {
+    /// Creates a limiter with the given default limit per rule.
+    ///
+    /// Rules without a default are only limited for tenants that override them.
+    pub fn new(
+        cache: Arc<RocksDbCache>,
+        tenant_repository: Arc<dyn TenantRepositoryTrait>,
+        defaults: HashMap<&'static str, RateLimit>,
+        enabled: bool,
+    ) -> Self {
+        Self {
+            cache,
+            tenant_repository,
+            defaults,
+            enabled,
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// Creates a limiter from `RATE_LIMIT_ENABLED` and the `RATE_LIMIT_*` defaults.
+    pub fn from_config(
+        config: &Config,
+        cache: Arc<RocksDbCache>,
+        tenant_repository: Arc<dyn TenantRepositoryTrait>,
+    ) -> Self {
+        let defaults = HashMap::from([
+            (LOGIN, config.rate_limit_login),
+            (LOGIN_IP, config.rate_limit_login_ip),
+            (REGISTER, config.rate_limit_register),
+            (REFRESH, config.rate_limit_refresh),
+            (MQTT_CHECK, config.rate_limit_mqtt_check),
+        ]);
+        Self::new(
+            cache,
+            tenant_repository,
+            defaults,
+            config.rate_limit_enabled,
+        )
+    }
+
+    /// Takes one token from the bucket of `rule` for `key` within a tenant.
+    ///
+    /// `key` is hashed before it becomes part of the cache key, so login
+    /// identifiers are not stored in clear text. Requests without a tenant
+    /// share a global bucket per key and only use the default limit.
+    pub async fn acquire(
+        &self,
+        rule: &str,
+        tenant_id: Option<Uuid>,
+        key: &str,
+    ) -> Result<(), RateLimited> {
+        if !self.enabled {
+            return Ok(());
+        }
+
+        let Some(limit) = self.limit_for(rule, tenant_id).await else {
+            return Ok(());
+        };
+        if limit.is_disabled() {
+            return Ok(());
+        }
+
+        let scope = tenant_id.map_or_else(|| "global".to_string(), |id| id.to_string());
+        let cache_key = format!(
+            "rate_limit:{}:{}:{}",
+            rule,
+            scope,
+            request_helper::hash_token(key)
+        );
+
+        let result = self.take_token(&cache_key, limit);
+        if let Err(limited) = result {
+            warn!(
+                "Rate limit '{}' exceeded for tenant {}, retry after {}s",
+                rule, scope, limited.retry_after
+            );
+        }
+        result
+    }
+
+    /// Returns the tenant's override of `rule`, falling back to the default.
+    async fn limit_for(&self, rule: &str, tenant_id: Option<Uuid>) -> Option<RateLimit> {
+        if let Some(tenant_id) = tenant_id {
+            if let Some(limit) = self.overrides(tenant_id).await.get(rule) {
+                return Some(limit);
+            }
+        }
+
+        self.defaults.get(rule).copied()
+    }
+
+    /// Returns the overrides of a tenant, cached so that requests do not load
+    /// the tenant for every rule.
+    async fn overrides(&self, tenant_id: Uuid) -> RateLimitOverrides {
+        let cache_key = format!("rate_limit_overrides:{}", tenant_id);
+        if let Some(overrides) = self.cache.get::<RateLimitOverrides>(&cache_key) {
+            return overrides;
+        }
+
+        match self.tenant_repository.find_by_id(tenant_id).await {
+            Ok(tenant) => {
+                let overrides =
+                    RateLimitOverrides::from_json(tenant.and_then(|tenant| tenant.rate_limits));
+                self.cache
+                    .set(&cache_key, overrides.clone(), OVERRIDES_CACHE_TTL);
+                overrides
+            }
+            Err(e) => {
+                error!("Failed to load rate limits of tenant {}: {}", tenant_id, e);
+                RateLimitOverrides::default()
+            }
+        }
+    }
+
+    fn take_token(&self, cache_key: &str, limit: RateLimit) -> Result<(), RateLimited> {
+        let _guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+
+        let now_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64;
+        let capacity = limit.max_requests as f64;
+        let refill_per_ms = capacity / (limit.window_secs * 1000) as f64;
+
+        let mut bucket = self
+            .cache
+            .get::<TokenBucket>(cache_key)
+            .unwrap_or(TokenBucket {
+                tokens: capacity,
+                updated_at_ms: now_ms,
+            });
+        let elapsed_ms = now_ms.saturating_sub(bucket.updated_at_ms) as f64;
+        bucket.tokens = (bucket.tokens + elapsed_ms * refill_per_ms).min(capacity);
+        bucket.updated_at_ms = now_ms;
+
+        let result = if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            let wait_ms = (1.0 - bucket.tokens) / refill_per_ms;
+            Err(RateLimited {
+                retry_after: ((wait_ms / 1000.0).ceil() as u64).max(1),
+            })
+        };
+
+        // An untouched bucket is full again after one window
+        self.cache
+            .set(cache_key, bucket, Duration::from_secs(limit.window_secs));
+        result
+    }
+}
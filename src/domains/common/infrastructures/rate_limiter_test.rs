@@ -0,0 +1,151 @@
+use super::rate_limiter::*;
+use super::rocksdb_connection::RocksDbCache;
+use crate::domains::common::errors::AppError;
+use crate::domains::tenant::dtos::tenant_dto::{CreateTenantRequest, UpdateTenantRequest};
+use crate::domains::tenant::entities::tenant::Model as Tenant;
+use crate::domains::tenant::repositories::tenant_repository::TenantRepositoryTrait;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use mockall::mock;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tempfile::tempdir;
+use uuid::Uuid;
+
+mock! {
+    pub TenantRepository {}
+    #[async_trait]
+    impl TenantRepositoryTrait for TenantRepository {
+        async fn create(&self, tenant: CreateTenantRequest) -> Result<Tenant, AppError>;
+        async fn find_by_id(&self, id: Uuid) -> Result<Option<Tenant>, AppError>;
+        async fn find_by_name(&self, name: &str) -> Result<Option<Tenant>, AppError>;
+        async fn find_by_name_with_deleted(&self, name: &str) -> Result<Option<Tenant>, AppError>;
+        async fn find_all(&self) -> Result<Vec<Tenant>, AppError>;
+        async fn update(&self, id: Uuid, tenant: UpdateTenantRequest) -> Result<Tenant, AppError>;
+        async fn delete(&self, id: Uuid) -> Result<(), AppError>;
+        async fn restore(&self, id: Uuid) -> Result<(), AppError>;
+        async fn purge_deleted_before(&self, cutoff: DateTime<Utc>) -> Result<u64, AppError>;
+    }
+}
+
+fn tenant_with_rate_limits(id: Uuid, rate_limits: Option<serde_json::Value>) -> Tenant {
+    Tenant {
+        id,
+        name: "Tenant".to_string(),
+        description: None,
+        api_key: None,
+        require_email_verification: false,
+        passwordless_login: false,
+        rate_limits,
+        deleted_at: None,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    }
+}
+
+fn limiter(
+    cache_path: &str,
+    tenant_repository: MockTenantRepository,
+    enabled: bool,
+) -> RateLimiter {
+    let cache = Arc::new(RocksDbCache::new(cache_path).unwrap());
+    let defaults = HashMap::from([(
+        LOGIN,
+        RateLimit {
+            max_requests: 2,
+            window_secs: 60,
+        },
+    )]);
+    RateLimiter::new(cache, Arc::new(tenant_repository), defaults, enabled)
+}
+
+#[test]
+fn test_rate_limit_parse() {
+    assert_eq!(
+        RateLimit::parse(" 10 / 300 "),
+        Some(RateLimit {
+            max_requests: 10,
+            window_secs: 300,
+        })
+    );
+    assert_eq!(RateLimit::parse("10"), None);
+    assert_eq!(RateLimit::parse("10/0"), None);
+    assert_eq!(RateLimit::parse("ten/60"), None);
+}
+
+#[tokio::test]
+async fn test_rate_limiter_blocks_after_limit_per_key() {
+    let dir = tempdir().unwrap();
+    let limiter = limiter(
+        dir.path().to_str().unwrap(),
+        MockTenantRepository::new(),
+        true,
+    );
+
+    assert!(limiter.acquire(LOGIN, None, "alice").await.is_ok());
+    assert!(limiter.acquire(LOGIN, None, "alice").await.is_ok());
+
+    // Two requests per minute refill one token every 30 seconds
+    let limited = limiter.acquire(LOGIN, None, "alice").await.unwrap_err();
+    assert!(limited.retry_after > 0 && limited.retry_after <= 30);
+
+    // Other keys and rules without a limit are not affected
+    assert!(limiter.acquire(LOGIN, None, "bob").await.is_ok());
+    for _ in 0..5 {
+        assert!(limiter.acquire(REFRESH, None, "alice").await.is_ok());
+    }
+}
+
+#[tokio::test]
+async fn test_rate_limiter_uses_tenant_override() {
+    let dir = tempdir().unwrap();
+    let tenant_id = Uuid::new_v4();
+    let mut tenant_repository = MockTenantRepository::new();
+    // Loaded once per tenant, then served from the cache
+    tenant_repository
+        .expect_find_by_id()
+        .times(2)
+        .returning(move |id| {
+            Ok(Some(tenant_with_rate_limits(
+                id,
+                Some(serde_json::json!({
+                    "login": { "max_requests": 1, "window_secs": 60 },
+                    "refresh": { "max_requests": 0, "window_secs": 60 }
+                })),
+            )))
+        });
+    let limiter = limiter(dir.path().to_str().unwrap(), tenant_repository, true);
+
+    assert!(limiter
+        .acquire(LOGIN, Some(tenant_id), "alice")
+        .await
+        .is_ok());
+    assert!(limiter
+        .acquire(LOGIN, Some(tenant_id), "alice")
+        .await
+        .is_err());
+    assert!(limiter
+        .acquire(REFRESH, Some(tenant_id), "alice")
+        .await
+        .is_ok());
+
+    // Buckets of other tenants are separate
+    assert!(limiter
+        .acquire(LOGIN, Some(Uuid::new_v4()), "alice")
+        .await
+        .is_ok());
+}
+
+#[tokio::test]
+async fn test_rate_limiter_disabled() {
+    let dir = tempdir().unwrap();
+    let limiter = limiter(
+        dir.path().to_str().unwrap(),
+        MockTenantRepository::new(),
+        false,
+    );
+
+    for _ in 0..5 {
+        assert!(limiter.acquire(LOGIN, None, "alice").await.is_ok());
+    }
+}
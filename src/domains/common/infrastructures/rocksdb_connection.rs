@@ -1,5 +1,6 @@
+use crate::domains::common::errors::AppError;
 use log::{error, info, warn};
-use rocksdb::{Options, DB};
+use rocksdb::{IteratorMode, Options, DB};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::sync::Arc;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
@@ -10,6 +11,12 @@ struct CachedItem<T> {
     expired_at: u64,
 }
 
+/// Expiry header of a `CachedItem`, read without deserializing the payload.
+#[derive(Deserialize)]
+struct CachedExpiry {
+    expired_at: u64,
+}
+
 pub struct RocksDbCache {
     db: Arc<DB>,
 }
@@ -71,38 +78,36 @@ impl RocksDbCache {
     }
 
     pub fn get<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
-        match self.db.get(key) {
-            Ok(Some(value)) => {
-                match serde_json::from_slice::<CachedItem<T>>(&value) {
-                    Ok(item) => {
-                        let now = SystemTime::now()
-                            .duration_since(UNIX_EPOCH)
-                            .unwrap()
-                            .as_secs();
-                        if item.expired_at > now {
-                            // info!("CACHE HIT: {}", key);
-                            return Some(item.data);
-                        }
+        self.try_get(key).unwrap_or_else(|e| {
+            error!("{}", e);
+            None
+        })
+    }
 
-                        // info!("CACHE EXPIRED: {}", key);
-                        let _ = self.db.delete(key); // Lazy delete
-                        None
-                    }
-                    Err(e) => {
-                        error!("Failed to deserialize cache item: {}", e);
-                        None
-                    }
-                }
-            }
-            Ok(None) => {
-                // info!("CACHE MISS: {}", key);
-                None
-            }
-            Err(e) => {
-                error!("RocksDB get error: {}", e);
-                None
-            }
+    /// Like `get`, but reports read errors and unreadable items instead of
+    /// treating them as a miss, for callers that must fail closed.
+    pub fn try_get<T: DeserializeOwned>(&self, key: &str) -> Result<Option<T>, AppError> {
+        let Some(value) = self
+            .db
+            .get(key)
+            .map_err(|e| AppError::InternalError(format!("RocksDB get error: {}", e)))?
+        else {
+            return Ok(None);
+        };
+
+        let item = serde_json::from_slice::<CachedItem<T>>(&value).map_err(|e| {
+            AppError::InternalError(format!("Failed to deserialize cache item: {}", e))
+        })?;
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        if item.expired_at > now {
+            return Ok(Some(item.data));
         }
+
+        let _ = self.db.delete(key); // Lazy delete
+        Ok(None)
     }
 
     pub fn set<T: Serialize>(&self, key: &str, value: T, ttl: Duration) {
@@ -132,6 +137,34 @@ impl RocksDbCache {
         let _ = self.db.delete(key);
     }
 
+    /// Deletes every expired entry and returns how many were removed.
+    ///
+    /// `get` only drops expired entries it happens to read, so keys that are
+    /// never read again (used invitation codes, abandoned challenges) would
+    /// otherwise stay on disk forever. Entries that cannot be parsed are
+    /// removed as well since `get` can never return them.
+    pub fn purge_expired(&self) -> Result<usize, rocksdb::Error> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+
+        let mut expired = Vec::new();
+        for entry in self.db.iterator(IteratorMode::Start) {
+            let (key, value) = entry?;
+            match serde_json::from_slice::<CachedExpiry>(&value) {
+                Ok(item) if item.expired_at > now => {}
+                _ => expired.push(key),
+            }
+        }
+
+        for key in &expired {
+            self.db.delete(key)?;
+        }
+
+        Ok(expired.len())
+    }
+
     /// Monitors the health of the RocksDB connection (basically checks if it's open)
     pub async fn monitor_health(self: Arc<Self>, shutdown_tx: tokio::sync::watch::Sender<bool>) {
         tokio::spawn(async move {
@@ -47,3 +47,21 @@ fn test_rocksdb_expiry() {
     let value: Option<String> = cache.get("expire_key");
     assert_eq!(value, None);
 }
+
+#[test]
+fn test_rocksdb_purge_expired() {
+    let dir = tempdir().unwrap();
+    let db_path = dir.path().to_str().unwrap();
+
+    let cache = RocksDbCache::new(db_path).unwrap();
+
+    cache.set("live_key", "live".to_string(), Duration::from_secs(60));
+    cache.set("dead_key_1", "dead".to_string(), Duration::from_secs(0));
+    cache.set("dead_key_2", "dead".to_string(), Duration::from_secs(0));
+
+    assert_eq!(cache.purge_expired().unwrap(), 2);
+    assert_eq!(cache.purge_expired().unwrap(), 0);
+
+    let value: Option<String> = cache.get("live_key");
+    assert_eq!(value, Some("live".to_string()));
+}
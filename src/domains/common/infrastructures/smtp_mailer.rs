@@ -0,0 +1,63 @@
+//! SMTP email transport.
+
+use crate::domains::common::errors::AppError;
+use crate::domains::common::infrastructures::mailer::{EmailMessage, Mailer};
+use crate::domains::common::utils::config::Config;
+use async_trait::async_trait;
+use lettre::message::Mailbox;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Tokio1Executor};
+use std::time::Duration;
+
+/// Upper bound for a single delivery so that a slow relay cannot stall requests.
+const SMTP_TIMEOUT_SECS: u64 = 10;
+
+/// Mailer that delivers messages through an SMTP relay.
+///
+/// `SMTP_TLS` selects implicit TLS (`tls`), STARTTLS (`starttls`, the default)
+/// or an unencrypted connection (`none`, for local relays such as Mailpit).
+pub struct SmtpMailer {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+}
+
+impl SmtpMailer {
+    /// Creates a new SmtpMailer from the SMTP settings in `Config`.
+    ///
+    /// No connection is made until the first message is sent.
+    pub fn new(config: &Config, from: Mailbox) -> Result<Self, AppError> {
+        let builder = match config.smtp_tls.as_str() {
+            "tls" => AsyncSmtpTransport::<Tokio1Executor>::relay(&config.smtp_host),
+            "none" => Ok(AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(
+                &config.smtp_host,
+            )),
+            _ => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&config.smtp_host),
+        }
+        .map_err(|e| AppError::InternalError(format!("Invalid SMTP configuration: {}", e)))?;
+
+        let mut builder = builder
+            .port(config.smtp_port)
+            .timeout(Some(Duration::from_secs(SMTP_TIMEOUT_SECS)));
+
+        if let (Some(username), Some(password)) = (&config.smtp_username, &config.smtp_password) {
+            builder = builder.credentials(Credentials::new(username.clone(), password.clone()));
+        }
+
+        Ok(Self {
+            transport: builder.build(),
+            from,
+        })
+    }
+}
+
+#[async_trait]
+impl Mailer for SmtpMailer {
+    async fn send(&self, message: EmailMessage) -> Result<(), AppError> {
+        let mime = message.to_mime(&self.from)?;
+        self.transport
+            .send(mime)
+            .await
+            .map_err(|e| AppError::InternalError(format!("Failed to send email: {}", e)))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,36 @@
+use crate::domains::common::errors::AppError;
+use crate::domains::common::infrastructures::rocksdb_connection::RocksDbCache;
+use crate::domains::common::jobs::scheduler::Job;
+use async_trait::async_trait;
+use std::sync::Arc;
+
+/// Removes expired RocksDB cache entries, including used invitation codes
+/// and abandoned WebAuthn challenges that are never read again.
+pub struct CacheCleanupJob {
+    cache: Arc<RocksDbCache>,
+}
+
+impl CacheCleanupJob {
+    /// Creates a new CacheCleanupJob instance.
+    pub fn new(cache: Arc<RocksDbCache>) -> Self {
+        Self { cache }
+    }
+}
+
+#[async_trait]
+impl Job for CacheCleanupJob {
+    fn name(&self) -> &'static str {
+        "cache_cleanup"
+    }
+
+    async fn run(&self) -> Result<u64, AppError> {
+        // Scanning RocksDB blocks, keep it off the async workers
+        let cache = self.cache.clone();
+        let purged = tokio::task::spawn_blocking(move || cache.purge_expired())
+            .await
+            .map_err(|e| AppError::InternalError(format!("Cache cleanup task failed: {}", e)))?
+            .map_err(|e| AppError::InternalError(format!("Cache cleanup failed: {}", e)))?;
+
+        Ok(purged as u64)
+    }
+}
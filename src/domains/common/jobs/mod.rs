@@ -0,0 +1,7 @@
+pub mod cache_cleanup_job;
+pub mod scheduler;
+#[cfg(test)]
+mod scheduler_test;
+pub mod session_cleanup_job;
+pub mod signing_key_refresh_job;
+pub mod soft_delete_purge_job;
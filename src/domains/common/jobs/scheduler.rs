@@ -0,0 +1,153 @@
+//! In-process scheduler for periodic maintenance jobs.
+//!
+//! Each registered job runs on its own interval in a background task until
+//! the server's shutdown signal fires. Runs are logged and counted in
+//! `JobMetrics`, which are served at `GET /health/jobs`.
+
+use crate::domains::common::errors::AppError;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use log::{error, info};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+
+/// A unit of periodic background work.
+#[async_trait]
+pub trait Job: Send + Sync {
+    /// Stable name used in logs and metrics.
+    fn name(&self) -> &'static str;
+
+    /// Runs the job once and returns the number of items it processed.
+    async fn run(&self) -> Result<u64, AppError>;
+}
+
+/// Counters and last-run details of a job.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct JobMetrics {
+    pub interval_secs: u64,
+    pub runs: u64,
+    pub failures: u64,
+    pub items_processed: u64,
+    pub last_items_processed: u64,
+    pub last_run_at: Option<DateTime<Utc>>,
+    pub last_duration_ms: u64,
+    pub last_error: Option<String>,
+}
+
+struct ScheduledJob {
+    job: Arc<dyn Job>,
+    interval: Duration,
+}
+
+/// Runs registered jobs on fixed intervals.
+#[derive(Default)]
+pub struct Scheduler {
+    jobs: Vec<ScheduledJob>,
+    metrics: Arc<Mutex<BTreeMap<&'static str, JobMetrics>>>,
+}
+
+impl Scheduler {
+    /// Creates a scheduler without jobs.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a job to run every `interval`, starting when the scheduler starts.
+    ///
+    /// A zero interval disables the job.
+    pub fn register(&mut self, job: Arc<dyn Job>, interval: Duration) {
+        if interval.is_zero() {
+            info!("⏸️ Job {} is disabled", job.name());
+            return;
+        }
+
+        self.metrics.lock().unwrap().insert(
+            job.name(),
+            JobMetrics {
+                interval_secs: interval.as_secs(),
+                ..Default::default()
+            },
+        );
+        self.jobs.push(ScheduledJob { job, interval });
+    }
+
+    /// Spawns one task per job. Tasks stop when `shutdown_rx` changes.
+    ///
+    /// A run in progress is allowed to finish before its task stops.
+    pub fn start(&self, shutdown_rx: watch::Receiver<bool>) -> Vec<JoinHandle<()>> {
+        self.jobs
+            .iter()
+            .map(|scheduled| {
+                let job = scheduled.job.clone();
+                let interval = scheduled.interval;
+                let metrics = self.metrics.clone();
+                let mut shutdown_rx = shutdown_rx.clone();
+
+                info!("⏱️ Scheduling job {} every {:?}", job.name(), interval);
+                tokio::spawn(async move {
+                    let mut ticker = tokio::time::interval(interval);
+                    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+                    loop {
+                        tokio::select! {
+                            _ = ticker.tick() => Self::run_job(job.as_ref(), &metrics).await,
+                            _ = shutdown_rx.changed() => break,
+                        }
+                    }
+
+                    info!("Job {} stopped", job.name());
+                })
+            })
+            .collect()
+    }
+
+    /// Returns a snapshot of every job's metrics, keyed by job name.
+    pub fn metrics(&self) -> BTreeMap<&'static str, JobMetrics> {
+        self.metrics.lock().unwrap().clone()
+    }
+
+    async fn run_job(job: &dyn Job, metrics: &Mutex<BTreeMap<&'static str, JobMetrics>>) {
+        let started_at = Utc::now();
+        let timer = Instant::now();
+        let result = job.run().await;
+        let duration_ms = timer.elapsed().as_millis() as u64;
+
+        match &result {
+            Ok(items) => info!(
+                "🧹 Job {} finished in {} ms, {} item(s) processed",
+                job.name(),
+                duration_ms,
+                items
+            ),
+            Err(e) => error!(
+                "❌ Job {} failed after {} ms: {}",
+                job.name(),
+                duration_ms,
+                e
+            ),
+        }
+
+        let mut metrics = metrics.lock().unwrap();
+        let entry = metrics.entry(job.name()).or_default();
+        entry.runs += 1;
+        entry.last_run_at = Some(started_at);
+        entry.last_duration_ms = duration_ms;
+        match result {
+            Ok(items) => {
+                entry.items_processed += items;
+                entry.last_items_processed = items;
+                entry.last_error = None;
+            }
+            Err(e) => {
+                entry.failures += 1;
+                entry.last_items_processed = 0;
+                entry.last_error = Some(e.to_string());
+            }
+        }
+    }
+}
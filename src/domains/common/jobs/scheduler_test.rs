@@ -0,0 +1,94 @@
+use super::scheduler::*;
+use crate::domains::common::errors::AppError;
+use async_trait::async_trait;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::watch;
+
+struct CountingJob {
+    calls: AtomicU64,
+    fail: bool,
+}
+
+#[async_trait]
+impl Job for CountingJob {
+    fn name(&self) -> &'static str {
+        if self.fail {
+            "failing"
+        } else {
+            "counting"
+        }
+    }
+
+    async fn run(&self) -> Result<u64, AppError> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        if self.fail {
+            return Err(AppError::InternalError("boom".to_string()));
+        }
+        Ok(2)
+    }
+}
+
+fn counting_job(fail: bool) -> Arc<CountingJob> {
+    Arc::new(CountingJob {
+        calls: AtomicU64::new(0),
+        fail,
+    })
+}
+
+#[tokio::test]
+async fn test_scheduler_records_job_metrics() {
+    let ok_job = counting_job(false);
+    let failing_job = counting_job(true);
+    let mut scheduler = Scheduler::new();
+    scheduler.register(ok_job.clone(), Duration::from_millis(20));
+    scheduler.register(failing_job.clone(), Duration::from_millis(20));
+
+    let (shutdown_tx, shutdown_rx) = watch::channel(false);
+    let handles = scheduler.start(shutdown_rx);
+    tokio::time::sleep(Duration::from_millis(70)).await;
+    let _ = shutdown_tx.send(true);
+    for handle in handles {
+        handle.await.unwrap();
+    }
+
+    let metrics = scheduler.metrics();
+    let ok = &metrics["counting"];
+    assert!(ok.runs >= 2);
+    assert_eq!(ok.runs, ok_job.calls.load(Ordering::SeqCst));
+    assert_eq!(ok.items_processed, ok.runs * 2);
+    assert_eq!(ok.last_items_processed, 2);
+    assert_eq!(ok.failures, 0);
+    assert!(ok.last_run_at.is_some());
+
+    let failing = &metrics["failing"];
+    assert!(failing.runs >= 2);
+    assert_eq!(failing.failures, failing.runs);
+    assert_eq!(failing.items_processed, 0);
+    assert!(failing.last_error.as_deref().unwrap().contains("boom"));
+}
+
+#[tokio::test]
+async fn test_scheduler_skips_disabled_jobs_and_stops_on_shutdown() {
+    let job = counting_job(false);
+    let mut scheduler = Scheduler::new();
+    scheduler.register(job.clone(), Duration::ZERO);
+    assert!(scheduler.metrics().is_empty());
+
+    scheduler.register(job.clone(), Duration::from_secs(3600));
+    let (shutdown_tx, shutdown_rx) = watch::channel(false);
+    let handles = scheduler.start(shutdown_rx);
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    let _ = shutdown_tx.send(true);
+    for handle in handles {
+        tokio::time::timeout(Duration::from_secs(1), handle)
+            .await
+            .expect("job task should stop on shutdown")
+            .unwrap();
+    }
+
+    // The first run happens on start, the next one only after an hour
+    assert_eq!(job.calls.load(Ordering::SeqCst), 1);
+    assert_eq!(scheduler.metrics()["counting"].runs, 1);
+}
@@ -0,0 +1,33 @@
+use crate::domains::common::errors::AppError;
+use crate::domains::common::jobs::scheduler::Job;
+use crate::domains::user::repositories::user_session_repository::UserSessionRepositoryTrait;
+use async_trait::async_trait;
+use chrono::Utc;
+use std::sync::Arc;
+
+/// Deletes expired `user_sessions` rows.
+///
+/// Rotated sessions are kept until they expire so that replaying their
+/// refresh token is still detected as reuse; after that the JWT itself is
+/// rejected and the row is no longer needed.
+pub struct SessionCleanupJob {
+    session_repository: Arc<dyn UserSessionRepositoryTrait>,
+}
+
+impl SessionCleanupJob {
+    /// Creates a new SessionCleanupJob instance.
+    pub fn new(session_repository: Arc<dyn UserSessionRepositoryTrait>) -> Self {
+        Self { session_repository }
+    }
+}
+
+#[async_trait]
+impl Job for SessionCleanupJob {
+    fn name(&self) -> &'static str {
+        "session_cleanup"
+    }
+
+    async fn run(&self) -> Result<u64, AppError> {
+        self.session_repository.delete_expired(Utc::now()).await
+    }
+}
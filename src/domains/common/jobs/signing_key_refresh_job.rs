@@ -0,0 +1,31 @@
+use crate::domains::auth::usecases::signing_key_usecase::SigningKeyUseCase;
+use crate::domains::common::errors::AppError;
+use crate::domains::common::jobs::scheduler::Job;
+use async_trait::async_trait;
+use std::sync::Arc;
+
+/// Reloads the JWT key ring from the database, so every instance follows
+/// rotations made elsewhere and stops accepting retired keys.
+pub struct SigningKeyRefreshJob {
+    signing_key_usecase: Arc<SigningKeyUseCase>,
+}
+
+impl SigningKeyRefreshJob {
+    /// Creates a new SigningKeyRefreshJob instance.
+    pub fn new(signing_key_usecase: Arc<SigningKeyUseCase>) -> Self {
+        Self {
+            signing_key_usecase,
+        }
+    }
+}
+
+#[async_trait]
+impl Job for SigningKeyRefreshJob {
+    fn name(&self) -> &'static str {
+        "signing_key_refresh"
+    }
+
+    async fn run(&self) -> Result<u64, AppError> {
+        self.signing_key_usecase.reload().await
+    }
+}
@@ -0,0 +1,55 @@
+use crate::domains::common::errors::AppError;
+use crate::domains::common::jobs::scheduler::Job;
+use crate::domains::tenant::repositories::tenant_repository::TenantRepositoryTrait;
+use crate::domains::user::repositories::user_repository::UserRepositoryTrait;
+use async_trait::async_trait;
+use chrono::Utc;
+use log::info;
+use std::sync::Arc;
+
+/// Permanently deletes users and tenants that stayed soft-deleted for longer
+/// than the retention window (`SOFT_DELETE_RETENTION_DAYS`).
+///
+/// Until then, registering again restores a soft-deleted account.
+pub struct SoftDeletePurgeJob {
+    user_repository: Arc<dyn UserRepositoryTrait>,
+    tenant_repository: Arc<dyn TenantRepositoryTrait>,
+    retention: chrono::Duration,
+}
+
+impl SoftDeletePurgeJob {
+    /// Creates a new SoftDeletePurgeJob instance.
+    pub fn new(
+        user_repository: Arc<dyn UserRepositoryTrait>,
+        tenant_repository: Arc<dyn TenantRepositoryTrait>,
+        retention: chrono::Duration,
+    ) -> Self {
+        Self {
+            user_repository,
+            tenant_repository,
+            retention,
+        }
+    }
+}
+
+#[async_trait]
+impl Job for SoftDeletePurgeJob {
+    fn name(&self) -> &'static str {
+        "soft_delete_purge"
+    }
+
+    async fn run(&self) -> Result<u64, AppError> {
+        let cutoff = Utc::now() - self.retention;
+
+        let users = self.user_repository.purge_deleted_before(cutoff).await?;
+        let tenants = self.tenant_repository.purge_deleted_before(cutoff).await?;
+        if users + tenants > 0 {
+            info!(
+                "Purged {} user(s) and {} tenant(s) soft-deleted before {}",
+                users, tenants, cutoff
+            );
+        }
+
+        Ok(users + tenants)
+    }
+}
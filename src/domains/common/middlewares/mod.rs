@@ -2,6 +2,9 @@ pub mod api_key_middleware;
 pub mod powered_by_middleware;
 #[cfg(test)]
 mod powered_by_middleware_test;
+pub mod rate_limit_middleware;
+#[cfg(test)]
+mod rate_limit_middleware_test;
 pub mod request_logger_middleware;
 #[cfg(test)]
 mod request_logger_middleware_test;
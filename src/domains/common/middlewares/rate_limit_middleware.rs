@@ -0,0 +1,221 @@
+use crate::domains::common::dtos::response_dto::ErrorResponseDTO;
+use crate::domains::common::infrastructures::rate_limiter::{
+    RateLimiter, LOGIN, LOGIN_IP, MQTT_CHECK, REFRESH, REGISTER,
+};
+use crate::domains::common::utils::request_helper;
+use crate::domains::tenant::middlewares::api_key_middleware::TenantId;
+use actix_web::{
+    body::EitherBody,
+    dev::{Payload, Service, ServiceRequest, ServiceResponse, Transform},
+    http::header,
+    web, Error, HttpMessage, HttpResponse,
+};
+use futures_util::future::{ok, LocalBoxFuture, Ready};
+use std::rc::Rc;
+
+/// What a rule counts requests by.
+#[derive(Clone, Copy, Debug)]
+pub enum RateLimitKey {
+    /// The client IP address, see `request_helper::client_ip`.
+    Ip,
+    /// A string field of the JSON body, compared case-insensitively.
+    Field(&'static str),
+}
+
+/// A rate limiter rule applied to a route.
+#[derive(Clone, Copy, Debug)]
+pub struct RateLimitRule {
+    pub name: &'static str,
+    pub key: RateLimitKey,
+}
+
+/// Rate limiting middleware.
+///
+/// Takes a token from every rule's bucket and answers `429 Too Many Requests`
+/// with a `Retry-After` header once one is empty. Buckets are scoped to the
+/// tenant resolved by `ApiKeyMiddleware`, so it must wrap this middleware.
+/// Requests pass through when no `RateLimiter` is registered as app data.
+#[derive(Clone)]
+pub struct RateLimitMiddleware {
+    rules: &'static [RateLimitRule],
+}
+
+impl RateLimitMiddleware {
+    pub fn new(rules: &'static [RateLimitRule]) -> Self {
+        Self { rules }
+    }
+
+    /// `POST /auth/login` and `POST /auth/recovery/reset`: per client IP and
+    /// per login identifier.
+    pub fn login() -> Self {
+        Self::new(&[
+            RateLimitRule {
+                name: LOGIN_IP,
+                key: RateLimitKey::Ip,
+            },
+            RateLimitRule {
+                name: LOGIN,
+                key: RateLimitKey::Field("email_or_username"),
+            },
+        ])
+    }
+
+    /// `POST /auth/passwordless/request`, `POST /auth/forgot-password` and
+    /// `POST /auth/verify-email/resend`: share the login buckets, keyed by the
+    /// email, so emails cannot be sent faster than passwords are tried.
+    pub fn send_email() -> Self {
+        Self::new(&[
+            RateLimitRule {
+                name: LOGIN_IP,
+                key: RateLimitKey::Ip,
+            },
+            RateLimitRule {
+                name: LOGIN,
+                key: RateLimitKey::Field("email"),
+            },
+        ])
+    }
+
+    /// Routes redeeming a code or emailed token (`/auth/mfa/verify`,
+    /// `/auth/mfa/recovery`, `/auth/passwordless/consume`,
+    /// `/auth/reset-password`, `/auth/verify-email`): share the login bucket
+    /// of the client IP, so codes cannot be guessed faster than passwords.
+    pub fn redeem_code() -> Self {
+        Self::new(&[RateLimitRule {
+            name: LOGIN_IP,
+            key: RateLimitKey::Ip,
+        }])
+    }
+
+    /// `POST /auth/register`: per client IP.
+    pub fn register() -> Self {
+        Self::new(&[RateLimitRule {
+            name: REGISTER,
+            key: RateLimitKey::Ip,
+        }])
+    }
+
+    /// `POST /auth/refresh`: per client IP.
+    pub fn refresh() -> Self {
+        Self::new(&[RateLimitRule {
+            name: REFRESH,
+            key: RateLimitKey::Ip,
+        }])
+    }
+
+    /// `POST /mqtt/check`: per MQTT username. All checks come from the
+    /// broker, so its IP says nothing about the client.
+    pub fn mqtt_check() -> Self {
+        Self::new(&[RateLimitRule {
+            name: MQTT_CHECK,
+            key: RateLimitKey::Field("username"),
+        }])
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for RateLimitMiddleware
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = RateLimitMiddlewareService<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ok(RateLimitMiddlewareService {
+            service: Rc::new(service),
+            rules: self.rules,
+        })
+    }
+}
+
+/// Service wrapper for rate limiting.
+pub struct RateLimitMiddlewareService<S> {
+    service: Rc<S>,
+    rules: &'static [RateLimitRule],
+}
+
+impl<S, B> Service<ServiceRequest> for RateLimitMiddlewareService<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(
+        &self,
+        ctx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(ctx)
+    }
+
+    fn call(&self, mut req: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+        let rules = self.rules;
+        let limiter = req.app_data::<web::Data<RateLimiter>>().cloned();
+
+        Box::pin(async move {
+            let Some(limiter) = limiter else {
+                let res = service.call(req).await?;
+                return Ok(res.map_into_left_body());
+            };
+
+            let tenant_id = req.extensions().get::<TenantId>().map(|t| t.0);
+
+            // Buffer the body to read identifiers, then hand it back to the handler
+            let body = if rules
+                .iter()
+                .any(|rule| matches!(rule.key, RateLimitKey::Field(_)))
+            {
+                let bytes = req.extract::<web::Bytes>().await?;
+                req.set_payload(Payload::from(bytes.clone()));
+                serde_json::from_slice::<serde_json::Value>(&bytes).ok()
+            } else {
+                None
+            };
+
+            let mut retry_after = None;
+            for rule in rules {
+                let key = match rule.key {
+                    RateLimitKey::Ip => request_helper::client_ip(req.request())
+                        .unwrap_or_else(|| "unknown".to_string()),
+                    RateLimitKey::Field(field) => {
+                        // Requests without the field are rejected by the handler anyway
+                        match body
+                            .as_ref()
+                            .and_then(|body| body.get(field))
+                            .and_then(|value| value.as_str())
+                        {
+                            Some(value) => value.trim().to_lowercase(),
+                            None => continue,
+                        }
+                    }
+                };
+
+                if let Err(limited) = limiter.acquire(rule.name, tenant_id, &key).await {
+                    retry_after = retry_after.max(Some(limited.retry_after));
+                }
+            }
+
+            if let Some(retry_after) = retry_after {
+                let res = HttpResponse::TooManyRequests()
+                    .insert_header((header::RETRY_AFTER, retry_after.to_string()))
+                    .json(ErrorResponseDTO {
+                        status: false,
+                        message: "Too Many Requests",
+                        details: None::<()>,
+                        result: None,
+                    });
+                return Ok(req.into_response(res.map_into_right_body()));
+            }
+
+            let res = service.call(req).await?;
+            Ok(res.map_into_left_body())
+        })
+    }
+}
@@ -0,0 +1,205 @@
+use super::rate_limit_middleware::*;
+use crate::domains::common::errors::AppError;
+use crate::domains::common::infrastructures::rate_limiter::{
+    RateLimit, RateLimiter, LOGIN, LOGIN_IP,
+};
+use crate::domains::common::infrastructures::rocksdb_connection::RocksDbCache;
+use crate::domains::tenant::dtos::tenant_dto::{CreateTenantRequest, UpdateTenantRequest};
+use crate::domains::tenant::entities::tenant::Model as Tenant;
+use crate::domains::tenant::repositories::tenant_repository::TenantRepositoryTrait;
+use actix_web::{http::StatusCode, test, web, App, HttpResponse};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use mockall::mock;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tempfile::tempdir;
+use uuid::Uuid;
+
+mock! {
+    pub TenantRepository {}
+    #[async_trait]
+    impl TenantRepositoryTrait for TenantRepository {
+        async fn create(&self, tenant: CreateTenantRequest) -> Result<Tenant, AppError>;
+        async fn find_by_id(&self, id: Uuid) -> Result<Option<Tenant>, AppError>;
+        async fn find_by_name(&self, name: &str) -> Result<Option<Tenant>, AppError>;
+        async fn find_by_name_with_deleted(&self, name: &str) -> Result<Option<Tenant>, AppError>;
+        async fn find_all(&self) -> Result<Vec<Tenant>, AppError>;
+        async fn update(&self, id: Uuid, tenant: UpdateTenantRequest) -> Result<Tenant, AppError>;
+        async fn delete(&self, id: Uuid) -> Result<(), AppError>;
+        async fn restore(&self, id: Uuid) -> Result<(), AppError>;
+        async fn purge_deleted_before(&self, cutoff: DateTime<Utc>) -> Result<u64, AppError>;
+    }
+}
+
+/// Echoes the body to check that it still reaches the handler.
+async fn echo(body: web::Json<serde_json::Value>) -> HttpResponse {
+    HttpResponse::Ok().json(body.into_inner())
+}
+
+fn login_request(identifier: &str) -> test::TestRequest {
+    test::TestRequest::post()
+        .uri("/login")
+        .peer_addr("10.0.0.1:5000".parse().unwrap())
+        .set_json(serde_json::json!({ "email_or_username": identifier, "password": "secret" }))
+}
+
+#[actix_web::test]
+async fn test_rate_limit_middleware_returns_429_with_retry_after() {
+    crate::domains::common::utils::config::Config::init_for_test();
+    let dir = tempdir().unwrap();
+    let cache = Arc::new(RocksDbCache::new(dir.path().to_str().unwrap()).unwrap());
+    let limit = |max_requests| RateLimit {
+        max_requests,
+        window_secs: 60,
+    };
+    let limiter = RateLimiter::new(
+        cache,
+        Arc::new(MockTenantRepository::new()),
+        HashMap::from([(LOGIN, limit(1)), (LOGIN_IP, limit(3))]),
+        true,
+    );
+
+    let app = test::init_service(
+        App::new().app_data(web::Data::new(limiter)).service(
+            web::resource("/login")
+                .wrap(RateLimitMiddleware::login())
+                .route(web::post().to(echo)),
+        ),
+    )
+    .await;
+
+    let resp = test::call_service(&app, login_request("Alice").to_request()).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["email_or_username"], "Alice");
+
+    // Same identifier, compared case-insensitively
+    let resp = test::call_service(&app, login_request(" alice ").to_request()).await;
+    assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
+    let retry_after: u64 = resp
+        .headers()
+        .get("retry-after")
+        .unwrap()
+        .to_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert!(retry_after > 0 && retry_after <= 60);
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["message"], "Too Many Requests");
+
+    // A new identifier passes until the IP bucket is empty as well
+    let resp = test::call_service(&app, login_request("bob").to_request()).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let resp = test::call_service(&app, login_request("carol").to_request()).await;
+    assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
+}
+
+#[actix_web::test]
+async fn test_rate_limit_middleware_without_limiter() {
+    let app = test::init_service(
+        App::new().service(
+            web::resource("/login")
+                .wrap(RateLimitMiddleware::login())
+                .route(web::post().to(echo)),
+        ),
+    )
+    .await;
+
+    for _ in 0..3 {
+        let resp = test::call_service(&app, login_request("alice").to_request()).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+}
+
+#[actix_web::test]
+async fn test_rate_limit_middleware_ignores_forwarded_for_from_clients() {
+    use crate::domains::common::infrastructures::rate_limiter::REGISTER;
+    use crate::domains::common::utils::config::Config;
+    Config::init_for_test();
+
+    let dir = tempdir().unwrap();
+    let cache = Arc::new(RocksDbCache::new(dir.path().to_str().unwrap()).unwrap());
+    let limiter = RateLimiter::new(
+        cache,
+        Arc::new(MockTenantRepository::new()),
+        HashMap::from([(
+            REGISTER,
+            RateLimit {
+                max_requests: 1,
+                window_secs: 60,
+            },
+        )]),
+        true,
+    );
+
+    let app = test::init_service(
+        App::new().app_data(web::Data::new(limiter)).service(
+            web::resource("/register")
+                .wrap(RateLimitMiddleware::register())
+                .route(web::post().to(HttpResponse::Ok)),
+        ),
+    )
+    .await;
+
+    // Rotating the header and the source port does not give a new bucket
+    let request = |forwarded: &str, port: u16| {
+        test::TestRequest::post()
+            .uri("/register")
+            .peer_addr(format!("10.9.9.9:{}", port).parse().unwrap())
+            .insert_header(("X-Forwarded-For", forwarded))
+            .to_request()
+    };
+    let resp = test::call_service(&app, request("1.1.1.1", 5000)).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let resp = test::call_service(&app, request("2.2.2.2", 5001)).await;
+    assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
+}
+
+#[actix_web::test]
+async fn test_rate_limit_middleware_email_routes_share_login_bucket() {
+    crate::domains::common::utils::config::Config::init_for_test();
+    let dir = tempdir().unwrap();
+    let cache = Arc::new(RocksDbCache::new(dir.path().to_str().unwrap()).unwrap());
+    let limit = |max_requests| RateLimit {
+        max_requests,
+        window_secs: 60,
+    };
+    let limiter = RateLimiter::new(
+        cache,
+        Arc::new(MockTenantRepository::new()),
+        HashMap::from([(LOGIN, limit(1)), (LOGIN_IP, limit(10))]),
+        true,
+    );
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(limiter))
+            .service(
+                web::resource("/forgot-password")
+                    .wrap(RateLimitMiddleware::send_email())
+                    .route(web::post().to(echo)),
+            )
+            .service(
+                web::resource("/verify-email/resend")
+                    .wrap(RateLimitMiddleware::send_email())
+                    .route(web::post().to(echo)),
+            ),
+    )
+    .await;
+    let email_request = |uri: &str| {
+        test::TestRequest::post()
+            .uri(uri)
+            .peer_addr("10.0.0.1:5000".parse().unwrap())
+            .set_json(serde_json::json!({ "email": "alice@example.com" }))
+            .to_request()
+    };
+
+    let resp = test::call_service(&app, email_request("/forgot-password")).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    // Another mail to the same address is limited on every route
+    let resp = test::call_service(&app, email_request("/verify-email/resend")).await;
+    assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
+}
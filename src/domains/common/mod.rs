@@ -4,5 +4,6 @@ pub mod middlewares;
 pub mod dtos;
 pub mod errors;
 pub mod infrastructures;
+pub mod jobs;
 pub mod utils;
 pub mod validators;
@@ -3,7 +3,11 @@
 //! This module provides a single source of truth for all environment variables
 //! with strict validation. All env vars must be explicitly set - no fallbacks.
 
+use crate::domains::common::infrastructures::rate_limiter::RateLimit;
+use crate::domains::common::utils::jwt_keys::{parse_algorithm, SUPPORTED_ALGORITHMS};
+use crate::domains::common::utils::lockout::LockoutPolicy;
 use std::env;
+use std::net::IpAddr;
 use std::sync::OnceLock;
 
 /// Global configuration instance
@@ -17,6 +21,8 @@ pub struct Config {
     pub tenant_secret_key: String,
     pub secret_key: String,
     pub jwt_secret: String,
+    pub jwt_algorithm: jsonwebtoken::Algorithm,
+    pub jwt_private_key_path: String,
 
     // Server
     pub server_host: String,
@@ -38,6 +44,75 @@ pub struct Config {
     pub access_token_expiry: i64,
     pub refresh_token_expiry: i64,
     pub cache_ttl: u64,
+
+    // Multi-factor authentication
+    pub mfa_issuer: String,
+    pub mfa_token_expiry: i64,
+
+    // Admin impersonation
+    pub impersonation_token_expiry: i64,
+
+    // Step-up authentication
+    pub step_up_max_age: i64,
+    pub step_up_require_mfa: bool,
+
+    // WebAuthn / passkeys
+    pub webauthn_rp_id: String,
+    pub webauthn_rp_name: String,
+    pub webauthn_origins: Vec<String>,
+
+    // Password reset
+    pub password_reset_url: String,
+    pub password_reset_token_expiry: i64,
+
+    // Email verification
+    pub email_verification_url: String,
+    pub email_verification_token_expiry: i64,
+
+    // Passwordless sign-in
+    pub passwordless_login_url: String,
+    pub passwordless_token_expiry: i64,
+    pub passwordless_max_attempts: u32,
+
+    // OpenID Connect provider
+    pub oidc_login_url: String,
+    pub oidc_code_expiry: u64,
+    pub oidc_device_verification_url: String,
+    pub oidc_device_code_expiry: u64,
+    pub oidc_device_poll_interval: u64,
+
+    // Outbound email
+    pub mail_transport: String,
+    pub mail_from: String,
+    pub mail_outbox_dir: Option<String>,
+    pub mail_template_dir: Option<String>,
+    pub mail_default_locale: String,
+    pub smtp_host: String,
+    pub smtp_port: u16,
+    pub smtp_username: Option<String>,
+    pub smtp_password: Option<String>,
+    pub smtp_tls: String,
+
+    // Background jobs (intervals in seconds, 0 disables a job)
+    pub session_cleanup_interval: u64,
+    pub cache_cleanup_interval: u64,
+    pub soft_delete_purge_interval: u64,
+    pub signing_key_refresh_interval: u64,
+    pub soft_delete_retention_days: i64,
+
+    // Rate limiting (defaults, tenants can override them)
+    pub rate_limit_enabled: bool,
+    pub rate_limit_login: RateLimit,
+    pub rate_limit_login_ip: RateLimit,
+    pub rate_limit_register: RateLimit,
+    pub rate_limit_refresh: RateLimit,
+    pub rate_limit_mqtt_check: RateLimit,
+
+    // Reverse proxies whose X-Forwarded-For header is trusted
+    pub trusted_proxies: Vec<IpAddr>,
+
+    // Account lockout after consecutive failed logins
+    pub login_lockout: LockoutPolicy,
 }
 
 impl Config {
@@ -57,6 +132,23 @@ impl Config {
             let secret_key = env::var("SECRET_KEY").expect("SECRET_KEY must be set in environment");
             let jwt_secret = env::var("JWT_SECRET").expect("JWT_SECRET must be set in environment");
 
+            // JWT signing, asymmetric keys are generated on first start if the file is missing
+            let jwt_algorithm_raw = env::var("JWT_ALGORITHM")
+                .ok()
+                .filter(|v| !v.is_empty())
+                .unwrap_or_else(|| "HS256".to_string());
+            let jwt_algorithm = parse_algorithm(&jwt_algorithm_raw).unwrap_or_else(|| {
+                panic!(
+                    "JWT_ALGORITHM must be one of {}, got: {}",
+                    SUPPORTED_ALGORITHMS.join(", "),
+                    jwt_algorithm_raw
+                )
+            });
+            let jwt_private_key_path = env::var("JWT_PRIVATE_KEY_PATH")
+                .ok()
+                .filter(|v| !v.is_empty())
+                .unwrap_or_else(|| "./keys/jwt_private_key.pem".to_string());
+
             // Server - REQUIRED
             let server_host =
                 env::var("SERVER_HOST").expect("SERVER_HOST must be set in environment");
@@ -120,11 +212,247 @@ impl Config {
                 .parse::<u64>()
                 .unwrap_or(3600);
 
+            let mfa_issuer = env::var("MFA_ISSUER")
+                .ok()
+                .filter(|v| !v.is_empty())
+                .unwrap_or_else(|| "User Auth Plugin".to_string());
+
+            let mfa_token_expiry = env::var("MFA_TOKEN_EXPIRY")
+                .unwrap_or_else(|_| "300".to_string())
+                .parse::<i64>()
+                .unwrap_or(300);
+
+            let impersonation_token_expiry = env::var("IMPERSONATION_TOKEN_EXPIRY")
+                .unwrap_or_else(|_| "600".to_string())
+                .parse::<i64>()
+                .unwrap_or(600);
+
+            let step_up_max_age = env::var("STEP_UP_MAX_AGE")
+                .unwrap_or_else(|_| "300".to_string())
+                .parse::<i64>()
+                .unwrap_or(300);
+
+            let step_up_require_mfa = env::var("STEP_UP_REQUIRE_MFA")
+                .map(|v| v.eq_ignore_ascii_case("true"))
+                .unwrap_or(false);
+
+            // Relying party ID defaults to the host of ENDPOINT
+            let webauthn_rp_id = env::var("WEBAUTHN_RP_ID")
+                .ok()
+                .filter(|v| !v.is_empty())
+                .or_else(|| {
+                    url::Url::parse(&endpoint)
+                        .ok()
+                        .and_then(|u| u.host_str().map(|h| h.to_string()))
+                })
+                .unwrap_or_else(|| "localhost".to_string());
+
+            let webauthn_rp_name = env::var("WEBAUTHN_RP_NAME")
+                .ok()
+                .filter(|v| !v.is_empty())
+                .unwrap_or_else(|| mfa_issuer.clone());
+
+            // Origins allowed in clientDataJSON default to the CORS origins
+            let webauthn_origins: Vec<String> = env::var("WEBAUTHN_ORIGINS")
+                .map(|raw| {
+                    raw.split(',')
+                        .map(|s| s.trim().to_string())
+                        .filter(|s| !s.is_empty())
+                        .collect()
+                })
+                .ok()
+                .filter(|v: &Vec<String>| !v.is_empty())
+                .unwrap_or_else(|| allowed_origins.clone());
+
+            // Link sent in reset emails; the token is appended as a query parameter
+            let password_reset_url = env::var("PASSWORD_RESET_URL")
+                .ok()
+                .filter(|v| !v.is_empty())
+                .unwrap_or_else(|| format!("{}/reset-password", endpoint.trim_end_matches('/')));
+
+            let password_reset_token_expiry = env::var("PASSWORD_RESET_TOKEN_EXPIRY")
+                .unwrap_or_else(|_| "3600".to_string())
+                .parse::<i64>()
+                .unwrap_or(3600);
+
+            // Link sent in verification emails; the token is appended as a query parameter
+            let email_verification_url = env::var("EMAIL_VERIFICATION_URL")
+                .ok()
+                .filter(|v| !v.is_empty())
+                .unwrap_or_else(|| format!("{}/verify-email", endpoint.trim_end_matches('/')));
+
+            let email_verification_token_expiry = env::var("EMAIL_VERIFICATION_TOKEN_EXPIRY")
+                .unwrap_or_else(|_| "86400".to_string())
+                .parse::<i64>()
+                .unwrap_or(86400);
+
+            // Link sent in passwordless sign-in emails; the token is appended as a query parameter
+            let passwordless_login_url = env::var("PASSWORDLESS_LOGIN_URL")
+                .ok()
+                .filter(|v| !v.is_empty())
+                .unwrap_or_else(|| format!("{}/passwordless", endpoint.trim_end_matches('/')));
+
+            let passwordless_token_expiry = env::var("PASSWORDLESS_TOKEN_EXPIRY")
+                .unwrap_or_else(|_| "600".to_string())
+                .parse::<i64>()
+                .unwrap_or(600);
+
+            let passwordless_max_attempts = env::var("PASSWORDLESS_MAX_ATTEMPTS")
+                .unwrap_or_else(|_| "5".to_string())
+                .parse::<u32>()
+                .unwrap_or(5);
+
+            // Login page that /oauth/authorize sends the browser to
+            let oidc_login_url = env::var("OIDC_LOGIN_URL")
+                .ok()
+                .filter(|v| !v.is_empty())
+                .unwrap_or_else(|| format!("{}/login", endpoint.trim_end_matches('/')));
+
+            let oidc_code_expiry = env::var("OIDC_CODE_EXPIRY")
+                .unwrap_or_else(|_| "60".to_string())
+                .parse::<u64>()
+                .unwrap_or(60);
+
+            // Page of the web app where users enter device codes
+            let oidc_device_verification_url = env::var("OIDC_DEVICE_VERIFICATION_URL")
+                .ok()
+                .filter(|v| !v.is_empty())
+                .unwrap_or_else(|| format!("{}/device", endpoint.trim_end_matches('/')));
+
+            let oidc_device_code_expiry = env::var("OIDC_DEVICE_CODE_EXPIRY")
+                .unwrap_or_else(|_| "600".to_string())
+                .parse::<u64>()
+                .unwrap_or(600);
+
+            let oidc_device_poll_interval = env::var("OIDC_DEVICE_POLL_INTERVAL")
+                .unwrap_or_else(|_| "5".to_string())
+                .parse::<u64>()
+                .unwrap_or(5);
+
+            // Outbound email: "outbox" keeps mail local, "smtp" delivers it
+            let mail_transport = env::var("MAIL_TRANSPORT")
+                .ok()
+                .filter(|v| !v.is_empty())
+                .unwrap_or_else(|| "outbox".to_string());
+            if mail_transport != "outbox" && mail_transport != "smtp" {
+                panic!(
+                    "MAIL_TRANSPORT must be 'outbox' or 'smtp', got: {}",
+                    mail_transport
+                );
+            }
+
+            let mail_from = env::var("MAIL_FROM")
+                .ok()
+                .filter(|v| !v.is_empty())
+                .unwrap_or_else(|| format!("{} <no-reply@{}>", mfa_issuer, webauthn_rp_id));
+
+            let mail_outbox_dir = env::var("MAIL_OUTBOX_DIR").ok().filter(|v| !v.is_empty());
+            let mail_template_dir = env::var("MAIL_TEMPLATE_DIR").ok().filter(|v| !v.is_empty());
+
+            let mail_default_locale = env::var("MAIL_DEFAULT_LOCALE")
+                .ok()
+                .filter(|v| !v.is_empty())
+                .unwrap_or_else(|| "en".to_string());
+
+            let smtp_host = env::var("SMTP_HOST").unwrap_or_default();
+            if mail_transport == "smtp" && smtp_host.trim().is_empty() {
+                panic!("SMTP_HOST must be set when MAIL_TRANSPORT is 'smtp'");
+            }
+
+            let smtp_port = env::var("SMTP_PORT")
+                .unwrap_or_else(|_| "587".to_string())
+                .parse::<u16>()
+                .unwrap_or(587);
+
+            let smtp_username = env::var("SMTP_USERNAME").ok().filter(|v| !v.is_empty());
+            let smtp_password = env::var("SMTP_PASSWORD").ok().filter(|v| !v.is_empty());
+
+            let smtp_tls = env::var("SMTP_TLS")
+                .ok()
+                .filter(|v| !v.is_empty())
+                .unwrap_or_else(|| "starttls".to_string());
+
+            // Background jobs
+            let session_cleanup_interval = env::var("JOB_SESSION_CLEANUP_INTERVAL")
+                .unwrap_or_else(|_| "3600".to_string())
+                .parse::<u64>()
+                .unwrap_or(3600);
+
+            let cache_cleanup_interval = env::var("JOB_CACHE_CLEANUP_INTERVAL")
+                .unwrap_or_else(|_| "3600".to_string())
+                .parse::<u64>()
+                .unwrap_or(3600);
+
+            let soft_delete_purge_interval = env::var("JOB_SOFT_DELETE_PURGE_INTERVAL")
+                .unwrap_or_else(|_| "86400".to_string())
+                .parse::<u64>()
+                .unwrap_or(86400);
+
+            let signing_key_refresh_interval = env::var("JOB_SIGNING_KEY_REFRESH_INTERVAL")
+                .unwrap_or_else(|_| "60".to_string())
+                .parse::<u64>()
+                .unwrap_or(60);
+
+            // Soft-deleted users and tenants can be restored until they are purged
+            let soft_delete_retention_days = env::var("SOFT_DELETE_RETENTION_DAYS")
+                .unwrap_or_else(|_| "30".to_string())
+                .parse::<i64>()
+                .unwrap_or(30);
+
+            // Rate limiting, limits are written as "{max_requests}/{window_secs}"
+            let rate_limit_enabled = env::var("RATE_LIMIT_ENABLED")
+                .map(|v| !v.eq_ignore_ascii_case("false"))
+                .unwrap_or(true);
+
+            let rate_limit = |name: &str, default: &str| {
+                env::var(name)
+                    .ok()
+                    .and_then(|v| RateLimit::parse(&v))
+                    .or_else(|| RateLimit::parse(default))
+                    .unwrap()
+            };
+            let rate_limit_login = rate_limit("RATE_LIMIT_LOGIN", "10/300");
+            let rate_limit_login_ip = rate_limit("RATE_LIMIT_LOGIN_IP", "100/300");
+            let rate_limit_register = rate_limit("RATE_LIMIT_REGISTER", "20/3600");
+            let rate_limit_refresh = rate_limit("RATE_LIMIT_REFRESH", "60/60");
+            let rate_limit_mqtt_check = rate_limit("RATE_LIMIT_MQTT_CHECK", "30/60");
+
+            // Without trusted proxies the client IP is the peer address
+            let trusted_proxies: Vec<IpAddr> = env::var("TRUSTED_PROXIES")
+                .unwrap_or_default()
+                .split(',')
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(|s| {
+                    s.parse().unwrap_or_else(|_| {
+                        panic!("TRUSTED_PROXIES entry '{}' is not an IP address", s)
+                    })
+                })
+                .collect();
+
+            // Account lockout, durations in seconds
+            let login_lockout = LockoutPolicy {
+                threshold: env::var("LOGIN_LOCKOUT_THRESHOLD")
+                    .unwrap_or_else(|_| "5".to_string())
+                    .parse::<i32>()
+                    .unwrap_or(5),
+                base_duration: env::var("LOGIN_LOCKOUT_DURATION")
+                    .unwrap_or_else(|_| "60".to_string())
+                    .parse::<i64>()
+                    .unwrap_or(60),
+                max_duration: env::var("LOGIN_LOCKOUT_MAX_DURATION")
+                    .unwrap_or_else(|_| "3600".to_string())
+                    .parse::<i64>()
+                    .unwrap_or(3600),
+            };
+
             Config {
                 api_key,
                 tenant_secret_key,
                 secret_key,
                 jwt_secret,
+                jwt_algorithm,
+                jwt_private_key_path,
                 server_host,
                 server_port,
                 endpoint,
@@ -138,6 +466,49 @@ impl Config {
                 access_token_expiry,
                 refresh_token_expiry,
                 cache_ttl,
+                mfa_issuer,
+                mfa_token_expiry,
+                impersonation_token_expiry,
+                step_up_max_age,
+                step_up_require_mfa,
+                webauthn_rp_id,
+                webauthn_rp_name,
+                webauthn_origins,
+                password_reset_url,
+                password_reset_token_expiry,
+                email_verification_url,
+                email_verification_token_expiry,
+                passwordless_login_url,
+                passwordless_token_expiry,
+                passwordless_max_attempts,
+                oidc_login_url,
+                oidc_code_expiry,
+                oidc_device_verification_url,
+                oidc_device_code_expiry,
+                oidc_device_poll_interval,
+                mail_transport,
+                mail_from,
+                mail_outbox_dir,
+                mail_template_dir,
+                mail_default_locale,
+                smtp_host,
+                smtp_port,
+                smtp_username,
+                smtp_password,
+                smtp_tls,
+                session_cleanup_interval,
+                cache_cleanup_interval,
+                soft_delete_purge_interval,
+                signing_key_refresh_interval,
+                soft_delete_retention_days,
+                rate_limit_enabled,
+                rate_limit_login,
+                rate_limit_login_ip,
+                rate_limit_register,
+                rate_limit_refresh,
+                rate_limit_mqtt_check,
+                trusted_proxies,
+                login_lockout,
             }
         })
     }
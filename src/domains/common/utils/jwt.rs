@@ -1,19 +1,37 @@
+use crate::domains::common::errors::AppError;
+use crate::domains::common::utils::config::Config;
+use crate::domains::common::utils::jwt_keys::{JwtKey, KeyRing};
 use chrono::{Duration, Utc};
-use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use jsonwebtoken::errors::ErrorKind;
+use jsonwebtoken::jwk::JwkSet;
+use jsonwebtoken::{decode, decode_header, encode, Algorithm, Header, Validation};
 use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::{Arc, OnceLock};
 use uuid::Uuid;
 
+/// Key ring shared by every `JwtService` in the process.
+static KEY_RING: OnceLock<Arc<KeyRing>> = OnceLock::new();
+
 /// JWT claims structure.
 ///
 /// # Fields
 ///
-/// * `sub` - Subject (user ID)
+/// * `sub` - Subject (user ID, or client ID for client tokens)
 /// * `tenant_id` - Tenant ID for tenant-scoped authentication
-/// * `role` - User's role within the tenant
+/// * `role` - User's role within the tenant (`service` for client tokens)
 /// * `exp` - Expiration time (Unix timestamp)
 /// * `iat` - Issued at (Unix timestamp)
-/// * `token_type` - Token type ("access" or "refresh")
-/// * `jti` - JWT ID (optional, for refresh tokens to ensure uniqueness)
+/// * `iat_ms` - Issued at in milliseconds, to order the token against subject-wide denials
+/// * `token_type` - Token type ("access", "refresh", "mfa_pending" or "client")
+/// * `jti` - JWT ID (keys the access token denylist, keeps refresh tokens unique)
+/// * `scope` - Space-separated OAuth scopes (only on tokens issued by the OIDC token endpoint)
+/// * `aud` - Audience requested in a token exchange, the service the token is meant for
+/// * `act` - Client acting for the subject, on tokens issued by a token exchange
+/// * `impersonator` - ID of the admin acting as the user, on impersonation tokens
+/// * `auth_time` - When the user last authenticated (Unix timestamp)
+/// * `amr` - Authentication methods used (RFC 8176), e.g. `pwd`, `otp`, `mfa`
+/// * `acr` - Authentication assurance level, `aal1` or `aal2` with a second factor
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Claims {
     pub sub: String,
@@ -21,9 +39,162 @@ pub struct Claims {
     pub role: String,
     pub exp: i64,
     pub iat: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub iat_ms: Option<i64>,
     pub token_type: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub jti: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub aud: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub act: Option<Actor>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub impersonator: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auth_time: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub amr: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub acr: Option<String>,
+}
+
+/// Actor of a delegated token (RFC 8693 section 4.1): the client that
+/// exchanged the token, and the previous actor when the subject token was
+/// itself delegated.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Actor {
+    pub sub: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub act: Option<Box<Actor>>,
+}
+
+/// Authentication methods recorded in the `amr` claim (RFC 8176).
+pub const AMR_PASSWORD: &str = "pwd";
+pub const AMR_OTP: &str = "otp";
+pub const AMR_HARDWARE_KEY: &str = "hwk";
+pub const AMR_MULTI_FACTOR: &str = "mfa";
+
+/// Values of the `acr` claim: single factor and multi-factor authentication.
+pub const ACR_SINGLE_FACTOR: &str = "aal1";
+pub const ACR_MULTI_FACTOR: &str = "aal2";
+
+/// How and when a user authenticated, carried from login to the access and
+/// refresh tokens of the session.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Authentication {
+    /// Unix timestamp of the authentication.
+    pub time: i64,
+    /// Methods used, see the `AMR_*` constants.
+    pub methods: Vec<String>,
+}
+
+impl Authentication {
+    /// Authentication that just happened with `methods`.
+    pub fn now(methods: &[&str]) -> Self {
+        Self {
+            time: Utc::now().timestamp(),
+            methods: methods.iter().map(|m| m.to_string()).collect(),
+        }
+    }
+
+    /// Returns true if a second factor was used. A passkey counts on its own,
+    /// as it combines the device with its PIN or biometric check.
+    pub fn is_multi_factor(&self) -> bool {
+        self.methods
+            .iter()
+            .any(|m| m == AMR_MULTI_FACTOR || m == AMR_HARDWARE_KEY)
+    }
+
+    /// Assurance level for the `acr` claim.
+    pub fn acr(&self) -> &'static str {
+        if self.is_multi_factor() {
+            ACR_MULTI_FACTOR
+        } else {
+            ACR_SINGLE_FACTOR
+        }
+    }
+}
+
+/// Token type of access tokens issued to machine clients by the client
+/// credentials grant. Their subject is a client ID, not a user.
+pub const TOKEN_TYPE_CLIENT: &str = "client";
+
+impl Claims {
+    /// Returns true for tokens of machine clients rather than users.
+    pub fn is_client(&self) -> bool {
+        self.token_type == TOKEN_TYPE_CLIENT
+    }
+
+    /// Returns true for user tokens issued to OAuth clients, which are limited
+    /// to the granted scopes and only accepted by the OIDC endpoints.
+    pub fn is_scoped(&self) -> bool {
+        !self.is_client() && self.scope.is_some()
+    }
+
+    /// Returns true for tokens issued by a token exchange, which name the
+    /// client acting for the user in `act`.
+    pub fn is_delegated(&self) -> bool {
+        self.act.is_some()
+    }
+
+    /// Returns true for tokens an admin obtained to act as the user.
+    pub fn is_impersonation(&self) -> bool {
+        self.impersonator.is_some()
+    }
+
+    /// How the user authenticated, for tokens issued by a login.
+    pub fn authentication(&self) -> Option<Authentication> {
+        self.auth_time.map(|time| Authentication {
+            time,
+            methods: self.amr.clone().unwrap_or_default(),
+        })
+    }
+
+    /// Records `authentication` in `auth_time`, `amr` and `acr`.
+    fn set_authentication(&mut self, authentication: Option<&Authentication>) {
+        self.auth_time = authentication.map(|a| a.time);
+        self.amr = authentication.map(|a| a.methods.clone());
+        self.acr = authentication.map(|a| a.acr().to_string());
+    }
+
+    /// Returns true if the token was granted `scope`.
+    pub fn has_scope(&self, scope: &str) -> bool {
+        self.scope
+            .as_deref()
+            .is_some_and(|s| s.split(' ').any(|granted| granted == scope))
+    }
+}
+
+/// OpenID Connect ID token claims.
+///
+/// # Fields
+///
+/// * `iss` - Issuer, the service `ENDPOINT`
+/// * `sub` - Subject (user ID)
+/// * `aud` - Audience, the client the token was issued to
+/// * `exp` / `iat` - Expiration and issue time (Unix timestamps)
+/// * `auth_time` - When the user authenticated (Unix timestamp)
+/// * `nonce` - Value sent by the client in the authorization request
+/// * `preferred_username` - Username, with the `profile` scope
+/// * `email` / `email_verified` - Email address, with the `email` scope
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct IdTokenClaims {
+    pub iss: String,
+    pub sub: String,
+    pub aud: String,
+    pub exp: i64,
+    pub iat: i64,
+    pub auth_time: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preferred_username: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email_verified: Option<bool>,
 }
 
 /// JWT token service for generating and validating tokens.
@@ -31,9 +202,11 @@ pub struct Claims {
 /// This service handles JWT token generation for both access and refresh tokens,
 /// with configurable expiry times from environment variables.
 pub struct JwtService {
-    secret: String,
+    key_ring: Arc<KeyRing>,
     access_token_expiry: i64,
     refresh_token_expiry: i64,
+    mfa_token_expiry: i64,
+    impersonation_token_expiry: i64,
 }
 
 impl Default for JwtService {
@@ -43,23 +216,74 @@ impl Default for JwtService {
 }
 
 impl JwtService {
-    /// Creates a new JwtService instance.
+    /// Creates a new JwtService instance using the shared key ring.
     ///
     /// Reads configuration from environment variables:
-    /// - `JWT_SECRET`: Secret key for signing tokens (default: "default-secret-key")
+    /// - `JWT_ALGORITHM`: Signing algorithm, `HS256` (default), `RS256`, `ES256` or `EdDSA`
+    /// - `JWT_SECRET`: Secret key for signing `HS256` tokens
+    /// - `JWT_PRIVATE_KEY_PATH`: PEM private key for asymmetric algorithms
     /// - `JWT_ACCESS_TOKEN_EXPIRY`: Access token lifetime in seconds (default: 900)
     /// - `JWT_REFRESH_TOKEN_EXPIRY`: Refresh token lifetime in seconds (default: 604800)
+    /// - `MFA_TOKEN_EXPIRY`: MFA challenge token lifetime in seconds (default: 300)
+    /// - `IMPERSONATION_TOKEN_EXPIRY`: Impersonation token lifetime in seconds (default: 600)
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key ring is first used before startup loaded it and the
+    /// configured signing key cannot be loaded.
     pub fn new() -> Self {
-        use crate::domains::common::utils::config::Config;
+        Self::with_key_ring(Self::key_ring())
+    }
+
+    /// Creates a JwtService using `key_ring` instead of the shared ring.
+    pub fn with_key_ring(key_ring: Arc<KeyRing>) -> Self {
         let config = Config::get();
 
         Self {
-            secret: config.jwt_secret.clone(),
+            key_ring,
             access_token_expiry: config.access_token_expiry,
             refresh_token_expiry: config.refresh_token_expiry,
+            mfa_token_expiry: config.mfa_token_expiry,
+            impersonation_token_expiry: config.impersonation_token_expiry,
         }
     }
 
+    /// Creates a JwtService that signs and verifies with `key` only.
+    pub fn with_key(key: JwtKey) -> Self {
+        Self::with_key_ring(Arc::new(KeyRing::new(key)))
+    }
+
+    /// Key ring shared by every `JwtService`.
+    ///
+    /// Holds the configured signing key until the signing key use case loads
+    /// the ring from the database at startup.
+    pub fn key_ring() -> Arc<KeyRing> {
+        KEY_RING
+            .get_or_init(|| {
+                let key = Self::load_signing_key(Config::get()).expect("JWT signing key");
+                Arc::new(KeyRing::new(key))
+            })
+            .clone()
+    }
+
+    fn load_signing_key(config: &Config) -> Result<JwtKey, AppError> {
+        match config.jwt_algorithm {
+            Algorithm::HS256 => Ok(JwtKey::from_secret(config.jwt_secret.as_bytes())),
+            algorithm => {
+                JwtKey::load_or_generate(algorithm, Path::new(&config.jwt_private_key_path))
+                    .map(|(key, _)| key)
+            }
+        }
+    }
+
+    fn sign<T: Serialize>(&self, claims: &T) -> Result<String, jsonwebtoken::errors::Error> {
+        let key = self.key_ring.active();
+        let mut header = Header::new(key.algorithm());
+        header.kid = key.kid().map(str::to_string);
+
+        encode(&header, claims, key.encoding_key())
+    }
+
     /// Generates an access token for a user.
     ///
     /// # Arguments
@@ -81,24 +305,150 @@ impl JwtService {
         tenant_id: Uuid,
         role: String,
     ) -> Result<String, jsonwebtoken::errors::Error> {
+        self.generate_scoped_access_token(user_id, tenant_id, role, None)
+    }
+
+    /// Generates an access token limited to the given OAuth scopes.
+    ///
+    /// # Errors
+    ///
+    /// Returns `jsonwebtoken::errors::Error` if token encoding fails.
+    pub fn generate_scoped_access_token(
+        &self,
+        user_id: Uuid,
+        tenant_id: Uuid,
+        role: String,
+        scope: Option<String>,
+    ) -> Result<String, jsonwebtoken::errors::Error> {
+        self.sign(&self.access_claims(user_id, tenant_id, role, scope))
+    }
+
+    /// Generates an access token recording how the user authenticated, so
+    /// routes requiring a recent or multi-factor login can check it.
+    ///
+    /// # Errors
+    ///
+    /// Returns `jsonwebtoken::errors::Error` if token encoding fails.
+    pub fn generate_session_access_token(
+        &self,
+        user_id: Uuid,
+        tenant_id: Uuid,
+        role: String,
+        authentication: Option<&Authentication>,
+    ) -> Result<String, jsonwebtoken::errors::Error> {
+        let mut claims = self.access_claims(user_id, tenant_id, role, None);
+        claims.set_authentication(authentication);
+        self.sign(&claims)
+    }
+
+    fn access_claims(
+        &self,
+        user_id: Uuid,
+        tenant_id: Uuid,
+        role: String,
+        scope: Option<String>,
+    ) -> Claims {
         let now = Utc::now();
         let exp = now + Duration::seconds(self.access_token_expiry);
 
-        let claims = Claims {
+        Claims {
             sub: user_id.to_string(),
             tenant_id: tenant_id.to_string(),
             role,
             exp: exp.timestamp(),
             iat: now.timestamp(),
+            iat_ms: Some(now.timestamp_millis()),
             token_type: "access".to_string(),
-            jti: None, // Access tokens don't need JTI
+            jti: Some(Uuid::new_v4().to_string()),
+            scope,
+            aud: None,
+            act: None,
+            impersonator: None,
+            auth_time: None,
+            amr: None,
+            acr: None,
+        }
+    }
+
+    /// Generates an access token for a machine client (client credentials grant).
+    ///
+    /// The subject is the client ID and the role is `service`, so role checks
+    /// for users never match. Only routes using `auth_middleware::client_validator`
+    /// accept these tokens.
+    ///
+    /// # Errors
+    ///
+    /// Returns `jsonwebtoken::errors::Error` if token encoding fails.
+    pub fn generate_client_token(
+        &self,
+        client_id: &str,
+        tenant_id: Uuid,
+        scope: String,
+    ) -> Result<String, jsonwebtoken::errors::Error> {
+        let now = Utc::now();
+        let exp = now + Duration::seconds(self.access_token_expiry);
+
+        let claims = Claims {
+            sub: client_id.to_string(),
+            tenant_id: tenant_id.to_string(),
+            role: "service".to_string(),
+            exp: exp.timestamp(),
+            iat: now.timestamp(),
+            iat_ms: Some(now.timestamp_millis()),
+            token_type: TOKEN_TYPE_CLIENT.to_string(),
+            jti: Some(Uuid::new_v4().to_string()),
+            scope: Some(scope),
+            aud: None,
+            act: None,
+            impersonator: None,
+            auth_time: None,
+            amr: None,
+            acr: None,
+        };
+
+        self.sign(&claims)
+    }
+
+    /// Generates an access token for the subject of `subject`, delegated to
+    /// `actor` (token exchange).
+    ///
+    /// The token keeps the subject, tenant and role of `subject` and never
+    /// outlives it.
+    ///
+    /// # Errors
+    ///
+    /// Returns `jsonwebtoken::errors::Error` if token encoding fails.
+    pub fn generate_delegated_token(
+        &self,
+        subject: &Claims,
+        scope: String,
+        audience: Option<String>,
+        actor: Actor,
+    ) -> Result<String, jsonwebtoken::errors::Error> {
+        let now = Utc::now();
+        let exp = (now + Duration::seconds(self.access_token_expiry))
+            .timestamp()
+            .min(subject.exp);
+
+        let claims = Claims {
+            sub: subject.sub.clone(),
+            tenant_id: subject.tenant_id.clone(),
+            role: subject.role.clone(),
+            exp,
+            iat: now.timestamp(),
+            iat_ms: Some(now.timestamp_millis()),
+            token_type: "access".to_string(),
+            jti: Some(Uuid::new_v4().to_string()),
+            scope: Some(scope),
+            aud: audience,
+            act: Some(actor),
+            impersonator: subject.impersonator.clone(),
+            auth_time: subject.auth_time,
+            amr: subject.amr.clone(),
+            acr: subject.acr.clone(),
         };
 
-        encode(
-            &Header::default(),
-            &claims,
-            &EncodingKey::from_secret(self.secret.as_bytes()),
-        )
+        self.sign(&claims)
     }
 
     /// Generates a refresh token for a user.
@@ -108,6 +458,9 @@ impl JwtService {
     /// * `user_id` - UUID of the user
     /// * `tenant_id` - UUID of the tenant
     /// * `role` - User's role within the tenant
+    /// * `jti` - Token ID, the session ID
+    /// * `authentication` - How the user authenticated, passed on to the
+    ///   access tokens issued when the refresh token is used
     ///
     /// # Returns
     ///
@@ -123,29 +476,137 @@ impl JwtService {
         tenant_id: Uuid,
         role: String,
         jti: Option<String>,
+        authentication: Option<&Authentication>,
     ) -> Result<String, jsonwebtoken::errors::Error> {
         let now = Utc::now();
         let exp = now + Duration::seconds(self.refresh_token_expiry);
 
-        let claims = Claims {
+        let mut claims = Claims {
             sub: user_id.to_string(),
             tenant_id: tenant_id.to_string(),
             role,
             exp: exp.timestamp(),
             iat: now.timestamp(),
+            iat_ms: Some(now.timestamp_millis()),
             token_type: "refresh".to_string(),
             jti: jti.or_else(|| Some(Uuid::new_v4().to_string())), // Use provided JTI or generate new
+            scope: None,
+            aud: None,
+            act: None,
+            impersonator: None,
+            auth_time: None,
+            amr: None,
+            acr: None,
+        };
+
+        claims.set_authentication(authentication);
+
+        self.sign(&claims)
+    }
+
+    /// Generates a short-lived MFA challenge token.
+    ///
+    /// Issued by login instead of access/refresh tokens when the user has a second
+    /// factor enrolled. It can only be exchanged for real tokens at the MFA verify
+    /// endpoint and is rejected by the JWT middleware.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - UUID of the user
+    /// * `tenant_id` - UUID of the tenant
+    /// * `role` - Role selected during login
+    ///
+    /// # Errors
+    ///
+    /// Returns `jsonwebtoken::errors::Error` if token encoding fails.
+    pub fn generate_mfa_token(
+        &self,
+        user_id: Uuid,
+        tenant_id: Uuid,
+        role: String,
+    ) -> Result<String, jsonwebtoken::errors::Error> {
+        let now = Utc::now();
+        let exp = now + Duration::seconds(self.mfa_token_expiry);
+
+        let claims = Claims {
+            sub: user_id.to_string(),
+            tenant_id: tenant_id.to_string(),
+            role,
+            exp: exp.timestamp(),
+            iat: now.timestamp(),
+            iat_ms: Some(now.timestamp_millis()),
+            token_type: "mfa_pending".to_string(),
+            jti: Some(Uuid::new_v4().to_string()),
+            scope: None,
+            aud: None,
+            act: None,
+            impersonator: None,
+            auth_time: None,
+            amr: None,
+            acr: None,
+        };
+
+        self.sign(&claims)
+    }
+
+    /// Generates an access token an admin uses to act as a user.
+    ///
+    /// The token carries the admin's ID in `impersonator`, so routes guarded
+    /// against impersonation refuse it, and lives `IMPERSONATION_TOKEN_EXPIRY`
+    /// seconds. It cannot be refreshed.
+    ///
+    /// # Errors
+    ///
+    /// Returns `jsonwebtoken::errors::Error` if token encoding fails.
+    pub fn generate_impersonation_token(
+        &self,
+        user_id: Uuid,
+        tenant_id: Uuid,
+        role: String,
+        impersonator: Uuid,
+    ) -> Result<String, jsonwebtoken::errors::Error> {
+        let now = Utc::now();
+        let exp = now + Duration::seconds(self.impersonation_token_expiry);
+
+        let claims = Claims {
+            sub: user_id.to_string(),
+            tenant_id: tenant_id.to_string(),
+            role,
+            exp: exp.timestamp(),
+            iat: now.timestamp(),
+            iat_ms: Some(now.timestamp_millis()),
+            token_type: "access".to_string(),
+            jti: Some(Uuid::new_v4().to_string()),
+            scope: None,
+            aud: None,
+            act: None,
+            impersonator: Some(impersonator.to_string()),
+            auth_time: None,
+            amr: None,
+            acr: None,
         };
 
-        encode(
-            &Header::default(),
-            &claims,
-            &EncodingKey::from_secret(self.secret.as_bytes()),
-        )
+        self.sign(&claims)
+    }
+
+    /// Signs an OpenID Connect ID token with the active key.
+    ///
+    /// # Errors
+    ///
+    /// Returns `jsonwebtoken::errors::Error` if token encoding fails.
+    pub fn generate_id_token(
+        &self,
+        claims: &IdTokenClaims,
+    ) -> Result<String, jsonwebtoken::errors::Error> {
+        self.sign(claims)
     }
 
     /// Validates and decodes a JWT token.
     ///
+    /// The token is verified with the ring key named by its `kid` header, so
+    /// tokens signed before a rotation stay valid while their key is kept for
+    /// verification.
+    ///
     /// # Arguments
     ///
     /// * `token` - JWT token string to validate
@@ -156,20 +617,51 @@ impl JwtService {
     ///
     /// # Errors
     ///
-    /// Returns `jsonwebtoken::errors::Error` if token is invalid or expired.
+    /// Returns `jsonwebtoken::errors::Error` if token is invalid or expired, or
+    /// if no key in the ring matches its `kid`.
     #[allow(dead_code)]
     pub fn validate_token(&self, token: &str) -> Result<Claims, jsonwebtoken::errors::Error> {
-        let mut validation = Validation::default();
-        validation.validate_nbf = true;
-        validation.leeway = 0; // Strict checking
+        let header = decode_header(token)?;
+        let mut last_error = jsonwebtoken::errors::Error::from(ErrorKind::InvalidToken);
+
+        for key in self.key_ring.verification_keys(header.kid.as_deref()) {
+            if key.algorithm() != header.alg {
+                continue;
+            }
 
-        let token_data = decode::<Claims>(
-            token,
-            &DecodingKey::from_secret(self.secret.as_bytes()),
-            &validation,
-        )?;
+            let mut validation = Validation::new(key.algorithm());
+            validation.validate_nbf = true;
+            // Audiences are checked by the services receiving exchanged tokens,
+            // and by `auth_middleware` against this issuer
+            validation.validate_aud = false;
+            validation.leeway = 0; // Strict checking
 
-        Ok(token_data.claims)
+            match decode::<Claims>(token, key.decoding_key(), &validation) {
+                Ok(token_data) => return Ok(token_data.claims),
+                Err(e) if matches!(e.kind(), ErrorKind::InvalidSignature) => last_error = e,
+                Err(e) => return Err(e),
+            }
+        }
+
+        Err(last_error)
+    }
+
+    /// Public keys of the ring as a JWK Set.
+    ///
+    /// Empty for `HS256`, whose shared secrets must never be published.
+    pub fn jwks(&self) -> JwkSet {
+        self.key_ring.jwks()
+    }
+
+    /// Algorithms of the keys in the ring, the active key's first.
+    pub fn signing_algorithms(&self) -> Vec<Algorithm> {
+        let mut algorithms = vec![self.key_ring.active().algorithm()];
+        for key in self.key_ring.verification_keys(None) {
+            if !algorithms.contains(&key.algorithm()) {
+                algorithms.push(key.algorithm());
+            }
+        }
+        algorithms
     }
 
     /// Gets the refresh token expiry duration in seconds.
@@ -187,4 +679,14 @@ impl JwtService {
     pub fn get_access_token_expiry(&self) -> i64 {
         self.access_token_expiry
     }
+
+    /// Gets the MFA challenge token expiry duration in seconds.
+    pub fn get_mfa_token_expiry(&self) -> i64 {
+        self.mfa_token_expiry
+    }
+
+    /// Gets the impersonation token expiry duration in seconds.
+    pub fn get_impersonation_token_expiry(&self) -> i64 {
+        self.impersonation_token_expiry
+    }
 }
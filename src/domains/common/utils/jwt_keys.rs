@@ -0,0 +1,408 @@
+//! JWT signing keys.
+//!
+//! `HS256` signs with the shared `JWT_SECRET`. `RS256`, `ES256` (P-256) and
+//! `EdDSA` (Ed25519) sign with a private key read from a PEM file, which is
+//! generated on first start if it does not exist yet. Asymmetric keys carry a
+//! `kid` (the RFC 7638 SHA-256 thumbprint of the public key) and are published
+//! as a JWK so other services can verify tokens without any secret.
+//!
+//! Keys are held in a `KeyRing`: one active key signs new tokens while
+//! verification-only keys, the former active keys, keep verifying tokens
+//! issued before a rotation until they expire.
+
+use crate::domains::common::errors::AppError;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use jsonwebtoken::jwk::{
+    AlgorithmParameters, CommonParameters, EllipticCurve, EllipticCurveKeyParameters,
+    EllipticCurveKeyType, Jwk, JwkSet, KeyAlgorithm, OctetKeyPairParameters, OctetKeyPairType,
+    PublicKeyUse, RSAKeyParameters, RSAKeyType,
+};
+use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey};
+use rand_core::RngCore;
+use rsa::pkcs1::{DecodeRsaPrivateKey, EncodeRsaPrivateKey};
+use rsa::pkcs8::{DecodePrivateKey, EncodePrivateKey, LineEnding};
+use rsa::traits::PublicKeyParts;
+use sha2::{Digest, Sha256};
+use std::path::Path;
+use std::sync::{Arc, PoisonError, RwLock};
+
+/// Modulus size of generated RSA keys.
+const RSA_KEY_BITS: usize = 2048;
+
+/// Length in bytes of generated `HS256` secrets.
+const HMAC_SECRET_BYTES: usize = 32;
+
+/// Algorithms accepted in `JWT_ALGORITHM`.
+pub const SUPPORTED_ALGORITHMS: [&str; 4] = ["HS256", "RS256", "ES256", "EdDSA"];
+
+/// Parses a `JWT_ALGORITHM` value, returning `None` for unsupported algorithms.
+pub fn parse_algorithm(value: &str) -> Option<Algorithm> {
+    match value {
+        "HS256" => Some(Algorithm::HS256),
+        "RS256" => Some(Algorithm::RS256),
+        "ES256" => Some(Algorithm::ES256),
+        "EdDSA" => Some(Algorithm::EdDSA),
+        _ => None,
+    }
+}
+
+fn key_error(message: impl std::fmt::Display) -> AppError {
+    AppError::InternalError(format!("Invalid JWT signing key: {}", message))
+}
+
+fn b64(bytes: &[u8]) -> String {
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+fn random_b64(len: usize) -> String {
+    let mut bytes = vec![0u8; len];
+    rand_core::OsRng.fill_bytes(&mut bytes);
+    b64(&bytes)
+}
+
+/// Computes the RFC 7638 JWK thumbprint from the required members, which
+/// must already be in lexicographic order.
+fn thumbprint(members: &[(&str, &str)]) -> String {
+    let json = members
+        .iter()
+        .map(|(name, value)| format!(r#""{}":"{}""#, name, value))
+        .collect::<Vec<_>>()
+        .join(",");
+    b64(&Sha256::digest(format!("{{{}}}", json)))
+}
+
+/// A key used to sign and verify JWTs.
+pub struct JwtKey {
+    algorithm: Algorithm,
+    kid: Option<String>,
+    encoding_key: EncodingKey,
+    decoding_key: DecodingKey,
+    jwk: Option<Jwk>,
+}
+
+impl JwtKey {
+    /// Creates an `HS256` key from a shared secret.
+    ///
+    /// Symmetric keys have no `kid` and are never published.
+    pub fn from_secret(secret: &[u8]) -> Self {
+        Self {
+            algorithm: Algorithm::HS256,
+            kid: None,
+            encoding_key: EncodingKey::from_secret(secret),
+            decoding_key: DecodingKey::from_secret(secret),
+            jwk: None,
+        }
+    }
+
+    /// Loads an asymmetric private key from PEM.
+    ///
+    /// RSA keys may be PKCS#1 or PKCS#8, P-256 keys SEC1 or PKCS#8 and
+    /// Ed25519 keys PKCS#8.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::InternalError` if the PEM does not hold a private key
+    /// for `algorithm`, or if `algorithm` is `HS256`.
+    pub fn from_pem(algorithm: Algorithm, pem: &str) -> Result<Self, AppError> {
+        match algorithm {
+            Algorithm::RS256 => {
+                let key = rsa::RsaPrivateKey::from_pkcs8_pem(pem)
+                    .or_else(|_| rsa::RsaPrivateKey::from_pkcs1_pem(pem))
+                    .map_err(key_error)?;
+                Self::from_rsa(key)
+            }
+            Algorithm::ES256 => {
+                let key = p256::SecretKey::from_pkcs8_pem(pem)
+                    .or_else(|_| p256::SecretKey::from_sec1_pem(pem))
+                    .map_err(key_error)?;
+                Self::from_p256(key)
+            }
+            Algorithm::EdDSA => {
+                let key = ed25519_dalek::SigningKey::from_pkcs8_pem(pem).map_err(key_error)?;
+                Self::from_ed25519(key)
+            }
+            other => Err(key_error(format!("{:?} does not use a private key", other))),
+        }
+    }
+
+    /// Restores a key ring entry from its stored `kid` and private material,
+    /// the shared secret for `HS256` and the PEM otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::InternalError` if the material is invalid or does not
+    /// match `kid`.
+    pub fn from_stored(algorithm: Algorithm, kid: &str, material: &str) -> Result<Self, AppError> {
+        if algorithm == Algorithm::HS256 {
+            let mut key = Self::from_secret(material.as_bytes());
+            key.kid = Some(kid.to_string());
+            return Ok(key);
+        }
+
+        let key = Self::from_pem(algorithm, material)?;
+        if key.kid() != Some(kid) {
+            return Err(key_error(format!(
+                "stored kid {} does not match the key",
+                kid
+            )));
+        }
+        Ok(key)
+    }
+
+    /// Generates a new key and returns it with its private material: the
+    /// PKCS#8 PEM for asymmetric algorithms, or a random secret with a random
+    /// `kid` for `HS256`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::InternalError` if key generation fails.
+    pub fn generate(algorithm: Algorithm) -> Result<(Self, String), AppError> {
+        let mut rng = rand_core::OsRng;
+        let pem = match algorithm {
+            Algorithm::HS256 => {
+                let secret = random_b64(HMAC_SECRET_BYTES);
+                let key = Self::from_stored(algorithm, &random_b64(16), &secret)?;
+                return Ok((key, secret));
+            }
+            Algorithm::RS256 => rsa::RsaPrivateKey::new(&mut rng, RSA_KEY_BITS)
+                .map_err(key_error)?
+                .to_pkcs8_pem(LineEnding::LF),
+            Algorithm::ES256 => p256::SecretKey::random(&mut rng).to_pkcs8_pem(LineEnding::LF),
+            Algorithm::EdDSA => {
+                ed25519_dalek::SigningKey::generate(&mut rng).to_pkcs8_pem(LineEnding::LF)
+            }
+            other => return Err(key_error(format!("{:?} is not supported", other))),
+        }
+        .map_err(key_error)?;
+
+        Ok((Self::from_pem(algorithm, &pem)?, pem.to_string()))
+    }
+
+    /// Loads the private key at `path`, generating and saving a new one if
+    /// the file does not exist. Returns the key with its PEM.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::InternalError` if the file cannot be read or
+    /// written, or does not hold a key for `algorithm`.
+    pub fn load_or_generate(algorithm: Algorithm, path: &Path) -> Result<(Self, String), AppError> {
+        if path.exists() {
+            let pem = std::fs::read_to_string(path)
+                .map_err(|e| key_error(format!("cannot read {}: {}", path.display(), e)))?;
+            return Ok((Self::from_pem(algorithm, &pem)?, pem));
+        }
+
+        let (key, pem) = Self::generate(algorithm)?;
+        if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)
+                .map_err(|e| key_error(format!("cannot create {}: {}", dir.display(), e)))?;
+        }
+        write_private(path, &pem)
+            .map_err(|e| key_error(format!("cannot write {}: {}", path.display(), e)))?;
+        log::info!(
+            "Generated {:?} JWT signing key {} at {}",
+            algorithm,
+            key.kid().unwrap_or_default(),
+            path.display()
+        );
+
+        Ok((key, pem))
+    }
+
+    fn from_rsa(key: rsa::RsaPrivateKey) -> Result<Self, AppError> {
+        let der = key.to_pkcs1_der().map_err(key_error)?;
+        let n = key.n().to_bytes_be();
+        let e = key.e().to_bytes_be();
+        let (n, e) = (b64(&n), b64(&e));
+        let kid = thumbprint(&[("e", &e), ("kty", "RSA"), ("n", &n)]);
+
+        Ok(Self {
+            algorithm: Algorithm::RS256,
+            encoding_key: EncodingKey::from_rsa_der(der.as_bytes()),
+            decoding_key: DecodingKey::from_rsa_components(&n, &e).map_err(key_error)?,
+            jwk: Some(Self::public_jwk(
+                &kid,
+                KeyAlgorithm::RS256,
+                AlgorithmParameters::RSA(RSAKeyParameters {
+                    key_type: RSAKeyType::RSA,
+                    n,
+                    e,
+                }),
+            )),
+            kid: Some(kid),
+        })
+    }
+
+    fn from_p256(key: p256::SecretKey) -> Result<Self, AppError> {
+        use p256::elliptic_curve::sec1::ToEncodedPoint;
+
+        let der = key.to_pkcs8_der().map_err(key_error)?;
+        let point = key.public_key().to_encoded_point(false);
+        let (x, y) = match (point.x(), point.y()) {
+            (Some(x), Some(y)) => (b64(x), b64(y)),
+            _ => return Err(key_error("P-256 public key has no coordinates")),
+        };
+        let kid = thumbprint(&[("crv", "P-256"), ("kty", "EC"), ("x", &x), ("y", &y)]);
+
+        Ok(Self {
+            algorithm: Algorithm::ES256,
+            encoding_key: EncodingKey::from_ec_der(der.as_bytes()),
+            decoding_key: DecodingKey::from_ec_components(&x, &y).map_err(key_error)?,
+            jwk: Some(Self::public_jwk(
+                &kid,
+                KeyAlgorithm::ES256,
+                AlgorithmParameters::EllipticCurve(EllipticCurveKeyParameters {
+                    key_type: EllipticCurveKeyType::EC,
+                    curve: EllipticCurve::P256,
+                    x,
+                    y,
+                }),
+            )),
+            kid: Some(kid),
+        })
+    }
+
+    fn from_ed25519(key: ed25519_dalek::SigningKey) -> Result<Self, AppError> {
+        let der = key.to_pkcs8_der().map_err(key_error)?;
+        let x = b64(key.verifying_key().as_bytes());
+        let kid = thumbprint(&[("crv", "Ed25519"), ("kty", "OKP"), ("x", &x)]);
+
+        Ok(Self {
+            algorithm: Algorithm::EdDSA,
+            encoding_key: EncodingKey::from_ed_der(der.as_bytes()),
+            decoding_key: DecodingKey::from_ed_components(&x).map_err(key_error)?,
+            jwk: Some(Self::public_jwk(
+                &kid,
+                KeyAlgorithm::EdDSA,
+                AlgorithmParameters::OctetKeyPair(OctetKeyPairParameters {
+                    key_type: OctetKeyPairType::OctetKeyPair,
+                    curve: EllipticCurve::Ed25519,
+                    x,
+                }),
+            )),
+            kid: Some(kid),
+        })
+    }
+
+    fn public_jwk(kid: &str, algorithm: KeyAlgorithm, parameters: AlgorithmParameters) -> Jwk {
+        Jwk {
+            common: CommonParameters {
+                public_key_use: Some(PublicKeyUse::Signature),
+                key_id: Some(kid.to_string()),
+                key_algorithm: Some(algorithm),
+                ..Default::default()
+            },
+            algorithm: parameters,
+        }
+    }
+
+    /// Signing algorithm of this key.
+    pub fn algorithm(&self) -> Algorithm {
+        self.algorithm
+    }
+
+    /// Key ID placed in the `kid` header, `None` for `HS256`.
+    pub fn kid(&self) -> Option<&str> {
+        self.kid.as_deref()
+    }
+
+    pub fn encoding_key(&self) -> &EncodingKey {
+        &self.encoding_key
+    }
+
+    pub fn decoding_key(&self) -> &DecodingKey {
+        &self.decoding_key
+    }
+
+    /// Public key as a JWK, `None` for `HS256`.
+    pub fn jwk(&self) -> Option<&Jwk> {
+        self.jwk.as_ref()
+    }
+}
+
+/// The keys a `JwtService` signs and verifies with.
+///
+/// The ring is shared by every `JwtService` in the process and replaced as a
+/// whole when keys are rotated or reloaded from the database.
+pub struct KeyRing {
+    keys: RwLock<RingKeys>,
+}
+
+struct RingKeys {
+    active: Arc<JwtKey>,
+    verification_only: Vec<Arc<JwtKey>>,
+}
+
+impl KeyRing {
+    /// Creates a ring holding a single active key.
+    pub fn new(active: JwtKey) -> Self {
+        Self {
+            keys: RwLock::new(RingKeys {
+                active: Arc::new(active),
+                verification_only: Vec::new(),
+            }),
+        }
+    }
+
+    /// Replaces the keys of the ring.
+    pub fn replace(&self, active: JwtKey, verification_only: Vec<JwtKey>) {
+        let mut keys = self.keys.write().unwrap_or_else(PoisonError::into_inner);
+        *keys = RingKeys {
+            active: Arc::new(active),
+            verification_only: verification_only.into_iter().map(Arc::new).collect(),
+        };
+    }
+
+    /// Key that signs new tokens.
+    pub fn active(&self) -> Arc<JwtKey> {
+        self.keys
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .active
+            .clone()
+    }
+
+    /// Keys that may have signed a token with the given `kid` header.
+    ///
+    /// Tokens without a `kid`, issued before key IDs were introduced, are
+    /// checked against every key. An unknown `kid` matches nothing.
+    pub fn verification_keys(&self, kid: Option<&str>) -> Vec<Arc<JwtKey>> {
+        let keys = self.keys.read().unwrap_or_else(PoisonError::into_inner);
+        std::iter::once(&keys.active)
+            .chain(keys.verification_only.iter())
+            .filter(|key| kid.is_none() || key.kid() == kid)
+            .cloned()
+            .collect()
+    }
+
+    /// Public keys of the active and verification-only keys as a JWK Set.
+    pub fn jwks(&self) -> JwkSet {
+        JwkSet {
+            keys: self
+                .verification_keys(None)
+                .iter()
+                .filter_map(|key| key.jwk().cloned())
+                .collect(),
+        }
+    }
+}
+
+/// Writes a private key readable only by the current user.
+#[cfg(unix)]
+fn write_private(path: &Path, contents: &str) -> std::io::Result<()> {
+    use std::io::Write;
+    use std::os::unix::fs::OpenOptionsExt;
+
+    std::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .mode(0o600)
+        .open(path)?
+        .write_all(contents.as_bytes())
+}
+
+#[cfg(not(unix))]
+fn write_private(path: &Path, contents: &str) -> std::io::Result<()> {
+    std::fs::write(path, contents)
+}
@@ -23,6 +23,27 @@ fn test_generate_and_validate_access_token() {
     assert_eq!(claims.tenant_id, tenant_id.to_string());
     assert_eq!(claims.role, role);
     assert_eq!(claims.token_type, "access");
+    assert!(claims.jti.is_some());
+}
+
+#[test]
+fn test_access_tokens_have_unique_jti() {
+    setup_env();
+    let jwt_service = JwtService::new();
+    let user_id = Uuid::new_v4();
+    let tenant_id = Uuid::new_v4();
+
+    let first = jwt_service
+        .generate_access_token(user_id, tenant_id, "user".to_string())
+        .unwrap();
+    let second = jwt_service
+        .generate_access_token(user_id, tenant_id, "user".to_string())
+        .unwrap();
+
+    assert_ne!(
+        jwt_service.validate_token(&first).unwrap().jti,
+        jwt_service.validate_token(&second).unwrap().jti
+    );
 }
 
 #[test]
@@ -34,7 +55,7 @@ fn test_generate_and_validate_refresh_token() {
     let role = "admin".to_string();
 
     let token = jwt_service
-        .generate_refresh_token(user_id, tenant_id, role.clone(), None)
+        .generate_refresh_token(user_id, tenant_id, role.clone(), None, None)
         .unwrap();
     let claims = jwt_service.validate_token(&token).unwrap();
 
@@ -62,3 +83,63 @@ fn test_token_expiry() {
     let result = jwt_service.validate_token(&token);
     assert!(result.is_ok());
 }
+
+#[test]
+fn test_generate_and_validate_mfa_token() {
+    setup_env();
+    let jwt_service = JwtService::new();
+    let user_id = Uuid::new_v4();
+    let tenant_id = Uuid::new_v4();
+
+    let token = jwt_service
+        .generate_mfa_token(user_id, tenant_id, "user".to_string())
+        .unwrap();
+    let claims = jwt_service.validate_token(&token).unwrap();
+
+    assert_eq!(claims.sub, user_id.to_string());
+    assert_eq!(claims.token_type, "mfa_pending");
+    assert!(claims.exp - claims.iat <= jwt_service.get_mfa_token_expiry());
+}
+
+#[test]
+fn test_session_tokens_carry_authentication() {
+    setup_env();
+    let jwt_service = JwtService::new();
+    let user_id = Uuid::new_v4();
+    let tenant_id = Uuid::new_v4();
+    let authentication = Authentication::now(&[AMR_PASSWORD]);
+
+    let access = jwt_service
+        .generate_session_access_token(
+            user_id,
+            tenant_id,
+            "user".to_string(),
+            Some(&authentication),
+        )
+        .unwrap();
+    let refresh = jwt_service
+        .generate_refresh_token(
+            user_id,
+            tenant_id,
+            "user".to_string(),
+            None,
+            Some(&authentication),
+        )
+        .unwrap();
+
+    for token in [access, refresh] {
+        let claims = jwt_service.validate_token(&token).unwrap();
+        assert_eq!(claims.auth_time, Some(authentication.time));
+        assert_eq!(claims.amr, Some(vec![AMR_PASSWORD.to_string()]));
+        assert_eq!(claims.acr.as_deref(), Some(ACR_SINGLE_FACTOR));
+        assert!(!claims.authentication().unwrap().is_multi_factor());
+    }
+
+    // Tokens not issued by a login carry no authentication
+    let token = jwt_service
+        .generate_access_token(user_id, tenant_id, "user".to_string())
+        .unwrap();
+    let claims = jwt_service.validate_token(&token).unwrap();
+    assert!(claims.authentication().is_none());
+    assert!(claims.acr.is_none());
+}
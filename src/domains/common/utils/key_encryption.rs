@@ -0,0 +1,98 @@
+//! Encryption of key material stored in the database.
+//!
+//! Values are sealed with AES-256-GCM under a key derived from `SECRET_KEY`
+//! and stored as `enc:v1:` followed by the base64url encoded nonce and
+//! ciphertext. Values without the prefix were stored in plaintext by earlier
+//! versions and are returned unchanged.
+
+use crate::domains::common::errors::AppError;
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Nonce};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use rand_core::RngCore;
+use sha2::{Digest, Sha256};
+
+/// Prefix of sealed values, versioned in case the scheme changes.
+const SEALED_PREFIX: &str = "enc:v1:";
+
+/// Length in bytes of the AES-GCM nonce.
+const NONCE_BYTES: usize = 12;
+
+/// Seals and opens stored secrets with a key derived from an application secret.
+pub struct KeyEncryption {
+    cipher: Aes256Gcm,
+}
+
+impl KeyEncryption {
+    /// Derives the encryption key from `secret`, normally `SECRET_KEY`.
+    pub fn new(secret: &str) -> Self {
+        let key = Sha256::new()
+            .chain_update(b"jwt-signing-key:")
+            .chain_update(secret.as_bytes())
+            .finalize();
+
+        Self {
+            cipher: Aes256Gcm::new(&key),
+        }
+    }
+
+    /// Returns true if `stored` was produced by `seal`.
+    pub fn is_sealed(stored: &str) -> bool {
+        stored.starts_with(SEALED_PREFIX)
+    }
+
+    /// Encrypts `plaintext` with a random nonce.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::InternalError` if encryption fails.
+    pub fn seal(&self, plaintext: &str) -> Result<String, AppError> {
+        let mut nonce = [0u8; NONCE_BYTES];
+        rand_core::OsRng.fill_bytes(&mut nonce);
+
+        let ciphertext = self
+            .cipher
+            .encrypt(Nonce::from_slice(&nonce), plaintext.as_bytes())
+            .map_err(|_| AppError::InternalError("Cannot encrypt key material".to_string()))?;
+
+        let mut sealed = nonce.to_vec();
+        sealed.extend(ciphertext);
+        Ok(format!(
+            "{}{}",
+            SEALED_PREFIX,
+            URL_SAFE_NO_PAD.encode(sealed)
+        ))
+    }
+
+    /// Decrypts a value from `seal`, or returns a plaintext value unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::InternalError` if the value is malformed or was
+    /// sealed with another secret.
+    pub fn open(&self, stored: &str) -> Result<String, AppError> {
+        let Some(encoded) = stored.strip_prefix(SEALED_PREFIX) else {
+            return Ok(stored.to_string());
+        };
+        let undecryptable = || {
+            AppError::InternalError(
+                "Cannot decrypt stored key material, was SECRET_KEY changed?".to_string(),
+            )
+        };
+
+        let sealed = URL_SAFE_NO_PAD
+            .decode(encoded)
+            .map_err(|_| undecryptable())?;
+        if sealed.len() < NONCE_BYTES {
+            return Err(undecryptable());
+        }
+        let (nonce, ciphertext) = sealed.split_at(NONCE_BYTES);
+
+        let plaintext = self
+            .cipher
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| undecryptable())?;
+        String::from_utf8(plaintext).map_err(|_| undecryptable())
+    }
+}
@@ -0,0 +1,36 @@
+//! Progressive account lockout policy.
+//!
+//! Every failed password login increments the user's consecutive failure
+//! count. Once it reaches `threshold` the account is locked for
+//! `base_duration` seconds, and every further failure after the lock expires
+//! doubles the lock, up to `max_duration`. A successful login resets the count.
+
+use chrono::Duration;
+
+/// Lockout thresholds. A `threshold` of zero disables lockout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LockoutPolicy {
+    pub threshold: i32,
+    pub base_duration: i64,
+    pub max_duration: i64,
+}
+
+impl LockoutPolicy {
+    /// Returns how long to lock the account after `failures` consecutive
+    /// failed logins, or `None` while the count is below the threshold.
+    pub fn lock_duration(&self, failures: i32) -> Option<Duration> {
+        if self.threshold <= 0 || failures < self.threshold {
+            return None;
+        }
+
+        // Cap the exponent so the shift cannot overflow before the max applies
+        let exponent = (failures - self.threshold).min(32) as u32;
+        let seconds = self
+            .base_duration
+            .max(1)
+            .saturating_mul(1i64 << exponent)
+            .min(self.max_duration.max(self.base_duration));
+
+        Some(Duration::seconds(seconds))
+    }
+}
@@ -0,0 +1,42 @@
+use super::lockout::*;
+use chrono::Duration;
+
+fn policy() -> LockoutPolicy {
+    LockoutPolicy {
+        threshold: 5,
+        base_duration: 60,
+        max_duration: 3600,
+    }
+}
+
+#[test]
+fn test_no_lock_below_threshold() {
+    for failures in 0..5 {
+        assert_eq!(policy().lock_duration(failures), None);
+    }
+}
+
+#[test]
+fn test_lock_doubles_after_threshold() {
+    assert_eq!(policy().lock_duration(5), Some(Duration::seconds(60)));
+    assert_eq!(policy().lock_duration(6), Some(Duration::seconds(120)));
+    assert_eq!(policy().lock_duration(7), Some(Duration::seconds(240)));
+}
+
+#[test]
+fn test_lock_is_capped_at_max_duration() {
+    assert_eq!(policy().lock_duration(11), Some(Duration::seconds(3600)));
+    assert_eq!(
+        policy().lock_duration(i32::MAX),
+        Some(Duration::seconds(3600))
+    );
+}
+
+#[test]
+fn test_zero_threshold_disables_lockout() {
+    let disabled = LockoutPolicy {
+        threshold: 0,
+        ..policy()
+    };
+    assert_eq!(disabled.lock_duration(100), None);
+}
@@ -1,20 +1,43 @@
 //! Utility Functions
 //!
 //! This module contains utility functions for common operations such as
-//! password hashing, JWT token management, and URL conversion.
+//! password hashing, JWT token management and signing keys, encryption of stored keys,
+//! one-time passwords, recovery codes, device flow user codes, account lockout, WebAuthn
+//! verification, and URL conversion.
 
 pub mod config;
 #[cfg(test)]
 mod config_test;
 pub mod jwt;
+pub mod jwt_keys;
+#[cfg(test)]
+mod jwt_keys_test;
 #[cfg(test)]
 mod jwt_test;
+pub mod key_encryption;
+#[cfg(test)]
+mod key_encryption_test;
+pub mod lockout;
+#[cfg(test)]
+mod lockout_test;
 pub mod password;
 #[cfg(test)]
 mod password_test;
+pub mod recovery_code;
+#[cfg(test)]
+mod recovery_code_test;
 pub mod request_helper;
 #[cfg(test)]
 mod request_helper_test;
+pub mod totp;
+#[cfg(test)]
+mod totp_test;
 pub mod url_helper;
 #[cfg(test)]
 mod url_helper_test;
+pub mod user_code;
+#[cfg(test)]
+mod user_code_test;
+pub mod webauthn;
+#[cfg(test)]
+pub(crate) mod webauthn_test;
@@ -0,0 +1,42 @@
+//! Account recovery code helpers.
+//!
+//! Recovery codes are shown to the user as `xxxxx-xxxxx` groups drawn from an
+//! alphabet without look-alike characters. Input is normalized before hashing
+//! and verification so that case, dashes and whitespace do not matter.
+
+use rand::Rng;
+
+/// Number of codes issued per set.
+pub const CODE_COUNT: usize = 10;
+
+/// Number of characters in a code, excluding the separator.
+pub const CODE_LENGTH: usize = 10;
+
+// Lowercase letters and digits without 0/o, 1/l/i.
+const ALPHABET: &[u8] = b"abcdefghjkmnpqrstuvwxyz23456789";
+
+/// Generates a single recovery code formatted as `xxxxx-xxxxx`.
+pub fn generate_code() -> String {
+    let mut rng = rand::rng();
+    let raw: String = (0..CODE_LENGTH)
+        .map(|_| ALPHABET[rng.random_range(0..ALPHABET.len())] as char)
+        .collect();
+
+    let (head, tail) = raw.split_at(CODE_LENGTH / 2);
+    format!("{}-{}", head, tail)
+}
+
+/// Generates a full set of `CODE_COUNT` recovery codes.
+pub fn generate_codes() -> Vec<String> {
+    (0..CODE_COUNT).map(|_| generate_code()).collect()
+}
+
+/// Normalizes user input for hashing and comparison.
+///
+/// Strips dashes and whitespace and lowercases the remaining characters.
+pub fn normalize(code: &str) -> String {
+    code.chars()
+        .filter(|c| *c != '-' && !c.is_whitespace())
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
@@ -0,0 +1,30 @@
+use super::recovery_code::*;
+
+#[test]
+fn test_generate_code_format() {
+    let code = generate_code();
+
+    assert_eq!(code.len(), CODE_LENGTH + 1);
+    assert_eq!(code.chars().nth(CODE_LENGTH / 2), Some('-'));
+    assert!(normalize(&code)
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit()));
+}
+
+#[test]
+fn test_generate_codes_are_distinct() {
+    let codes = generate_codes();
+    assert_eq!(codes.len(), CODE_COUNT);
+
+    let mut unique = codes.clone();
+    unique.sort();
+    unique.dedup();
+    assert_eq!(unique.len(), CODE_COUNT);
+}
+
+#[test]
+fn test_normalize_ignores_case_dashes_and_whitespace() {
+    assert_eq!(normalize("ABCDE-FGHJK"), "abcdefghjk");
+    assert_eq!(normalize(" abcde fghjk \n"), "abcdefghjk");
+    assert_eq!(normalize("abcdefghjk"), "abcdefghjk");
+}
@@ -1,14 +1,16 @@
+use crate::domains::common::utils::config::Config;
 use actix_web::HttpRequest;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
 use sha2::{Digest, Sha256};
+use std::net::IpAddr;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 /// Extracts client information from HTTP request.
 ///
 /// Returns a tuple of (ip_address, user_agent) as Option<String>.
 pub fn extract_client_info(req: &HttpRequest) -> (Option<String>, Option<String>) {
-    let ip_address = req
-        .connection_info()
-        .realip_remote_addr()
-        .map(|s| s.to_string());
+    let ip_address = client_ip(req);
 
     let user_agent = req
         .headers()
@@ -19,6 +21,72 @@ pub fn extract_client_info(req: &HttpRequest) -> (Option<String>, Option<String>
     (ip_address, user_agent)
 }
 
+/// Set once the warning about a proxy missing from `TRUSTED_PROXIES` was logged.
+static UNTRUSTED_PROXY_WARNED: AtomicBool = AtomicBool::new(false);
+
+/// Returns the client IP address, trusting `X-Forwarded-For` only when the
+/// request comes from one of `TRUSTED_PROXIES`.
+///
+/// The same address is logged in the activity log and keys the rate limits.
+/// Behind a proxy that is not listed, both see the proxy's address, which
+/// is logged as a warning on the first such request.
+pub fn client_ip(req: &HttpRequest) -> Option<String> {
+    let peer = req.peer_addr()?.ip();
+    let trusted_proxies = &Config::get().trusted_proxies;
+    if trusted_proxies.is_empty()
+        && is_forwarded_by_private_peer(req, peer)
+        && !UNTRUSTED_PROXY_WARNED.swap(true, Ordering::Relaxed)
+    {
+        log::warn!(
+            "Request from private address {} carries X-Forwarded-For but TRUSTED_PROXIES is \
+             not set, so all clients behind it are logged and rate limited as that address",
+            peer
+        );
+    }
+
+    Some(resolve_client_ip(req, peer, trusted_proxies).to_string())
+}
+
+/// Returns true if a request from `peer` looks like it was forwarded by a
+/// reverse proxy: it has an `X-Forwarded-For` header and comes from a
+/// loopback or private address.
+pub fn is_forwarded_by_private_peer(req: &HttpRequest, peer: IpAddr) -> bool {
+    let private = match peer {
+        IpAddr::V4(ip) => ip.is_loopback() || ip.is_private() || ip.is_link_local(),
+        IpAddr::V6(ip) => ip.is_loopback() || ip.is_unique_local() || ip.is_unicast_link_local(),
+    };
+
+    private && req.headers().contains_key("x-forwarded-for")
+}
+
+/// Resolves the client IP of a request received from `peer`.
+///
+/// Proxies append the address they received the request from, so the chain
+/// is walked from the right and the first hop that is not a trusted proxy is
+/// the client. Entries further left could have been sent by the client itself.
+pub fn resolve_client_ip(req: &HttpRequest, peer: IpAddr, trusted_proxies: &[IpAddr]) -> IpAddr {
+    if !trusted_proxies.contains(&peer) {
+        return peer;
+    }
+
+    let hops: Vec<&str> = req
+        .headers()
+        .get_all("x-forwarded-for")
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .collect();
+
+    for hop in hops.into_iter().rev() {
+        match hop.parse::<IpAddr>() {
+            Ok(ip) if trusted_proxies.contains(&ip) => continue,
+            Ok(ip) => return ip,
+            Err(_) => break,
+        }
+    }
+    peer
+}
+
 /// Hashes a refresh token using SHA-256 for secure storage.
 ///
 /// # Arguments
@@ -33,3 +101,35 @@ pub fn hash_token(token: &str) -> String {
     hasher.update(token.as_bytes());
     format!("{:x}", hasher.finalize())
 }
+
+/// Generates a random URL-safe token with 256 bits of entropy.
+///
+/// Used for single-use links sent to users. Store only `hash_token(token)`.
+pub fn generate_token() -> String {
+    let bytes: [u8; 32] = rand::random();
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+/// Returns the most preferred language tag from the `Accept-Language` header.
+///
+/// Used to pick the locale of emails sent in response to the request.
+/// Wildcards and entries with `q=0` are ignored.
+pub fn preferred_locale(req: &HttpRequest) -> Option<String> {
+    let header = req.headers().get("accept-language")?.to_str().ok()?;
+
+    header
+        .split(',')
+        .filter_map(|entry| {
+            let mut parts = entry.split(';');
+            let tag = parts.next()?.trim();
+            let quality = parts
+                .find_map(|p| p.trim().strip_prefix("q="))
+                .map_or(Some(1.0), |q| q.trim().parse::<f32>().ok())?;
+            (!tag.is_empty() && tag != "*" && quality > 0.0).then(|| (tag.to_string(), quality))
+        })
+        .fold(None, |best: Option<(String, f32)>, candidate| match best {
+            Some(b) if b.1 >= candidate.1 => Some(b),
+            _ => Some(candidate),
+        })
+        .map(|(tag, _)| tag)
+}
@@ -16,3 +16,103 @@ fn test_hash_token() {
     let different_hash = hash_token("different_token");
     assert_ne!(hash1, different_hash);
 }
+
+#[test]
+fn test_generate_token() {
+    let token = generate_token();
+
+    // 32 random bytes encode to 43 base64url characters
+    assert_eq!(token.len(), 43);
+    assert!(token
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+    assert_ne!(token, generate_token());
+}
+
+#[test]
+fn test_preferred_locale() {
+    use actix_web::test::TestRequest;
+
+    let locale = |header: &str| {
+        let req = TestRequest::default()
+            .insert_header(("Accept-Language", header))
+            .to_http_request();
+        preferred_locale(&req)
+    };
+
+    assert_eq!(locale("pt-BR,pt;q=0.9,en;q=0.8"), Some("pt-BR".to_string()));
+    assert_eq!(locale("en;q=0.5, de"), Some("de".to_string()));
+    assert_eq!(locale("*, fr;q=0.1"), Some("fr".to_string()));
+    assert_eq!(locale("es;q=0"), None);
+    assert_eq!(
+        preferred_locale(&TestRequest::default().to_http_request()),
+        None
+    );
+}
+
+#[test]
+fn test_resolve_client_ip_trusts_forwarded_only_from_proxies() {
+    use actix_web::test::TestRequest;
+    use std::net::IpAddr;
+
+    let ip = |s: &str| s.parse::<IpAddr>().unwrap();
+    let proxies = [ip("10.0.0.1"), ip("10.0.0.2")];
+    let req = |forwarded: &str| {
+        TestRequest::default()
+            .insert_header(("X-Forwarded-For", forwarded))
+            .to_http_request()
+    };
+
+    // Direct clients cannot pick their address
+    assert_eq!(
+        resolve_client_ip(&req("1.2.3.4"), ip("203.0.113.9"), &proxies),
+        ip("203.0.113.9")
+    );
+    assert_eq!(
+        resolve_client_ip(&req("1.2.3.4"), ip("203.0.113.9"), &[]),
+        ip("203.0.113.9")
+    );
+
+    // Behind proxies, the nearest untrusted hop is the client, whatever it prepended
+    assert_eq!(
+        resolve_client_ip(
+            &req("1.2.3.4, 203.0.113.9, 10.0.0.2"),
+            ip("10.0.0.1"),
+            &proxies
+        ),
+        ip("203.0.113.9")
+    );
+    assert_eq!(
+        resolve_client_ip(&req("garbage, 203.0.113.9"), ip("10.0.0.1"), &proxies),
+        ip("203.0.113.9")
+    );
+    assert_eq!(
+        resolve_client_ip(
+            &TestRequest::default().to_http_request(),
+            ip("10.0.0.1"),
+            &proxies
+        ),
+        ip("10.0.0.1")
+    );
+}
+
+#[test]
+fn test_is_forwarded_by_private_peer() {
+    use actix_web::test::TestRequest;
+    use std::net::IpAddr;
+
+    let ip = |s: &str| s.parse::<IpAddr>().unwrap();
+    let forwarded = TestRequest::default()
+        .insert_header(("X-Forwarded-For", "203.0.113.7"))
+        .to_http_request();
+    let direct = TestRequest::default().to_http_request();
+
+    assert!(is_forwarded_by_private_peer(&forwarded, ip("10.0.0.1")));
+    assert!(is_forwarded_by_private_peer(&forwarded, ip("127.0.0.1")));
+    assert!(is_forwarded_by_private_peer(&forwarded, ip("fd00::1")));
+    assert!(!is_forwarded_by_private_peer(
+        &forwarded,
+        ip("198.51.100.1")
+    ));
+    assert!(!is_forwarded_by_private_peer(&direct, ip("10.0.0.1")));
+}
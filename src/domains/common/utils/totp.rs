@@ -0,0 +1,157 @@
+//! Time-based One-Time Password (RFC 6238) helpers.
+//!
+//! Implements HOTP (RFC 4226) with HMAC-SHA1, 6 digits and a 30 second step,
+//! which is what every mainstream authenticator app expects by default.
+
+use crate::domains::common::errors::AppError;
+use hmac::{Hmac, Mac};
+use sha1::Sha1;
+
+type HmacSha1 = Hmac<Sha1>;
+
+/// Number of digits in a generated code.
+pub const DIGITS: u32 = 6;
+
+/// Length of a time step in seconds.
+pub const PERIOD: u64 = 30;
+
+/// Number of steps accepted before and after the current one to absorb clock drift.
+pub const ALLOWED_SKEW: u64 = 1;
+
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// Generates a new random 160-bit shared secret encoded as unpadded Base32.
+pub fn generate_secret() -> String {
+    let bytes: [u8; 20] = rand::random();
+    base32_encode(&bytes)
+}
+
+/// Encodes bytes as unpadded RFC 4648 Base32.
+pub fn base32_encode(data: &[u8]) -> String {
+    let mut output = String::with_capacity(data.len().div_ceil(5) * 8);
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+
+    for &byte in data {
+        buffer = (buffer << 8) | byte as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            output.push(BASE32_ALPHABET[((buffer >> bits) & 0x1f) as usize] as char);
+        }
+    }
+
+    if bits > 0 {
+        output.push(BASE32_ALPHABET[((buffer << (5 - bits)) & 0x1f) as usize] as char);
+    }
+
+    output
+}
+
+/// Decodes RFC 4648 Base32, ignoring padding, whitespace and letter case.
+///
+/// # Errors
+///
+/// Returns `AppError::InternalError` if the input contains characters outside the alphabet.
+pub fn base32_decode(input: &str) -> Result<Vec<u8>, AppError> {
+    let mut output = Vec::with_capacity(input.len() * 5 / 8);
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+
+    for c in input.chars() {
+        if c == '=' || c.is_whitespace() {
+            continue;
+        }
+        let value = BASE32_ALPHABET
+            .iter()
+            .position(|&a| a as char == c.to_ascii_uppercase())
+            .ok_or_else(|| AppError::InternalError("Invalid Base32 secret".to_string()))?;
+
+        buffer = (buffer << 5) | value as u32;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            output.push((buffer >> bits) as u8);
+        }
+    }
+
+    Ok(output)
+}
+
+/// Computes the HOTP value for a raw secret and counter.
+pub fn hotp(secret: &[u8], counter: u64) -> Result<String, AppError> {
+    let mut mac = HmacSha1::new_from_slice(secret)
+        .map_err(|e| AppError::InternalError(format!("Invalid TOTP secret: {}", e)))?;
+    mac.update(&counter.to_be_bytes());
+    let digest = mac.finalize().into_bytes();
+
+    // Dynamic truncation (RFC 4226, section 5.3)
+    let offset = (digest[digest.len() - 1] & 0x0f) as usize;
+    let binary = ((digest[offset] as u32 & 0x7f) << 24)
+        | ((digest[offset + 1] as u32) << 16)
+        | ((digest[offset + 2] as u32) << 8)
+        | (digest[offset + 3] as u32);
+
+    let code = binary % 10u32.pow(DIGITS);
+    Ok(format!("{:0width$}", code, width = DIGITS as usize))
+}
+
+/// Returns the time step that contains `unix_time`.
+pub fn time_step(unix_time: u64) -> u64 {
+    unix_time / PERIOD
+}
+
+/// Generates the TOTP code for a Base32 secret at the given Unix time.
+pub fn generate_code(secret_base32: &str, unix_time: u64) -> Result<String, AppError> {
+    let secret = base32_decode(secret_base32)?;
+    hotp(&secret, time_step(unix_time))
+}
+
+/// Verifies a TOTP code against a Base32 secret.
+///
+/// Codes from `ALLOWED_SKEW` steps around `unix_time` are accepted.
+///
+/// # Returns
+///
+/// Returns the matched time step, so callers can reject replays of the same
+/// code, or `None` if the code does not match.
+pub fn verify_code(
+    secret_base32: &str,
+    code: &str,
+    unix_time: u64,
+) -> Result<Option<u64>, AppError> {
+    let code = code.trim();
+    if code.len() != DIGITS as usize || !code.chars().all(|c| c.is_ascii_digit()) {
+        return Ok(None);
+    }
+
+    let secret = base32_decode(secret_base32)?;
+    let current = time_step(unix_time);
+
+    for step in current.saturating_sub(ALLOWED_SKEW)..=current + ALLOWED_SKEW {
+        if hotp(&secret, step)? == code {
+            return Ok(Some(step));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Builds the `otpauth://` provisioning URI rendered as a QR code by authenticator apps.
+///
+/// # Arguments
+///
+/// * `issuer` - Service name shown in the authenticator app
+/// * `account` - Account label, usually the user's email
+/// * `secret_base32` - Shared secret
+pub fn provisioning_uri(issuer: &str, account: &str, secret_base32: &str) -> String {
+    let mut uri = url::Url::parse("otpauth://totp").expect("static URI is valid");
+    uri.set_path(&format!("/{}:{}", issuer, account));
+    uri.query_pairs_mut()
+        .append_pair("secret", secret_base32)
+        .append_pair("issuer", issuer)
+        .append_pair("algorithm", "SHA1")
+        .append_pair("digits", &DIGITS.to_string())
+        .append_pair("period", &PERIOD.to_string());
+    uri.to_string()
+}
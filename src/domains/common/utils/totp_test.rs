@@ -0,0 +1,54 @@
+use super::totp::*;
+
+// RFC 6238 Appendix B test secret for HMAC-SHA1 ("12345678901234567890").
+const RFC_SECRET: &[u8] = b"12345678901234567890";
+
+#[test]
+fn test_base32_round_trip() {
+    let encoded = base32_encode(RFC_SECRET);
+    assert_eq!(encoded, "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ");
+    assert_eq!(base32_decode(&encoded).unwrap(), RFC_SECRET);
+    assert_eq!(base32_decode(&encoded.to_lowercase()).unwrap(), RFC_SECRET);
+}
+
+#[test]
+fn test_rfc6238_vectors() {
+    let secret = base32_encode(RFC_SECRET);
+
+    // The RFC lists 8-digit values; the last 6 digits are the 6-digit codes.
+    assert_eq!(generate_code(&secret, 59).unwrap(), "287082");
+    assert_eq!(generate_code(&secret, 1111111109).unwrap(), "081804");
+    assert_eq!(generate_code(&secret, 1234567890).unwrap(), "005924");
+    assert_eq!(generate_code(&secret, 2000000000).unwrap(), "279037");
+}
+
+#[test]
+fn test_verify_code_accepts_adjacent_step() {
+    let secret = generate_secret();
+    let now = 1_700_000_000;
+    let previous = generate_code(&secret, now - PERIOD).unwrap();
+
+    let step = verify_code(&secret, &previous, now).unwrap();
+    assert_eq!(step, Some(time_step(now) - 1));
+}
+
+#[test]
+fn test_verify_code_rejects_stale_or_malformed_code() {
+    let secret = generate_secret();
+    let now = 1_700_000_000;
+    let stale = generate_code(&secret, now - 5 * PERIOD).unwrap();
+
+    assert_eq!(verify_code(&secret, &stale, now).unwrap(), None);
+    assert_eq!(verify_code(&secret, "12ab56", now).unwrap(), None);
+    assert_eq!(verify_code(&secret, "1234567", now).unwrap(), None);
+}
+
+#[test]
+fn test_provisioning_uri() {
+    let uri = provisioning_uri("Auth Service", "user@example.com", "JBSWY3DPEHPK3PXP");
+
+    assert!(uri.starts_with("otpauth://totp/Auth%20Service:user@example.com?"));
+    assert!(uri.contains("secret=JBSWY3DPEHPK3PXP"));
+    assert!(uri.contains("issuer=Auth+Service"));
+    assert!(uri.contains("digits=6"));
+}
@@ -0,0 +1,35 @@
+//! Device flow user code helpers.
+//!
+//! User codes are typed by hand on another device, so they are short
+//! `XXXX-XXXX` groups of uppercase consonants (RFC 8628 section 6.1), which
+//! avoids look-alike characters and spelling words. Input is normalized before
+//! lookup so that case, dashes and whitespace do not matter.
+
+use rand::Rng;
+
+/// Number of characters in a code, excluding the separator.
+pub const CODE_LENGTH: usize = 8;
+
+// Uppercase consonants without vowels, so codes cannot spell words.
+const ALPHABET: &[u8] = b"BCDFGHJKLMNPQRSTVWXZ";
+
+/// Generates a user code formatted as `XXXX-XXXX`.
+pub fn generate_code() -> String {
+    let mut rng = rand::rng();
+    let raw: String = (0..CODE_LENGTH)
+        .map(|_| ALPHABET[rng.random_range(0..ALPHABET.len())] as char)
+        .collect();
+
+    let (head, tail) = raw.split_at(CODE_LENGTH / 2);
+    format!("{}-{}", head, tail)
+}
+
+/// Normalizes user input for lookup.
+///
+/// Strips dashes and whitespace and uppercases the remaining characters.
+pub fn normalize(code: &str) -> String {
+    code.chars()
+        .filter(|c| *c != '-' && !c.is_whitespace())
+        .map(|c| c.to_ascii_uppercase())
+        .collect()
+}
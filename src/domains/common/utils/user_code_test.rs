@@ -0,0 +1,19 @@
+use super::user_code::*;
+
+#[test]
+fn test_generate_code_format() {
+    let code = generate_code();
+
+    assert_eq!(code.len(), CODE_LENGTH + 1);
+    assert_eq!(code.chars().nth(CODE_LENGTH / 2), Some('-'));
+    assert!(normalize(&code)
+        .chars()
+        .all(|c| c.is_ascii_uppercase() && !"AEIOUY".contains(c)));
+}
+
+#[test]
+fn test_normalize_ignores_case_dashes_and_whitespace() {
+    assert_eq!(normalize("bcdf-ghjk"), "BCDFGHJK");
+    assert_eq!(normalize(" BCDF GHJK \n"), "BCDFGHJK");
+    assert_eq!(normalize("BCDFGHJK"), "BCDFGHJK");
+}
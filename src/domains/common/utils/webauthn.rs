@@ -0,0 +1,306 @@
+//! WebAuthn (FIDO2) parsing and verification helpers.
+//!
+//! Covers the parts of the WebAuthn Level 2 relying party ceremonies that do
+//! not touch storage: decoding `clientDataJSON`, authenticator data and the
+//! CBOR attestation object, and verifying ES256 (COSE algorithm -7) signatures.
+//! Only `none` and self `packed` attestation are accepted, matching the
+//! `attestation: "none"` preference sent in registration options.
+
+use crate::domains::common::errors::AppError;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use ciborium::Value;
+use p256::ecdsa::signature::Verifier;
+use p256::ecdsa::{Signature, VerifyingKey};
+use p256::EncodedPoint;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+/// User Present flag in authenticator data.
+pub const FLAG_USER_PRESENT: u8 = 0x01;
+
+/// User Verified flag in authenticator data.
+pub const FLAG_USER_VERIFIED: u8 = 0x04;
+
+/// Attested credential data included flag in authenticator data.
+pub const FLAG_ATTESTED_CREDENTIAL: u8 = 0x40;
+
+/// COSE algorithm identifier for ECDSA P-256 with SHA-256.
+pub const COSE_ALG_ES256: i64 = -7;
+
+// COSE key map labels and values (RFC 9052 / RFC 9053)
+const COSE_KEY_KTY: i128 = 1;
+const COSE_KEY_ALG: i128 = 3;
+const COSE_KEY_CRV: i128 = -1;
+const COSE_KEY_X: i128 = -2;
+const COSE_KEY_Y: i128 = -3;
+const COSE_KTY_EC2: i128 = 2;
+const COSE_CRV_P256: i128 = 1;
+
+fn invalid(message: &str) -> AppError {
+    AppError::BadRequest(format!("Invalid WebAuthn response: {}", message))
+}
+
+/// Encodes bytes as unpadded base64url, the encoding used by the WebAuthn JSON API.
+pub fn base64url_encode(data: &[u8]) -> String {
+    URL_SAFE_NO_PAD.encode(data)
+}
+
+/// Decodes unpadded base64url, tolerating trailing padding.
+pub fn base64url_decode(input: &str) -> Result<Vec<u8>, AppError> {
+    URL_SAFE_NO_PAD
+        .decode(input.trim_end_matches('='))
+        .map_err(|_| invalid("malformed base64url"))
+}
+
+/// Generates a random 32-byte challenge encoded as base64url.
+pub fn generate_challenge() -> String {
+    let bytes: [u8; 32] = rand::random();
+    base64url_encode(&bytes)
+}
+
+/// Returns the SHA-256 hash of the relying party ID.
+pub fn rp_id_hash(rp_id: &str) -> [u8; 32] {
+    Sha256::digest(rp_id.as_bytes()).into()
+}
+
+/// Returns the SHA-256 hash of the raw `clientDataJSON` bytes.
+pub fn client_data_hash(client_data_json: &[u8]) -> [u8; 32] {
+    Sha256::digest(client_data_json).into()
+}
+
+/// Collected client data sent by the browser with every ceremony.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ClientData {
+    #[serde(rename = "type")]
+    pub ceremony_type: String,
+    pub challenge: String,
+    pub origin: String,
+}
+
+/// Parses `clientDataJSON`.
+pub fn parse_client_data(client_data_json: &[u8]) -> Result<ClientData, AppError> {
+    serde_json::from_slice(client_data_json).map_err(|_| invalid("malformed clientDataJSON"))
+}
+
+/// Credential data attested during registration.
+#[derive(Debug, Clone)]
+pub struct AttestedCredential {
+    pub aaguid: [u8; 16],
+    pub credential_id: Vec<u8>,
+    /// COSE-encoded credential public key.
+    pub public_key: Vec<u8>,
+}
+
+/// Decoded authenticator data.
+#[derive(Debug, Clone)]
+pub struct AuthenticatorData {
+    pub rp_id_hash: [u8; 32],
+    pub flags: u8,
+    pub sign_count: u32,
+    pub attested_credential: Option<AttestedCredential>,
+}
+
+impl AuthenticatorData {
+    /// Returns true if the user was present (touched the authenticator).
+    pub fn user_present(&self) -> bool {
+        self.flags & FLAG_USER_PRESENT != 0
+    }
+
+    /// Returns true if the authenticator verified the user (PIN, biometrics).
+    pub fn user_verified(&self) -> bool {
+        self.flags & FLAG_USER_VERIFIED != 0
+    }
+}
+
+/// Parses the binary authenticator data structure.
+pub fn parse_authenticator_data(data: &[u8]) -> Result<AuthenticatorData, AppError> {
+    if data.len() < 37 {
+        return Err(invalid("authenticator data too short"));
+    }
+
+    let mut rp_id_hash = [0u8; 32];
+    rp_id_hash.copy_from_slice(&data[..32]);
+    let flags = data[32];
+    let sign_count = u32::from_be_bytes([data[33], data[34], data[35], data[36]]);
+
+    let attested_credential = if flags & FLAG_ATTESTED_CREDENTIAL != 0 {
+        let rest = &data[37..];
+        if rest.len() < 18 {
+            return Err(invalid("attested credential data too short"));
+        }
+
+        let mut aaguid = [0u8; 16];
+        aaguid.copy_from_slice(&rest[..16]);
+        let id_len = u16::from_be_bytes([rest[16], rest[17]]) as usize;
+        let rest = &rest[18..];
+        if rest.len() < id_len {
+            return Err(invalid("credential ID truncated"));
+        }
+        let credential_id = rest[..id_len].to_vec();
+
+        // The public key is a CBOR map of unknown length; decode it to find its end
+        let key_bytes = &rest[id_len..];
+        let mut reader = key_bytes;
+        let _: Value =
+            ciborium::from_reader(&mut reader).map_err(|_| invalid("malformed public key"))?;
+        let consumed = key_bytes.len() - reader.len();
+
+        Some(AttestedCredential {
+            aaguid,
+            credential_id,
+            public_key: key_bytes[..consumed].to_vec(),
+        })
+    } else {
+        None
+    };
+
+    Ok(AuthenticatorData {
+        rp_id_hash,
+        flags,
+        sign_count,
+        attested_credential,
+    })
+}
+
+/// Decoded attestation object returned by `navigator.credentials.create()`.
+#[derive(Debug, Clone)]
+pub struct AttestationObject {
+    pub fmt: String,
+    pub att_stmt: Vec<(Value, Value)>,
+    pub auth_data: Vec<u8>,
+}
+
+/// Parses the CBOR attestation object.
+pub fn parse_attestation_object(data: &[u8]) -> Result<AttestationObject, AppError> {
+    let value: Value =
+        ciborium::from_reader(data).map_err(|_| invalid("malformed attestation object"))?;
+    let map = value
+        .into_map()
+        .map_err(|_| invalid("attestation object is not a map"))?;
+
+    let mut fmt = None;
+    let mut att_stmt = None;
+    let mut auth_data = None;
+    for (key, value) in map {
+        match key.as_text() {
+            Some("fmt") => fmt = value.into_text().ok(),
+            Some("attStmt") => att_stmt = value.into_map().ok(),
+            Some("authData") => auth_data = value.into_bytes().ok(),
+            _ => {}
+        }
+    }
+
+    Ok(AttestationObject {
+        fmt: fmt.ok_or_else(|| invalid("missing fmt"))?,
+        att_stmt: att_stmt.ok_or_else(|| invalid("missing attStmt"))?,
+        auth_data: auth_data.ok_or_else(|| invalid("missing authData"))?,
+    })
+}
+
+fn map_get<'a>(map: &'a [(Value, Value)], label: &str) -> Option<&'a Value> {
+    map.iter()
+        .find(|(k, _)| k.as_text() == Some(label))
+        .map(|(_, v)| v)
+}
+
+/// Verifies the attestation statement of a registration response.
+///
+/// Accepts `none` (empty statement) and self `packed` attestation (no
+/// certificate chain, signed with the credential key itself).
+///
+/// # Errors
+///
+/// Returns `AppError::BadRequest` for unsupported formats or invalid signatures.
+pub fn verify_attestation(
+    attestation: &AttestationObject,
+    client_data_hash: &[u8],
+    credential_public_key: &[u8],
+) -> Result<(), AppError> {
+    match attestation.fmt.as_str() {
+        "none" => {
+            if !attestation.att_stmt.is_empty() {
+                return Err(invalid("unexpected attestation statement"));
+            }
+            Ok(())
+        }
+        "packed" => {
+            if map_get(&attestation.att_stmt, "x5c").is_some() {
+                return Err(invalid("certificate attestation is not supported"));
+            }
+
+            let alg = map_get(&attestation.att_stmt, "alg")
+                .and_then(|v| v.as_integer())
+                .map(i128::from);
+            if alg != Some(COSE_ALG_ES256 as i128) {
+                return Err(invalid("unsupported attestation algorithm"));
+            }
+
+            let sig = map_get(&attestation.att_stmt, "sig")
+                .and_then(|v| v.as_bytes())
+                .ok_or_else(|| invalid("missing attestation signature"))?;
+
+            let mut signed = attestation.auth_data.clone();
+            signed.extend_from_slice(client_data_hash);
+            if !verify_signature(credential_public_key, &signed, sig)? {
+                return Err(invalid("attestation signature mismatch"));
+            }
+            Ok(())
+        }
+        _ => Err(invalid("unsupported attestation format")),
+    }
+}
+
+/// Decodes a COSE EC2 P-256 public key with algorithm ES256.
+pub fn parse_es256_public_key(cose_key: &[u8]) -> Result<VerifyingKey, AppError> {
+    let value: Value =
+        ciborium::from_reader(cose_key).map_err(|_| invalid("malformed COSE key"))?;
+    let map = value
+        .as_map()
+        .ok_or_else(|| invalid("COSE key is not a map"))?;
+
+    let get = |label: i128| {
+        map.iter()
+            .find(|(k, _)| k.as_integer().map(i128::from) == Some(label))
+            .map(|(_, v)| v)
+    };
+    let get_int = |label: i128| get(label).and_then(|v| v.as_integer()).map(i128::from);
+
+    if get_int(COSE_KEY_KTY) != Some(COSE_KTY_EC2)
+        || get_int(COSE_KEY_ALG) != Some(COSE_ALG_ES256 as i128)
+        || get_int(COSE_KEY_CRV) != Some(COSE_CRV_P256)
+    {
+        return Err(invalid("only ES256 (P-256) credentials are supported"));
+    }
+
+    let x = get(COSE_KEY_X).and_then(|v| v.as_bytes());
+    let y = get(COSE_KEY_Y).and_then(|v| v.as_bytes());
+    let (Some(x), Some(y)) = (x, y) else {
+        return Err(invalid("COSE key coordinates missing"));
+    };
+    if x.len() != 32 || y.len() != 32 {
+        return Err(invalid("COSE key coordinates have the wrong length"));
+    }
+
+    let point =
+        EncodedPoint::from_affine_coordinates(x.as_slice().into(), y.as_slice().into(), false);
+    VerifyingKey::from_encoded_point(&point).map_err(|_| invalid("COSE key is not on the curve"))
+}
+
+/// Verifies a DER-encoded ES256 signature over `message` with a COSE public key.
+///
+/// # Returns
+///
+/// Returns `Ok(false)` if the signature does not match.
+pub fn verify_signature(
+    cose_public_key: &[u8],
+    message: &[u8],
+    signature_der: &[u8],
+) -> Result<bool, AppError> {
+    let key = parse_es256_public_key(cose_public_key)?;
+    let Ok(signature) = Signature::from_der(signature_der) else {
+        return Ok(false);
+    };
+
+    Ok(key.verify(message, &signature).is_ok())
+}
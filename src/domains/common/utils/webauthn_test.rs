@@ -0,0 +1,216 @@
+use super::webauthn::*;
+use ciborium::Value;
+use p256::ecdsa::signature::Signer;
+use p256::ecdsa::{Signature, SigningKey};
+
+/// Minimal software authenticator producing ES256 credentials, so WebAuthn
+/// ceremonies can be exercised in tests without hardware.
+pub(crate) struct SoftwareAuthenticator {
+    signing_key: SigningKey,
+    pub credential_id: Vec<u8>,
+    pub sign_count: u32,
+}
+
+impl SoftwareAuthenticator {
+    pub(crate) fn new() -> Self {
+        let signing_key = loop {
+            if let Ok(key) = SigningKey::from_slice(&rand::random::<[u8; 32]>()) {
+                break key;
+            }
+        };
+
+        Self {
+            signing_key,
+            credential_id: rand::random::<[u8; 16]>().to_vec(),
+            sign_count: 0,
+        }
+    }
+
+    pub(crate) fn cose_public_key(&self) -> Vec<u8> {
+        let point = self.signing_key.verifying_key().to_encoded_point(false);
+        let key = Value::Map(vec![
+            (Value::from(1), Value::from(2)),
+            (Value::from(3), Value::from(COSE_ALG_ES256)),
+            (Value::from(-1), Value::from(1)),
+            (Value::from(-2), Value::Bytes(point.x().unwrap().to_vec())),
+            (Value::from(-3), Value::Bytes(point.y().unwrap().to_vec())),
+        ]);
+        let mut out = Vec::new();
+        ciborium::into_writer(&key, &mut out).unwrap();
+        out
+    }
+
+    pub(crate) fn client_data(ceremony_type: &str, challenge: &str, origin: &str) -> Vec<u8> {
+        serde_json::json!({
+            "type": ceremony_type,
+            "challenge": challenge,
+            "origin": origin,
+            "crossOrigin": false
+        })
+        .to_string()
+        .into_bytes()
+    }
+
+    fn authenticator_data(&self, rp_id: &str, flags: u8, attested: bool) -> Vec<u8> {
+        let mut data = rp_id_hash(rp_id).to_vec();
+        data.push(flags);
+        data.extend_from_slice(&self.sign_count.to_be_bytes());
+        if attested {
+            data.extend_from_slice(&[0u8; 16]);
+            data.extend_from_slice(&(self.credential_id.len() as u16).to_be_bytes());
+            data.extend_from_slice(&self.credential_id);
+            data.extend_from_slice(&self.cose_public_key());
+        }
+        data
+    }
+
+    fn sign(&self, message: &[u8]) -> Vec<u8> {
+        let signature: Signature = self.signing_key.sign(message);
+        signature.to_der().as_bytes().to_vec()
+    }
+
+    /// Performs a registration ceremony and returns `(clientDataJSON, attestationObject)`.
+    pub(crate) fn make_credential(
+        &self,
+        rp_id: &str,
+        origin: &str,
+        challenge: &str,
+        packed: bool,
+    ) -> (Vec<u8>, Vec<u8>) {
+        let client_data = Self::client_data("webauthn.create", challenge, origin);
+        let flags = FLAG_USER_PRESENT | FLAG_USER_VERIFIED | FLAG_ATTESTED_CREDENTIAL;
+        let auth_data = self.authenticator_data(rp_id, flags, true);
+
+        let (fmt, att_stmt) = if packed {
+            let mut signed = auth_data.clone();
+            signed.extend_from_slice(&client_data_hash(&client_data));
+            (
+                "packed",
+                vec![
+                    (Value::from("alg"), Value::from(COSE_ALG_ES256)),
+                    (Value::from("sig"), Value::Bytes(self.sign(&signed))),
+                ],
+            )
+        } else {
+            ("none", vec![])
+        };
+
+        let object = Value::Map(vec![
+            (Value::from("fmt"), Value::from(fmt)),
+            (Value::from("attStmt"), Value::Map(att_stmt)),
+            (Value::from("authData"), Value::Bytes(auth_data)),
+        ]);
+        let mut out = Vec::new();
+        ciborium::into_writer(&object, &mut out).unwrap();
+
+        (client_data, out)
+    }
+
+    /// Performs an authentication ceremony and returns
+    /// `(clientDataJSON, authenticatorData, signature)`.
+    pub(crate) fn get_assertion(
+        &mut self,
+        rp_id: &str,
+        origin: &str,
+        challenge: &str,
+    ) -> (Vec<u8>, Vec<u8>, Vec<u8>) {
+        self.sign_count += 1;
+        let client_data = Self::client_data("webauthn.get", challenge, origin);
+        let auth_data =
+            self.authenticator_data(rp_id, FLAG_USER_PRESENT | FLAG_USER_VERIFIED, false);
+
+        let mut signed = auth_data.clone();
+        signed.extend_from_slice(&client_data_hash(&client_data));
+        let signature = self.sign(&signed);
+
+        (client_data, auth_data, signature)
+    }
+}
+
+#[test]
+fn test_base64url_round_trip() {
+    let data = [0xfbu8, 0xff, 0x00, 0x10];
+    let encoded = base64url_encode(&data);
+    assert_eq!(encoded, "-_8AEA");
+    assert_eq!(base64url_decode(&encoded).unwrap(), data);
+    assert_eq!(base64url_decode("-_8AEA==").unwrap(), data);
+    assert!(base64url_decode("not base64!").is_err());
+}
+
+#[test]
+fn test_parse_registration_with_none_attestation() {
+    let authenticator = SoftwareAuthenticator::new();
+    let challenge = generate_challenge();
+    let (client_data_json, attestation_object) =
+        authenticator.make_credential("example.com", "https://example.com", &challenge, false);
+
+    let client_data = parse_client_data(&client_data_json).unwrap();
+    assert_eq!(client_data.ceremony_type, "webauthn.create");
+    assert_eq!(client_data.challenge, challenge);
+
+    let attestation = parse_attestation_object(&attestation_object).unwrap();
+    assert_eq!(attestation.fmt, "none");
+
+    let auth_data = parse_authenticator_data(&attestation.auth_data).unwrap();
+    assert_eq!(auth_data.rp_id_hash, rp_id_hash("example.com"));
+    assert!(auth_data.user_present());
+    assert!(auth_data.user_verified());
+
+    let credential = auth_data.attested_credential.unwrap();
+    assert_eq!(credential.credential_id, authenticator.credential_id);
+    assert_eq!(credential.public_key, authenticator.cose_public_key());
+
+    verify_attestation(
+        &attestation,
+        &client_data_hash(&client_data_json),
+        &credential.public_key,
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_packed_self_attestation_is_verified() {
+    let authenticator = SoftwareAuthenticator::new();
+    let (client_data_json, attestation_object) =
+        authenticator.make_credential("example.com", "https://example.com", "abc", true);
+
+    let attestation = parse_attestation_object(&attestation_object).unwrap();
+    let credential = parse_authenticator_data(&attestation.auth_data)
+        .unwrap()
+        .attested_credential
+        .unwrap();
+    verify_attestation(
+        &attestation,
+        &client_data_hash(&client_data_json),
+        &credential.public_key,
+    )
+    .unwrap();
+
+    // A different client data hash invalidates the attestation signature
+    assert!(verify_attestation(&attestation, &[0u8; 32], &credential.public_key).is_err());
+}
+
+#[test]
+fn test_assertion_signature_verification() {
+    let mut authenticator = SoftwareAuthenticator::new();
+    let (client_data_json, auth_data, signature) =
+        authenticator.get_assertion("example.com", "https://example.com", "abc");
+
+    let parsed = parse_authenticator_data(&auth_data).unwrap();
+    assert_eq!(parsed.sign_count, 1);
+    assert!(parsed.attested_credential.is_none());
+
+    let mut signed = auth_data.clone();
+    signed.extend_from_slice(&client_data_hash(&client_data_json));
+    let public_key = authenticator.cose_public_key();
+    assert!(verify_signature(&public_key, &signed, &signature).unwrap());
+
+    signed[0] ^= 0xff;
+    assert!(!verify_signature(&public_key, &signed, &signature).unwrap());
+    assert!(!verify_signature(&public_key, &signed, b"garbage").unwrap());
+}
+
+#[test]
+fn test_rejects_truncated_authenticator_data() {
+    assert!(parse_authenticator_data(&[0u8; 10]).is_err());
+}
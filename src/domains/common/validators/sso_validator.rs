@@ -63,10 +63,52 @@ pub fn validate_sso_params(
     Ok(())
 }
 
-/// Validates redirect_uri against the allowed origins whitelist.
+/// Validates redirect_uri against the redirect URIs of registered clients.
 ///
 /// This prevents Open Redirect attacks by ensuring the redirect destination
-/// is explicitly whitelisted. Extracts origin (protocol + host) and performs exact match.
+/// is explicitly registered. The URI without its query must equal one of
+/// `registered_uris` exactly, so another path on the same origin is rejected.
+///
+/// # Arguments
+/// * `redirect_uri` - The redirect URI to validate (optional)
+/// * `registered_uris` - Redirect URIs of the client registrations
+///
+/// # Returns
+/// * `Ok(())` if redirect_uri is None or matches a registered URI
+/// * `Err(AppError::Forbidden)` if redirect_uri is not registered
+pub fn validate_redirect_uri_whitelist(
+    redirect_uri: &Option<String>,
+    registered_uris: &[String],
+) -> Result<(), AppError> {
+    if let Some(uri) = redirect_uri {
+        let parsed = url::Url::parse(uri)
+            .map_err(|_| AppError::Forbidden("Invalid redirect URI format".to_string()))?;
+
+        if parsed.fragment().is_some()
+            || !registered_uris.contains(&normalize_redirect_uri(&parsed))
+        {
+            log::warn!(
+                "[SSO Security] Blocked redirect_uri not registered for the client: {}",
+                uri
+            );
+            return Err(AppError::Forbidden(
+                "Redirect URI is not registered".to_string(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Returns the URI without query and fragment, in the normalized form used
+/// to store and compare registered redirect URIs.
+pub fn normalize_redirect_uri(uri: &url::Url) -> String {
+    uri[..url::Position::AfterPath].to_string()
+}
+
+/// Validates redirect_uri against the global allowed origins (`VITE_ALLOWED_ORIGINS`).
+///
+/// Fallback for tenants without registered clients. Extracts origin
+/// (protocol + host) and performs exact match.
 ///
 /// # Arguments
 /// * `redirect_uri` - The redirect URI to validate (optional)
@@ -75,7 +117,7 @@ pub fn validate_sso_params(
 /// # Returns
 /// * `Ok(())` if redirect_uri is None or matches an allowed origin
 /// * `Err(AppError::Forbidden)` if redirect_uri is not whitelisted
-pub fn validate_redirect_uri_whitelist(
+pub fn validate_redirect_uri_origin(
     redirect_uri: &Option<String>,
     allowed_origins: &[String],
 ) -> Result<(), AppError> {
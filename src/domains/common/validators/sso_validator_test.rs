@@ -57,50 +57,86 @@ fn test_redirect_uri_injection() {
     assert!(matches!(result, Err(AppError::ValidationError(..))));
 }
 
-// Tests for validate_redirect_uri_whitelist
+// Tests for validate_redirect_uri_origin
 #[test]
-fn test_whitelist_valid_origin() {
+fn test_origin_valid() {
     let allowed = vec![
         "https://app.example.com".to_string(),
         "https://localhost:3000".to_string(),
     ];
     let uri = Some("https://app.example.com/callback".to_string());
-    assert!(validate_redirect_uri_whitelist(&uri, &allowed).is_ok());
+    assert!(validate_redirect_uri_origin(&uri, &allowed).is_ok());
 }
 
 #[test]
-fn test_whitelist_none_uri() {
+fn test_origin_none_uri() {
     let allowed = vec!["https://app.example.com".to_string()];
-    assert!(validate_redirect_uri_whitelist(&None, &allowed).is_ok());
+    assert!(validate_redirect_uri_origin(&None, &allowed).is_ok());
 }
 
 #[test]
-fn test_whitelist_invalid_origin() {
+fn test_origin_invalid() {
     let allowed = vec!["https://app.example.com".to_string()];
     let uri = Some("https://evil.com/callback".to_string());
-    let result = validate_redirect_uri_whitelist(&uri, &allowed);
+    let result = validate_redirect_uri_origin(&uri, &allowed);
     assert!(matches!(result, Err(AppError::Forbidden(..))));
 }
 
 #[test]
-fn test_whitelist_invalid_url_format() {
+fn test_origin_invalid_url_format() {
     let allowed = vec!["https://app.example.com".to_string()];
     let uri = Some("not-a-valid-url".to_string());
-    let result = validate_redirect_uri_whitelist(&uri, &allowed);
+    let result = validate_redirect_uri_origin(&uri, &allowed);
     assert!(matches!(result, Err(AppError::Forbidden(..))));
 }
 
 #[test]
-fn test_whitelist_origin_with_port() {
+fn test_origin_with_port() {
     let allowed = vec!["http://localhost:3000".to_string()];
     let uri = Some("http://localhost:3000/auth/callback".to_string());
-    assert!(validate_redirect_uri_whitelist(&uri, &allowed).is_ok());
+    assert!(validate_redirect_uri_origin(&uri, &allowed).is_ok());
 }
 
 #[test]
-fn test_whitelist_different_port_rejected() {
+fn test_origin_different_port_rejected() {
     let allowed = vec!["http://localhost:3000".to_string()];
     let uri = Some("http://localhost:4000/auth/callback".to_string());
-    let result = validate_redirect_uri_whitelist(&uri, &allowed);
+    let result = validate_redirect_uri_origin(&uri, &allowed);
+    assert!(matches!(result, Err(AppError::Forbidden(..))));
+}
+
+// Tests for validate_redirect_uri_whitelist
+#[test]
+fn test_whitelist_registered_uri() {
+    let registered = vec!["https://app.example.com/callback".to_string()];
+    let uri = Some("https://app.example.com/callback".to_string());
+    assert!(validate_redirect_uri_whitelist(&uri, &registered).is_ok());
+}
+
+#[test]
+fn test_whitelist_ignores_query() {
+    let registered = vec!["https://app.example.com/callback".to_string()];
+    let uri = Some("https://app.example.com/callback?lang=en".to_string());
+    assert!(validate_redirect_uri_whitelist(&uri, &registered).is_ok());
+}
+
+#[test]
+fn test_whitelist_none_uri() {
+    assert!(validate_redirect_uri_whitelist(&None, &[]).is_ok());
+}
+
+#[test]
+fn test_whitelist_other_path_rejected() {
+    let registered = vec!["https://app.example.com/callback".to_string()];
+    let uri = Some("https://app.example.com/admin".to_string());
+    let result = validate_redirect_uri_whitelist(&uri, &registered);
+    assert!(matches!(result, Err(AppError::Forbidden(..))));
+}
+
+#[test]
+fn test_whitelist_fragment_rejected() {
+    let registered = vec!["https://app.example.com/callback".to_string()];
+    let uri = Some("https://app.example.com/callback#token".to_string());
+    let result = validate_redirect_uri_whitelist(&uri, &registered);
     assert!(matches!(result, Err(AppError::Forbidden(..))));
 }
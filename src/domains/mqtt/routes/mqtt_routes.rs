@@ -2,6 +2,7 @@ use actix_web::web;
 
 use crate::domains::mqtt::controllers::mqtt_controller::MqttController;
 use crate::domains::common::middlewares::api_key_middleware::ApiKeyMiddleware;
+use crate::domains::common::middlewares::rate_limit_middleware::RateLimitMiddleware;
 
 pub fn configure_routes<T>(cfg: &mut web::ServiceConfig) {
     cfg.service(
@@ -13,6 +14,7 @@ pub fn configure_routes<T>(cfg: &mut web::ServiceConfig) {
         web::resource("/check") // check likely needs api key too if it's external, or maybe internal secret?
         // EMQX usually uses HTTP request, so we should protect it.
         // Contract scenario "Missing API Key" implies authentication is required.
+            .wrap(RateLimitMiddleware::mqtt_check())
             .wrap(ApiKeyMiddleware)
             .route(web::post().to(MqttController::check)),
     )
@@ -6,9 +6,11 @@ use crate::domains::mqtt::dtos::mqtt_dto::{
     CheckMqttUserRequest, CreateMqttUserRequest, MqttAclRequest, MqttUserResponse,
 };
 use crate::domains::mqtt::repositories::mqtt_repository::MqttRepositoryTrait;
+use crate::domains::user::repositories::user_repository::UserRepositoryTrait;
 
 pub struct MqttUseCase {
     repo: Arc<dyn MqttRepositoryTrait>,
+    user_repository: Arc<dyn UserRepositoryTrait>,
 }
 
 pub enum MqttAuthResult {
@@ -24,8 +26,14 @@ pub enum MqttAclResult {
 }
 
 impl MqttUseCase {
-    pub fn new(repo: Arc<dyn MqttRepositoryTrait>) -> Self {
-        Self { repo }
+    pub fn new(
+        repo: Arc<dyn MqttRepositoryTrait>,
+        user_repository: Arc<dyn UserRepositoryTrait>,
+    ) -> Self {
+        Self {
+            repo,
+            user_repository,
+        }
     }
 
     pub async fn create_user(
@@ -150,6 +158,13 @@ impl MqttUseCase {
 
         match user_opt {
             Some(user) => {
+                // Suspended, banned or pending app users may not connect either
+                if let Some(app_user) = self.user_repository.find_by_username(&username).await? {
+                    if !app_user.is_active() {
+                        return Ok(MqttAuthResult::Deny);
+                    }
+                }
+
                 let valid = password::verify_password(&password, &user.password)
                     .map_err(|e| AppError::InternalError(e.to_string()))?;
 
@@ -0,0 +1,122 @@
+use crate::domains::common::dtos::response_dto::SuccessResponseDTO;
+use crate::domains::common::errors::AppError;
+use crate::domains::common::utils::jwt::Claims;
+use crate::domains::tenant::dtos::client_dto::{CreateClientRequest, UpdateClientRequest};
+use crate::domains::tenant::middlewares::api_key_middleware::TenantId;
+use crate::domains::tenant::usecases::client_usecase::ClientUseCase;
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse, Responder};
+use serde_json::json;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Returns the tenant resolved from the API key and the caller's role in it.
+///
+/// The access token must have been issued for that same tenant.
+fn tenant_context(req: &HttpRequest) -> Result<(Uuid, String), AppError> {
+    let tenant_id = req
+        .extensions()
+        .get::<TenantId>()
+        .map(|id| id.0)
+        .ok_or_else(|| {
+            AppError::Unauthorized("Tenant ID not found in request context".to_string())
+        })?;
+    let claims = req
+        .extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or_else(|| AppError::Unauthorized("Unauthorized".to_string()))?;
+
+    if claims.tenant_id != tenant_id.to_string() {
+        return Err(AppError::Forbidden("Forbidden".to_string()));
+    }
+
+    Ok((tenant_id, claims.role))
+}
+
+/// Registers an OAuth client for the tenant (admin only).
+///
+/// # Returns
+///
+/// * `Result<impl Responder, AppError>` - 201 Created with the client and, for
+///   confidential clients, its secret
+pub async fn create_client(
+    usecase: web::Data<Arc<ClientUseCase>>,
+    body: web::Json<CreateClientRequest>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let (tenant_id, role) = tenant_context(&req)?;
+
+    let client = usecase
+        .create_client(tenant_id, &role, body.into_inner())
+        .await?;
+
+    Ok(HttpResponse::Created().json(SuccessResponseDTO::new(
+        "Client created successfully",
+        client,
+    )))
+}
+
+/// Lists the tenant's OAuth clients (admin only).
+pub async fn get_clients(
+    usecase: web::Data<Arc<ClientUseCase>>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let (tenant_id, role) = tenant_context(&req)?;
+
+    let clients = usecase.list_clients(tenant_id, &role).await?;
+
+    Ok(HttpResponse::Ok().json(SuccessResponseDTO::new(
+        "Clients retrieved successfully",
+        json!({ "clients": clients }),
+    )))
+}
+
+/// Gets one of the tenant's OAuth clients (admin only).
+pub async fn get_client(
+    usecase: web::Data<Arc<ClientUseCase>>,
+    client_id: web::Path<String>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let (tenant_id, role) = tenant_context(&req)?;
+
+    let client = usecase.get_client(tenant_id, &role, &client_id).await?;
+
+    Ok(HttpResponse::Ok().json(SuccessResponseDTO::new(
+        "Client retrieved successfully",
+        client,
+    )))
+}
+
+/// Updates one of the tenant's OAuth clients (admin only).
+pub async fn update_client(
+    usecase: web::Data<Arc<ClientUseCase>>,
+    client_id: web::Path<String>,
+    body: web::Json<UpdateClientRequest>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let (tenant_id, role) = tenant_context(&req)?;
+
+    let client = usecase
+        .update_client(tenant_id, &role, &client_id, body.into_inner())
+        .await?;
+
+    Ok(HttpResponse::Ok().json(SuccessResponseDTO::new(
+        "Client updated successfully",
+        client,
+    )))
+}
+
+/// Deletes one of the tenant's OAuth clients (admin only).
+pub async fn delete_client(
+    usecase: web::Data<Arc<ClientUseCase>>,
+    client_id: web::Path<String>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let (tenant_id, role) = tenant_context(&req)?;
+
+    usecase.delete_client(tenant_id, &role, &client_id).await?;
+
+    Ok(HttpResponse::Ok().json(SuccessResponseDTO::<()>::no_data(
+        "Client deleted successfully",
+    )))
+}
@@ -1 +1,2 @@
+pub mod client_controller;
 pub mod tenant_controller;
@@ -0,0 +1,76 @@
+use crate::domains::tenant::entities::client::Model as Client;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Request DTO for registering an OAuth client.
+#[derive(Debug, Deserialize)]
+pub struct CreateClientRequest {
+    /// Display name of the application (required)
+    pub name: String,
+
+    /// Redirect URIs the client may send users back to
+    pub redirect_uris: Option<Vec<String>>,
+
+    /// Allowed grant types: `authorization_code` (default) and/or `client_credentials`
+    pub grant_types: Option<Vec<String>>,
+
+    /// Allowed scopes (defaults to `openid profile email`). Machine clients
+    /// may use their own API scopes.
+    pub scopes: Option<Vec<String>>,
+
+    /// Public clients get no secret and rely on PKCE alone (defaults to false)
+    pub public: Option<bool>,
+}
+
+/// Request DTO for updating an OAuth client.
+///
+/// All fields are optional to allow partial updates. Lists are replaced as a whole.
+#[derive(Debug, Deserialize)]
+pub struct UpdateClientRequest {
+    pub name: Option<String>,
+    pub redirect_uris: Option<Vec<String>>,
+    pub grant_types: Option<Vec<String>>,
+    pub scopes: Option<Vec<String>>,
+}
+
+/// Response DTO for an OAuth client. The secret is never included.
+#[derive(Debug, Serialize)]
+pub struct ClientResponse {
+    pub client_id: String,
+    pub tenant_id: Uuid,
+    pub name: String,
+    pub redirect_uris: Vec<String>,
+    pub grant_types: Vec<String>,
+    pub scopes: Vec<String>,
+    pub public: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<Client> for ClientResponse {
+    fn from(client: Client) -> Self {
+        Self {
+            redirect_uris: client.redirect_uris(),
+            grant_types: client.grant_types(),
+            scopes: client.scopes(),
+            public: client.is_public(),
+            client_id: client.client_id,
+            tenant_id: client.tenant_id,
+            name: client.name,
+            created_at: client.created_at,
+            updated_at: client.updated_at,
+        }
+    }
+}
+
+/// Response DTO for a newly registered client.
+///
+/// `client_secret` is only returned here; just its hash is stored.
+#[derive(Debug, Serialize)]
+pub struct CreatedClientResponse {
+    #[serde(flatten)]
+    pub client: ClientResponse,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_secret: Option<String>,
+}
@@ -1 +1,2 @@
+pub mod client_dto;
 pub mod tenant_dto;
@@ -1,3 +1,4 @@
+use crate::domains::common::infrastructures::rate_limiter::RateLimitOverrides;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -12,6 +13,15 @@ pub struct CreateTenantRequest {
 
     /// Tenant description (optional)
     pub description: Option<String>,
+
+    /// Whether users must verify their email before logging in (defaults to false)
+    pub require_email_verification: Option<bool>,
+
+    /// Whether users can sign in with an emailed link or code (defaults to false)
+    pub passwordless_login: Option<bool>,
+
+    /// Overrides of the default rate limits, keyed by rule name (optional)
+    pub rate_limits: Option<RateLimitOverrides>,
 }
 
 /// Request DTO for updating an existing tenant.
@@ -24,6 +34,15 @@ pub struct UpdateTenantRequest {
 
     /// Updated tenant description
     pub description: Option<String>,
+
+    /// Updated email verification policy
+    pub require_email_verification: Option<bool>,
+
+    /// Updated passwordless sign-in policy
+    pub passwordless_login: Option<bool>,
+
+    /// Replaces the rate limit overrides, an empty object removes them
+    pub rate_limits: Option<RateLimitOverrides>,
 }
 
 /// Response DTO for tenant data.
@@ -35,6 +54,9 @@ pub struct TenantResponse {
     pub name: String,
     pub description: Option<String>,
     pub api_key: Option<String>,
+    pub require_email_verification: bool,
+    pub passwordless_login: bool,
+    pub rate_limits: RateLimitOverrides,
     pub deleted_at: Option<DateTime<Utc>>,
     pub is_active: bool,
     pub created_at: DateTime<Utc>,
@@ -48,6 +70,9 @@ impl From<crate::domains::tenant::entities::tenant::Model> for TenantResponse {
             name: tenant.name,
             description: tenant.description,
             api_key: tenant.api_key,
+            require_email_verification: tenant.require_email_verification,
+            passwordless_login: tenant.passwordless_login,
+            rate_limits: RateLimitOverrides::from_json(tenant.rate_limits),
             is_active: tenant.deleted_at.is_none(),
             deleted_at: tenant.deleted_at,
             created_at: tenant.created_at,
@@ -1,2 +1,3 @@
+pub mod client;
 pub mod tenant;
 pub mod user_tenant;
@@ -15,6 +15,12 @@ pub struct Model {
     pub description: Option<String>,
     #[sea_orm(unique)]
     pub api_key: Option<String>,
+    /// When set, users must verify their email address before they can log in.
+    pub require_email_verification: bool,
+    /// When set, users can sign in with an emailed link or code instead of a password.
+    pub passwordless_login: bool,
+    /// Overrides of the default rate limits, keyed by rule name (see `RateLimitOverrides`).
+    pub rate_limits: Option<Json>,
     pub deleted_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
@@ -39,6 +39,35 @@ where
         ok(ApiKeyMiddlewareService {
             service: Rc::new(service),
             api_key,
+            required: true,
+        })
+    }
+}
+
+/// Same as `ApiKeyMiddleware`, but requests without an X-API-Key header pass
+/// through without a `TenantId`, for endpoints that also accept other
+/// credentials. A key that does not match is still rejected.
+#[derive(Clone)]
+pub struct OptionalApiKeyMiddleware;
+
+impl<S, B> Transform<S, ServiceRequest> for OptionalApiKeyMiddleware
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = ApiKeyMiddlewareService<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        use crate::domains::common::utils::config::Config;
+        let api_key = Config::get().api_key.clone();
+        ok(ApiKeyMiddlewareService {
+            service: Rc::new(service),
+            api_key,
+            required: false,
         })
     }
 }
@@ -47,6 +76,7 @@ where
 pub struct ApiKeyMiddlewareService<S> {
     service: Rc<S>,
     api_key: String,
+    required: bool,
 }
 
 impl<S, B> Service<ServiceRequest> for ApiKeyMiddlewareService<S>
@@ -78,8 +108,14 @@ where
 
         let db = req.app_data::<web::Data<DatabaseConnection>>().cloned();
         let service = self.service.clone();
+        let required = self.required;
 
         Box::pin(async move {
+            if !required && api_key_value.is_empty() {
+                let res = service.call(req).await?;
+                return Ok(res.map_into_left_body());
+            }
+
             let mut resolved_tenant_id = None;
 
             if db.is_none() {
@@ -65,3 +65,27 @@ async fn test_api_key_middleware_valid_key_fails_without_db() {
     // Fails because DB is required to resolve Tenant ID
     assert_eq!(resp.status(), http::StatusCode::UNAUTHORIZED);
 }
+
+#[actix_web::test]
+async fn test_optional_api_key_middleware_passes_requests_without_key() {
+    use crate::domains::common::utils::config::Config;
+    Config::init_for_test();
+
+    let srv = test::init_service(
+        App::new()
+            .wrap(OptionalApiKeyMiddleware)
+            .route("/", web::get().to(|| async { "ok" })),
+    )
+    .await;
+
+    let req = test::TestRequest::get().uri("/").to_request();
+    let resp = test::call_service(&srv, req).await;
+    assert_eq!(resp.status(), http::StatusCode::OK);
+
+    let req = test::TestRequest::get()
+        .uri("/")
+        .insert_header(("X-API-Key", "wrong_key"))
+        .to_request();
+    let resp = test::call_service(&srv, req).await;
+    assert_eq!(resp.status(), http::StatusCode::UNAUTHORIZED);
+}
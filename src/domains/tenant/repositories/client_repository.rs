@@ -0,0 +1,110 @@
+use crate::domains::common::errors::AppError;
+use crate::domains::tenant::entities::client::{self, Entity as ClientEntity, Model as Client};
+use async_trait::async_trait;
+use sea_orm::*;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Trait defining OAuth client repository operations.
+///
+/// This trait abstracts database operations for the client applications
+/// registered by tenants.
+#[async_trait]
+pub trait ClientRepositoryTrait: Send + Sync {
+    /// Stores a new client.
+    async fn create(&self, client: Client) -> Result<Client, AppError>;
+
+    /// Finds a client by its public client_id.
+    async fn find_by_client_id(&self, client_id: &str) -> Result<Option<Client>, AppError>;
+
+    /// Lists the clients of a tenant, oldest first.
+    async fn find_by_tenant(&self, tenant_id: Uuid) -> Result<Vec<Client>, AppError>;
+
+    /// Saves the name, redirect URIs, grant types and scopes of a client.
+    async fn update(&self, client: Client) -> Result<Client, AppError>;
+
+    /// Permanently deletes a client.
+    async fn delete(&self, id: Uuid) -> Result<(), AppError>;
+}
+
+/// OAuth client repository implementation using SeaORM.
+pub struct ClientRepository {
+    db: Arc<DatabaseConnection>,
+}
+
+impl ClientRepository {
+    /// Creates a new ClientRepository instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - Arc-wrapped database connection
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl ClientRepositoryTrait for ClientRepository {
+    async fn create(&self, client: Client) -> Result<Client, AppError> {
+        let active = client::ActiveModel {
+            id: Set(client.id),
+            tenant_id: Set(client.tenant_id),
+            client_id: Set(client.client_id),
+            client_secret_hash: Set(client.client_secret_hash),
+            name: Set(client.name),
+            redirect_uris: Set(client.redirect_uris),
+            grant_types: Set(client.grant_types),
+            scopes: Set(client.scopes),
+            created_at: Set(client.created_at),
+            updated_at: Set(client.updated_at),
+        };
+
+        active
+            .insert(&*self.db)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+
+    async fn find_by_client_id(&self, client_id: &str) -> Result<Option<Client>, AppError> {
+        ClientEntity::find()
+            .filter(client::Column::ClientId.eq(client_id))
+            .one(&*self.db)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+
+    async fn find_by_tenant(&self, tenant_id: Uuid) -> Result<Vec<Client>, AppError> {
+        ClientEntity::find()
+            .filter(client::Column::TenantId.eq(tenant_id))
+            .order_by_asc(client::Column::CreatedAt)
+            .all(&*self.db)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+
+    async fn update(&self, client: Client) -> Result<Client, AppError> {
+        let active = client::ActiveModel {
+            id: Unchanged(client.id),
+            name: Set(client.name),
+            redirect_uris: Set(client.redirect_uris),
+            grant_types: Set(client.grant_types),
+            scopes: Set(client.scopes),
+            updated_at: Set(client.updated_at),
+            ..Default::default()
+        };
+
+        active
+            .update(&*self.db)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<(), AppError> {
+        ClientEntity::delete_by_id(id)
+            .exec(&*self.db)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Ok(())
+    }
+}
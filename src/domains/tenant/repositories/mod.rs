@@ -1,2 +1,3 @@
+pub mod client_repository;
 pub mod tenant_repository;
 pub mod user_tenant_repository;
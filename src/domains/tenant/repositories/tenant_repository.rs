@@ -3,6 +3,7 @@ use crate::domains::common::infrastructures::rocksdb_connection::RocksDbCache;
 use crate::domains::tenant::dtos::tenant_dto::{CreateTenantRequest, UpdateTenantRequest};
 use crate::domains::tenant::entities::tenant::{Entity as TenantEntity, Model as Tenant};
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use sea_orm::*;
 use std::sync::Arc;
 use std::time::Duration;
@@ -36,6 +37,12 @@ pub trait TenantRepositoryTrait: Send + Sync {
 
     /// Restores a soft-deleted tenant.
     async fn restore(&self, id: Uuid) -> Result<(), AppError>;
+
+    /// Permanently deletes tenants soft-deleted before `cutoff`.
+    ///
+    /// Returns the number of tenants removed. Memberships are removed by the
+    /// database through `ON DELETE CASCADE`.
+    async fn purge_deleted_before(&self, cutoff: DateTime<Utc>) -> Result<u64, AppError>;
 }
 
 /// Tenant repository implementation using SeaORM.
@@ -68,6 +75,13 @@ impl TenantRepositoryTrait for TenantRepository {
                 uuid::Uuid::new_v4().to_string().replace("-", "")
                     + &uuid::Uuid::new_v4().to_string().replace("-", ""),
             )), // 64 chars
+            require_email_verification: Set(req.require_email_verification.unwrap_or(false)),
+            passwordless_login: Set(req.passwordless_login.unwrap_or(false)),
+            rate_limits: Set(req
+                .rate_limits
+                .as_ref()
+                .filter(|limits| !limits.is_empty())
+                .map(|limits| serde_json::json!(limits))),
             created_at: Set(chrono::Utc::now()),
             updated_at: Set(chrono::Utc::now()),
             deleted_at: Set(None),
@@ -175,6 +189,17 @@ impl TenantRepositoryTrait for TenantRepository {
         if let Some(ref description) = req.description {
             tenant.description = Set(Some(description.clone()));
         }
+        if let Some(require_email_verification) = req.require_email_verification {
+            tenant.require_email_verification = Set(require_email_verification);
+        }
+        if let Some(passwordless_login) = req.passwordless_login {
+            tenant.passwordless_login = Set(passwordless_login);
+        }
+        // An empty map removes all overrides
+        if let Some(ref rate_limits) = req.rate_limits {
+            tenant.rate_limits =
+                Set((!rate_limits.is_empty()).then(|| serde_json::json!(rate_limits)));
+        }
 
         tenant.updated_at = Set(chrono::Utc::now());
 
@@ -261,4 +286,33 @@ impl TenantRepositoryTrait for TenantRepository {
 
         Ok(())
     }
+
+    async fn purge_deleted_before(&self, cutoff: DateTime<Utc>) -> Result<u64, AppError> {
+        let tenants = TenantEntity::find()
+            .filter(crate::domains::tenant::entities::tenant::Column::DeletedAt.lt(cutoff))
+            .all(&*self.db)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        if tenants.is_empty() {
+            return Ok(0);
+        }
+
+        let result = TenantEntity::delete_many()
+            .filter(
+                crate::domains::tenant::entities::tenant::Column::Id
+                    .is_in(tenants.iter().map(|t| t.id).collect::<Vec<_>>()),
+            )
+            .exec(&*self.db)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        // Invalidate cache
+        for tenant in &tenants {
+            self.cache.del(&format!("tenant:{}", tenant.id));
+            self.cache.del(&format!("tenant:name:{}", tenant.name));
+        }
+
+        Ok(result.rows_affected)
+    }
 }
@@ -1,5 +1,7 @@
 use crate::domains::auth::middlewares::auth_middleware::validator;
-use crate::domains::tenant::controllers::tenant_controller;
+use crate::domains::auth::middlewares::impersonation_middleware::DenyImpersonationMiddleware;
+use crate::domains::tenant::controllers::{client_controller, tenant_controller};
+use crate::domains::tenant::middlewares::api_key_middleware::ApiKeyMiddleware;
 use crate::domains::tenant::middlewares::tenant_secret_middleware::TenantSecretMiddleware;
 use actix_web::{guard, web};
 use actix_web_httpauth::middleware::HttpAuthentication;
@@ -9,6 +11,14 @@ use actix_web_httpauth::middleware::HttpAuthentication;
 /// create_tenant supports dual authentication (JWT or TENANT_SECRET_KEY).
 /// Other tenant routes require JWT authentication.
 ///
+/// OAuth clients are managed under `/clients` by admins of the tenant
+/// resolved from the API key (JWT + ApiKey, impersonation tokens refused):
+/// - `POST /clients`
+/// - `GET /clients`
+/// - `GET /clients/{client_id}`
+/// - `PUT /clients/{client_id}`
+/// - `DELETE /clients/{client_id}`
+///
 /// # Arguments
 ///
 /// * `cfg` - Service configuration
@@ -37,4 +47,22 @@ pub fn configure_tenant_routes(cfg: &mut web::ServiceConfig) {
                     .route("/{id}", web::delete().to(tenant_controller::delete_tenant)),
             ),
     );
+
+    cfg.service(
+        web::scope("/clients")
+            .wrap(DenyImpersonationMiddleware)
+            .wrap(HttpAuthentication::bearer(validator))
+            .wrap(ApiKeyMiddleware)
+            .route("", web::post().to(client_controller::create_client))
+            .route("", web::get().to(client_controller::get_clients))
+            .route("/{client_id}", web::get().to(client_controller::get_client))
+            .route(
+                "/{client_id}",
+                web::put().to(client_controller::update_client),
+            )
+            .route(
+                "/{client_id}",
+                web::delete().to(client_controller::delete_client),
+            ),
+    );
 }
@@ -0,0 +1,389 @@
+use crate::domains::auth::usecases::oidc_usecase::SUPPORTED_SCOPES;
+use crate::domains::common::errors::{AppError, ValidationDetail};
+use crate::domains::common::utils::request_helper;
+use crate::domains::common::validators::sso_validator::{
+    normalize_redirect_uri, validate_redirect_uri_origin, validate_redirect_uri_whitelist,
+};
+use crate::domains::tenant::dtos::client_dto::{
+    ClientResponse, CreateClientRequest, CreatedClientResponse, UpdateClientRequest,
+};
+use crate::domains::tenant::entities::client::{
+    Model as Client, GRANT_TYPE_AUTHORIZATION_CODE, GRANT_TYPE_CLIENT_CREDENTIALS,
+    GRANT_TYPE_TOKEN_EXCHANGE, SUPPORTED_GRANT_TYPES,
+};
+use crate::domains::tenant::repositories::client_repository::ClientRepositoryTrait;
+use chrono::Utc;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Maximum length of a redirect URI, matching the SSO parameter limit.
+const MAX_REDIRECT_URI_LENGTH: usize = 256;
+
+/// Maximum length of a single scope.
+const MAX_SCOPE_LENGTH: usize = 64;
+
+/// OAuth client use case: registration of client applications per tenant
+/// and validation of redirect URIs against those registrations.
+///
+/// Managing clients is restricted to tenant admins.
+pub struct ClientUseCase {
+    client_repo: Arc<dyn ClientRepositoryTrait>,
+}
+
+impl ClientUseCase {
+    /// Creates a new ClientUseCase instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `client_repo` - Arc-wrapped client repository
+    pub fn new(client_repo: Arc<dyn ClientRepositoryTrait>) -> Self {
+        Self { client_repo }
+    }
+
+    /// Registers a client for the tenant.
+    ///
+    /// Confidential clients get a generated secret, returned only in this
+    /// response.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Forbidden` for non-admins and
+    /// `AppError::ValidationError` for invalid fields.
+    pub async fn create_client(
+        &self,
+        tenant_id: Uuid,
+        requesting_user_role: &str,
+        req: CreateClientRequest,
+    ) -> Result<CreatedClientResponse, AppError> {
+        require_admin(requesting_user_role)?;
+
+        let name = validate_name(&req.name)?;
+        let public = req.public.unwrap_or(false);
+        let grant_types = validate_grant_types(
+            req.grant_types
+                .unwrap_or_else(|| vec![GRANT_TYPE_AUTHORIZATION_CODE.to_string()]),
+            public,
+        )?;
+        let redirect_uris =
+            validate_redirect_uris(req.redirect_uris.unwrap_or_default(), &grant_types)?;
+        let scopes = validate_scopes(
+            req.scopes
+                .unwrap_or_else(|| SUPPORTED_SCOPES.map(str::to_string).to_vec()),
+        )?;
+
+        let client_secret = (!public).then(request_helper::generate_token);
+        let now = Utc::now();
+        let client = Client {
+            id: Uuid::new_v4(),
+            tenant_id,
+            client_id: Uuid::new_v4().simple().to_string(),
+            client_secret_hash: client_secret.as_deref().map(request_helper::hash_token),
+            name,
+            redirect_uris: serde_json::json!(redirect_uris),
+            grant_types: serde_json::json!(grant_types),
+            scopes: serde_json::json!(scopes),
+            created_at: now,
+            updated_at: now,
+        };
+
+        let client = self.client_repo.create(client).await?;
+        log::info!(
+            "Registered OAuth client {} for tenant {}",
+            client.client_id,
+            tenant_id
+        );
+
+        Ok(CreatedClientResponse {
+            client: client.into(),
+            client_secret,
+        })
+    }
+
+    /// Lists the clients of the tenant.
+    pub async fn list_clients(
+        &self,
+        tenant_id: Uuid,
+        requesting_user_role: &str,
+    ) -> Result<Vec<ClientResponse>, AppError> {
+        require_admin(requesting_user_role)?;
+
+        let clients = self.client_repo.find_by_tenant(tenant_id).await?;
+        Ok(clients.into_iter().map(ClientResponse::from).collect())
+    }
+
+    /// Gets a client of the tenant.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::NotFound` if the client belongs to another tenant.
+    pub async fn get_client(
+        &self,
+        tenant_id: Uuid,
+        requesting_user_role: &str,
+        client_id: &str,
+    ) -> Result<ClientResponse, AppError> {
+        require_admin(requesting_user_role)?;
+
+        Ok(self.find_tenant_client(tenant_id, client_id).await?.into())
+    }
+
+    /// Updates the name, redirect URIs, grant types or scopes of a client.
+    ///
+    /// Whether a client is public and its secret cannot be changed.
+    pub async fn update_client(
+        &self,
+        tenant_id: Uuid,
+        requesting_user_role: &str,
+        client_id: &str,
+        req: UpdateClientRequest,
+    ) -> Result<ClientResponse, AppError> {
+        require_admin(requesting_user_role)?;
+
+        let mut client = self.find_tenant_client(tenant_id, client_id).await?;
+
+        if let Some(name) = req.name {
+            client.name = validate_name(&name)?;
+        }
+        let grant_types = match req.grant_types {
+            Some(grant_types) => validate_grant_types(grant_types, client.is_public())?,
+            None => client.grant_types(),
+        };
+        let redirect_uris = validate_redirect_uris(
+            req.redirect_uris.unwrap_or_else(|| client.redirect_uris()),
+            &grant_types,
+        )?;
+        if let Some(scopes) = req.scopes {
+            client.scopes = serde_json::json!(validate_scopes(scopes)?);
+        }
+        client.grant_types = serde_json::json!(grant_types);
+        client.redirect_uris = serde_json::json!(redirect_uris);
+        client.updated_at = Utc::now();
+
+        Ok(self.client_repo.update(client).await?.into())
+    }
+
+    /// Deletes a client. Tokens already issued to it stay valid until they expire.
+    pub async fn delete_client(
+        &self,
+        tenant_id: Uuid,
+        requesting_user_role: &str,
+        client_id: &str,
+    ) -> Result<(), AppError> {
+        require_admin(requesting_user_role)?;
+
+        let client = self.find_tenant_client(tenant_id, client_id).await?;
+        self.client_repo.delete(client.id).await?;
+
+        log::info!(
+            "Deleted OAuth client {} of tenant {}",
+            client.client_id,
+            tenant_id
+        );
+        Ok(())
+    }
+
+    /// Checks a redirect URI sent to a tenant's login or register endpoint.
+    ///
+    /// It must be registered by one of the tenant's clients. Tenants without
+    /// registered clients fall back to the global allowed origins.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Forbidden` if the redirect URI is not allowed.
+    pub async fn validate_redirect_uri(
+        &self,
+        tenant_id: Uuid,
+        redirect_uri: &Option<String>,
+        allowed_origins: &[String],
+    ) -> Result<(), AppError> {
+        if redirect_uri.is_none() {
+            return Ok(());
+        }
+
+        let clients = self.client_repo.find_by_tenant(tenant_id).await?;
+        if clients.is_empty() {
+            return validate_redirect_uri_origin(redirect_uri, allowed_origins);
+        }
+
+        let registered: Vec<String> = clients.iter().flat_map(Client::redirect_uris).collect();
+        validate_redirect_uri_whitelist(redirect_uri, &registered)
+    }
+
+    /// Checks a redirect URI against the registration of a single client.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Forbidden` for an unknown client or a redirect URI
+    /// it did not register.
+    pub async fn validate_client_redirect_uri(
+        &self,
+        client_id: &str,
+        redirect_uri: &Option<String>,
+    ) -> Result<(), AppError> {
+        let client = self
+            .client_repo
+            .find_by_client_id(client_id)
+            .await?
+            .ok_or_else(|| AppError::Forbidden("Unknown client_id".to_string()))?;
+
+        validate_redirect_uri_whitelist(redirect_uri, &client.redirect_uris())
+    }
+
+    /// Checks that the machine client a client credentials token was issued
+    /// to still exists and may use that grant.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Unauthorized` if the client was deleted or lost the grant.
+    pub async fn verify_client(
+        &self,
+        client_id: &str,
+        tenant_id: &str,
+    ) -> Result<ClientResponse, AppError> {
+        self.client_repo
+            .find_by_client_id(client_id)
+            .await?
+            .filter(|c| c.tenant_id.to_string() == tenant_id)
+            .filter(|c| c.allows_grant_type(GRANT_TYPE_CLIENT_CREDENTIALS))
+            .map(ClientResponse::from)
+            .ok_or_else(|| AppError::Unauthorized("Client no longer exists".to_string()))
+    }
+
+    async fn find_tenant_client(
+        &self,
+        tenant_id: Uuid,
+        client_id: &str,
+    ) -> Result<Client, AppError> {
+        self.client_repo
+            .find_by_client_id(client_id)
+            .await?
+            .filter(|c| c.tenant_id == tenant_id)
+            .ok_or_else(|| AppError::NotFound(format!("Client {} not found", client_id)))
+    }
+}
+
+fn require_admin(requesting_user_role: &str) -> Result<(), AppError> {
+    if requesting_user_role != "admin" {
+        return Err(AppError::Forbidden("Forbidden".to_string()));
+    }
+    Ok(())
+}
+
+fn invalid(field: &str, message: String) -> AppError {
+    AppError::ValidationError(
+        message.clone(),
+        Some(vec![ValidationDetail {
+            field: field.to_string(),
+            message,
+        }]),
+    )
+}
+
+fn validate_name(name: &str) -> Result<String, AppError> {
+    let name = name.trim();
+    if name.is_empty() || name.len() > 255 {
+        return Err(invalid(
+            "name",
+            "Name must be between 1 and 255 characters".to_string(),
+        ));
+    }
+    Ok(name.to_string())
+}
+
+/// Public clients cannot use the client credentials or token exchange grants,
+/// as they have no secret.
+fn validate_grant_types(grant_types: Vec<String>, public: bool) -> Result<Vec<String>, AppError> {
+    if grant_types.is_empty() {
+        return Err(invalid(
+            "grant_types",
+            "At least one grant type is required".to_string(),
+        ));
+    }
+
+    let mut unique = Vec::new();
+    for grant_type in grant_types {
+        if !SUPPORTED_GRANT_TYPES.contains(&grant_type.as_str()) {
+            return Err(invalid(
+                "grant_types",
+                format!(
+                    "Grant types must be one of: {}",
+                    SUPPORTED_GRANT_TYPES.join(", ")
+                ),
+            ));
+        }
+        if public
+            && (grant_type == GRANT_TYPE_CLIENT_CREDENTIALS
+                || grant_type == GRANT_TYPE_TOKEN_EXCHANGE)
+        {
+            return Err(invalid(
+                "grant_types",
+                format!("Public clients cannot use the {} grant", grant_type),
+            ));
+        }
+        if !unique.contains(&grant_type) {
+            unique.push(grant_type);
+        }
+    }
+    Ok(unique)
+}
+
+/// Redirect URIs must be absolute http(s) URIs without query or fragment.
+/// Clients using the authorization code grant need at least one.
+fn validate_redirect_uris(
+    redirect_uris: Vec<String>,
+    grant_types: &[String],
+) -> Result<Vec<String>, AppError> {
+    let mut normalized = Vec::new();
+    for uri in redirect_uris {
+        let parsed = url::Url::parse(&uri)
+            .ok()
+            .filter(|u| matches!(u.scheme(), "http" | "https") && u.has_host())
+            .filter(|u| u.query().is_none() && u.fragment().is_none())
+            .filter(|_| uri.len() <= MAX_REDIRECT_URI_LENGTH)
+            .ok_or_else(|| {
+                invalid(
+                    "redirect_uris",
+                    format!(
+                        "Invalid redirect URI {}: use an absolute http(s) URI without query or fragment",
+                        uri
+                    ),
+                )
+            })?;
+
+        let uri = normalize_redirect_uri(&parsed);
+        if !normalized.contains(&uri) {
+            normalized.push(uri);
+        }
+    }
+
+    if normalized.is_empty()
+        && grant_types
+            .iter()
+            .any(|g| g == GRANT_TYPE_AUTHORIZATION_CODE)
+    {
+        return Err(invalid(
+            "redirect_uris",
+            "The authorization_code grant requires at least one redirect URI".to_string(),
+        ));
+    }
+    Ok(normalized)
+}
+
+/// Scopes are printable ASCII tokens without spaces, quotes or backslashes (RFC 6749 section 3.3).
+fn validate_scopes(scopes: Vec<String>) -> Result<Vec<String>, AppError> {
+    let mut unique = Vec::new();
+    for scope in scopes {
+        let valid = !scope.is_empty()
+            && scope.len() <= MAX_SCOPE_LENGTH
+            && scope
+                .chars()
+                .all(|c| c.is_ascii_graphic() && c != '"' && c != '\\');
+        if !valid {
+            return Err(invalid("scopes", format!("Invalid scope: {}", scope)));
+        }
+        if !unique.contains(&scope) {
+            unique.push(scope);
+        }
+    }
+    Ok(unique)
+}
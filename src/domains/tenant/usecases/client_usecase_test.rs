@@ -0,0 +1,303 @@
+#[cfg(test)]
+mod tests {
+    use crate::domains::common::errors::AppError;
+    use crate::domains::tenant::dtos::client_dto::{CreateClientRequest, UpdateClientRequest};
+    use crate::domains::tenant::entities::client::Model as Client;
+    use crate::domains::tenant::repositories::client_repository::ClientRepositoryTrait;
+    use crate::domains::tenant::usecases::client_usecase::ClientUseCase;
+    use async_trait::async_trait;
+    use mockall::mock;
+    use std::sync::{Arc, Mutex};
+    use uuid::Uuid;
+
+    mock! {
+        pub ClientRepository {}
+        #[async_trait]
+        impl ClientRepositoryTrait for ClientRepository {
+            async fn create(&self, client: Client) -> Result<Client, AppError>;
+            async fn find_by_client_id(&self, client_id: &str) -> Result<Option<Client>, AppError>;
+            async fn find_by_tenant(&self, tenant_id: Uuid) -> Result<Vec<Client>, AppError>;
+            async fn update(&self, client: Client) -> Result<Client, AppError>;
+            async fn delete(&self, id: Uuid) -> Result<(), AppError>;
+        }
+    }
+
+    /// Mock repository backed by an in-memory client list.
+    fn repository_with(clients: Arc<Mutex<Vec<Client>>>) -> MockClientRepository {
+        let mut repo = MockClientRepository::new();
+
+        let state = clients.clone();
+        repo.expect_create().returning(move |client| {
+            state.lock().unwrap().push(client.clone());
+            Ok(client)
+        });
+
+        let state = clients.clone();
+        repo.expect_find_by_client_id().returning(move |client_id| {
+            Ok(state
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|c| c.client_id == client_id)
+                .cloned())
+        });
+
+        let state = clients.clone();
+        repo.expect_find_by_tenant().returning(move |tenant_id| {
+            Ok(state
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|c| c.tenant_id == tenant_id)
+                .cloned()
+                .collect())
+        });
+
+        let state = clients.clone();
+        repo.expect_update().returning(move |client| {
+            let mut clients = state.lock().unwrap();
+            if let Some(stored) = clients.iter_mut().find(|c| c.id == client.id) {
+                *stored = client.clone();
+            }
+            Ok(client)
+        });
+
+        let state = clients;
+        repo.expect_delete().returning(move |id| {
+            state.lock().unwrap().retain(|c| c.id != id);
+            Ok(())
+        });
+
+        repo
+    }
+
+    fn create_request(redirect_uris: &[&str]) -> CreateClientRequest {
+        CreateClientRequest {
+            name: "Dashboard".to_string(),
+            redirect_uris: Some(redirect_uris.iter().map(|u| u.to_string()).collect()),
+            grant_types: None,
+            scopes: None,
+            public: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_client_returns_secret_once() {
+        let clients = Arc::new(Mutex::new(Vec::new()));
+        let usecase = ClientUseCase::new(Arc::new(repository_with(clients.clone())));
+        let tenant_id = Uuid::new_v4();
+
+        let created = usecase
+            .create_client(
+                tenant_id,
+                "admin",
+                create_request(&["https://app.example.com/callback"]),
+            )
+            .await
+            .unwrap();
+
+        let secret = created.client_secret.unwrap();
+        let stored = clients.lock().unwrap()[0].clone();
+        assert!(!created.client.public);
+        assert_eq!(created.client.grant_types, vec!["authorization_code"]);
+        assert_eq!(created.client.scopes, vec!["openid", "profile", "email"]);
+        assert_ne!(stored.client_secret_hash.as_deref(), Some(secret.as_str()));
+        assert!(stored.verify_secret(&secret));
+        assert!(!stored.verify_secret("wrong"));
+    }
+
+    #[tokio::test]
+    async fn test_create_public_client_has_no_secret() {
+        let clients = Arc::new(Mutex::new(Vec::new()));
+        let usecase = ClientUseCase::new(Arc::new(repository_with(clients)));
+        let mut req = create_request(&["http://localhost:3000/callback"]);
+        req.public = Some(true);
+
+        let created = usecase
+            .create_client(Uuid::new_v4(), "admin", req)
+            .await
+            .unwrap();
+
+        assert!(created.client.public);
+        assert!(created.client_secret.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_machine_client_must_be_confidential() {
+        let clients = Arc::new(Mutex::new(Vec::new()));
+        let usecase = ClientUseCase::new(Arc::new(repository_with(clients)));
+        let tenant_id = Uuid::new_v4();
+        let machine_request = || CreateClientRequest {
+            name: "Reporting Service".to_string(),
+            redirect_uris: None,
+            grant_types: Some(vec!["client_credentials".to_string()]),
+            scopes: Some(vec!["reports:read".to_string()]),
+            public: None,
+        };
+
+        let mut req = machine_request();
+        req.public = Some(true);
+        let result = usecase.create_client(tenant_id, "admin", req).await;
+        assert!(matches!(result, Err(AppError::ValidationError(..))));
+
+        let mut req = machine_request();
+        req.grant_types = Some(vec![
+            "urn:ietf:params:oauth:grant-type:token-exchange".to_string()
+        ]);
+        req.public = Some(true);
+        let result = usecase.create_client(tenant_id, "admin", req).await;
+        assert!(matches!(result, Err(AppError::ValidationError(..))));
+
+        let created = usecase
+            .create_client(tenant_id, "admin", machine_request())
+            .await
+            .unwrap();
+        assert!(created.client_secret.is_some());
+        assert!(created.client.redirect_uris.is_empty());
+
+        let verified = usecase
+            .verify_client(&created.client.client_id, &tenant_id.to_string())
+            .await
+            .unwrap();
+        assert_eq!(verified.client_id, created.client.client_id);
+        let result = usecase
+            .verify_client(&created.client.client_id, &Uuid::new_v4().to_string())
+            .await;
+        assert!(matches!(result, Err(AppError::Unauthorized(_))));
+    }
+
+    #[tokio::test]
+    async fn test_create_client_requires_admin() {
+        let usecase = ClientUseCase::new(Arc::new(MockClientRepository::new()));
+
+        let result = usecase
+            .create_client(
+                Uuid::new_v4(),
+                "user",
+                create_request(&["https://app.example.com/callback"]),
+            )
+            .await;
+
+        assert!(matches!(result, Err(AppError::Forbidden(_))));
+    }
+
+    #[tokio::test]
+    async fn test_create_client_validates_redirect_uris() {
+        let usecase = ClientUseCase::new(Arc::new(MockClientRepository::new()));
+        let tenant_id = Uuid::new_v4();
+
+        for uris in [
+            vec![],
+            vec!["/callback"],
+            vec!["javascript:alert(1)"],
+            vec!["https://app.example.com/callback#token"],
+        ] {
+            let result = usecase
+                .create_client(tenant_id, "admin", create_request(&uris))
+                .await;
+            assert!(
+                matches!(result, Err(AppError::ValidationError(..))),
+                "{:?} should be rejected",
+                uris
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_clients_of_other_tenants_are_not_found() {
+        let clients = Arc::new(Mutex::new(Vec::new()));
+        let usecase = ClientUseCase::new(Arc::new(repository_with(clients)));
+        let created = usecase
+            .create_client(
+                Uuid::new_v4(),
+                "admin",
+                create_request(&["https://app.example.com/callback"]),
+            )
+            .await
+            .unwrap();
+
+        let result = usecase
+            .delete_client(Uuid::new_v4(), "admin", &created.client.client_id)
+            .await;
+
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_update_client_replaces_redirect_uris() {
+        let clients = Arc::new(Mutex::new(Vec::new()));
+        let usecase = ClientUseCase::new(Arc::new(repository_with(clients)));
+        let tenant_id = Uuid::new_v4();
+        let created = usecase
+            .create_client(
+                tenant_id,
+                "admin",
+                create_request(&["https://app.example.com/callback"]),
+            )
+            .await
+            .unwrap();
+
+        let updated = usecase
+            .update_client(
+                tenant_id,
+                "admin",
+                &created.client.client_id,
+                UpdateClientRequest {
+                    name: None,
+                    redirect_uris: Some(vec!["https://new.example.com/callback".to_string()]),
+                    grant_types: None,
+                    scopes: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(updated.name, "Dashboard");
+        assert_eq!(
+            updated.redirect_uris,
+            vec!["https://new.example.com/callback"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_validate_redirect_uri_uses_registered_clients() {
+        let clients = Arc::new(Mutex::new(Vec::new()));
+        let usecase = ClientUseCase::new(Arc::new(repository_with(clients)));
+        let tenant_id = Uuid::new_v4();
+        let origins = vec!["https://legacy.example.com".to_string()];
+        usecase
+            .create_client(
+                tenant_id,
+                "admin",
+                create_request(&["https://app.example.com/callback"]),
+            )
+            .await
+            .unwrap();
+
+        let registered = Some("https://app.example.com/callback".to_string());
+        let legacy = Some("https://legacy.example.com/callback".to_string());
+        assert!(usecase
+            .validate_redirect_uri(tenant_id, &registered, &origins)
+            .await
+            .is_ok());
+        assert!(matches!(
+            usecase
+                .validate_redirect_uri(tenant_id, &legacy, &origins)
+                .await,
+            Err(AppError::Forbidden(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_validate_redirect_uri_without_clients_uses_allowed_origins() {
+        let clients = Arc::new(Mutex::new(Vec::new()));
+        let usecase = ClientUseCase::new(Arc::new(repository_with(clients)));
+        let origins = vec!["https://legacy.example.com".to_string()];
+
+        let legacy = Some("https://legacy.example.com/callback".to_string());
+        assert!(usecase
+            .validate_redirect_uri(Uuid::new_v4(), &legacy, &origins)
+            .await
+            .is_ok());
+    }
+}
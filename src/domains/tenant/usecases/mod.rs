@@ -1,4 +1,7 @@
+pub mod client_usecase;
 pub mod tenant_usecase;
 
+#[cfg(test)]
+mod client_usecase_test;
 #[cfg(test)]
 mod tenant_usecase_test;
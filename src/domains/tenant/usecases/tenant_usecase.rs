@@ -1,4 +1,5 @@
 use crate::domains::common::errors::{AppError, ValidationDetail};
+use crate::domains::common::infrastructures::rate_limiter::{RateLimitOverrides, RATE_LIMIT_RULES};
 use crate::domains::tenant::dtos::tenant_dto::{
     CreateTenantRequest, TenantResponse, UpdateTenantRequest,
 };
@@ -75,6 +76,8 @@ impl TenantUseCase {
             ));
         }
 
+        validate_rate_limits(req.rate_limits.as_ref())?;
+
         // Check if tenant name already exists (including deleted)
         if let Some(existing_tenant) = self
             .tenant_repo
@@ -86,6 +89,9 @@ impl TenantUseCase {
                 let updated_req = UpdateTenantRequest {
                     name: Some(req.name),
                     description: req.description.clone(),
+                    require_email_verification: req.require_email_verification,
+                    passwordless_login: req.passwordless_login,
+                    rate_limits: req.rate_limits.clone(),
                 };
 
                 // We need to support "restoring" in repository or handle it via update.
@@ -104,8 +110,12 @@ impl TenantUseCase {
                 // For now, let's assume we add restore method.
                 self.tenant_repo.restore(existing_tenant.id).await?;
 
-                // After restore, we might want to update description if provided.
-                if req.description.is_some() {
+                // After restore, we might want to update description or policies if provided.
+                if req.description.is_some()
+                    || req.require_email_verification.is_some()
+                    || req.passwordless_login.is_some()
+                    || req.rate_limits.is_some()
+                {
                     self.tenant_repo
                         .update(existing_tenant.id, updated_req)
                         .await?;
@@ -195,6 +205,8 @@ impl TenantUseCase {
             }
         }
 
+        validate_rate_limits(req.rate_limits.as_ref())?;
+
         // Check if new name conflicts with existing tenant
         if let Some(ref name) = req.name {
             if let Some(existing) = self.tenant_repo.find_by_name(name).await? {
@@ -223,3 +235,36 @@ impl TenantUseCase {
         self.tenant_repo.delete(id).await
     }
 }
+
+/// Rejects overrides of unknown rate limit rules and limits without a window.
+fn validate_rate_limits(rate_limits: Option<&RateLimitOverrides>) -> Result<(), AppError> {
+    let Some(rate_limits) = rate_limits else {
+        return Ok(());
+    };
+
+    let details: Vec<ValidationDetail> = rate_limits
+        .iter()
+        .filter_map(|(rule, limit)| {
+            let message = if !RATE_LIMIT_RULES.contains(&rule.as_str()) {
+                "Unknown rate limit rule"
+            } else if limit.window_secs == 0 {
+                "window_secs must be greater than 0"
+            } else {
+                return None;
+            };
+            Some(ValidationDetail {
+                field: format!("rate_limits.{}", rule),
+                message: message.to_string(),
+            })
+        })
+        .collect();
+
+    if details.is_empty() {
+        Ok(())
+    } else {
+        Err(AppError::ValidationError(
+            "Invalid rate limits".to_string(),
+            Some(details),
+        ))
+    }
+}
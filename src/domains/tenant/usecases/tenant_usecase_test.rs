@@ -1,12 +1,13 @@
 #[cfg(test)]
 mod tests {
     use crate::domains::common::errors::AppError;
-    use crate::domains::tenant::dtos::tenant_dto::CreateTenantRequest;
+    use crate::domains::common::infrastructures::rate_limiter::RateLimitOverrides;
+    use crate::domains::tenant::dtos::tenant_dto::{CreateTenantRequest, UpdateTenantRequest};
     use crate::domains::tenant::entities::tenant::Model as Tenant;
     use crate::domains::tenant::repositories::tenant_repository::TenantRepositoryTrait;
     use crate::domains::tenant::usecases::tenant_usecase::TenantUseCase;
     use async_trait::async_trait;
-    use chrono::Utc;
+    use chrono::{DateTime, Utc};
     use mockall::mock;
     use mockall::predicate::*;
     use std::sync::Arc;
@@ -25,6 +26,7 @@ mod tests {
             async fn update(&self, id: Uuid, tenant: crate::domains::tenant::dtos::tenant_dto::UpdateTenantRequest) -> Result<Tenant, AppError>;
             async fn delete(&self, id: Uuid) -> Result<(), AppError>;
             async fn restore(&self, id: Uuid) -> Result<(), AppError>;
+            async fn purge_deleted_before(&self, cutoff: DateTime<Utc>) -> Result<u64, AppError>;
         }
     }
 
@@ -41,6 +43,9 @@ mod tests {
             name: name.to_string(),
             description: Some(description.to_string()),
             api_key: Some("test-api-key".to_string()),
+            require_email_verification: false,
+            passwordless_login: false,
+            rate_limits: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
             deleted_at: None,
@@ -64,6 +69,9 @@ mod tests {
         let req = CreateTenantRequest {
             name: name.to_string(),
             description: Some(description.to_string()),
+            require_email_verification: None,
+            passwordless_login: None,
+            rate_limits: None,
         };
 
         let result = usecase.create_tenant(req).await;
@@ -87,6 +95,9 @@ mod tests {
             name: name.to_string(),
             description: None,
             api_key: Some("existing-api-key".to_string()),
+            require_email_verification: false,
+            passwordless_login: false,
+            rate_limits: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
             deleted_at: None,
@@ -106,6 +117,9 @@ mod tests {
         let req = CreateTenantRequest {
             name: name.to_string(),
             description: None,
+            require_email_verification: None,
+            passwordless_login: None,
+            rate_limits: None,
         };
 
         let result = usecase.create_tenant(req).await;
@@ -115,4 +129,33 @@ mod tests {
         assert_eq!(response.id, tenant_id);
         assert!(!created);
     }
+
+    #[tokio::test]
+    async fn test_update_tenant_rejects_unknown_rate_limit_rule() {
+        // Validation fails before the repository is touched
+        let usecase = TenantUseCase::new(Arc::new(MockTenantRepository::new()));
+
+        let rate_limits: RateLimitOverrides = serde_json::from_value(serde_json::json!({
+            "login": { "max_requests": 20, "window_secs": 300 },
+            "logout": { "max_requests": 5, "window_secs": 60 }
+        }))
+        .unwrap();
+        let req = UpdateTenantRequest {
+            name: None,
+            description: None,
+            require_email_verification: None,
+            passwordless_login: None,
+            rate_limits: Some(rate_limits),
+        };
+
+        let result = usecase.update_tenant(Uuid::new_v4(), req).await;
+
+        match result {
+            Err(AppError::ValidationError(_, Some(details))) => {
+                assert_eq!(details.len(), 1);
+                assert_eq!(details[0].field, "rate_limits.logout");
+            }
+            other => panic!("expected validation error, got {:?}", other.map(|_| ())),
+        }
+    }
 }
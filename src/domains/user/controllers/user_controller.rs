@@ -1,11 +1,12 @@
 use crate::domains::auth::usecases::auth_usecase::AuthUseCase;
 use crate::domains::common::dtos::response_dto::SuccessResponseDTO;
 use crate::domains::common::errors::AppError;
-use crate::domains::user::dtos::user_dto::UpdateUserRequest;
+use crate::domains::user::dtos::user_dto::{UpdateUserRequest, UpdateUserStatusRequest};
 use crate::domains::user::usecases::user_usecase::UserUseCase;
 use actix_web::{web, HttpMessage, HttpRequest, HttpResponse, Responder};
 use serde_json::json;
 use std::sync::Arc;
+use uuid::Uuid;
 
 use crate::domains::tenant::middlewares::api_key_middleware::TenantId;
 
@@ -107,3 +108,90 @@ pub async fn delete_user(
         "User deleted successfully",
     )))
 }
+
+/// Unlock a user locked out after repeated failed logins (admin only)
+pub async fn unlock_user(
+    usecase: web::Data<Arc<UserUseCase>>,
+    id: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let user_id = AuthUseCase::extract_user_id_from_request(&req)?;
+    let tenant_id = req
+        .extensions()
+        .get::<TenantId>()
+        .map(|id| id.0)
+        .ok_or_else(|| {
+            AppError::Unauthorized("Tenant ID not found in request context".to_string())
+        })?;
+
+    // Check permissions by fetching current user's role
+    let current_user = usecase.get_user(user_id, tenant_id).await?;
+
+    usecase
+        .unlock_user(id.into_inner(), tenant_id, &current_user.role)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(SuccessResponseDTO::<()>::no_data(
+        "User unlocked successfully",
+    )))
+}
+
+/// Change a user's account status (admin only)
+pub async fn update_user_status(
+    usecase: web::Data<Arc<UserUseCase>>,
+    id: web::Path<Uuid>,
+    body: web::Json<UpdateUserStatusRequest>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let user_id = AuthUseCase::extract_user_id_from_request(&req)?;
+    let tenant_id = req
+        .extensions()
+        .get::<TenantId>()
+        .map(|id| id.0)
+        .ok_or_else(|| {
+            AppError::Unauthorized("Tenant ID not found in request context".to_string())
+        })?;
+
+    // Check permissions by fetching current user's role
+    let current_user = usecase.get_user(user_id, tenant_id).await?;
+
+    let user = usecase
+        .set_user_status(
+            id.into_inner(),
+            tenant_id,
+            user_id,
+            &current_user.role,
+            body.into_inner(),
+        )
+        .await?;
+
+    Ok(HttpResponse::Ok().json(SuccessResponseDTO::new(
+        "User status updated successfully",
+        user,
+    )))
+}
+
+/// Issue a short-lived access token to act as a user (admin only)
+pub async fn impersonate_user(
+    usecase: web::Data<Arc<AuthUseCase>>,
+    id: web::Path<Uuid>,
+    req: HttpRequest,
+) -> Result<impl Responder, AppError> {
+    let user_id = AuthUseCase::extract_user_id_from_request(&req)?;
+    let tenant_id = req
+        .extensions()
+        .get::<TenantId>()
+        .map(|id| id.0)
+        .ok_or_else(|| {
+            AppError::Unauthorized("Tenant ID not found in request context".to_string())
+        })?;
+
+    let auth_response = usecase
+        .impersonate(user_id, id.into_inner(), tenant_id, &req)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(SuccessResponseDTO::new(
+        "Impersonation started",
+        auth_response,
+    )))
+}
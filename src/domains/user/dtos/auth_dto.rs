@@ -1,3 +1,4 @@
+use crate::domains::user::dtos::mfa_dto::MfaChallengeResponse;
 use serde::{Deserialize, Serialize};
 
 /// Request DTO for user login (JSON Body).
@@ -60,6 +61,15 @@ pub struct RegisterRequest {
     pub invitation_code: Option<String>,
 }
 
+/// Request DTO for confirming the signed-in user's credentials again.
+///
+/// `code` is a TOTP code and is required for users with MFA enabled.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ReauthenticateRequest {
+    pub password: String,
+    pub code: Option<String>,
+}
+
 /// Authentication response containing only access token.
 ///
 /// The refresh token is not included in the response body as it is set
@@ -72,8 +82,43 @@ pub struct AuthResponse {
     pub expires_in: i64,
 }
 
+/// Outcome of a password login.
+///
+/// Users with a second factor enrolled receive an MFA challenge instead of tokens.
+#[derive(Debug, Clone)]
+pub enum LoginResult {
+    /// Credentials accepted: access token response and the raw refresh token.
+    Authenticated(AuthResponse, String),
+    /// Password accepted but a second factor must be verified first.
+    MfaRequired(MfaChallengeResponse),
+}
+
+/// Outcome of a registration.
+///
+/// Tenants requiring a verified email withhold tokens until the address is verified.
+#[derive(Debug, Clone)]
+pub enum RegisterResult {
+    /// Registered and signed in.
+    Authenticated(AuthResponse),
+    /// Registered, but the email address must be verified before signing in.
+    VerificationPending(uuid::Uuid),
+}
+
+impl RegisterResult {
+    /// ID of the registered user.
+    pub fn user_id(&self) -> uuid::Uuid {
+        match self {
+            Self::Authenticated(response) => response.user_id,
+            Self::VerificationPending(user_id) => *user_id,
+        }
+    }
+}
+
 /// Query parameters for SSO logout endpoint.
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct SsoLogoutQuery {
     pub redirect_uri: Option<String>,
+    /// Registered client whose redirect URIs `redirect_uri` is checked against.
+    /// Without it the global allowed origins apply.
+    pub client_id: Option<String>,
 }
@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+
+/// Request DTO for confirming an email address.
+///
+/// `token` is the value from the link sent after registration or by
+/// `/auth/verify-email/resend`.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct VerifyEmailRequest {
+    pub token: String,
+}
+
+/// Request DTO for sending a new verification link.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ResendVerificationRequest {
+    pub email: String,
+}
@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+
+/// Request DTO for completing a login that requires a second factor.
+///
+/// The `mfa_token` is the challenge token returned by `/auth/login`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct MfaVerifyRequest {
+    pub mfa_token: String,
+    pub code: String,
+}
+
+/// Request DTO carrying a TOTP code for confirming or disabling MFA.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TotpCodeRequest {
+    pub code: String,
+}
+
+/// Response DTO for starting a TOTP enrollment.
+///
+/// `provisioning_uri` is an `otpauth://` URI meant to be rendered as a QR code.
+#[derive(Debug, Clone, Serialize)]
+pub struct TotpEnrollmentResponse {
+    pub secret: String,
+    pub provisioning_uri: String,
+}
+
+/// Challenge returned by login when the user must present a second factor.
+#[derive(Debug, Clone, Serialize)]
+pub struct MfaChallengeResponse {
+    pub mfa_token: String,
+    pub expires_in: i64,
+}
@@ -1,4 +1,11 @@
 pub mod auth_dto;
 pub mod change_password_dto;
+pub mod email_verification_dto;
+pub mod mfa_dto;
+pub mod passkey_dto;
+pub mod password_reset_dto;
+pub mod passwordless_dto;
+pub mod recovery_code_dto;
+pub mod session_dto;
 pub mod user_details_dto;
 pub mod user_dto;
@@ -0,0 +1,145 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Relying party description sent in registration options.
+#[derive(Debug, Clone, Serialize)]
+pub struct RelyingParty {
+    pub id: String,
+    pub name: String,
+}
+
+/// User account description sent in registration options.
+///
+/// `id` is the base64url-encoded user UUID and is returned by the
+/// authenticator as `userHandle` during login.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PasskeyUserEntity {
+    pub id: String,
+    pub name: String,
+    pub display_name: String,
+}
+
+/// Public key algorithm accepted for new credentials.
+#[derive(Debug, Clone, Serialize)]
+pub struct PubKeyCredParam {
+    #[serde(rename = "type")]
+    pub credential_type: String,
+    pub alg: i64,
+}
+
+/// Reference to an existing credential by its base64url ID.
+#[derive(Debug, Clone, Serialize)]
+pub struct CredentialDescriptor {
+    #[serde(rename = "type")]
+    pub credential_type: String,
+    pub id: String,
+}
+
+/// Authenticator requirements sent in registration options.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthenticatorSelection {
+    pub resident_key: String,
+    pub user_verification: String,
+}
+
+/// Options for `navigator.credentials.create()`.
+///
+/// Binary fields are base64url-encoded and must be decoded by the client.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PasskeyRegistrationOptions {
+    pub challenge: String,
+    pub rp: RelyingParty,
+    pub user: PasskeyUserEntity,
+    pub pub_key_cred_params: Vec<PubKeyCredParam>,
+    pub timeout: u64,
+    pub attestation: String,
+    pub authenticator_selection: AuthenticatorSelection,
+    pub exclude_credentials: Vec<CredentialDescriptor>,
+}
+
+/// Options for `navigator.credentials.get()`.
+///
+/// `allow_credentials` is empty when no user was named, letting the
+/// authenticator offer its discoverable credentials.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PasskeyLoginOptions {
+    pub challenge: String,
+    pub rp_id: String,
+    pub timeout: u64,
+    pub user_verification: String,
+    pub allow_credentials: Vec<CredentialDescriptor>,
+}
+
+/// Authenticator response of a registration ceremony.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AttestationResponse {
+    #[serde(rename = "clientDataJSON")]
+    pub client_data_json: String,
+    pub attestation_object: String,
+}
+
+/// `PublicKeyCredential` returned by `navigator.credentials.create()`, serialized as JSON.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RegistrationCredential {
+    pub id: String,
+    pub response: AttestationResponse,
+    #[serde(rename = "type")]
+    pub credential_type: String,
+}
+
+/// Authenticator response of an authentication ceremony.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssertionResponse {
+    #[serde(rename = "clientDataJSON")]
+    pub client_data_json: String,
+    pub authenticator_data: String,
+    pub signature: String,
+    pub user_handle: Option<String>,
+}
+
+/// `PublicKeyCredential` returned by `navigator.credentials.get()`, serialized as JSON.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AuthenticationCredential {
+    pub id: String,
+    pub response: AssertionResponse,
+    #[serde(rename = "type")]
+    pub credential_type: String,
+}
+
+/// Request DTO for completing passkey registration.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PasskeyRegisterRequest {
+    pub credential: RegistrationCredential,
+    pub name: Option<String>,
+}
+
+/// Request DTO for starting a passkey login.
+///
+/// Naming the user is optional; without it the login relies on discoverable credentials.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct PasskeyLoginOptionsRequest {
+    pub email_or_username: Option<String>,
+}
+
+/// Request DTO for completing a passkey login.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PasskeyLoginRequest {
+    pub credential: AuthenticationCredential,
+    pub role: Option<String>,
+}
+
+/// Response DTO describing a registered passkey.
+#[derive(Debug, Clone, Serialize)]
+pub struct PasskeyResponse {
+    pub id: uuid::Uuid,
+    pub credential_id: String,
+    pub name: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+}
@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+
+/// Request DTO for requesting a password reset link.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ForgotPasswordRequest {
+    pub email: String,
+}
+
+/// Request DTO for setting a new password with a reset token.
+///
+/// `token` is the value from the link sent by `/auth/forgot-password`.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ResetPasswordRequest {
+    pub token: String,
+    pub new_password: String,
+    pub confirm_new_password: String,
+}
@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+
+/// Request DTO for emailing a passwordless sign-in link and code.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PasswordlessRequest {
+    pub email: String,
+}
+
+/// Request DTO for completing a passwordless sign-in.
+///
+/// Carries either `token` from the emailed link or the 6-digit `code`. `role`
+/// selects the role like in a password login.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PasswordlessConsumeRequest {
+    pub token: Option<String>,
+    pub code: Option<String>,
+    pub role: Option<String>,
+}
@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+
+/// Request DTO for completing an MFA-challenged login with a recovery code
+/// instead of a TOTP code.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct MfaRecoveryRequest {
+    pub mfa_token: String,
+    pub recovery_code: String,
+}
+
+/// Request DTO for regenerating recovery codes.
+///
+/// The current password is required so that a stolen access token alone
+/// cannot mint new recovery codes.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct RegenerateRecoveryCodesRequest {
+    pub password: String,
+}
+
+/// Request DTO for resetting credentials with a recovery code.
+///
+/// Used when the user has lost their password or second factor and cannot sign in.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct RecoveryResetRequest {
+    pub email_or_username: String,
+    pub recovery_code: String,
+    pub new_password: String,
+    pub confirm_new_password: String,
+}
+
+/// Response DTO carrying a freshly generated set of recovery codes.
+///
+/// The plain codes are only returned once; only their hashes are stored.
+#[derive(Debug, Clone, Serialize)]
+pub struct RecoveryCodesResponse {
+    pub recovery_codes: Vec<String>,
+}
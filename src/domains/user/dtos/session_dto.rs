@@ -0,0 +1,16 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+/// Response DTO describing a signed-in device.
+///
+/// `current` marks the session whose refresh token cookie came with the request.
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionResponse {
+    pub id: uuid::Uuid,
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub expires_at: DateTime<Utc>,
+    pub current: bool,
+}
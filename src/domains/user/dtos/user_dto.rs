@@ -23,6 +23,17 @@ pub struct UpdateUserRequest {
     pub password: Option<String>,
 }
 
+/// Request DTO for changing a user's account status (admin only).
+///
+/// `status` is one of `active`, `suspended`, `banned` or `pending`. When
+/// `expires_at` passes the user counts as active again.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct UpdateUserStatusRequest {
+    pub status: String,
+    pub reason: Option<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
 /// Response DTO for user data.
 ///
 /// This DTO excludes sensitive information like password hash.
@@ -33,6 +44,11 @@ pub struct UserResponse {
     pub id: Uuid,
     pub username: String,
     pub email: String,
+    pub email_verified: bool,
+    pub email_verified_at: Option<DateTime<Utc>>,
+    pub status: String,
+    pub status_reason: Option<String>,
+    pub status_expires_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub role: String,
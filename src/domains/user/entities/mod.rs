@@ -1,4 +1,8 @@
 pub mod user;
 pub mod user_activity_log;
 pub mod user_details;
+pub mod user_mfa;
+pub mod user_passkey;
+pub mod user_recovery_code;
 pub mod user_session;
+pub mod user_token;
@@ -2,6 +2,23 @@ use chrono::{DateTime, Utc};
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 
+/// The user can log in.
+pub const STATUS_ACTIVE: &str = "active";
+/// Temporarily blocked by an admin, usually with an expiry.
+pub const STATUS_SUSPENDED: &str = "suspended";
+/// Blocked by an admin for abuse.
+pub const STATUS_BANNED: &str = "banned";
+/// Waiting for an admin to approve the account.
+pub const STATUS_PENDING: &str = "pending";
+
+/// All values accepted in `users.status`.
+pub const USER_STATUSES: [&str; 4] = [
+    STATUS_ACTIVE,
+    STATUS_SUSPENDED,
+    STATUS_BANNED,
+    STATUS_PENDING,
+];
+
 /// User entity representing the users table in the database.
 ///
 /// This SeaORM model maps to the `users` table and includes all user-related fields
@@ -17,6 +34,12 @@ pub struct Model {
     #[sea_orm(unique)]
     pub email: String,
     pub password_hash: String,
+    pub email_verified_at: Option<DateTime<Utc>>,
+    pub failed_login_attempts: i32,
+    pub locked_until: Option<DateTime<Utc>>,
+    pub status: String,
+    pub status_reason: Option<String>,
+    pub status_expires_at: Option<DateTime<Utc>>,
     pub deleted_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
@@ -30,6 +53,14 @@ pub enum Relation {
     UserSessions,
     #[sea_orm(has_many = "super::user_activity_log::Entity")]
     UserActivityLogs,
+    #[sea_orm(has_one = "super::user_mfa::Entity")]
+    UserMfa,
+    #[sea_orm(has_many = "super::user_recovery_code::Entity")]
+    UserRecoveryCodes,
+    #[sea_orm(has_many = "super::user_passkey::Entity")]
+    UserPasskeys,
+    #[sea_orm(has_many = "super::user_token::Entity")]
+    UserTokens,
 }
 
 impl Related<super::user_details::Entity> for Entity {
@@ -38,6 +69,22 @@ impl Related<super::user_details::Entity> for Entity {
     }
 }
 
+impl Model {
+    /// Returns the status in effect now. A status whose expiry has passed
+    /// counts as active again.
+    pub fn effective_status(&self) -> &str {
+        match self.status_expires_at {
+            Some(expires_at) if expires_at <= Utc::now() => STATUS_ACTIVE,
+            _ => &self.status,
+        }
+    }
+
+    /// Returns true if the user may authenticate.
+    pub fn is_active(&self) -> bool {
+        self.effective_status() == STATUS_ACTIVE
+    }
+}
+
 // Many-to-many relation with Tenant through UserTenant
 // Relations to Tenant and UserTenant removed due to domain separation.
 // Use UserTenant::find().filter(UserTenant::Column::UserId.eq(user.id)) instead.
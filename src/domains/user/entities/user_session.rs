@@ -7,6 +7,11 @@ use serde::{Deserialize, Serialize};
 /// This SeaORM model tracks active user sessions, enabling multi-device login support.
 /// Each session is identified by a hashed refresh token and includes metadata for
 /// security auditing such as user agent and IP address.
+///
+/// Refreshing rotates the token: the old session is marked `rotated_at` and a
+/// new one is created with `parent_id` pointing at it. Sessions descending from
+/// the same login share a `family_id`, and the successor keeps the login's
+/// `created_at` so that a device is listed with the time it signed in.
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
 #[sea_orm(table_name = "user_sessions")]
 pub struct Model {
@@ -19,6 +24,14 @@ pub struct Model {
     pub ip_address: Option<String>,
     pub expires_at: DateTime<Utc>,
     pub created_at: DateTime<Utc>,
+    /// ID of the first session of the rotation chain.
+    pub family_id: Uuid,
+    /// Session this one was rotated from, if any.
+    pub parent_id: Option<Uuid>,
+    /// When the refresh token was exchanged for a new one.
+    pub rotated_at: Option<DateTime<Utc>>,
+    /// When the session was last created or refreshed.
+    pub last_used_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
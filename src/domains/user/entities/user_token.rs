@@ -0,0 +1,49 @@
+use chrono::{DateTime, Utc};
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Purpose of a password reset token.
+pub const PURPOSE_PASSWORD_RESET: &str = "password_reset";
+
+/// Purpose of an email verification token.
+pub const PURPOSE_EMAIL_VERIFICATION: &str = "email_verification";
+
+/// User token entity representing the user_tokens table in the database.
+///
+/// Each row stores the SHA-256 hash of a single-use token sent to the user,
+/// scoped to the tenant it was requested for. `purpose` distinguishes the
+/// flows sharing this table, and `used_at` is set once the token is consumed.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "user_tokens")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub tenant_id: Uuid,
+    pub purpose: String,
+    #[sea_orm(unique)]
+    pub token_hash: String,
+    pub expires_at: DateTime<Utc>,
+    pub used_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "crate::domains::user::entities::user::Entity",
+        from = "Column::UserId",
+        to = "crate::domains::user::entities::user::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Cascade"
+    )]
+    User,
+}
+
+impl Related<crate::domains::user::entities::user::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::User.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
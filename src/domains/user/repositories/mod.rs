@@ -1,4 +1,8 @@
 pub mod user_activity_log_repository;
 pub mod user_details_repository;
+pub mod user_mfa_repository;
+pub mod user_passkey_repository;
+pub mod user_recovery_code_repository;
 pub mod user_repository;
 pub mod user_session_repository;
+pub mod user_token_repository;
@@ -0,0 +1,144 @@
+use crate::domains::common::errors::AppError;
+use crate::domains::user::entities::user_mfa::{self, Entity as UserMfaEntity, Model as UserMfa};
+use async_trait::async_trait;
+use chrono::Utc;
+use sea_orm::sea_query::Expr;
+use sea_orm::*;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Trait defining user MFA repository operations.
+///
+/// This trait abstracts database operations for TOTP enrollment, confirmation
+/// and replay tracking.
+#[async_trait]
+pub trait UserMfaRepositoryTrait: Send + Sync {
+    /// Finds the MFA record (pending or enabled) for a user.
+    async fn find_by_user_id(&self, user_id: Uuid) -> Result<Option<UserMfa>, AppError>;
+
+    /// Creates or replaces a pending (unconfirmed) enrollment with a new secret.
+    async fn upsert_pending(&self, user_id: Uuid, totp_secret: String)
+        -> Result<UserMfa, AppError>;
+
+    /// Marks the enrollment as enabled and records the time step used to confirm it.
+    async fn enable(&self, user_id: Uuid, used_step: i64) -> Result<UserMfa, AppError>;
+
+    /// Records the last accepted time step to prevent code replay.
+    ///
+    /// Returns `false` if the same or a newer step was already recorded.
+    async fn update_last_used_step(&self, user_id: Uuid, used_step: i64) -> Result<bool, AppError>;
+
+    /// Removes the MFA record for a user.
+    async fn delete_by_user_id(&self, user_id: Uuid) -> Result<(), AppError>;
+}
+
+/// User MFA repository implementation using SeaORM.
+pub struct UserMfaRepository {
+    db: Arc<DatabaseConnection>,
+}
+
+impl UserMfaRepository {
+    /// Creates a new UserMfaRepository instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - Arc-wrapped database connection
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl UserMfaRepositoryTrait for UserMfaRepository {
+    async fn find_by_user_id(&self, user_id: Uuid) -> Result<Option<UserMfa>, AppError> {
+        UserMfaEntity::find()
+            .filter(user_mfa::Column::UserId.eq(user_id))
+            .one(&*self.db)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+
+    async fn upsert_pending(
+        &self,
+        user_id: Uuid,
+        totp_secret: String,
+    ) -> Result<UserMfa, AppError> {
+        let now = Utc::now();
+
+        if let Some(existing) = self.find_by_user_id(user_id).await? {
+            let mut active: user_mfa::ActiveModel = existing.into();
+            active.totp_secret = Set(totp_secret);
+            active.enabled_at = Set(None);
+            active.last_used_step = Set(None);
+            active.updated_at = Set(now);
+
+            return active
+                .update(&*self.db)
+                .await
+                .map_err(|e| AppError::DatabaseError(e.to_string()));
+        }
+
+        let record = user_mfa::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            user_id: Set(user_id),
+            totp_secret: Set(totp_secret),
+            enabled_at: Set(None),
+            last_used_step: Set(None),
+            created_at: Set(now),
+            updated_at: Set(now),
+        };
+
+        UserMfaEntity::insert(record.clone())
+            .exec(&*self.db)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Ok(record.try_into_model().unwrap())
+    }
+
+    async fn enable(&self, user_id: Uuid, used_step: i64) -> Result<UserMfa, AppError> {
+        let existing = self
+            .find_by_user_id(user_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("MFA enrollment not found".to_string()))?;
+
+        let now = Utc::now();
+        let mut active: user_mfa::ActiveModel = existing.into();
+        active.enabled_at = Set(Some(now));
+        active.last_used_step = Set(Some(used_step));
+        active.updated_at = Set(now);
+
+        active
+            .update(&*self.db)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+
+    async fn update_last_used_step(&self, user_id: Uuid, used_step: i64) -> Result<bool, AppError> {
+        // Conditional update so concurrent requests cannot both accept the same step
+        let result = UserMfaEntity::update_many()
+            .col_expr(user_mfa::Column::LastUsedStep, Expr::value(used_step))
+            .col_expr(user_mfa::Column::UpdatedAt, Expr::value(Utc::now()))
+            .filter(user_mfa::Column::UserId.eq(user_id))
+            .filter(
+                Condition::any()
+                    .add(user_mfa::Column::LastUsedStep.is_null())
+                    .add(user_mfa::Column::LastUsedStep.lt(used_step)),
+            )
+            .exec(&*self.db)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Ok(result.rows_affected > 0)
+    }
+
+    async fn delete_by_user_id(&self, user_id: Uuid) -> Result<(), AppError> {
+        UserMfaEntity::delete_many()
+            .filter(user_mfa::Column::UserId.eq(user_id))
+            .exec(&*self.db)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Ok(())
+    }
+}
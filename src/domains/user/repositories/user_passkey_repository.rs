@@ -0,0 +1,132 @@
+use crate::domains::common::errors::AppError;
+use crate::domains::user::entities::user_passkey::{
+    self, Entity as UserPasskeyEntity, Model as UserPasskey,
+};
+use async_trait::async_trait;
+use chrono::Utc;
+use sea_orm::sea_query::Expr;
+use sea_orm::*;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Trait defining user passkey repository operations.
+///
+/// This trait abstracts database operations for WebAuthn credentials.
+#[async_trait]
+pub trait UserPasskeyRepositoryTrait: Send + Sync {
+    /// Stores a newly registered credential.
+    async fn create(
+        &self,
+        user_id: Uuid,
+        credential_id: String,
+        public_key: Vec<u8>,
+        sign_count: i64,
+        name: Option<String>,
+    ) -> Result<UserPasskey, AppError>;
+
+    /// Finds a credential by its base64url credential ID.
+    async fn find_by_credential_id(
+        &self,
+        credential_id: &str,
+    ) -> Result<Option<UserPasskey>, AppError>;
+
+    /// Lists all credentials registered by a user.
+    async fn find_by_user_id(&self, user_id: Uuid) -> Result<Vec<UserPasskey>, AppError>;
+
+    /// Records a successful assertion with the new signature counter.
+    async fn update_usage(&self, id: Uuid, sign_count: i64) -> Result<(), AppError>;
+
+    /// Deletes a credential owned by the user.
+    ///
+    /// Returns `false` if no matching credential exists.
+    async fn delete(&self, user_id: Uuid, id: Uuid) -> Result<bool, AppError>;
+}
+
+/// User passkey repository implementation using SeaORM.
+pub struct UserPasskeyRepository {
+    db: Arc<DatabaseConnection>,
+}
+
+impl UserPasskeyRepository {
+    /// Creates a new UserPasskeyRepository instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - Arc-wrapped database connection
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl UserPasskeyRepositoryTrait for UserPasskeyRepository {
+    async fn create(
+        &self,
+        user_id: Uuid,
+        credential_id: String,
+        public_key: Vec<u8>,
+        sign_count: i64,
+        name: Option<String>,
+    ) -> Result<UserPasskey, AppError> {
+        let passkey = user_passkey::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            user_id: Set(user_id),
+            credential_id: Set(credential_id),
+            public_key: Set(public_key),
+            sign_count: Set(sign_count),
+            name: Set(name),
+            created_at: Set(Utc::now()),
+            last_used_at: Set(None),
+        };
+
+        UserPasskeyEntity::insert(passkey.clone())
+            .exec(&*self.db)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Ok(passkey.try_into_model().unwrap())
+    }
+
+    async fn find_by_credential_id(
+        &self,
+        credential_id: &str,
+    ) -> Result<Option<UserPasskey>, AppError> {
+        UserPasskeyEntity::find()
+            .filter(user_passkey::Column::CredentialId.eq(credential_id))
+            .one(&*self.db)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+
+    async fn find_by_user_id(&self, user_id: Uuid) -> Result<Vec<UserPasskey>, AppError> {
+        UserPasskeyEntity::find()
+            .filter(user_passkey::Column::UserId.eq(user_id))
+            .order_by_asc(user_passkey::Column::CreatedAt)
+            .all(&*self.db)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+
+    async fn update_usage(&self, id: Uuid, sign_count: i64) -> Result<(), AppError> {
+        UserPasskeyEntity::update_many()
+            .col_expr(user_passkey::Column::SignCount, Expr::value(sign_count))
+            .col_expr(user_passkey::Column::LastUsedAt, Expr::value(Utc::now()))
+            .filter(user_passkey::Column::Id.eq(id))
+            .exec(&*self.db)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn delete(&self, user_id: Uuid, id: Uuid) -> Result<bool, AppError> {
+        let result = UserPasskeyEntity::delete_many()
+            .filter(user_passkey::Column::Id.eq(id))
+            .filter(user_passkey::Column::UserId.eq(user_id))
+            .exec(&*self.db)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Ok(result.rows_affected > 0)
+    }
+}
@@ -0,0 +1,120 @@
+use crate::domains::common::errors::AppError;
+use crate::domains::user::entities::user_recovery_code::{
+    self, Entity as UserRecoveryCodeEntity, Model as UserRecoveryCode,
+};
+use async_trait::async_trait;
+use chrono::Utc;
+use sea_orm::sea_query::Expr;
+use sea_orm::*;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Trait defining user recovery code repository operations.
+///
+/// This trait abstracts database operations for storing and consuming
+/// single-use account recovery codes.
+#[async_trait]
+pub trait UserRecoveryCodeRepositoryTrait: Send + Sync {
+    /// Replaces all recovery codes of a user with a new set of hashed codes.
+    async fn replace_for_user(
+        &self,
+        user_id: Uuid,
+        code_hashes: Vec<String>,
+    ) -> Result<(), AppError>;
+
+    /// Finds the recovery codes of a user that have not been used yet.
+    async fn find_unused_by_user_id(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<UserRecoveryCode>, AppError>;
+
+    /// Marks a recovery code as used.
+    ///
+    /// Returns `false` if the code was already consumed.
+    async fn mark_used(&self, id: Uuid) -> Result<bool, AppError>;
+}
+
+/// User recovery code repository implementation using SeaORM.
+pub struct UserRecoveryCodeRepository {
+    db: Arc<DatabaseConnection>,
+}
+
+impl UserRecoveryCodeRepository {
+    /// Creates a new UserRecoveryCodeRepository instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - Arc-wrapped database connection
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl UserRecoveryCodeRepositoryTrait for UserRecoveryCodeRepository {
+    async fn replace_for_user(
+        &self,
+        user_id: Uuid,
+        code_hashes: Vec<String>,
+    ) -> Result<(), AppError> {
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        UserRecoveryCodeEntity::delete_many()
+            .filter(user_recovery_code::Column::UserId.eq(user_id))
+            .exec(&txn)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let now = Utc::now();
+        let records = code_hashes
+            .into_iter()
+            .map(|code_hash| user_recovery_code::ActiveModel {
+                id: Set(Uuid::new_v4()),
+                user_id: Set(user_id),
+                code_hash: Set(code_hash),
+                used_at: Set(None),
+                created_at: Set(now),
+            })
+            .collect::<Vec<_>>();
+
+        if !records.is_empty() {
+            UserRecoveryCodeEntity::insert_many(records)
+                .exec(&txn)
+                .await
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
+
+        txn.commit()
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+
+    async fn find_unused_by_user_id(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<UserRecoveryCode>, AppError> {
+        UserRecoveryCodeEntity::find()
+            .filter(user_recovery_code::Column::UserId.eq(user_id))
+            .filter(user_recovery_code::Column::UsedAt.is_null())
+            .all(&*self.db)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+
+    async fn mark_used(&self, id: Uuid) -> Result<bool, AppError> {
+        // Conditional update so concurrent requests cannot both consume the same code
+        let result = UserRecoveryCodeEntity::update_many()
+            .col_expr(user_recovery_code::Column::UsedAt, Expr::value(Utc::now()))
+            .filter(user_recovery_code::Column::Id.eq(id))
+            .filter(user_recovery_code::Column::UsedAt.is_null())
+            .exec(&*self.db)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Ok(result.rows_affected > 0)
+    }
+}
@@ -2,8 +2,10 @@ use crate::domains::common::errors::AppError;
 use crate::domains::common::infrastructures::rocksdb_connection::RocksDbCache;
 use crate::domains::common::utils::password;
 use crate::domains::user::dtos::user_dto::{CreateUserRequest, UpdateUserRequest};
-use crate::domains::user::entities::user::{Entity as UserEntity, Model as User};
+use crate::domains::user::entities::user::{Entity as UserEntity, Model as User, STATUS_ACTIVE};
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sea_orm::sea_query::Expr;
 use sea_orm::*;
 use std::sync::Arc;
 use std::time::Duration;
@@ -42,6 +44,41 @@ pub trait UserRepositoryTrait: Send + Sync {
 
     /// Restores a soft-deleted user.
     async fn restore(&self, id: Uuid, req: CreateUserRequest) -> Result<User, AppError>;
+
+    /// Marks the user's current email address as verified.
+    async fn mark_email_verified(&self, id: Uuid) -> Result<User, AppError>;
+
+    /// Stores the user's consecutive failed login count and lock expiry.
+    ///
+    /// Passing `0` and `None` clears the lockout.
+    async fn set_login_failures(
+        &self,
+        id: Uuid,
+        failed_login_attempts: i32,
+        locked_until: Option<DateTime<Utc>>,
+    ) -> Result<(), AppError>;
+
+    /// Counts a failed login in a single statement, so concurrent failures
+    /// are not lost, and returns the new number of consecutive failures.
+    async fn increment_login_failures(&self, id: Uuid) -> Result<i32, AppError>;
+
+    /// Locks the user until `locked_until`, keeping the failure count.
+    async fn lock_until(&self, id: Uuid, locked_until: DateTime<Utc>) -> Result<(), AppError>;
+
+    /// Sets the user's account status with an optional reason and expiry.
+    async fn set_status(
+        &self,
+        id: Uuid,
+        status: String,
+        reason: Option<String>,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<User, AppError>;
+
+    /// Permanently deletes users soft-deleted before `cutoff`.
+    ///
+    /// Returns the number of users removed. Dependent rows are removed by the
+    /// database through `ON DELETE CASCADE`.
+    async fn purge_deleted_before(&self, cutoff: DateTime<Utc>) -> Result<u64, AppError>;
 }
 
 /// User repository implementation using SeaORM.
@@ -73,6 +110,12 @@ impl UserRepositoryTrait for UserRepository {
             username: Set(req.username.clone()),
             email: Set(req.email.clone()),
             password_hash: Set(password_hash),
+            email_verified_at: Set(None),
+            failed_login_attempts: Set(0),
+            locked_until: Set(None),
+            status: Set(STATUS_ACTIVE.to_string()),
+            status_reason: Set(None),
+            status_expires_at: Set(None),
             created_at: Set(chrono::Utc::now()),
             updated_at: Set(chrono::Utc::now()),
             deleted_at: Set(None),
@@ -172,6 +215,10 @@ impl UserRepositoryTrait for UserRepository {
             user.username = Set(username.clone());
         }
         if let Some(ref email) = req.email {
+            // A new address has to be verified again
+            if existing.as_ref().is_some_and(|u| &u.email != email) {
+                user.email_verified_at = Set(None);
+            }
             user.email = Set(email.clone());
         }
         if let Some(ref password) = req.password {
@@ -287,9 +334,15 @@ impl UserRepositoryTrait for UserRepository {
             return Err(AppError::NotFound("User not found".to_string()));
         }
 
-        let mut user: crate::domains::user::entities::user::ActiveModel = existing.unwrap().into();
+        let existing = existing.unwrap();
+        let email_changed = existing.email != req.email;
+        let mut user: crate::domains::user::entities::user::ActiveModel = existing.into();
         let password_hash = password::hash_password(&req.password)?;
 
+        if email_changed {
+            user.email_verified_at = Set(None);
+        }
+
         user.username = Set(req.username);
         user.email = Set(req.email);
         user.password_hash = Set(password_hash);
@@ -303,4 +356,173 @@ impl UserRepositoryTrait for UserRepository {
 
         Ok(result)
     }
+
+    async fn mark_email_verified(&self, id: Uuid) -> Result<User, AppError> {
+        let existing = UserEntity::find_by_id(id)
+            .filter(crate::domains::user::entities::user::Column::DeletedAt.is_null())
+            .one(&*self.db)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?
+            .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+        if existing.email_verified_at.is_some() {
+            return Ok(existing);
+        }
+
+        let mut user: crate::domains::user::entities::user::ActiveModel = existing.into();
+        user.email_verified_at = Set(Some(chrono::Utc::now()));
+        user.updated_at = Set(chrono::Utc::now());
+
+        let result = user
+            .update(&*self.db)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        // Invalidate cache
+        self.cache.del(&format!("user:{}", id));
+        self.cache
+            .del(&format!("user:username:{}", result.username));
+
+        Ok(result)
+    }
+
+    async fn set_login_failures(
+        &self,
+        id: Uuid,
+        failed_login_attempts: i32,
+        locked_until: Option<DateTime<Utc>>,
+    ) -> Result<(), AppError> {
+        let existing = UserEntity::find_by_id(id)
+            .one(&*self.db)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?
+            .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+        let mut user: crate::domains::user::entities::user::ActiveModel = existing.into();
+        user.failed_login_attempts = Set(failed_login_attempts);
+        user.locked_until = Set(locked_until);
+
+        let result = user
+            .update(&*self.db)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        // Invalidate cache
+        self.cache.del(&format!("user:{}", id));
+        self.cache
+            .del(&format!("user:username:{}", result.username));
+
+        Ok(())
+    }
+
+    async fn increment_login_failures(&self, id: Uuid) -> Result<i32, AppError> {
+        UserEntity::update_many()
+            .col_expr(
+                crate::domains::user::entities::user::Column::FailedLoginAttempts,
+                Expr::col(crate::domains::user::entities::user::Column::FailedLoginAttempts).add(1),
+            )
+            .filter(crate::domains::user::entities::user::Column::Id.eq(id))
+            .exec(&*self.db)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let user = UserEntity::find_by_id(id)
+            .one(&*self.db)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?
+            .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+        // Invalidate cache
+        self.cache.del(&format!("user:{}", id));
+        self.cache.del(&format!("user:username:{}", user.username));
+
+        Ok(user.failed_login_attempts)
+    }
+
+    async fn lock_until(&self, id: Uuid, locked_until: DateTime<Utc>) -> Result<(), AppError> {
+        let user = UserEntity::find_by_id(id)
+            .one(&*self.db)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?
+            .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+        UserEntity::update_many()
+            .col_expr(
+                crate::domains::user::entities::user::Column::LockedUntil,
+                Expr::value(locked_until),
+            )
+            .filter(crate::domains::user::entities::user::Column::Id.eq(id))
+            .exec(&*self.db)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        // Invalidate cache
+        self.cache.del(&format!("user:{}", id));
+        self.cache.del(&format!("user:username:{}", user.username));
+
+        Ok(())
+    }
+
+    async fn set_status(
+        &self,
+        id: Uuid,
+        status: String,
+        reason: Option<String>,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<User, AppError> {
+        let existing = UserEntity::find_by_id(id)
+            .filter(crate::domains::user::entities::user::Column::DeletedAt.is_null())
+            .one(&*self.db)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?
+            .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+        let mut user: crate::domains::user::entities::user::ActiveModel = existing.into();
+        user.status = Set(status);
+        user.status_reason = Set(reason);
+        user.status_expires_at = Set(expires_at);
+        user.updated_at = Set(chrono::Utc::now());
+
+        let result = user
+            .update(&*self.db)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        // Invalidate cache
+        self.cache.del(&format!("user:{}", id));
+        self.cache
+            .del(&format!("user:username:{}", result.username));
+
+        Ok(result)
+    }
+
+    async fn purge_deleted_before(&self, cutoff: DateTime<Utc>) -> Result<u64, AppError> {
+        let users = UserEntity::find()
+            .filter(crate::domains::user::entities::user::Column::DeletedAt.lt(cutoff))
+            .all(&*self.db)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        if users.is_empty() {
+            return Ok(0);
+        }
+
+        let result = UserEntity::delete_many()
+            .filter(
+                crate::domains::user::entities::user::Column::Id
+                    .is_in(users.iter().map(|u| u.id).collect::<Vec<_>>()),
+            )
+            .exec(&*self.db)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        // Invalidate cache
+        for user in &users {
+            self.cache.del(&format!("user:{}", user.id));
+            self.cache.del(&format!("user:username:{}", user.username));
+            self.cache.del(&format!("user_all_tenants:{}", user.id));
+        }
+
+        Ok(result.rows_affected)
+    }
 }
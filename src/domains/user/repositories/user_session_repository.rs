@@ -4,6 +4,7 @@ use crate::domains::user::entities::user_session::{
 };
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
+use sea_orm::sea_query::Expr;
 use sea_orm::*;
 use std::sync::Arc;
 use uuid::Uuid;
@@ -14,7 +15,7 @@ use uuid::Uuid;
 /// multi-device login tracking and session lifecycle management.
 #[async_trait]
 pub trait UserSessionRepositoryTrait: Send + Sync {
-    /// Creates a new session in the database.
+    /// Creates a new session in the database, starting a new token family.
     async fn create_session(
         &self,
         id: Option<Uuid>,
@@ -29,11 +30,44 @@ pub trait UserSessionRepositoryTrait: Send + Sync {
     async fn find_by_refresh_token_hash(&self, hash: &str)
         -> Result<Option<UserSession>, AppError>;
 
+    /// Finds a session by ID.
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<UserSession>, AppError>;
+
+    /// Lists a user's sessions that are neither rotated nor expired, newest first.
+    async fn find_active_by_user_id(&self, user_id: Uuid) -> Result<Vec<UserSession>, AppError>;
+
+    /// Marks `parent` as rotated and creates its successor in the same family.
+    ///
+    /// Returns `None` without creating a session if `parent` was already rotated,
+    /// so that one refresh token can only ever be exchanged once.
+    async fn rotate_session(
+        &self,
+        parent: &UserSession,
+        id: Uuid,
+        refresh_token_hash: String,
+        user_agent: Option<String>,
+        ip_address: Option<String>,
+        expires_at: DateTime<Utc>,
+    ) -> Result<Option<UserSession>, AppError>;
+
     /// Deletes a specific session by ID.
     async fn delete_session(&self, id: Uuid) -> Result<(), AppError>;
 
+    /// Deletes every session of a token family.
+    async fn delete_family(&self, family_id: Uuid) -> Result<(), AppError>;
+
     /// Deletes all sessions for a specific user.
     async fn delete_all_sessions_for_user(&self, user_id: Uuid) -> Result<(), AppError>;
+
+    /// Deletes sessions that expired before `now` and returns how many were removed.
+    async fn delete_expired(&self, now: DateTime<Utc>) -> Result<u64, AppError>;
+
+    /// Deletes all sessions for a user except those of one token family.
+    async fn delete_other_sessions_for_user(
+        &self,
+        user_id: Uuid,
+        family_id: Uuid,
+    ) -> Result<(), AppError>;
 }
 
 /// User session repository implementation using SeaORM.
@@ -65,14 +99,19 @@ impl UserSessionRepositoryTrait for UserSessionRepository {
         ip_address: Option<String>,
         expires_at: DateTime<Utc>,
     ) -> Result<UserSession, AppError> {
+        let id = id.unwrap_or_else(Uuid::new_v4); // Use provided or generate new
         let session = user_session::ActiveModel {
-            id: Set(id.unwrap_or_else(Uuid::new_v4)), // Use provided or generate new
+            id: Set(id),
             user_id: Set(user_id),
             refresh_token_hash: Set(refresh_token_hash),
             user_agent: Set(user_agent),
             ip_address: Set(ip_address),
             expires_at: Set(expires_at),
             created_at: Set(Utc::now()),
+            family_id: Set(id),
+            parent_id: Set(None),
+            rotated_at: Set(None),
+            last_used_at: Set(Some(Utc::now())),
         };
 
         UserSessionEntity::insert(session.clone())
@@ -96,6 +135,79 @@ impl UserSessionRepositoryTrait for UserSessionRepository {
         Ok(session)
     }
 
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<UserSession>, AppError> {
+        UserSessionEntity::find_by_id(id)
+            .one(&*self.db)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+
+    async fn find_active_by_user_id(&self, user_id: Uuid) -> Result<Vec<UserSession>, AppError> {
+        UserSessionEntity::find()
+            .filter(user_session::Column::UserId.eq(user_id))
+            .filter(user_session::Column::RotatedAt.is_null())
+            .filter(user_session::Column::ExpiresAt.gt(Utc::now()))
+            .order_by_desc(user_session::Column::CreatedAt)
+            .all(&*self.db)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+
+    async fn rotate_session(
+        &self,
+        parent: &UserSession,
+        id: Uuid,
+        refresh_token_hash: String,
+        user_agent: Option<String>,
+        ip_address: Option<String>,
+        expires_at: DateTime<Utc>,
+    ) -> Result<Option<UserSession>, AppError> {
+        let now = Utc::now();
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        // Conditional update so concurrent refreshes cannot both rotate the same session
+        let result = UserSessionEntity::update_many()
+            .col_expr(user_session::Column::RotatedAt, Expr::value(now))
+            .filter(user_session::Column::Id.eq(parent.id))
+            .filter(user_session::Column::RotatedAt.is_null())
+            .exec(&txn)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        if result.rows_affected == 0 {
+            return Ok(None);
+        }
+
+        let session = user_session::ActiveModel {
+            id: Set(id),
+            user_id: Set(parent.user_id),
+            refresh_token_hash: Set(refresh_token_hash),
+            user_agent: Set(user_agent),
+            ip_address: Set(ip_address),
+            expires_at: Set(expires_at),
+            created_at: Set(parent.created_at),
+            family_id: Set(parent.family_id),
+            parent_id: Set(Some(parent.id)),
+            rotated_at: Set(None),
+            last_used_at: Set(Some(now)),
+        };
+
+        UserSessionEntity::insert(session.clone())
+            .exec(&txn)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        txn.commit()
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Ok(Some(session.try_into_model().unwrap()))
+    }
+
     async fn delete_session(&self, id: Uuid) -> Result<(), AppError> {
         let result = UserSessionEntity::delete_by_id(id)
             .exec(&*self.db)
@@ -112,6 +224,16 @@ impl UserSessionRepositoryTrait for UserSessionRepository {
         Ok(())
     }
 
+    async fn delete_family(&self, family_id: Uuid) -> Result<(), AppError> {
+        UserSessionEntity::delete_many()
+            .filter(user_session::Column::FamilyId.eq(family_id))
+            .exec(&*self.db)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Ok(())
+    }
+
     async fn delete_all_sessions_for_user(&self, user_id: Uuid) -> Result<(), AppError> {
         UserSessionEntity::delete_many()
             .filter(user_session::Column::UserId.eq(user_id))
@@ -121,4 +243,29 @@ impl UserSessionRepositoryTrait for UserSessionRepository {
 
         Ok(())
     }
+
+    async fn delete_expired(&self, now: DateTime<Utc>) -> Result<u64, AppError> {
+        let result = UserSessionEntity::delete_many()
+            .filter(user_session::Column::ExpiresAt.lt(now))
+            .exec(&*self.db)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Ok(result.rows_affected)
+    }
+
+    async fn delete_other_sessions_for_user(
+        &self,
+        user_id: Uuid,
+        family_id: Uuid,
+    ) -> Result<(), AppError> {
+        UserSessionEntity::delete_many()
+            .filter(user_session::Column::UserId.eq(user_id))
+            .filter(user_session::Column::FamilyId.ne(family_id))
+            .exec(&*self.db)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Ok(())
+    }
 }
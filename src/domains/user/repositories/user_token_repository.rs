@@ -0,0 +1,134 @@
+use crate::domains::common::errors::AppError;
+use crate::domains::user::entities::user_token::{
+    self, Entity as UserTokenEntity, Model as UserToken,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sea_orm::sea_query::Expr;
+use sea_orm::*;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Trait defining user token repository operations.
+///
+/// This trait abstracts database operations for single-use tokens delivered
+/// out of band (e.g. password reset links).
+#[async_trait]
+pub trait UserTokenRepositoryTrait: Send + Sync {
+    /// Stores a new token hash, invalidating the user's unused tokens of the same purpose.
+    async fn issue(
+        &self,
+        user_id: Uuid,
+        tenant_id: Uuid,
+        purpose: &str,
+        token_hash: String,
+        expires_at: DateTime<Utc>,
+    ) -> Result<UserToken, AppError>;
+
+    /// Consumes an unused, unexpired token.
+    ///
+    /// Returns `None` if the token is unknown, expired, already used or issued
+    /// for another purpose.
+    async fn consume(&self, purpose: &str, token_hash: &str)
+        -> Result<Option<UserToken>, AppError>;
+}
+
+/// User token repository implementation using SeaORM.
+pub struct UserTokenRepository {
+    db: Arc<DatabaseConnection>,
+}
+
+impl UserTokenRepository {
+    /// Creates a new UserTokenRepository instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - Arc-wrapped database connection
+    pub fn new(db: Arc<DatabaseConnection>) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl UserTokenRepositoryTrait for UserTokenRepository {
+    async fn issue(
+        &self,
+        user_id: Uuid,
+        tenant_id: Uuid,
+        purpose: &str,
+        token_hash: String,
+        expires_at: DateTime<Utc>,
+    ) -> Result<UserToken, AppError> {
+        let txn = self
+            .db
+            .begin()
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        UserTokenEntity::delete_many()
+            .filter(user_token::Column::UserId.eq(user_id))
+            .filter(user_token::Column::Purpose.eq(purpose))
+            .filter(user_token::Column::UsedAt.is_null())
+            .exec(&txn)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let token = user_token::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            user_id: Set(user_id),
+            tenant_id: Set(tenant_id),
+            purpose: Set(purpose.to_string()),
+            token_hash: Set(token_hash),
+            expires_at: Set(expires_at),
+            used_at: Set(None),
+            created_at: Set(Utc::now()),
+        };
+
+        UserTokenEntity::insert(token.clone())
+            .exec(&txn)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        txn.commit()
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Ok(token.try_into_model().unwrap())
+    }
+
+    async fn consume(
+        &self,
+        purpose: &str,
+        token_hash: &str,
+    ) -> Result<Option<UserToken>, AppError> {
+        let now = Utc::now();
+        let token = UserTokenEntity::find()
+            .filter(user_token::Column::TokenHash.eq(token_hash))
+            .filter(user_token::Column::Purpose.eq(purpose))
+            .filter(user_token::Column::UsedAt.is_null())
+            .filter(user_token::Column::ExpiresAt.gt(now))
+            .one(&*self.db)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let Some(mut token) = token else {
+            return Ok(None);
+        };
+
+        // Conditional update so concurrent requests cannot both consume the same token
+        let result = UserTokenEntity::update_many()
+            .col_expr(user_token::Column::UsedAt, Expr::value(now))
+            .filter(user_token::Column::Id.eq(token.id))
+            .filter(user_token::Column::UsedAt.is_null())
+            .exec(&*self.db)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        if result.rows_affected == 0 {
+            return Ok(None);
+        }
+
+        token.used_at = Some(now);
+        Ok(Some(token))
+    }
+}
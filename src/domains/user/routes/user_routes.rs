@@ -1,7 +1,10 @@
 use crate::domains::auth::middlewares::auth_middleware;
+use crate::domains::auth::middlewares::impersonation_middleware::DenyImpersonationMiddleware;
+use crate::domains::auth::middlewares::step_up_middleware::StepUpMiddleware;
 use crate::domains::tenant::middlewares::api_key_middleware::ApiKeyMiddleware;
 use crate::domains::user::controllers::user_controller::{
-    delete_user, get_all_users, get_user, update_user,
+    delete_user, get_all_users, get_user, impersonate_user, unlock_user, update_user,
+    update_user_status,
 };
 use crate::domains::user::controllers::user_details_controller::{
     get_user_details, update_user_details, upload_profile_picture,
@@ -20,8 +23,13 @@ use actix_web_httpauth::middleware::HttpAuthentication;
 /// - `DELETE /users` - Delete current user (from JWT) - Returns ID only
 /// - `PUT /users/details` - Update current user's details (from JWT) - Returns ID only
 /// - `PATCH /users/uploads` - Upload profile picture (from JWT) - Returns ID only
+/// - `POST /users/{id}/unlock` - Clear a user's login lockout (admin only)
+/// - `PUT /users/{id}/status` - Suspend, ban, approve or reactivate a user (admin only)
+/// - `POST /users/{id}/impersonate` - Get a short-lived token to act as a user (admin only)
 ///
-/// All routes require JWT Bearer token authentication.
+/// All routes require JWT Bearer token authentication. `PUT /users`,
+/// `DELETE /users` and the admin routes under `/users/{id}` refuse
+/// impersonation tokens.
 pub fn configure_user_routes(cfg: &mut web::ServiceConfig) {
     let jwt_auth = HttpAuthentication::bearer(auth_middleware::validator);
 
@@ -33,10 +41,40 @@ pub fn configure_user_routes(cfg: &mut web::ServiceConfig) {
             .wrap(ApiKeyMiddleware) // Apply ApiKey middleware to resolve Tenant ID
             .route("", web::get().to(get_user)) // GET /users (current user)
             .route("/all", web::get().to(get_all_users)) // GET /users/all
-            .route("", web::put().to(update_user)) // PUT /users (current user)
-            .route("", web::delete().to(delete_user)) // DELETE /users (current user)
+            .route(
+                "",
+                web::put()
+                    .to(update_user)
+                    .wrap(StepUpMiddleware::from_config())
+                    .wrap(DenyImpersonationMiddleware),
+            ) // PUT /users (current user)
+            .route(
+                "",
+                web::delete()
+                    .to(delete_user)
+                    .wrap(StepUpMiddleware::from_config())
+                    .wrap(DenyImpersonationMiddleware),
+            ) // DELETE /users (current user)
             .route("/details", web::get().to(get_user_details)) // GET /users/details
             .route("/details", web::put().to(update_user_details)) // PUT /users/details
-            .route("/uploads", web::patch().to(upload_profile_picture)), // PATCH /users/uploads
+            .route("/uploads", web::patch().to(upload_profile_picture)) // PATCH /users/uploads
+            .route(
+                "/{id}/unlock",
+                web::post()
+                    .to(unlock_user)
+                    .wrap(DenyImpersonationMiddleware),
+            ) // POST /users/{id}/unlock
+            .route(
+                "/{id}/status",
+                web::put()
+                    .to(update_user_status)
+                    .wrap(DenyImpersonationMiddleware),
+            ) // PUT /users/{id}/status
+            .route(
+                "/{id}/impersonate",
+                web::post()
+                    .to(impersonate_user)
+                    .wrap(DenyImpersonationMiddleware),
+            ), // POST /users/{id}/impersonate
     );
 }
@@ -1,13 +1,19 @@
-use crate::domains::common::errors::AppError;
+use crate::domains::auth::repositories::token_denylist_repository::TokenDenylistRepositoryTrait;
+use crate::domains::common::errors::{AppError, ValidationDetail};
+use crate::domains::common::utils::config::Config;
 use crate::domains::tenant::repositories::user_tenant_repository::UserTenantRepositoryTrait;
 use crate::domains::user::dtos::user_details_dto::UserDetailsResponse;
-use crate::domains::user::dtos::user_dto::{UpdateUserRequest, UserResponse};
-use crate::domains::user::entities::user::Model as User;
+use crate::domains::user::dtos::user_dto::{
+    UpdateUserRequest, UpdateUserStatusRequest, UserResponse,
+};
+use crate::domains::user::entities::user::{Model as User, STATUS_ACTIVE, USER_STATUSES};
 use crate::domains::user::entities::user_details::Model as UserDetails;
 use crate::domains::user::repositories::user_details_repository::UserDetailsRepositoryTrait;
 use crate::domains::user::repositories::user_repository::UserRepositoryTrait;
+use crate::domains::user::repositories::user_session_repository::UserSessionRepositoryTrait;
 use crate::domains::user::validators::user_validator;
 use std::sync::Arc;
+use std::time::Duration;
 use uuid::Uuid;
 
 /// User use case orchestrating user management business logic.
@@ -19,6 +25,8 @@ pub struct UserUseCase {
     repository: Arc<dyn UserRepositoryTrait>,
     user_details_repository: Arc<dyn UserDetailsRepositoryTrait>,
     user_tenant_repository: Arc<dyn UserTenantRepositoryTrait>,
+    session_repository: Arc<dyn UserSessionRepositoryTrait>,
+    token_denylist_repository: Arc<dyn TokenDenylistRepositoryTrait>,
 }
 
 impl UserUseCase {
@@ -29,15 +37,21 @@ impl UserUseCase {
     /// * `repository` - Arc-wrapped user repository implementation
     /// * `user_details_repository` - Arc-wrapped user_details repository implementation
     /// * `user_tenant_repository` - Arc-wrapped user_tenant repository implementation
+    /// * `session_repository` - Arc-wrapped user_session repository implementation
+    /// * `token_denylist_repository` - Denylist of revoked access tokens
     pub fn new(
         repository: Arc<dyn UserRepositoryTrait>,
         user_details_repository: Arc<dyn UserDetailsRepositoryTrait>,
         user_tenant_repository: Arc<dyn UserTenantRepositoryTrait>,
+        session_repository: Arc<dyn UserSessionRepositoryTrait>,
+        token_denylist_repository: Arc<dyn TokenDenylistRepositoryTrait>,
     ) -> Self {
         Self {
             repository,
             user_details_repository,
             user_tenant_repository,
+            session_repository,
+            token_denylist_repository,
         }
     }
 
@@ -175,8 +189,183 @@ impl UserUseCase {
     /// # Returns
     ///
     /// Returns `Ok(())` if successful, or `AppError::NotFound` if user doesn't exist.
+    /// Access tokens already issued to the user are denied.
     pub async fn delete_user(&self, id: Uuid) -> Result<(), AppError> {
-        self.repository.delete(id).await
+        self.repository.delete(id).await?;
+
+        let lifetime = Config::get().access_token_expiry.max(0) as u64;
+        self.token_denylist_repository
+            .deny_subject(&id.to_string(), Duration::from_secs(lifetime))
+            .await
+    }
+
+    /// Clears a user's login lockout and failed login count.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - UUID of the user to unlock
+    /// * `tenant_id` - Tenant of the requesting admin
+    /// * `requesting_user_role` - Role of the requesting user in that tenant
+    ///
+    /// # Errors
+    ///
+    /// - `AppError::Forbidden` if the requesting user is not an admin
+    /// - `AppError::NotFound` if the user does not exist or is not a member of the tenant
+    /// - `AppError::Forbidden` if the user is also a member of another tenant
+    pub async fn unlock_user(
+        &self,
+        id: Uuid,
+        tenant_id: Uuid,
+        requesting_user_role: &str,
+    ) -> Result<(), AppError> {
+        if requesting_user_role != "admin" {
+            return Err(AppError::Forbidden("Forbidden".to_string()));
+        }
+
+        let not_found = || AppError::NotFound(format!("User with id {} not found", id));
+        let user = self
+            .repository
+            .find_by_id(id)
+            .await?
+            .ok_or_else(not_found)?;
+
+        self.managed_user_role(user.id, tenant_id).await?;
+
+        self.repository.set_login_failures(user.id, 0, None).await?;
+        log::info!("unlock_user: user_id={}, tenant_id={}", user.id, tenant_id);
+
+        Ok(())
+    }
+
+    /// Changes a user's account status.
+    ///
+    /// Setting a user back to `active` clears the reason and expiry. Any other
+    /// status revokes all of the user's sessions and denies their issued access
+    /// tokens, so that neither keeps working.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - UUID of the user to update
+    /// * `tenant_id` - Tenant of the requesting admin
+    /// * `requesting_user_id` - UUID of the requesting admin
+    /// * `requesting_user_role` - Role of the requesting user in that tenant
+    /// * `req` - New status, optional reason and optional expiry
+    ///
+    /// # Errors
+    ///
+    /// - `AppError::Forbidden` if the requesting user is not an admin
+    /// - `AppError::BadRequest` if an admin tries to change their own status
+    /// - `AppError::ValidationError` if the status is unknown or the expiry is in the past
+    /// - `AppError::NotFound` if the user does not exist or is not a member of the tenant
+    /// - `AppError::Forbidden` if the user is also a member of another tenant
+    pub async fn set_user_status(
+        &self,
+        id: Uuid,
+        tenant_id: Uuid,
+        requesting_user_id: Uuid,
+        requesting_user_role: &str,
+        req: UpdateUserStatusRequest,
+    ) -> Result<UserResponse, AppError> {
+        if requesting_user_role != "admin" {
+            return Err(AppError::Forbidden("Forbidden".to_string()));
+        }
+
+        if id == requesting_user_id {
+            return Err(AppError::BadRequest(
+                "You cannot change your own account status".to_string(),
+            ));
+        }
+
+        if !USER_STATUSES.contains(&req.status.as_str()) {
+            let message = format!("Status must be one of: {}", USER_STATUSES.join(", "));
+            return Err(AppError::ValidationError(
+                message.clone(),
+                Some(vec![ValidationDetail {
+                    field: "status".to_string(),
+                    message,
+                }]),
+            ));
+        }
+
+        if req.expires_at.is_some_and(|at| at <= chrono::Utc::now()) {
+            return Err(AppError::ValidationError(
+                "Expiry must be in the future".to_string(),
+                Some(vec![ValidationDetail {
+                    field: "expires_at".to_string(),
+                    message: "Expiry must be in the future".to_string(),
+                }]),
+            ));
+        }
+
+        let not_found = || AppError::NotFound(format!("User with id {} not found", id));
+        let user = self
+            .repository
+            .find_by_id(id)
+            .await?
+            .ok_or_else(not_found)?;
+
+        let role = self.managed_user_role(user.id, tenant_id).await?;
+
+        let (reason, expires_at) = if req.status == STATUS_ACTIVE {
+            (None, None)
+        } else {
+            (req.reason, req.expires_at)
+        };
+
+        let user = self
+            .repository
+            .set_status(user.id, req.status, reason, expires_at)
+            .await?;
+
+        if !user.is_active() {
+            self.session_repository
+                .delete_all_sessions_for_user(user.id)
+                .await?;
+
+            let lifetime = Config::get().access_token_expiry.max(0) as u64;
+            self.token_denylist_repository
+                .deny_subject(&user.id.to_string(), Duration::from_secs(lifetime))
+                .await?;
+        }
+
+        log::info!(
+            "set_user_status: user_id={}, tenant_id={}, status={}",
+            user.id,
+            tenant_id,
+            user.status
+        );
+
+        let user_details = self
+            .user_details_repository
+            .find_by_user_id(user.id)
+            .await?;
+
+        Ok(Self::user_to_response(user, user_details, role))
+    }
+
+    /// Returns the user's role in the admin's tenant.
+    ///
+    /// Status and lockout are stored on the user, not per tenant, so an admin
+    /// may only change them for users that belong to no other tenant.
+    async fn managed_user_role(&self, user_id: Uuid, tenant_id: Uuid) -> Result<String, AppError> {
+        let tenants = self
+            .user_tenant_repository
+            .get_all_tenants_for_user(user_id)
+            .await?;
+
+        let role = tenants
+            .iter()
+            .find(|t| t.tenant_id == tenant_id)
+            .map(|t| t.role.clone())
+            .ok_or_else(|| AppError::NotFound(format!("User with id {} not found", user_id)))?;
+
+        if tenants.iter().any(|t| t.tenant_id != tenant_id) {
+            return Err(AppError::Forbidden(
+                "User is also a member of other tenants".to_string(),
+            ));
+        }
+
+        Ok(role)
     }
 
     /// Converts a User entity to UserResponse DTO with optional user_details.
@@ -188,10 +377,17 @@ impl UserUseCase {
     ) -> UserResponse {
         use crate::domains::common::utils::url_helper::to_full_url;
 
+        let status = user.effective_status().to_string();
+
         UserResponse {
             id: user.id,
             username: user.username,
             email: user.email,
+            email_verified: user.email_verified_at.is_some(),
+            email_verified_at: user.email_verified_at,
+            status,
+            status_reason: user.status_reason,
+            status_expires_at: user.status_expires_at,
             created_at: user.created_at,
             updated_at: user.updated_at,
             role,
@@ -1,14 +1,20 @@
 #[cfg(test)]
 mod tests {
-    use crate::domains::tenant::repositories::user_tenant_repository::UserTenantRepositoryTrait;
+    use crate::domains::auth::repositories::token_denylist_repository::TokenDenylistRepositoryTrait;
+    use crate::domains::tenant::repositories::user_tenant_repository::{
+        UserTenantInfo, UserTenantRepositoryTrait,
+    };
+    use crate::domains::user::dtos::user_dto::UpdateUserStatusRequest;
     use crate::domains::user::entities::user::Model as User;
+    use crate::domains::user::entities::user_session::Model as UserSession;
     use crate::domains::user::repositories::user_details_repository::UserDetailsRepositoryTrait;
     use crate::domains::user::repositories::user_repository::UserRepositoryTrait;
+    use crate::domains::user::repositories::user_session_repository::UserSessionRepositoryTrait;
     use crate::domains::user::usecases::user_usecase::UserUseCase;
 
     use crate::domains::common::errors::AppError;
     use async_trait::async_trait;
-    use chrono::Utc;
+    use chrono::{DateTime, Utc};
     use mockall::mock;
     use mockall::predicate::*;
     use std::sync::Arc;
@@ -28,6 +34,12 @@ mod tests {
             async fn find_by_email_with_deleted(&self, email: &str) -> Result<Option<User>, AppError>;
             async fn find_by_username_with_deleted(&self, username: &str) -> Result<Option<User>, AppError>;
             async fn restore(&self, id: Uuid, req: crate::domains::user::dtos::user_dto::CreateUserRequest) -> Result<User, AppError>;
+            async fn mark_email_verified(&self, id: Uuid) -> Result<User, AppError>;
+            async fn set_login_failures(&self, id: Uuid, failed_login_attempts: i32, locked_until: Option<DateTime<Utc>>) -> Result<(), AppError>;
+            async fn increment_login_failures(&self, id: Uuid) -> Result<i32, AppError>;
+            async fn lock_until(&self, id: Uuid, locked_until: DateTime<Utc>) -> Result<(), AppError>;
+            async fn set_status(&self, id: Uuid, status: String, reason: Option<String>, expires_at: Option<DateTime<Utc>>) -> Result<User, AppError>;
+            async fn purge_deleted_before(&self, cutoff: DateTime<Utc>) -> Result<u64, AppError>;
         }
     }
 
@@ -50,7 +62,36 @@ mod tests {
         impl UserTenantRepositoryTrait for UserTenantRepository {
              async fn add_user_to_tenant(&self, user_id: Uuid, tenant_id: Uuid, role: String) -> Result<(), AppError>;
              async fn get_user_roles_in_tenant(&self, user_id: Uuid, tenant_id: Uuid) -> Result<Vec<String>, AppError>;
-             async fn get_all_tenants_for_user(&self, user_id: Uuid) -> Result<Vec<crate::domains::tenant::repositories::user_tenant_repository::UserTenantInfo>, AppError>;
+             async fn get_all_tenants_for_user(&self, user_id: Uuid) -> Result<Vec<UserTenantInfo>, AppError>;
+        }
+    }
+
+    // Mocking UserSessionRepositoryTrait
+    mock! {
+        pub UserSessionRepository {}
+        #[async_trait]
+        impl UserSessionRepositoryTrait for UserSessionRepository {
+            async fn create_session(&self, id: Option<Uuid>, user_id: Uuid, refresh_token_hash: String, user_agent: Option<String>, ip_address: Option<String>, expires_at: DateTime<Utc>) -> Result<UserSession, AppError>;
+            async fn find_by_refresh_token_hash(&self, hash: &str) -> Result<Option<UserSession>, AppError>;
+            async fn find_by_id(&self, id: Uuid) -> Result<Option<UserSession>, AppError>;
+            async fn find_active_by_user_id(&self, user_id: Uuid) -> Result<Vec<UserSession>, AppError>;
+            async fn rotate_session(&self, parent: &UserSession, id: Uuid, refresh_token_hash: String, user_agent: Option<String>, ip_address: Option<String>, expires_at: DateTime<Utc>) -> Result<Option<UserSession>, AppError>;
+            async fn delete_session(&self, id: Uuid) -> Result<(), AppError>;
+            async fn delete_family(&self, family_id: Uuid) -> Result<(), AppError>;
+            async fn delete_all_sessions_for_user(&self, user_id: Uuid) -> Result<(), AppError>;
+            async fn delete_expired(&self, now: DateTime<Utc>) -> Result<u64, AppError>;
+            async fn delete_other_sessions_for_user(&self, user_id: Uuid, family_id: Uuid) -> Result<(), AppError>;
+        }
+    }
+
+    mock! {
+        pub TokenDenylistRepository {}
+        #[async_trait]
+        impl TokenDenylistRepositoryTrait for TokenDenylistRepository {
+            async fn deny(&self, jti: &str, ttl: std::time::Duration) -> Result<(), AppError>;
+            async fn is_denied(&self, jti: &str) -> Result<bool, AppError>;
+            async fn deny_subject(&self, subject: &str, ttl: std::time::Duration) -> Result<(), AppError>;
+            async fn subject_denied_at(&self, subject: &str) -> Result<Option<i64>, AppError>;
         }
     }
 
@@ -68,6 +109,12 @@ mod tests {
             username: "testuser".to_string(),
             email: "test@example.com".to_string(),
             password_hash: "hashed".to_string(),
+            email_verified_at: None,
+            failed_login_attempts: 0,
+            locked_until: None,
+            status: "active".to_string(),
+            status_reason: None,
+            status_expires_at: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
             deleted_at: None,
@@ -98,6 +145,8 @@ mod tests {
             Arc::new(mock_user_repo),
             Arc::new(mock_details_repo),
             Arc::new(mock_tenant_repo),
+            Arc::new(MockUserSessionRepository::new()),
+            Arc::new(MockTokenDenylistRepository::new()),
         );
 
         let result = usecase.get_user(user_id, tenant_id).await;
@@ -127,6 +176,8 @@ mod tests {
             Arc::new(mock_user_repo),
             Arc::new(mock_details_repo),
             Arc::new(mock_tenant_repo),
+            Arc::new(MockUserSessionRepository::new()),
+            Arc::new(MockTokenDenylistRepository::new()),
         );
 
         let result = usecase.get_user(user_id, tenant_id).await;
@@ -137,4 +188,387 @@ mod tests {
             _ => panic!("Expected NotFound error"),
         }
     }
+
+    fn locked_user(user_id: Uuid) -> User {
+        User {
+            id: user_id,
+            username: "lockeduser".to_string(),
+            email: "locked@example.com".to_string(),
+            password_hash: "hashed".to_string(),
+            email_verified_at: None,
+            failed_login_attempts: 5,
+            locked_until: Some(Utc::now() + chrono::Duration::minutes(5)),
+            status: "active".to_string(),
+            status_reason: None,
+            status_expires_at: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+        }
+    }
+
+    fn member_of(tenant_id: Uuid) -> UserTenantInfo {
+        UserTenantInfo {
+            tenant_id,
+            role: "user".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unlock_user_clears_lockout() {
+        let mut mock_user_repo = MockUserRepository::new();
+        let mut mock_tenant_repo = MockUserTenantRepository::new();
+
+        let user_id = Uuid::new_v4();
+        let tenant_id = Uuid::new_v4();
+        let user = locked_user(user_id);
+
+        mock_user_repo
+            .expect_find_by_id()
+            .with(eq(user_id))
+            .returning(move |_| Ok(Some(user.clone())));
+        mock_user_repo
+            .expect_set_login_failures()
+            .with(eq(user_id), eq(0), eq(None))
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+        mock_tenant_repo
+            .expect_get_all_tenants_for_user()
+            .with(eq(user_id))
+            .returning(move |_| Ok(vec![member_of(tenant_id)]));
+
+        let usecase = UserUseCase::new(
+            Arc::new(mock_user_repo),
+            Arc::new(MockUserDetailsRepository::new()),
+            Arc::new(mock_tenant_repo),
+            Arc::new(MockUserSessionRepository::new()),
+            Arc::new(MockTokenDenylistRepository::new()),
+        );
+
+        assert!(usecase
+            .unlock_user(user_id, tenant_id, "admin")
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_unlock_user_requires_admin() {
+        let mut mock_user_repo = MockUserRepository::new();
+        mock_user_repo.expect_set_login_failures().never();
+
+        let usecase = UserUseCase::new(
+            Arc::new(mock_user_repo),
+            Arc::new(MockUserDetailsRepository::new()),
+            Arc::new(MockUserTenantRepository::new()),
+            Arc::new(MockUserSessionRepository::new()),
+            Arc::new(MockTokenDenylistRepository::new()),
+        );
+
+        let result = usecase
+            .unlock_user(Uuid::new_v4(), Uuid::new_v4(), "user")
+            .await;
+
+        assert!(matches!(result, Err(AppError::Forbidden(_))));
+    }
+
+    #[tokio::test]
+    async fn test_unlock_user_from_other_tenant_not_found() {
+        let mut mock_user_repo = MockUserRepository::new();
+        let mut mock_tenant_repo = MockUserTenantRepository::new();
+
+        let user_id = Uuid::new_v4();
+        let user = locked_user(user_id);
+
+        mock_user_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(user.clone())));
+        mock_user_repo.expect_set_login_failures().never();
+        mock_tenant_repo
+            .expect_get_all_tenants_for_user()
+            .returning(|_| Ok(vec![member_of(Uuid::new_v4())]));
+
+        let usecase = UserUseCase::new(
+            Arc::new(mock_user_repo),
+            Arc::new(MockUserDetailsRepository::new()),
+            Arc::new(mock_tenant_repo),
+            Arc::new(MockUserSessionRepository::new()),
+            Arc::new(MockTokenDenylistRepository::new()),
+        );
+
+        let result = usecase.unlock_user(user_id, Uuid::new_v4(), "admin").await;
+
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+    }
+
+    fn status_request(status: &str) -> UpdateUserStatusRequest {
+        UpdateUserStatusRequest {
+            status: status.to_string(),
+            reason: Some("Terms of service violation".to_string()),
+            expires_at: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_set_user_status_suspend_revokes_sessions() {
+        crate::domains::common::utils::config::Config::init_for_test();
+        let mut mock_user_repo = MockUserRepository::new();
+        let mut mock_details_repo = MockUserDetailsRepository::new();
+        let mut mock_tenant_repo = MockUserTenantRepository::new();
+        let mut mock_session_repo = MockUserSessionRepository::new();
+
+        let user_id = Uuid::new_v4();
+        let tenant_id = Uuid::new_v4();
+        let user = locked_user(user_id);
+
+        mock_user_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(user.clone())));
+        mock_user_repo
+            .expect_set_status()
+            .with(eq(user_id), eq("suspended".to_string()), always(), always())
+            .times(1)
+            .returning(move |id, status, reason, expires_at| {
+                let mut user = locked_user(id);
+                user.status = status;
+                user.status_reason = reason;
+                user.status_expires_at = expires_at;
+                Ok(user)
+            });
+        mock_tenant_repo
+            .expect_get_all_tenants_for_user()
+            .returning(move |_| Ok(vec![member_of(tenant_id)]));
+        mock_details_repo
+            .expect_find_by_user_id()
+            .returning(|_| Ok(None));
+        mock_session_repo
+            .expect_delete_all_sessions_for_user()
+            .with(eq(user_id))
+            .times(1)
+            .returning(|_| Ok(()));
+        let mut mock_denylist_repo = MockTokenDenylistRepository::new();
+        mock_denylist_repo
+            .expect_deny_subject()
+            .withf(move |subject, _| subject == user_id.to_string())
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let usecase = UserUseCase::new(
+            Arc::new(mock_user_repo),
+            Arc::new(mock_details_repo),
+            Arc::new(mock_tenant_repo),
+            Arc::new(mock_session_repo),
+            Arc::new(mock_denylist_repo),
+        );
+
+        let response = usecase
+            .set_user_status(
+                user_id,
+                tenant_id,
+                Uuid::new_v4(),
+                "admin",
+                status_request("suspended"),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status, "suspended");
+        assert_eq!(
+            response.status_reason.as_deref(),
+            Some("Terms of service violation")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_set_user_status_active_clears_reason_and_keeps_sessions() {
+        let mut mock_user_repo = MockUserRepository::new();
+        let mut mock_details_repo = MockUserDetailsRepository::new();
+        let mut mock_tenant_repo = MockUserTenantRepository::new();
+        let mut mock_session_repo = MockUserSessionRepository::new();
+
+        let user_id = Uuid::new_v4();
+        let tenant_id = Uuid::new_v4();
+        let user = locked_user(user_id);
+
+        mock_user_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(user.clone())));
+        mock_user_repo
+            .expect_set_status()
+            .with(eq(user_id), eq("active".to_string()), eq(None), eq(None))
+            .times(1)
+            .returning(|id, _, _, _| Ok(locked_user(id)));
+        mock_tenant_repo
+            .expect_get_all_tenants_for_user()
+            .returning(move |_| Ok(vec![member_of(tenant_id)]));
+        mock_details_repo
+            .expect_find_by_user_id()
+            .returning(|_| Ok(None));
+        mock_session_repo
+            .expect_delete_all_sessions_for_user()
+            .never();
+
+        let usecase = UserUseCase::new(
+            Arc::new(mock_user_repo),
+            Arc::new(mock_details_repo),
+            Arc::new(mock_tenant_repo),
+            Arc::new(mock_session_repo),
+            Arc::new(MockTokenDenylistRepository::new()),
+        );
+
+        let response = usecase
+            .set_user_status(
+                user_id,
+                tenant_id,
+                Uuid::new_v4(),
+                "admin",
+                status_request("active"),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status, "active");
+    }
+
+    #[tokio::test]
+    async fn test_set_user_status_rejects_unknown_status() {
+        let mut mock_user_repo = MockUserRepository::new();
+        mock_user_repo.expect_set_status().never();
+
+        let usecase = UserUseCase::new(
+            Arc::new(mock_user_repo),
+            Arc::new(MockUserDetailsRepository::new()),
+            Arc::new(MockUserTenantRepository::new()),
+            Arc::new(MockUserSessionRepository::new()),
+            Arc::new(MockTokenDenylistRepository::new()),
+        );
+
+        let result = usecase
+            .set_user_status(
+                Uuid::new_v4(),
+                Uuid::new_v4(),
+                Uuid::new_v4(),
+                "admin",
+                status_request("deleted"),
+            )
+            .await;
+
+        assert!(matches!(result, Err(AppError::ValidationError(_, _))));
+    }
+
+    #[tokio::test]
+    async fn test_set_user_status_requires_admin() {
+        let mut mock_user_repo = MockUserRepository::new();
+        mock_user_repo.expect_set_status().never();
+
+        let usecase = UserUseCase::new(
+            Arc::new(mock_user_repo),
+            Arc::new(MockUserDetailsRepository::new()),
+            Arc::new(MockUserTenantRepository::new()),
+            Arc::new(MockUserSessionRepository::new()),
+            Arc::new(MockTokenDenylistRepository::new()),
+        );
+
+        let result = usecase
+            .set_user_status(
+                Uuid::new_v4(),
+                Uuid::new_v4(),
+                Uuid::new_v4(),
+                "user",
+                status_request("banned"),
+            )
+            .await;
+
+        assert!(matches!(result, Err(AppError::Forbidden(_))));
+    }
+
+    #[tokio::test]
+    async fn test_set_user_status_rejects_member_of_other_tenants() {
+        let mut mock_user_repo = MockUserRepository::new();
+        let mut mock_tenant_repo = MockUserTenantRepository::new();
+
+        let user_id = Uuid::new_v4();
+        let tenant_id = Uuid::new_v4();
+        let user = locked_user(user_id);
+
+        mock_user_repo
+            .expect_find_by_id()
+            .returning(move |_| Ok(Some(user.clone())));
+        mock_user_repo.expect_set_status().never();
+        mock_tenant_repo
+            .expect_get_all_tenants_for_user()
+            .returning(move |_| Ok(vec![member_of(tenant_id), member_of(Uuid::new_v4())]));
+
+        let usecase = UserUseCase::new(
+            Arc::new(mock_user_repo),
+            Arc::new(MockUserDetailsRepository::new()),
+            Arc::new(mock_tenant_repo),
+            Arc::new(MockUserSessionRepository::new()),
+            Arc::new(MockTokenDenylistRepository::new()),
+        );
+
+        let result = usecase
+            .set_user_status(
+                user_id,
+                tenant_id,
+                Uuid::new_v4(),
+                "admin",
+                status_request("banned"),
+            )
+            .await;
+
+        assert!(matches!(result, Err(AppError::Forbidden(_))));
+    }
+
+    #[tokio::test]
+    async fn test_set_user_status_rejects_own_account() {
+        let user_id = Uuid::new_v4();
+        let usecase = UserUseCase::new(
+            Arc::new(MockUserRepository::new()),
+            Arc::new(MockUserDetailsRepository::new()),
+            Arc::new(MockUserTenantRepository::new()),
+            Arc::new(MockUserSessionRepository::new()),
+            Arc::new(MockTokenDenylistRepository::new()),
+        );
+
+        let result = usecase
+            .set_user_status(
+                user_id,
+                Uuid::new_v4(),
+                user_id,
+                "admin",
+                status_request("banned"),
+            )
+            .await;
+
+        assert!(matches!(result, Err(AppError::BadRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn test_delete_user_denies_issued_tokens() {
+        crate::domains::common::utils::config::Config::init_for_test();
+        let user_id = Uuid::new_v4();
+
+        let mut mock_user_repo = MockUserRepository::new();
+        mock_user_repo
+            .expect_delete()
+            .with(eq(user_id))
+            .times(1)
+            .returning(|_| Ok(()));
+        let mut mock_denylist_repo = MockTokenDenylistRepository::new();
+        mock_denylist_repo
+            .expect_deny_subject()
+            .withf(move |subject, _| subject == user_id.to_string())
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let usecase = UserUseCase::new(
+            Arc::new(mock_user_repo),
+            Arc::new(MockUserDetailsRepository::new()),
+            Arc::new(MockUserTenantRepository::new()),
+            Arc::new(MockUserSessionRepository::new()),
+            Arc::new(mock_denylist_repo),
+        );
+
+        usecase.delete_user(user_id).await.unwrap();
+    }
 }
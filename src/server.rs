@@ -16,24 +16,45 @@ use crate::domains::user::user_module::UserModule;
 
 // Repositories
 use crate::domains::mqtt::repositories::mqtt_repository::MqttRepository;
+use crate::domains::tenant::repositories::client_repository::ClientRepository;
 use crate::domains::tenant::repositories::tenant_repository::TenantRepository;
 use crate::domains::tenant::repositories::user_tenant_repository::UserTenantRepository;
 use crate::domains::user::repositories::user_activity_log_repository::UserActivityLogRepository;
 use crate::domains::user::repositories::user_details_repository::UserDetailsRepository;
+use crate::domains::user::repositories::user_mfa_repository::UserMfaRepository;
+use crate::domains::user::repositories::user_passkey_repository::UserPasskeyRepository;
+use crate::domains::user::repositories::user_recovery_code_repository::UserRecoveryCodeRepository;
 use crate::domains::user::repositories::user_repository::UserRepository;
 use crate::domains::user::repositories::user_session_repository::UserSessionRepository;
+use crate::domains::user::repositories::user_token_repository::UserTokenRepository;
 
 // UseCases
 use crate::domains::auth::usecases::auth_usecase::AuthUseCase;
+use crate::domains::auth::usecases::email_verification_usecase::EmailVerificationUseCase;
+use crate::domains::auth::usecases::oidc_usecase::OidcUseCase;
+use crate::domains::auth::usecases::passkey_usecase::PasskeyUseCase;
+use crate::domains::auth::usecases::password_reset_usecase::PasswordResetUseCase;
+use crate::domains::auth::usecases::passwordless_usecase::PasswordlessUseCase;
+use crate::domains::auth::usecases::session_usecase::SessionUseCase;
+use crate::domains::auth::usecases::token_usecase::TokenUseCase;
 use crate::domains::mqtt::usecases::mqtt_usecase::MqttUseCase;
+use crate::domains::tenant::usecases::client_usecase::ClientUseCase;
 use crate::domains::tenant::usecases::tenant_usecase::TenantUseCase;
 use crate::domains::user::usecases::user_details_usecase::UserDetailsUseCase;
 use crate::domains::user::usecases::user_usecase::UserUseCase;
 
+use crate::domains::common::dtos::response_dto::SuccessResponseDTO;
 use crate::domains::common::infrastructures::postgres_connection;
+use crate::domains::common::jobs::cache_cleanup_job::CacheCleanupJob;
+use crate::domains::common::jobs::scheduler::Scheduler;
+use crate::domains::common::jobs::session_cleanup_job::SessionCleanupJob;
+use crate::domains::common::jobs::signing_key_refresh_job::SigningKeyRefreshJob;
+use crate::domains::common::jobs::soft_delete_purge_job::SoftDeletePurgeJob;
 use crate::domains::common::middlewares::powered_by_middleware::PoweredByMiddleware;
 use crate::domains::common::middlewares::request_logger_middleware::RequestLoggerMiddleware;
+use crate::domains::tenant::middlewares::tenant_secret_middleware::TenantSecretMiddleware;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::watch;
 
 /// Health check endpoint handler.
@@ -43,6 +64,16 @@ async fn healthcheck() -> impl Responder {
         .body("OK")
 }
 
+/// Reports run counts and last results of the background jobs.
+///
+/// It must be protected by TenantSecretMiddleware.
+async fn job_metrics(scheduler: web::Data<Scheduler>) -> impl Responder {
+    HttpResponse::Ok().json(SuccessResponseDTO::new(
+        "Job metrics retrieved successfully",
+        scheduler.metrics(),
+    ))
+}
+
 /// Serves dynamic runtime configuration for the frontend.
 async fn serve_runtime_config(allowed_origins: web::Data<Vec<String>>) -> impl Responder {
     use crate::domains::common::utils::config::Config;
@@ -73,7 +104,7 @@ async fn serve_runtime_config(allowed_origins: web::Data<Vec<String>>) -> impl R
 /// 3. Establishes database connections
 /// 4. Sets up dependency injection
 /// 5. Configures HTTP server with middlewares
-/// 6. Starts health monitoring
+/// 6. Starts health monitoring and background jobs
 /// 7. Handles graceful shutdown
 
 pub async fn run_server() -> std::io::Result<()> {
@@ -229,12 +260,56 @@ pub async fn run_server() -> std::io::Result<()> {
     let user_details_repo = Arc::new(UserDetailsRepository::new(db_arc.clone(), cache.clone()));
     let user_session_repo = Arc::new(UserSessionRepository::new(db_arc.clone()));
     let user_activity_log_repo = Arc::new(UserActivityLogRepository::new(db_arc.clone()));
+    let user_mfa_repo = Arc::new(UserMfaRepository::new(db_arc.clone()));
+    let user_recovery_code_repo = Arc::new(UserRecoveryCodeRepository::new(db_arc.clone()));
+    let user_passkey_repo = Arc::new(UserPasskeyRepository::new(db_arc.clone()));
+    let user_token_repo = Arc::new(UserTokenRepository::new(db_arc.clone()));
     let user_tenant_repo = Arc::new(UserTenantRepository::new(db_arc.clone(), cache.clone()));
     let tenant_repo = Arc::new(TenantRepository::new(db_arc.clone(), cache.clone()));
+    let client_repo = Arc::new(ClientRepository::new(db_arc.clone()));
     let mqtt_repo = Arc::new(MqttRepository::new(db_arc.clone(), cache.clone()));
 
     use crate::domains::auth::repositories::invitation_code_repository::InvitationCodeRepository;
     let invitation_code_repo = Arc::new(InvitationCodeRepository::new(cache.clone()));
+    use crate::domains::auth::repositories::webauthn_challenge_repository::WebauthnChallengeRepository;
+    let webauthn_challenge_repo = Arc::new(WebauthnChallengeRepository::new(cache.clone()));
+    use crate::domains::auth::repositories::authorization_code_repository::AuthorizationCodeRepository;
+    let authorization_code_repo = Arc::new(AuthorizationCodeRepository::new(cache.clone()));
+    use crate::domains::auth::repositories::device_code_repository::DeviceCodeRepository;
+    let device_code_repo = Arc::new(DeviceCodeRepository::new(cache.clone()));
+    use crate::domains::auth::repositories::passwordless_repository::PasswordlessRepository;
+    let passwordless_repo = Arc::new(PasswordlessRepository::new(cache.clone()));
+    use crate::domains::auth::repositories::token_denylist_repository::TokenDenylistRepository;
+    let token_denylist_repo = Arc::new(TokenDenylistRepository::new(cache.clone()));
+
+    // Rate limits on auth endpoints (RATE_LIMIT_*), tenants can override them
+    use crate::domains::common::infrastructures::rate_limiter::RateLimiter;
+    let rate_limiter = Arc::new(RateLimiter::from_config(
+        config,
+        cache.clone(),
+        tenant_repo.clone(),
+    ));
+
+    // JWT key ring, seeded from JWT_SECRET or JWT_PRIVATE_KEY_PATH on first start
+    use crate::domains::auth::repositories::jwt_signing_key_repository::JwtSigningKeyRepository;
+    use crate::domains::auth::usecases::signing_key_usecase::SigningKeyUseCase;
+    use crate::domains::common::utils::jwt::JwtService;
+    let signing_key_usecase = Arc::new(SigningKeyUseCase::new(
+        Arc::new(JwtSigningKeyRepository::new(db_arc.clone())),
+        JwtService::key_ring(),
+    ));
+    signing_key_usecase
+        .initialize()
+        .await
+        .map_err(|e| std::io::Error::other(e.to_string()))?;
+
+    // Outbound email transport and templates (MAIL_TRANSPORT, MAIL_TEMPLATE_DIR)
+    use crate::domains::common::infrastructures::email_templates::EmailTemplates;
+    use crate::domains::common::infrastructures::mailer;
+    let mailer = mailer::from_config(config).map_err(|e| std::io::Error::other(e.to_string()))?;
+    let email_templates = Arc::new(
+        EmailTemplates::from_config(config).map_err(|e| std::io::Error::other(e.to_string()))?,
+    );
 
     // ================================================================================================
     // 🧠 USECASE SECTION
@@ -243,6 +318,8 @@ pub async fn run_server() -> std::io::Result<()> {
         user_repo.clone(),
         user_details_repo.clone(),
         user_tenant_repo.clone(),
+        user_session_repo.clone(),
+        token_denylist_repo.clone(),
     ));
     let auth_usecase = Arc::new(AuthUseCase::new(
         user_repo.clone(),
@@ -251,10 +328,94 @@ pub async fn run_server() -> std::io::Result<()> {
         user_session_repo.clone(),
         user_activity_log_repo.clone(),
         invitation_code_repo.clone(),
+        user_mfa_repo.clone(),
+        user_recovery_code_repo.clone(),
+        tenant_repo.clone(),
+        token_denylist_repo.clone(),
+    ));
+    let passkey_usecase = Arc::new(PasskeyUseCase::new(
+        user_repo.clone(),
+        user_tenant_repo.clone(),
+        user_passkey_repo.clone(),
+        webauthn_challenge_repo.clone(),
+        auth_usecase.clone(),
+    ));
+    let password_reset_usecase = Arc::new(PasswordResetUseCase::new(
+        user_repo.clone(),
+        user_tenant_repo.clone(),
+        user_session_repo.clone(),
+        user_token_repo.clone(),
+        mailer.clone(),
+        email_templates.clone(),
+        auth_usecase.clone(),
+    ));
+    let email_verification_usecase = Arc::new(EmailVerificationUseCase::new(
+        user_repo.clone(),
+        user_tenant_repo.clone(),
+        user_token_repo.clone(),
+        mailer.clone(),
+        email_templates.clone(),
+        auth_usecase.clone(),
+    ));
+    let passwordless_usecase = Arc::new(PasswordlessUseCase::new(
+        user_repo.clone(),
+        user_tenant_repo.clone(),
+        tenant_repo.clone(),
+        passwordless_repo.clone(),
+        mailer.clone(),
+        email_templates.clone(),
+        auth_usecase.clone(),
+    ));
+    let session_usecase = Arc::new(SessionUseCase::new(
+        user_session_repo.clone(),
+        auth_usecase.clone(),
+    ));
+    let oidc_usecase = Arc::new(OidcUseCase::new(
+        authorization_code_repo.clone(),
+        device_code_repo.clone(),
+        client_repo.clone(),
+        tenant_repo.clone(),
+        user_repo.clone(),
+        token_denylist_repo.clone(),
+    ));
+    let token_usecase = Arc::new(TokenUseCase::new(
+        client_repo.clone(),
+        user_repo.clone(),
+        user_session_repo.clone(),
+        token_denylist_repo.clone(),
     ));
     let user_details_usecase = Arc::new(UserDetailsUseCase::new(user_details_repo.clone()));
     let tenant_usecase = Arc::new(TenantUseCase::new(tenant_repo.clone()));
-    let mqtt_usecase = Arc::new(MqttUseCase::new(mqtt_repo.clone()));
+    let client_usecase = Arc::new(ClientUseCase::new(client_repo.clone()));
+    let mqtt_usecase = Arc::new(MqttUseCase::new(mqtt_repo.clone(), user_repo.clone()));
+
+    // ================================================================================================
+    // ⏱️ JOB SECTION
+    // ================================================================================================
+    //
+    // Periodic cleanup jobs. An interval of 0 disables a job.
+    let mut scheduler = Scheduler::new();
+    scheduler.register(
+        Arc::new(SessionCleanupJob::new(user_session_repo.clone())),
+        Duration::from_secs(config.session_cleanup_interval),
+    );
+    scheduler.register(
+        Arc::new(CacheCleanupJob::new(cache.clone())),
+        Duration::from_secs(config.cache_cleanup_interval),
+    );
+    scheduler.register(
+        Arc::new(SoftDeletePurgeJob::new(
+            user_repo.clone(),
+            tenant_repo.clone(),
+            chrono::Duration::days(config.soft_delete_retention_days),
+        )),
+        Duration::from_secs(config.soft_delete_purge_interval),
+    );
+    scheduler.register(
+        Arc::new(SigningKeyRefreshJob::new(signing_key_usecase.clone())),
+        Duration::from_secs(config.signing_key_refresh_interval),
+    );
+    let scheduler = Arc::new(scheduler);
 
     // Prepare variables for the factory closure
     let db_for_factory = db.clone();
@@ -263,9 +424,21 @@ pub async fn run_server() -> std::io::Result<()> {
 
     let user_usecase_for_factory = user_usecase.clone();
     let auth_usecase_for_factory = auth_usecase.clone();
+    let passkey_usecase_for_factory = passkey_usecase.clone();
+    let password_reset_usecase_for_factory = password_reset_usecase.clone();
+    let email_verification_usecase_for_factory = email_verification_usecase.clone();
+    let passwordless_usecase_for_factory = passwordless_usecase.clone();
+    let session_usecase_for_factory = session_usecase.clone();
+    let signing_key_usecase_for_factory = signing_key_usecase.clone();
+    let oidc_usecase_for_factory = oidc_usecase.clone();
+    let token_usecase_for_factory = token_usecase.clone();
     let user_details_usecase_for_factory = user_details_usecase.clone();
     let tenant_usecase_for_factory = tenant_usecase.clone();
+    let client_usecase_for_factory = client_usecase.clone();
     let mqtt_usecase_for_factory = mqtt_usecase.clone();
+    let scheduler_for_factory = scheduler.clone();
+    let rate_limiter_for_factory = rate_limiter.clone();
+    let token_denylist_for_factory = token_denylist_repo.clone();
 
     let server = HttpServer::new(move || {
         let mut cors = actix_cors::Cors::default()
@@ -290,10 +463,24 @@ pub async fn run_server() -> std::io::Result<()> {
             // Register App Data
             .app_data(web::Data::new(user_usecase_for_factory.clone()))
             .app_data(web::Data::new(auth_usecase_for_factory.clone()))
+            .app_data(web::Data::new(passkey_usecase_for_factory.clone()))
+            .app_data(web::Data::new(password_reset_usecase_for_factory.clone()))
+            .app_data(web::Data::new(
+                email_verification_usecase_for_factory.clone(),
+            ))
+            .app_data(web::Data::new(passwordless_usecase_for_factory.clone()))
+            .app_data(web::Data::new(session_usecase_for_factory.clone()))
+            .app_data(web::Data::new(signing_key_usecase_for_factory.clone()))
+            .app_data(web::Data::new(oidc_usecase_for_factory.clone()))
+            .app_data(web::Data::new(token_usecase_for_factory.clone()))
             .app_data(web::Data::new(user_details_usecase_for_factory.clone()))
             .app_data(web::Data::new(tenant_usecase_for_factory.clone()))
+            .app_data(web::Data::new(client_usecase_for_factory.clone()))
             .app_data(web::Data::new(mqtt_usecase_for_factory.clone()))
             .app_data(web::Data::from(allowed_origins_for_factory.clone()))
+            .app_data(web::Data::from(scheduler_for_factory.clone()))
+            .app_data(web::Data::from(rate_limiter_for_factory.clone()))
+            .app_data(web::Data::from(token_denylist_for_factory.clone()))
             // Register Modules
             .configure(AuthModule::configure_module)
             .configure(MqttModule::configure_module)
@@ -306,6 +493,11 @@ pub async fn run_server() -> std::io::Result<()> {
             .wrap(RequestLoggerMiddleware)
             .wrap(middleware::Compress::default())
             .route("/health", web::get().to(healthcheck))
+            .service(
+                web::resource("/health/jobs")
+                    .wrap(TenantSecretMiddleware)
+                    .route(web::get().to(job_metrics)),
+            )
             .route("/runtime-env.js", web::get().to(serve_runtime_config))
             .wrap(cors);
 
@@ -398,6 +590,9 @@ pub async fn run_server() -> std::io::Result<()> {
         cache_for_shutdown.shutdown(cache_rx).await;
     });
 
+    // Background jobs stop at the next shutdown signal
+    scheduler.start(shutdown_rx.clone());
+
     let cache_tx = shutdown_tx.clone();
     let cache_for_health = cache.clone();
     RocksDbCache::monitor_health(cache_for_health, cache_tx).await;
@@ -143,9 +143,8 @@ describe("POST /auth/refresh - Refresh Token", () => {
     }
   });
 
-  // 5. Token can be reused multiple times (no rotation)
-  test("Scenario 5: Token can be reused multiple times", async () => {
-    // Since we disabled rotation, the same refresh token can be used multiple times
+  // 5. Security: Token Reuse Detection
+  test("Scenario 5: Security: Token Reuse Detection", async () => {
     let reuseCookie = "";
     const timestamp = Date.now();
     await axios.post(
@@ -161,19 +160,40 @@ describe("POST /auth/refresh - Refresh Token", () => {
     const reuseRawCookie = l.headers["set-cookie"][0];
     reuseCookie = reuseRawCookie.split(";")[0];
 
-    // First refresh - should succeed
+    // First refresh - should succeed and rotate the cookie
     const firstRefresh = await axios.post(`${BASE_URL}/auth/refresh`, {}, {
       headers: { "X-API-Key": API_KEY, Cookie: reuseCookie },
     });
     expect(firstRefresh.status).toBe(200);
     expect(firstRefresh.data.status).toBe(true);
+    const rotatedCookie = firstRefresh.headers["set-cookie"][0].split(";")[0];
+    expect(rotatedCookie).not.toBe(reuseCookie);
 
-    // Second refresh with same token - should also succeed (no rotation)
-    const secondRefresh = await axios.post(`${BASE_URL}/auth/refresh`, {}, {
-      headers: { "X-API-Key": API_KEY, Cookie: reuseCookie },
-    });
-    expect(secondRefresh.status).toBe(200);
-    expect(secondRefresh.data.status).toBe(true);
+    // Second refresh with the consumed token - rejected as reuse
+    try {
+      await axios.post(`${BASE_URL}/auth/refresh`, {}, {
+        headers: { "X-API-Key": API_KEY, Cookie: reuseCookie },
+      });
+      throw new Error("Should have failed");
+    } catch (error) {
+      expect(error.response.status).toBe(401);
+      expect(error.response.data).toEqual(
+        expect.objectContaining({
+          status: false,
+          message: "Unauthorized",
+        }),
+      );
+    }
+
+    // Reuse revokes the whole family, including the rotated token
+    try {
+      await axios.post(`${BASE_URL}/auth/refresh`, {}, {
+        headers: { "X-API-Key": API_KEY, Cookie: rotatedCookie },
+      });
+      throw new Error("Should have failed");
+    } catch (error) {
+      expect(error.response.status).toBe(401);
+    }
   });
 
   // 6. Security: User State Check
@@ -21,6 +21,12 @@ const router = createRouter({
       component: () => import("../views/Register.vue"),
       meta: { guestOnly: true, title: "Create Account" },
     },
+    {
+      path: "/device",
+      name: "device",
+      component: () => import("../views/Device.vue"),
+      meta: { requiresAuth: true, title: "Connect Device" },
+    },
     {
       path: "/forbidden",
       name: "forbidden",
@@ -38,6 +44,11 @@ router.beforeEach(async (to, _from, next) => {
   // Store redirect_uri and tenant_id in sessionStorage
   // state and nonce will be generated fresh in the component
   if (to.name === "login" || to.name === "register") {
+    // OIDC: pending request created by /oauth/authorize
+    if (to.query.authorization_request) {
+      sessionStorage.setItem("oidc_authorization_request", to.query.authorization_request);
+    }
+
     const redirectUri = to.query.redirect_uri;
     const tenantId = to.query.tenant_id;
 
@@ -62,8 +73,23 @@ router.beforeEach(async (to, _from, next) => {
     await authStore.refreshToken();
   }
 
-  // Only login and register routes exist, both are guest-only
+  // Device approval needs a signed-in user: sign in first, then come back
+  if (to.meta.requiresAuth && !authStore.isAuthenticated) {
+    sessionStorage.setItem("device_verification_path", to.fullPath);
+    return next({ name: "login" });
+  }
+
+  // Login and register are guest-only
   if (to.meta.guestOnly && authStore.isAuthenticated) {
+    // Already signed in: complete a pending OIDC request without a new login
+    if (await authStore.completeOidcAuthorization(authStore.accessToken)) return;
+
+    const devicePath = sessionStorage.getItem("device_verification_path");
+    if (devicePath) {
+      sessionStorage.removeItem("device_verification_path");
+      return next(devicePath);
+    }
+
     const redirectUri = sessionStorage.getItem("sso_redirect_uri");
     if (redirectUri) {
       // Helper to extract role from JWT
@@ -35,6 +35,42 @@ class AuthService {
     return response.data;
   }
 
+  async completeAuthorization(token, authorizationRequest) {
+    const response = await api.post(
+      "/oauth/authorize",
+      { authorization_request: authorizationRequest },
+      {
+        headers: {
+          Authorization: `Bearer ${token}`,
+        },
+      },
+    );
+    return response.data;
+  }
+
+  async findDevice(token, userCode) {
+    const response = await api.get("/oauth/device", {
+      params: { user_code: userCode },
+      headers: {
+        Authorization: `Bearer ${token}`,
+      },
+    });
+    return response.data;
+  }
+
+  async decideDevice(token, userCode, approve) {
+    const response = await api.post(
+      "/oauth/device",
+      { user_code: userCode, approve },
+      {
+        headers: {
+          Authorization: `Bearer ${token}`,
+        },
+      },
+    );
+    return response.data;
+  }
+
   async verify(token) {
     const response = await api.get("/auth/verify", {
       headers: {
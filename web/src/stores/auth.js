@@ -23,6 +23,34 @@ export const useAuthStore = defineStore("auth", () => {
   // Getters
   const isAuthenticated = computed(() => !!accessToken.value);
 
+  // OIDC: finish the pending /oauth/authorize request and return to the client
+  const completeOidcAuthorization = async (token) => {
+    const authorizationRequest = sessionStorage.getItem("oidc_authorization_request");
+    if (!authorizationRequest) return false;
+
+    sessionStorage.removeItem("oidc_authorization_request");
+    try {
+      const response = await AuthService.completeAuthorization(token, authorizationRequest);
+      if (response?.data?.redirect_to) {
+        window.location.href = response.data.redirect_to;
+        return true;
+      }
+    } catch (err) {
+      toast.error(parseError(err).message);
+    }
+    return false;
+  };
+
+  // Device flow: return to the device page the user was sent to login from
+  const resumeDeviceVerification = () => {
+    const devicePath = sessionStorage.getItem("device_verification_path");
+    if (!devicePath) return false;
+
+    sessionStorage.removeItem("device_verification_path");
+    router.push(devicePath);
+    return true;
+  };
+
   // Helper for SSO redirection
   const performSSORedirect = (token) => {
     const urlParams = new URLSearchParams(window.location.search);
@@ -92,6 +120,8 @@ export const useAuthStore = defineStore("auth", () => {
           /* ignore */
         }
 
+        if (await completeOidcAuthorization(access_token)) return;
+        if (resumeDeviceVerification()) return;
         if (performSSORedirect(access_token)) return;
 
         toast.success("Login successful! You can close this window.");
@@ -195,6 +225,7 @@ export const useAuthStore = defineStore("auth", () => {
     isAuthenticated,
     login,
     register,
+    completeOidcAuthorization,
     logout,
     refreshToken,
   };